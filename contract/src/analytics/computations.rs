@@ -34,7 +34,8 @@ pub fn compute_spending_summary(
             }
             TransactionType::Withdrawal
             | TransactionType::BountyFunding
-            | TransactionType::MilestonePayment => {
+            | TransactionType::MilestonePayment
+            | TransactionType::TreasuryTransfer(_) => {
                 total_withdrawals += tx.amount;
             }
             TransactionType::AllowanceGrant => {}
@@ -129,7 +130,7 @@ pub fn compute_category_breakdown(
                 deposit_amount += tx.amount;
                 deposit_count += 1;
             }
-            TransactionType::Withdrawal => {
+            TransactionType::Withdrawal | TransactionType::TreasuryTransfer(_) => {
                 withdrawal_amount += tx.amount;
                 withdrawal_count += 1;
             }
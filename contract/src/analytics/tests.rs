@@ -4,7 +4,8 @@ mod tests {
         BudgetUtilization, CategoryBreakdown, SpendingForecast, SpendingSummary, SpendingTrend,
         TreasurySnapshot,
     };
-    use crate::treasury::types::{TransactionStatus, TransactionType};
+    use crate::guild::types::Role;
+    use crate::treasury::types::{TransactionStatus, TransactionType, WithdrawalCategory};
     use crate::StellarGuildsContract;
     use crate::StellarGuildsContractClient;
     use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
@@ -58,7 +59,14 @@ mod tests {
         signers.push_back(signer1.clone());
         signers.push_back(signer2.clone());
 
-        let treasury_id = client.initialize_treasury(&guild_id, &signers, &2u32);
+        // treasury actions are limited to members of the linked guild
+        let guild_owner = client.get_guild(&guild_id).owner;
+        for signer in signers.iter() {
+            client.add_member(&guild_id, &signer, &Role::Admin, &guild_owner);
+        }
+
+        let treasury_id =
+            client.initialize_treasury(&guild_id, &signers, &2u32, &false, &false, &Vec::new(env));
 
         (treasury_id, owner, signer1, signer2)
     }
@@ -79,10 +87,10 @@ mod tests {
 
         // Two deposits at different timestamps
         set_ledger_timestamp(&env, 2000);
-        client.deposit_treasury(&treasury_id, &depositor, &1000i128, &None);
+        client.deposit_treasury(&treasury_id, &depositor, &1000i128, &None, &None, &None);
 
         set_ledger_timestamp(&env, 3000);
-        client.deposit_treasury(&treasury_id, &depositor, &500i128, &None);
+        client.deposit_treasury(&treasury_id, &depositor, &500i128, &None, &None, &None);
 
         // Query the full period
         let summary = client.get_spending_summary(&treasury_id, &1500u64, &3500u64);
@@ -110,7 +118,7 @@ mod tests {
 
         // Deposit at timestamp 2000
         set_ledger_timestamp(&env, 2000);
-        client.deposit_treasury(&treasury_id, &depositor, &1000i128, &None);
+        client.deposit_treasury(&treasury_id, &depositor, &1000i128, &None, &None, &None);
 
         // Query period that does NOT contain any transactions
         let summary = client.get_spending_summary(&treasury_id, &5000u64, &6000u64);
@@ -139,12 +147,19 @@ mod tests {
 
         // Deposit
         set_ledger_timestamp(&env, 2000);
-        client.deposit_treasury(&treasury_id, &depositor, &2000i128, &None);
+        client.deposit_treasury(&treasury_id, &depositor, &2000i128, &None, &None, &None);
 
         // Propose + approve + execute withdrawal
         set_ledger_timestamp(&env, 3000);
-        let tx_id =
-            client.propose_withdrawal(&treasury_id, &signer1, &recipient, &500i128, &None, &reason);
+        let tx_id = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &recipient,
+            &500i128,
+            &None,
+            &WithdrawalCategory::Operations,
+            &reason,
+        );
         client.approve_transaction(&tx_id, &signer2);
         client.execute_transaction(&tx_id, &owner);
 
@@ -173,14 +188,21 @@ mod tests {
         let reason = String::from_str(&env, "test");
 
         // Set budget and fund treasury
-        let category = String::from_str(&env, "withdrawal");
+        let category = WithdrawalCategory::Operations;
         client.set_budget(&treasury_id, &category, &1000i128, &3600u64, &owner);
-        client.deposit_treasury(&treasury_id, &depositor, &5000i128, &None);
+        client.deposit_treasury(&treasury_id, &depositor, &5000i128, &None, &None, &None);
 
         // Spend 300 of 1000 budget (= 30%)
         set_ledger_timestamp(&env, 1500);
-        let tx_id =
-            client.propose_withdrawal(&treasury_id, &signer1, &recipient, &300i128, &None, &reason);
+        let tx_id = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &recipient,
+            &300i128,
+            &None,
+            &WithdrawalCategory::Operations,
+            &reason,
+        );
         client.approve_transaction(&tx_id, &signer2);
         client.execute_transaction(&tx_id, &owner);
 
@@ -210,9 +232,9 @@ mod tests {
 
         // Multiple deposits
         set_ledger_timestamp(&env, 2000);
-        client.deposit_treasury(&treasury_id, &depositor, &1000i128, &None);
+        client.deposit_treasury(&treasury_id, &depositor, &1000i128, &None, &None, &None);
         set_ledger_timestamp(&env, 2500);
-        client.deposit_treasury(&treasury_id, &depositor, &500i128, &None);
+        client.deposit_treasury(&treasury_id, &depositor, &500i128, &None, &None, &None);
 
         let breakdown = client.get_category_breakdown(&treasury_id, &1500u64, &3500u64);
 
@@ -239,11 +261,11 @@ mod tests {
 
         // Period 1: deposit 1000
         set_ledger_timestamp(&env, 2000);
-        client.deposit_treasury(&treasury_id, &depositor, &1000i128, &None);
+        client.deposit_treasury(&treasury_id, &depositor, &1000i128, &None, &None, &None);
 
         // Period 2: deposit 2000 (100% increase)
         set_ledger_timestamp(&env, 5000);
-        client.deposit_treasury(&treasury_id, &depositor, &2000i128, &None);
+        client.deposit_treasury(&treasury_id, &depositor, &2000i128, &None, &None, &None);
 
         let trend = client.get_spending_trend(&treasury_id, &1500u64, &3000u64, &4000u64, &6000u64);
 
@@ -268,15 +290,15 @@ mod tests {
         // Create deposits across multiple periods
         // Period 1 (1000-2000): 1000
         set_ledger_timestamp(&env, 1500);
-        client.deposit_treasury(&treasury_id, &depositor, &1000i128, &None);
+        client.deposit_treasury(&treasury_id, &depositor, &1000i128, &None, &None, &None);
 
         // Period 2 (2000-3000): 2000
         set_ledger_timestamp(&env, 2500);
-        client.deposit_treasury(&treasury_id, &depositor, &2000i128, &None);
+        client.deposit_treasury(&treasury_id, &depositor, &2000i128, &None, &None, &None);
 
         // Period 3 (3000-4000): 3000
         set_ledger_timestamp(&env, 3500);
-        client.deposit_treasury(&treasury_id, &depositor, &3000i128, &None);
+        client.deposit_treasury(&treasury_id, &depositor, &3000i128, &None, &None, &None);
 
         // Forecast from current time = 4000, 3 periods of 1000s each
         set_ledger_timestamp(&env, 4000);
@@ -305,13 +327,13 @@ mod tests {
 
         // Three deposits should create three snapshots
         set_ledger_timestamp(&env, 2000);
-        client.deposit_treasury(&treasury_id, &depositor, &100i128, &None);
+        client.deposit_treasury(&treasury_id, &depositor, &100i128, &None, &None, &None);
 
         set_ledger_timestamp(&env, 3000);
-        client.deposit_treasury(&treasury_id, &depositor, &200i128, &None);
+        client.deposit_treasury(&treasury_id, &depositor, &200i128, &None, &None, &None);
 
         set_ledger_timestamp(&env, 4000);
-        client.deposit_treasury(&treasury_id, &depositor, &300i128, &None);
+        client.deposit_treasury(&treasury_id, &depositor, &300i128, &None, &None, &None);
 
         let snapshots = client.get_treasury_snapshots(&treasury_id, &10u32);
         assert_eq!(snapshots.len(), 3);
@@ -343,7 +365,7 @@ mod tests {
         // Create 5 snapshots
         for i in 0u64..5 {
             set_ledger_timestamp(&env, 2000 + i * 1000);
-            client.deposit_treasury(&treasury_id, &depositor, &100i128, &None);
+            client.deposit_treasury(&treasury_id, &depositor, &100i128, &None, &None, &None);
         }
 
         // Request only 2 most recent
@@ -1,5 +1,7 @@
 ﻿use soroban_sdk::{contracttype, String};
 
+use crate::treasury::types::WithdrawalCategory;
+
 /// Reporting period options for analytics queries
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -28,7 +30,7 @@ pub struct SpendingSummary {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BudgetUtilization {
-    pub category: String,
+    pub category: WithdrawalCategory,
     pub allocated: i128,
     pub spent: i128,
     pub remaining: i128,
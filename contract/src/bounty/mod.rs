@@ -18,28 +18,41 @@
 /// | Release escrow      | `(bounty, released)`     | `EscrowReleasedEvent`    |
 /// | Cancel bounty       | `(bounty, cancelled)`    | `BountyCancelledEvent`   |
 /// | Expire bounty       | `(bounty, expired)`      | `BountyExpiredEvent`     |
+/// | Unassign claimer    | `(bounty, unassigned)`   | `BountyUnassignedEvent`  |
+/// | Dispute bounty      | `(bounty, disputed)`     | `BountyDisputedEvent`    |
 pub mod escrow;
 pub mod storage;
 pub mod types;
 
 use crate::bounty::escrow::{lock_funds, release_funds};
-use crate::bounty::storage::{get_bounty, get_guild_bounties, get_next_bounty_id, store_bounty};
+use crate::bounty::storage::{
+    get_bounty, get_claim_window as load_claim_window, get_claimed_at, get_guild_bounties, get_milestones,
+    get_next_bounty_id, get_reputation as load_reputation, set_claim_window as store_claim_window,
+    set_claimed_at, store_bounty, store_milestones, store_reputation,
+};
 use crate::bounty::types::{
     BountyApprovedEvent, BountyCancelledEvent, BountyClaimedEvent, BountyCreatedEvent,
-    BountyExpiredEvent, BountyFundedEvent, EscrowReleasedEvent, WorkSubmittedEvent,
+    BountyDisputedEvent, BountyExpiredEvent, BountyFundedEvent, BountyUnassignedEvent,
+    EscrowReleasedEvent, WorkSubmittedEvent,
 };
 use crate::dispute::storage as dispute_storage;
 use crate::dispute::types::DisputeReference;
 use crate::events::emit::emit_event;
 use crate::events::topics::{
-    ACT_APPROVED, ACT_CANCELLED, ACT_CLAIMED, ACT_CREATED, ACT_EXPIRED, ACT_FUNDED, ACT_RELEASED,
-    ACT_SUBMITTED, MOD_BOUNTY,
+    ACT_APPROVED, ACT_CANCELLED, ACT_CLAIMED, ACT_CREATED, ACT_DISPUTED, ACT_EXPIRED, ACT_FUNDED,
+    ACT_RELEASED, ACT_SUBMITTED, ACT_UNASSIGNED, MOD_BOUNTY,
 };
+use crate::governance::proposals::open_bounty_dispute;
 use crate::guild::membership::has_permission;
+use crate::guild::storage as guild_storage;
 use crate::guild::types::Role;
+use crate::utils::reentrancy::ensure_not_entered;
 use soroban_sdk::{Address, Env, String, Vec};
 
-pub use types::{Bounty, BountyStatus};
+pub use types::{Bounty, BountyMilestone, BountyStatus};
+
+/// Maximum number of stages in a milestone bounty
+const MAX_BOUNTY_MILESTONES: u32 = 20;
 
 /// Create a new bounty
 ///
@@ -99,6 +112,7 @@ pub fn create_bounty(
         expires_at: expiry,
     };
     store_bounty(env, &bounty);
+    guild_storage::touch_member(env, guild_id, &creator);
 
     emit_event(
         env,
@@ -117,6 +131,143 @@ pub fn create_bounty(
     bounty_id
 }
 
+/// Create a bounty that pays out in stages. The total of all milestone
+/// amounts is locked from the creator up front and the bounty opens for
+/// claiming immediately.
+///
+/// # Events emitted
+/// - `(bounty, created)` → `BountyCreatedEvent`
+/// - `(bounty, funded)`  → `BountyFundedEvent`
+#[allow(clippy::too_many_arguments)]
+pub fn create_milestone_bounty(
+    env: &Env,
+    guild_id: u64,
+    funder: Address,
+    title: String,
+    description: String,
+    milestones: Vec<i128>,
+    token: Address,
+    expiry: u64,
+) -> u64 {
+    if milestones.is_empty() || milestones.len() > MAX_BOUNTY_MILESTONES {
+        panic!("Milestone bounty must have between 1 and 20 milestones");
+    }
+
+    let mut total: i128 = 0;
+    let mut stages = Vec::new(env);
+    for amount in milestones.iter() {
+        if amount <= 0 {
+            panic!("Milestone amounts must be positive");
+        }
+        total += amount;
+        stages.push_back(BountyMilestone {
+            amount,
+            released: false,
+        });
+    }
+
+    let bounty_id = create_bounty(
+        env,
+        guild_id,
+        funder.clone(),
+        title,
+        description,
+        total,
+        token,
+        expiry,
+    );
+
+    let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
+    lock_funds(env, &bounty.token, &funder, total);
+    bounty.funded_amount = total;
+    bounty.status = BountyStatus::Open;
+    store_bounty(env, &bounty);
+    store_milestones(env, bounty_id, &stages);
+
+    emit_event(
+        env,
+        MOD_BOUNTY,
+        ACT_FUNDED,
+        BountyFundedEvent {
+            bounty_id,
+            funder,
+            amount: total,
+            total_funded: total,
+            is_fully_funded: true,
+        },
+    );
+
+    bounty_id
+}
+
+/// Approve one milestone of a claimed bounty, releasing its slice of the
+/// escrow to the claimer. Approving the last outstanding milestone
+/// completes the bounty.
+///
+/// # Events emitted
+/// - `(bounty, released)` → `EscrowReleasedEvent`
+pub fn approve_bounty_milestone(
+    env: &Env,
+    bounty_id: u64,
+    milestone_index: u32,
+    caller: Address,
+) -> bool {
+    caller.require_auth();
+    ensure_not_entered(env);
+
+    if dispute_storage::is_reference_locked(env, &DisputeReference::Bounty, bounty_id) {
+        panic!("Bounty is in active dispute");
+    }
+
+    let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
+
+    if !has_permission(env, bounty.guild_id, caller.clone(), Role::Admin) {
+        panic!("Unauthorized: Approver must be a guild admin or owner");
+    }
+    if bounty.status != BountyStatus::Claimed && bounty.status != BountyStatus::UnderReview {
+        panic!("Bounty has no active claimer");
+    }
+
+    let mut milestones = get_milestones(env, bounty_id);
+    let mut milestone = milestones
+        .get(milestone_index)
+        .expect("Milestone not found");
+    if milestone.released {
+        panic!("Milestone already released");
+    }
+
+    let claimer = bounty.claimer.clone().expect("No claimer for this bounty");
+
+    // EFFECTS before the transfer
+    milestone.released = true;
+    milestones.set(milestone_index, milestone.clone());
+    store_milestones(env, bounty_id, &milestones);
+
+    bounty.funded_amount -= milestone.amount;
+    let completed = milestones.iter().all(|m| m.released);
+    if completed {
+        bounty.status = BountyStatus::Completed;
+    }
+    store_bounty(env, &bounty);
+    record_payout(env, bounty.guild_id, &claimer, milestone.amount, completed);
+
+    release_funds(env, &bounty.token, &claimer, milestone.amount);
+
+    emit_event(
+        env,
+        MOD_BOUNTY,
+        ACT_RELEASED,
+        EscrowReleasedEvent {
+            bounty_id,
+            recipient: claimer,
+            amount: milestone.amount,
+            token: bounty.token,
+        },
+    );
+
+    true
+}
+
 /// Fund a bounty with tokens
 ///
 /// # Events emitted
@@ -124,6 +275,7 @@ pub fn create_bounty(
 /// - `(bounty, expired)` â†’ `BountyExpiredEvent`  (if bounty found to be expired)
 pub fn fund_bounty(env: &Env, bounty_id: u64, funder: Address, amount: i128) -> bool {
     funder.require_auth();
+    ensure_not_entered(env);
 
     if amount <= 0 {
         panic!("Amount must be positive");
@@ -211,6 +363,8 @@ pub fn claim_bounty(env: &Env, bounty_id: u64, claimer: Address) -> bool {
     bounty.status = BountyStatus::Claimed;
     bounty.claimer = Some(claimer.clone());
     store_bounty(env, &bounty);
+    set_claimed_at(env, bounty_id, Some(now));
+    guild_storage::touch_member(env, bounty.guild_id, &claimer);
 
     emit_event(
         env,
@@ -222,7 +376,7 @@ pub fn claim_bounty(env: &Env, bounty_id: u64, claimer: Address) -> bool {
     true
 }
 
-/// Submit work for a claimed bounty
+/// Submit work for a claimed bounty. Only the assigned claimer can submit.
 ///
 /// # Events emitted
 /// - `(bounty, submitted)` â†’ `WorkSubmittedEvent`
@@ -304,8 +458,10 @@ pub fn approve_completion(env: &Env, bounty_id: u64, approver: Address) -> bool
         panic!("Bounty is not under review");
     }
 
+    let claimer = bounty.claimer.clone().expect("No claimer for this bounty");
     bounty.status = BountyStatus::Completed;
     store_bounty(env, &bounty);
+    record_payout(env, bounty.guild_id, &claimer, bounty.funded_amount, true);
 
     emit_event(
         env,
@@ -314,7 +470,7 @@ pub fn approve_completion(env: &Env, bounty_id: u64, approver: Address) -> bool
         BountyApprovedEvent {
             bounty_id,
             approver,
-            claimer: bounty.claimer.expect("No claimer for this bounty"),
+            claimer,
         },
     );
 
@@ -326,6 +482,8 @@ pub fn approve_completion(env: &Env, bounty_id: u64, approver: Address) -> bool
 /// # Events emitted
 /// - `(bounty, released)` â†’ `EscrowReleasedEvent`
 pub fn release_escrow(env: &Env, bounty_id: u64) -> bool {
+    ensure_not_entered(env);
+
     if dispute_storage::is_reference_locked(env, &DisputeReference::Bounty, bounty_id) {
         panic!("Bounty is in active dispute");
     }
@@ -366,6 +524,7 @@ pub fn release_escrow(env: &Env, bounty_id: u64) -> bool {
 /// - `(bounty, cancelled)` â†’ `BountyCancelledEvent`
 pub fn cancel_bounty(env: &Env, bounty_id: u64, canceller: Address) -> bool {
     canceller.require_auth();
+    ensure_not_entered(env);
 
     if dispute_storage::is_reference_locked(env, &DisputeReference::Bounty, bounty_id) {
         panic!("Bounty is in active dispute");
@@ -374,7 +533,7 @@ pub fn cancel_bounty(env: &Env, bounty_id: u64, canceller: Address) -> bool {
     let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
 
     match bounty.status {
-        BountyStatus::Completed | BountyStatus::Cancelled => {
+        BountyStatus::Completed | BountyStatus::Cancelled | BountyStatus::Disputed => {
             panic!("Bounty cannot be cancelled in current status");
         }
         _ => {}
@@ -415,6 +574,9 @@ pub fn cancel_bounty(env: &Env, bounty_id: u64, canceller: Address) -> bool {
 
 /// Expire a bounty and refund escrowed funds if past its expiry timestamp
 ///
+/// Submitted work that is still under review is never expired, so the
+/// worker cannot lose the payout while waiting on reviewers.
+///
 /// # Events emitted
 /// - `(bounty, expired)` â†’ `BountyExpiredEvent`
 pub fn expire_bounty(env: &Env, bounty_id: u64) -> bool {
@@ -424,10 +586,14 @@ pub fn expire_bounty(env: &Env, bounty_id: u64) -> bool {
 
     let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
 
-    if bounty.status == BountyStatus::Expired
-        || bounty.status == BountyStatus::Completed
-        || bounty.status == BountyStatus::Cancelled
-    {
+    if matches!(
+        bounty.status,
+        BountyStatus::Expired
+            | BountyStatus::Completed
+            | BountyStatus::Cancelled
+            | BountyStatus::UnderReview
+            | BountyStatus::Disputed
+    ) {
         return false;
     }
 
@@ -436,24 +602,200 @@ pub fn expire_bounty(env: &Env, bounty_id: u64) -> bool {
         return false;
     }
 
+    refund_and_expire(env, &mut bounty);
+    true
+}
+
+/// Refund the creator of a bounty whose deadline passed without approved work
+///
+/// # Events emitted
+/// - `(bounty, expired)` → `BountyExpiredEvent`
+pub fn refund_expired_bounty(env: &Env, bounty_id: u64, funder: Address) -> bool {
+    funder.require_auth();
+
+    if dispute_storage::is_reference_locked(env, &DisputeReference::Bounty, bounty_id) {
+        panic!("Bounty is in active dispute");
+    }
+
+    let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
+
+    if bounty.creator != funder {
+        panic!("Unauthorized: Only the bounty creator can be refunded");
+    }
+    match bounty.status {
+        BountyStatus::UnderReview => panic!("Submitted work is awaiting review"),
+        BountyStatus::Disputed => panic!("Bounty is in active dispute"),
+        BountyStatus::Completed | BountyStatus::Cancelled | BountyStatus::Expired => {
+            panic!("Bounty cannot be refunded in current status");
+        }
+        _ => {}
+    }
+    if env.ledger().timestamp() <= bounty.expires_at {
+        panic!("Bounty deadline has not passed");
+    }
+
+    refund_and_expire(env, &mut bounty);
+    true
+}
+
+fn refund_and_expire(env: &Env, bounty: &mut Bounty) {
+    // an assigned claimer let the deadline pass without submitting work
+    if let Some(claimer) = bounty.claimer.clone() {
+        record_failure(env, bounty.guild_id, &claimer);
+    }
+
     if bounty.funded_amount > 0 {
         release_funds(env, &bounty.token, &bounty.creator, bounty.funded_amount);
         bounty.funded_amount = 0;
     }
 
     bounty.status = BountyStatus::Expired;
-    store_bounty(env, &bounty);
+    store_bounty(env, bounty);
 
     emit_event(
         env,
         MOD_BOUNTY,
         ACT_EXPIRED,
-        BountyExpiredEvent { bounty_id },
+        BountyExpiredEvent {
+            bounty_id: bounty.id,
+        },
+    );
+}
+
+/// Remove a claimer who has not submitted work, reopening the bounty for others.
+/// A claimer cannot be removed during the guild's exclusive claim window.
+///
+/// # Events emitted
+/// - `(bounty, unassigned)` → `BountyUnassignedEvent`
+pub fn unassign_claimer(env: &Env, bounty_id: u64, caller: Address) -> bool {
+    caller.require_auth();
+
+    let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
+
+    let is_creator = bounty.creator == caller;
+    let is_admin = has_permission(env, bounty.guild_id, caller.clone(), Role::Admin);
+    if !is_creator && !is_admin {
+        panic!("Unauthorized: Only creator or guild admin can unassign");
+    }
+    if bounty.status != BountyStatus::Claimed {
+        panic!("Bounty has no unsubmitted claim");
+    }
+    let now = env.ledger().timestamp();
+    if now > bounty.expires_at {
+        panic!("Bounty has expired");
+    }
+    let window = load_claim_window(env, bounty.guild_id);
+    if now < get_claimed_at(env, bounty_id).saturating_add(window) {
+        panic!("Claim window is still open");
+    }
+
+    let claimer = bounty.claimer.clone().expect("No claimer for this bounty");
+    bounty.status = BountyStatus::Open;
+    bounty.claimer = None;
+    store_bounty(env, &bounty);
+    set_claimed_at(env, bounty_id, None);
+
+    emit_event(
+        env,
+        MOD_BOUNTY,
+        ACT_UNASSIGNED,
+        BountyUnassignedEvent {
+            bounty_id,
+            claimer,
+            caller,
+        },
     );
 
     true
 }
 
+/// Escalate submitted work to a guild vote, freezing the escrow until the
+/// governance proposal settles. Callable by the funder or the claimer.
+///
+/// # Events emitted
+/// - `(bounty, disputed)` → `BountyDisputedEvent`
+pub fn dispute_bounty(env: &Env, bounty_id: u64, caller: Address) -> u64 {
+    caller.require_auth();
+
+    let mut bounty = get_bounty(env, bounty_id).expect("Bounty not found");
+
+    let is_claimer = bounty.claimer.as_ref() == Some(&caller);
+    if bounty.creator != caller && !is_claimer {
+        panic!("Unauthorized: Only the funder or claimer can dispute");
+    }
+    if bounty.status != BountyStatus::UnderReview {
+        panic!("Only bounties under review can be disputed");
+    }
+
+    bounty.status = BountyStatus::Disputed;
+    store_bounty(env, &bounty);
+
+    let proposal_id = open_bounty_dispute(env, bounty.guild_id, caller.clone(), bounty_id);
+
+    emit_event(
+        env,
+        MOD_BOUNTY,
+        ACT_DISPUTED,
+        BountyDisputedEvent {
+            bounty_id,
+            caller,
+            proposal_id,
+        },
+    );
+
+    proposal_id
+}
+
+/// Pay out a disputed bounty as decided by governance: to the claimer if the
+/// dispute proposal passed, otherwise back to the creator. No-op unless the
+/// bounty is still disputed.
+///
+/// # Events emitted
+/// - `(bounty, released)` → `EscrowReleasedEvent`
+pub fn resolve_dispute(env: &Env, bounty_id: u64, release_to_claimer: bool) {
+    let mut bounty = match get_bounty(env, bounty_id) {
+        Some(b) => b,
+        None => return,
+    };
+    if bounty.status != BountyStatus::Disputed {
+        return;
+    }
+
+    let claimer = bounty.claimer.clone().expect("No claimer for this bounty");
+    let recipient = if release_to_claimer {
+        bounty.status = BountyStatus::Completed;
+        claimer.clone()
+    } else {
+        bounty.status = BountyStatus::Cancelled;
+        bounty.creator.clone()
+    };
+
+    let amount = bounty.funded_amount;
+    bounty.funded_amount = 0;
+    store_bounty(env, &bounty);
+    if release_to_claimer {
+        record_payout(env, bounty.guild_id, &claimer, amount, true);
+    } else {
+        record_failure(env, bounty.guild_id, &claimer);
+    }
+
+    if amount > 0 {
+        release_funds(env, &bounty.token, &recipient, amount);
+
+        emit_event(
+            env,
+            MOD_BOUNTY,
+            ACT_RELEASED,
+            EscrowReleasedEvent {
+                bounty_id,
+                recipient,
+                amount,
+                token: bounty.token,
+            },
+        );
+    }
+}
+
 /// Claim bounty payout - allows claimer to pull funds from escrow to their own address
 ///
 /// This is called by the claimer (assignee) after bounty completion approval.
@@ -463,6 +805,7 @@ pub fn expire_bounty(env: &Env, bounty_id: u64) -> bool {
 /// - `(bounty, released)` → `EscrowReleasedEvent`
 pub fn claim_payout(env: &Env, bounty_id: u64, claimer: Address) -> bool {
     claimer.require_auth();
+    ensure_not_entered(env);
 
     if dispute_storage::is_reference_locked(env, &DisputeReference::Bounty, bounty_id) {
         panic!("Bounty is in active dispute");
@@ -514,6 +857,62 @@ pub fn get_guild_bounties_list(env: &Env, guild_id: u64) -> Vec<Bounty> {
     get_guild_bounties(env, guild_id)
 }
 
+pub fn get_bounty_milestones(env: &Env, bounty_id: u64) -> Vec<BountyMilestone> {
+    get_milestones(env, bounty_id)
+}
+
+/// Set how long a claimer holds a bounty exclusively before the creator or
+/// an admin may unassign them for not submitting work. 0 disables the window.
+pub fn set_claim_window(env: &Env, guild_id: u64, seconds: u64, caller: Address) -> bool {
+    caller.require_auth();
+
+    if !has_permission(env, guild_id, caller, Role::Admin) {
+        panic!("Unauthorized: Caller must be a guild admin or owner");
+    }
+
+    store_claim_window(env, guild_id, seconds);
+    true
+}
+
+/// A guild's exclusive claim window in seconds (0 if none)
+pub fn get_claim_window(env: &Env, guild_id: u64) -> u64 {
+    load_claim_window(env, guild_id)
+}
+
+/// Whether `address` is the claimer of a guild bounty still in progress
+pub fn has_assigned_bounty(env: &Env, guild_id: u64, address: &Address) -> bool {
+    get_guild_bounties(env, guild_id).iter().any(|b| {
+        matches!(b.status, BountyStatus::Claimed | BountyStatus::UnderReview)
+            && b.claimer.as_ref() == Some(address)
+    })
+}
+
+/// A claimer's bounty record in a guild: `(completed, earned)`
+pub fn get_reputation(env: &Env, guild_id: u64, address: Address) -> (u32, i128) {
+    let rep = load_reputation(env, guild_id, &address);
+    (rep.completed, rep.earned)
+}
+
+/// How many bounties in a guild a claimer let expire or lost in a dispute
+pub fn get_failed_count(env: &Env, guild_id: u64, address: Address) -> u32 {
+    load_reputation(env, guild_id, &address).failed
+}
+
+fn record_payout(env: &Env, guild_id: u64, claimer: &Address, amount: i128, completed: bool) {
+    let mut rep = load_reputation(env, guild_id, claimer);
+    rep.earned += amount;
+    if completed {
+        rep.completed += 1;
+    }
+    store_reputation(env, guild_id, claimer, &rep);
+}
+
+fn record_failure(env: &Env, guild_id: u64, claimer: &Address) {
+    let mut rep = load_reputation(env, guild_id, claimer);
+    rep.failed += 1;
+    store_reputation(env, guild_id, claimer, &rep);
+}
+
 #[allow(dead_code)]
 pub fn cancel_bounty_auth(env: &Env, bounty_id: u64, canceller: Address) -> bool {
     cancel_bounty(env, bounty_id, canceller)
@@ -1,10 +1,14 @@
-﻿use crate::bounty::types::Bounty;
-use soroban_sdk::{symbol_short, Env, Map, Symbol, Vec};
+﻿use crate::bounty::types::{Bounty, BountyMilestone, ClaimerReputation};
+use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
 
 // Storage keys
 const BOUNTIES_KEY: Symbol = symbol_short!("bounties");
 const BOUNTY_CNT_KEY: Symbol = symbol_short!("b_cnt");
 const GUILD_BOUNTIES_KEY: Symbol = symbol_short!("g_bnties");
+const MILESTONES_KEY: Symbol = symbol_short!("b_mstones");
+const REPUTATION_KEY: Symbol = symbol_short!("b_rep");
+const CLAIM_WINDOWS_KEY: Symbol = symbol_short!("b_cwin");
+const CLAIMED_AT_KEY: Symbol = symbol_short!("b_clmd");
 
 /// Initialize bounty storage
 #[allow(dead_code)]
@@ -98,3 +102,101 @@ pub fn get_guild_bounties(env: &Env, guild_id: u64) -> Vec<Bounty> {
     }
     result
 }
+
+/// Store the staged milestones of a bounty
+pub fn store_milestones(env: &Env, bounty_id: u64, milestones: &Vec<BountyMilestone>) {
+    let mut all: Map<u64, Vec<BountyMilestone>> = env
+        .storage()
+        .persistent()
+        .get(&MILESTONES_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    all.set(bounty_id, milestones.clone());
+    env.storage().persistent().set(&MILESTONES_KEY, &all);
+}
+
+/// Get the staged milestones of a bounty (empty for single-payout bounties)
+pub fn get_milestones(env: &Env, bounty_id: u64) -> Vec<BountyMilestone> {
+    let all: Map<u64, Vec<BountyMilestone>> = env
+        .storage()
+        .persistent()
+        .get(&MILESTONES_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    all.get(bounty_id).unwrap_or_else(|| Vec::new(env))
+}
+
+/// Get a guild's exclusive claim window in seconds (0 if unset)
+pub fn get_claim_window(env: &Env, guild_id: u64) -> u64 {
+    let windows: Map<u64, u64> = env
+        .storage()
+        .persistent()
+        .get(&CLAIM_WINDOWS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    windows.get(guild_id).unwrap_or(0)
+}
+
+/// Set a guild's exclusive claim window in seconds
+pub fn set_claim_window(env: &Env, guild_id: u64, seconds: u64) {
+    let mut windows: Map<u64, u64> = env
+        .storage()
+        .persistent()
+        .get(&CLAIM_WINDOWS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    windows.set(guild_id, seconds);
+    env.storage().persistent().set(&CLAIM_WINDOWS_KEY, &windows);
+}
+
+/// When the current claimer claimed the bounty (0 if unclaimed). Kept apart
+/// from `Bounty` so bounties stored before it existed still decode.
+pub fn get_claimed_at(env: &Env, bounty_id: u64) -> u64 {
+    let claimed: Map<u64, u64> = env
+        .storage()
+        .persistent()
+        .get(&CLAIMED_AT_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    claimed.get(bounty_id).unwrap_or(0)
+}
+
+/// Record when a bounty was claimed, or clear it with `None`
+pub fn set_claimed_at(env: &Env, bounty_id: u64, timestamp: Option<u64>) {
+    let mut claimed: Map<u64, u64> = env
+        .storage()
+        .persistent()
+        .get(&CLAIMED_AT_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    match timestamp {
+        Some(timestamp) => claimed.set(bounty_id, timestamp),
+        None => {
+            claimed.remove(bounty_id);
+        }
+    }
+    env.storage().persistent().set(&CLAIMED_AT_KEY, &claimed);
+}
+
+/// Get a claimer's track record in a guild (zeroed if they have none)
+pub fn get_reputation(env: &Env, guild_id: u64, address: &Address) -> ClaimerReputation {
+    let all: Map<(u64, Address), ClaimerReputation> = env
+        .storage()
+        .persistent()
+        .get(&REPUTATION_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    all.get((guild_id, address.clone())).unwrap_or_default()
+}
+
+/// Store a claimer's track record in a guild
+pub fn store_reputation(env: &Env, guild_id: u64, address: &Address, rep: &ClaimerReputation) {
+    let mut all: Map<(u64, Address), ClaimerReputation> = env
+        .storage()
+        .persistent()
+        .get(&REPUTATION_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    all.set((guild_id, address.clone()), rep.clone());
+    env.storage().persistent().set(&REPUTATION_KEY, &all);
+}
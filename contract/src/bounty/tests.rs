@@ -6,12 +6,14 @@
 //! NOTE: These tests use the contract client to test through the main lib.rs
 //! contract interface, ensuring proper contract context execution.
 
+use crate::bounty::storage::get_claimed_at;
 use crate::bounty::types::BountyStatus;
+use crate::governance::{ProposalStatus, ProposalType, VoteDecision};
 use crate::guild::types::Role;
 use crate::StellarGuildsContract;
 use crate::StellarGuildsContractClient;
 use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
-use soroban_sdk::{token, Address, Env, String};
+use soroban_sdk::{token, vec, Address, Env, String};
 
 // ============ Test Helpers ============
 
@@ -1248,6 +1250,462 @@ fn test_expire_bounty_not_expired_yet() {
     assert_eq!(bounty.status, BountyStatus::Funded);
 }
 
+/// Funded bounty expiring at 2000, claimed by `claimer`.
+fn setup_claimed_bounty(env: &Env, owner: &Address, claimer: &Address) -> (Address, Address, u64) {
+    let funder = Address::generate(env);
+    let token = create_mock_token(env, owner);
+
+    set_ledger_timestamp(env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(env);
+    let client = StellarGuildsContractClient::new(env, &contract_id);
+    let guild_id = setup_guild(&client, env, owner);
+    mint_tokens(env, &token, &funder, 1000);
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        owner,
+        &String::from_str(env, "Task"),
+        &String::from_str(env, "Description"),
+        &100i128,
+        &token,
+        &2000u64,
+    );
+    client.fund_bounty(&bounty_id, &funder, &100i128);
+    client.approve_bounty(&bounty_id, owner, claimer);
+    client.claim_bounty(&bounty_id, claimer);
+
+    (contract_id, token, bounty_id)
+}
+
+#[test]
+fn test_refund_expired_claimed_bounty() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let (contract_id, token, bounty_id) = setup_claimed_bounty(&env, &owner, &claimer);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    set_ledger_timestamp(&env, 2001);
+    assert!(client.refund_expired_bounty(&bounty_id, &owner));
+
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.status, BountyStatus::Expired);
+    assert_eq!(bounty.funded_amount, 0);
+    assert_eq!(get_token_balance(&env, &token, &owner), 100);
+}
+
+#[test]
+fn test_claimer_reputation_tracks_completions_and_lapses() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let (contract_id, token, lapsed_id) = setup_claimed_bounty(&env, &owner, &claimer);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = client.get_bounty(&lapsed_id).guild_id;
+
+    mint_tokens(&env, &token, &owner, 250);
+    let done_id = client.create_bounty(
+        &guild_id,
+        &owner,
+        &String::from_str(&env, "Second task"),
+        &String::from_str(&env, "Description"),
+        &250i128,
+        &token,
+        &3000u64,
+    );
+    client.fund_bounty(&done_id, &owner, &250i128);
+    client.approve_bounty(&done_id, &owner, &claimer);
+    client.claim_bounty(&done_id, &claimer);
+    client.submit_work(&done_id, &String::from_str(&env, "https://github.com/pr/2"));
+    client.approve_completion(&done_id, &owner);
+
+    assert_eq!(client.get_bounty_reputation(&guild_id, &claimer), (1, 250));
+    assert_eq!(client.get_bounty_failures(&guild_id, &claimer), 0);
+
+    // the first bounty lapses while still assigned
+    set_ledger_timestamp(&env, 2001);
+    assert!(client.expire_bounty(&lapsed_id));
+    assert_eq!(client.get_bounty_failures(&guild_id, &claimer), 1);
+    assert_eq!(client.get_bounty_reputation(&guild_id, &claimer), (1, 250));
+
+    // reputation is guild-scoped
+    assert_eq!(
+        client.get_bounty_reputation(&(guild_id + 1), &claimer),
+        (0, 0)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Bounty deadline has not passed")]
+fn test_refund_before_deadline_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let (contract_id, _, bounty_id) = setup_claimed_bounty(&env, &owner, &claimer);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    client.refund_expired_bounty(&bounty_id, &owner);
+}
+
+#[test]
+#[should_panic(expected = "Submitted work is awaiting review")]
+fn test_refund_blocked_while_work_under_review() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let (contract_id, _, bounty_id) = setup_claimed_bounty(&env, &owner, &claimer);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let submission = String::from_str(&env, "https://github.com/pr/1");
+    client.submit_work(&bounty_id, &submission);
+    set_ledger_timestamp(&env, 2001);
+
+    // The permissionless path leaves the submission untouched as well.
+    assert!(!client.expire_bounty(&bounty_id));
+    client.refund_expired_bounty(&bounty_id, &owner);
+}
+
+#[test]
+fn test_unassign_claimer_reopens_bounty() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let (contract_id, _, bounty_id) = setup_claimed_bounty(&env, &owner, &claimer);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    assert!(client.unassign_claimer(&bounty_id, &owner));
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.status, BountyStatus::Open);
+    assert_eq!(bounty.claimer, None);
+
+    let replacement = Address::generate(&env);
+    client.claim_bounty(&bounty_id, &replacement);
+    assert_eq!(client.get_bounty(&bounty_id).claimer, Some(replacement));
+}
+
+#[test]
+fn test_claim_window_protects_claimer_until_it_lapses() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let (contract_id, _, bounty_id) = setup_claimed_bounty(&env, &owner, &claimer);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = client.get_bounty(&bounty_id).guild_id;
+
+    client.set_claim_window(&guild_id, &500u64, &owner);
+    assert_eq!(client.get_claim_window(&guild_id), 500);
+    let claimed_at = || env.as_contract(&contract_id, || get_claimed_at(&env, bounty_id));
+    assert_eq!(claimed_at(), 1000);
+
+    set_ledger_timestamp(&env, 1499);
+    assert!(client.try_unassign_claimer(&bounty_id, &owner).is_err());
+
+    // nobody but the claimer can submit, with or without the window
+    env.mock_auths(&[]);
+    let submission = String::from_str(&env, "https://github.com/pr/9");
+    assert!(client.try_submit_work(&bounty_id, &submission).is_err());
+    env.mock_all_auths();
+
+    set_ledger_timestamp(&env, 1500);
+    assert!(client.unassign_claimer(&bounty_id, &owner));
+    assert_eq!(claimed_at(), 0);
+}
+
+#[test]
+fn test_claimer_cannot_leave_guild_with_assigned_bounty() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let (contract_id, _, bounty_id) = setup_claimed_bounty(&env, &owner, &claimer);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = client.get_bounty(&bounty_id).guild_id;
+    client.join_guild(&guild_id, &claimer);
+
+    assert!(client
+        .try_remove_member(&guild_id, &claimer, &claimer)
+        .is_err());
+
+    client.unassign_claimer(&bounty_id, &owner);
+    assert!(client.remove_member(&guild_id, &claimer, &claimer));
+}
+
+#[test]
+#[should_panic(expected = "Bounty has no unsubmitted claim")]
+fn test_unassign_after_submission_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let (contract_id, _, bounty_id) = setup_claimed_bounty(&env, &owner, &claimer);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let submission = String::from_str(&env, "https://github.com/pr/2");
+    client.submit_work(&bounty_id, &submission);
+    client.unassign_claimer(&bounty_id, &owner);
+}
+
+// ============ Dispute Tests ============
+
+const AFTER_DISPUTE_VOTE: u64 = 1000 + 7 * 24 * 60 * 60 + 1;
+
+fn open_dispute(env: &Env, owner: &Address, claimer: &Address) -> (Address, Address, u64, u64) {
+    let (contract_id, token, bounty_id) = setup_claimed_bounty(env, owner, claimer);
+    let client = StellarGuildsContractClient::new(env, &contract_id);
+    let submission = String::from_str(env, "https://github.com/pr/3");
+    client.submit_work(&bounty_id, &submission);
+    let proposal_id = client.dispute_bounty(&bounty_id, claimer);
+    (contract_id, token, bounty_id, proposal_id)
+}
+
+#[test]
+fn test_dispute_passed_releases_to_claimer() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let (contract_id, token, bounty_id, proposal_id) = open_dispute(&env, &owner, &claimer);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    assert_eq!(client.get_bounty(&bounty_id).status, BountyStatus::Disputed);
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!(proposal.proposal_type, ProposalType::BountyDispute);
+
+    client.vote(&proposal_id, &owner, &VoteDecision::For);
+    set_ledger_timestamp(&env, AFTER_DISPUTE_VOTE);
+    // The bounty deadline has passed, but the frozen escrow is left alone.
+    assert!(!client.expire_bounty(&bounty_id));
+    assert!(client.execute_proposal(&proposal_id, &owner));
+
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.status, BountyStatus::Completed);
+    assert_eq!(bounty.funded_amount, 0);
+    assert_eq!(get_token_balance(&env, &token, &claimer), 100);
+}
+
+#[test]
+fn test_unexecuted_dispute_expires_and_refunds_funder() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let (contract_id, token, bounty_id, proposal_id) = open_dispute(&env, &owner, &claimer);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = client.get_proposal(&proposal_id).guild_id;
+
+    client.vote(&proposal_id, &owner, &VoteDecision::For);
+    set_ledger_timestamp(&env, AFTER_DISPUTE_VOTE);
+    assert_eq!(
+        client.finalize_proposal(&proposal_id),
+        ProposalStatus::Passed
+    );
+    // still inside the execution window: nothing to sweep
+    assert_eq!(client.sweep_proposals(&guild_id), 0);
+
+    // nobody executes it within the three-day window
+    set_ledger_timestamp(&env, AFTER_DISPUTE_VOTE + 3 * 24 * 60 * 60 + 1);
+    assert!(client.try_execute_proposal(&proposal_id, &owner).is_err());
+    assert_eq!(client.sweep_proposals(&guild_id), 1);
+
+    assert_eq!(
+        client.get_proposal(&proposal_id).status,
+        ProposalStatus::Expired
+    );
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.status, BountyStatus::Cancelled);
+    assert_eq!(get_token_balance(&env, &token, &owner), 100);
+    assert_eq!(get_token_balance(&env, &token, &claimer), 0);
+    assert_eq!(client.sweep_proposals(&guild_id), 0);
+}
+
+#[test]
+fn test_dispute_rejected_refunds_funder() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let (contract_id, token, bounty_id, proposal_id) = open_dispute(&env, &owner, &claimer);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    client.vote(&proposal_id, &owner, &VoteDecision::Against);
+    set_ledger_timestamp(&env, AFTER_DISPUTE_VOTE);
+    assert_eq!(
+        client.finalize_proposal(&proposal_id),
+        ProposalStatus::Rejected
+    );
+
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.status, BountyStatus::Cancelled);
+    assert_eq!(get_token_balance(&env, &token, &owner), 100);
+    assert_eq!(get_token_balance(&env, &token, &claimer), 0);
+}
+
+#[test]
+#[should_panic(expected = "bounty disputes are settled only by vote")]
+fn test_funder_cannot_cancel_own_dispute() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let (contract_id, _, bounty_id) = setup_claimed_bounty(&env, &owner, &claimer);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    client.submit_work(
+        &bounty_id,
+        &String::from_str(&env, "https://github.com/pr/3"),
+    );
+
+    // the funder opens the dispute, then pulls it before anyone votes
+    let proposal_id = client.dispute_bounty(&bounty_id, &owner);
+    client.cancel_proposal(&proposal_id, &owner);
+}
+
+#[test]
+fn test_passed_dispute_cannot_be_vetoed() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let (contract_id, token, bounty_id, proposal_id) = open_dispute(&env, &owner, &claimer);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    // the guild owner cannot cancel a dispute mid-vote either
+    assert!(client.try_cancel_proposal(&proposal_id, &owner).is_err());
+
+    client.vote(&proposal_id, &owner, &VoteDecision::For);
+    set_ledger_timestamp(&env, AFTER_DISPUTE_VOTE);
+    assert_eq!(
+        client.finalize_proposal(&proposal_id),
+        ProposalStatus::Passed
+    );
+    assert!(client
+        .try_veto_proposal(&proposal_id, &owner, &String::from_str(&env, "overturn"))
+        .is_err());
+    assert_eq!(client.get_bounty(&bounty_id).status, BountyStatus::Disputed);
+
+    assert!(client.execute_proposal(&proposal_id, &owner));
+    assert_eq!(get_token_balance(&env, &token, &claimer), 100);
+}
+
+#[test]
+#[should_panic(expected = "Bounty cannot be cancelled in current status")]
+fn test_disputed_bounty_cannot_be_cancelled() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let (contract_id, _, bounty_id, _) = open_dispute(&env, &owner, &claimer);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    client.cancel_bounty(&bounty_id, &owner);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: Only the funder or claimer can dispute")]
+fn test_dispute_requires_funder_or_claimer() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let (contract_id, _, bounty_id) = setup_claimed_bounty(&env, &owner, &claimer);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let submission = String::from_str(&env, "https://github.com/pr/4");
+    client.submit_work(&bounty_id, &submission);
+
+    client.dispute_bounty(&bounty_id, &Address::generate(&env));
+}
+
+// ============ Milestone Bounty Tests ============
+
+/// Milestone bounty (30/70) created by `owner`, claimed by `claimer`.
+fn setup_milestone_bounty(
+    env: &Env,
+    owner: &Address,
+    claimer: &Address,
+) -> (Address, Address, u64) {
+    let token = create_mock_token(env, owner);
+
+    set_ledger_timestamp(env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(env);
+    let client = StellarGuildsContractClient::new(env, &contract_id);
+    let guild_id = setup_guild(&client, env, owner);
+    mint_tokens(env, &token, owner, 1000);
+
+    let bounty_id = client.create_milestone_bounty(
+        &guild_id,
+        owner,
+        &String::from_str(env, "Staged task"),
+        &String::from_str(env, "Paid per milestone"),
+        &vec![env, 30i128, 70i128],
+        &token,
+        &5000u64,
+    );
+    client.claim_bounty(&bounty_id, claimer);
+
+    (contract_id, token, bounty_id)
+}
+
+#[test]
+fn test_milestone_bounty_releases_in_stages() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let (contract_id, token, bounty_id) = setup_milestone_bounty(&env, &owner, &claimer);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.reward_amount, 100);
+    assert_eq!(bounty.funded_amount, 100);
+    assert_eq!(get_token_balance(&env, &token, &owner), 900);
+
+    client.approve_bounty_milestone(&bounty_id, &0u32, &owner);
+    assert_eq!(get_token_balance(&env, &token, &claimer), 30);
+    assert_eq!(client.get_bounty(&bounty_id).status, BountyStatus::Claimed);
+
+    client.approve_bounty_milestone(&bounty_id, &1u32, &owner);
+    assert_eq!(get_token_balance(&env, &token, &claimer), 100);
+
+    let bounty = client.get_bounty(&bounty_id);
+    assert_eq!(bounty.status, BountyStatus::Completed);
+    assert_eq!(bounty.funded_amount, 0);
+    let milestones = client.get_bounty_milestones(&bounty_id);
+    assert!(milestones.iter().all(|m| m.released));
+}
+
+#[test]
+fn test_cancel_milestone_bounty_refunds_unreleased_remainder() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let (contract_id, token, bounty_id) = setup_milestone_bounty(&env, &owner, &claimer);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    client.approve_bounty_milestone(&bounty_id, &0u32, &owner);
+    client.cancel_bounty(&bounty_id, &owner);
+
+    assert_eq!(get_token_balance(&env, &token, &claimer), 30);
+    assert_eq!(get_token_balance(&env, &token, &owner), 970);
+}
+
+#[test]
+#[should_panic(expected = "Milestone already released")]
+fn test_milestone_cannot_be_released_twice() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let (contract_id, _, bounty_id) = setup_milestone_bounty(&env, &owner, &claimer);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    client.approve_bounty_milestone(&bounty_id, &0u32, &owner);
+    client.approve_bounty_milestone(&bounty_id, &0u32, &owner);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: Approver must be a guild admin or owner")]
+fn test_milestone_approval_requires_admin() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let (contract_id, _, bounty_id) = setup_milestone_bounty(&env, &owner, &claimer);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    client.approve_bounty_milestone(&bounty_id, &0u32, &claimer);
+}
+
 // ============ Query Tests ============
 
 #[test]
@@ -12,6 +12,7 @@ pub enum BountyStatus {
     Expired = 5,
     AwaitingFunds = 6,
     Funded = 7,
+    Disputed = 8,
 }
 
 /// Bounty struct containing all bounty metadata and state
@@ -46,6 +47,28 @@ pub struct Bounty {
     pub expires_at: u64,
 }
 
+/// One stage of a milestone bounty's escrow
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BountyMilestone {
+    /// Amount paid to the claimer when this milestone is approved
+    pub amount: i128,
+    /// Whether the amount has been paid out
+    pub released: bool,
+}
+
+/// A claimer's bounty track record within one guild
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ClaimerReputation {
+    /// Bounties completed, including disputes decided for the claimer
+    pub completed: u32,
+    /// Total escrow paid out to the claimer, summed across tokens
+    pub earned: i128,
+    /// Bounties that expired while assigned, or disputes decided against them
+    pub failed: u32,
+}
+
 /// Represents the state of funds locked in escrow for a bounty
 #[contracttype]
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -133,3 +156,21 @@ pub struct BountyCancelledEvent {
 pub struct BountyExpiredEvent {
     pub bounty_id: u64,
 }
+
+/// Event emitted when a bounty is escalated to a guild dispute vote
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BountyDisputedEvent {
+    pub bounty_id: u64,
+    pub caller: Address,
+    pub proposal_id: u64,
+}
+
+/// Event emitted when a claimer is removed from a bounty
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BountyUnassignedEvent {
+    pub bounty_id: u64,
+    pub claimer: Address,
+    pub caller: Address,
+}
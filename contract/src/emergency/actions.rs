@@ -1,7 +1,10 @@
-﻿use crate::emergency::storage::{get_emergency_config, log_emergency_action, set_emergency_config};
+﻿use crate::emergency::storage::{
+    get_emergency_config, log_emergency_action, set_contract_paused_flag, set_emergency_config,
+};
 use crate::emergency::types::{EmergencyConfig, EmergencyStatus};
 use crate::multisig::storage::get_operation;
 use crate::multisig::types::{MultiSigOperation, OperationStatus, OperationType};
+use crate::DataKey;
 use soroban_sdk::{Address, Env, String};
 
 pub fn pause_contract(
@@ -65,3 +68,34 @@ pub fn resume_contract(env: &Env, multisig_op_id: u64, reason: String) -> bool {
 
     true
 }
+
+/// Contract-wide kill switch for incident response (contract admin only).
+/// While paused every mutating entry point reverts; reads keep working.
+pub fn set_contract_paused(env: &Env, paused: bool, admin: Address) -> bool {
+    admin.require_auth();
+
+    let stored_admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .expect("contract admin not initialized");
+    if stored_admin != admin {
+        panic!("only the contract admin can pause the contract");
+    }
+
+    set_contract_paused_flag(env, paused);
+
+    let action = if paused {
+        "Contract pause"
+    } else {
+        "Contract resume"
+    };
+    log_emergency_action(
+        env,
+        String::from_str(env, action),
+        admin,
+        String::from_str(env, "admin kill switch"),
+    );
+
+    true
+}
@@ -8,6 +8,7 @@ pub enum DataKey {
     EmergencyConfig,
     EmergencyLog(u64),
     LogCounter,
+    ContractPaused,
 }
 
 pub fn get_emergency_config(env: &Env) -> EmergencyConfig {
@@ -43,6 +44,26 @@ pub fn is_paused(env: &Env) -> bool {
     false
 }
 
+pub fn is_contract_paused(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKey::ContractPaused)
+        .unwrap_or(false)
+}
+
+pub fn set_contract_paused_flag(env: &Env, paused: bool) {
+    env.storage()
+        .instance()
+        .set(&DataKey::ContractPaused, &paused);
+}
+
+/// Revert a mutating entry point while the admin kill switch is on.
+pub fn ensure_not_paused(env: &Env) {
+    if is_contract_paused(env) {
+        panic!("contract paused");
+    }
+}
+
 pub fn next_log_id(env: &Env) -> u64 {
     let mut count: u64 = env
         .storage()
@@ -1,9 +1,10 @@
 #![cfg(test)]
 
 use crate::emergency::{actions, storage, types::EmergencyStatus};
+use crate::guild::types::Role;
 use crate::multisig::storage as multisig_storage;
-use crate::multisig::types::{MultiSigOperation, OperationStatus, OperationType};
-use crate::StellarGuildsContract;
+use crate::multisig::types::{MultiSigOperation, OperationPayload, OperationStatus, OperationType};
+use crate::{StellarGuildsContract, StellarGuildsContractClient};
 use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
 use soroban_sdk::{Address, Env, String, Vec};
 
@@ -26,12 +27,14 @@ fn store_emergency_op(env: &Env, id: u64, proposer: &Address, status: OperationS
         account_id: 1,
         op_type,
         description: String::from_str(env, "emergency"),
+        payload: OperationPayload::None,
         proposer: proposer.clone(),
         signatures: Vec::new(env),
         nonce: 0,
         created_at: env.ledger().timestamp(),
         expires_at: env.ledger().timestamp() + 100,
         status,
+        content_hash: None,
     };
     multisig_storage::store_operation(env, id, &op);
 }
@@ -157,3 +160,47 @@ fn test_pause_enforces_duration_bounds() {
         );
     });
 }
+
+#[test]
+fn test_contract_pause_blocks_mutations_but_not_reads() {
+    let env = Env::default();
+    env.budget().reset_unlimited();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let owner = Address::generate(&env);
+    let name = String::from_str(&env, "Guild");
+    let description = String::from_str(&env, "Paused guild");
+    let guild_id = client.create_guild(&name, &description, &owner);
+
+    assert!(client.set_contract_paused(&true, &admin));
+    assert!(client.is_contract_paused());
+    assert!(client
+        .try_create_guild(&name, &description, &owner)
+        .is_err());
+    assert!(client
+        .try_add_member(&guild_id, &Address::generate(&env), &Role::Member, &owner)
+        .is_err());
+    assert!(client
+        .try_ms_register_account(&owner, &Vec::new(&env), &1, &None, &0)
+        .is_err());
+    assert!(client.is_member(&guild_id, &owner));
+
+    assert!(client.set_contract_paused(&false, &admin));
+    client.create_guild(&name, &description, &owner);
+}
+
+#[test]
+#[should_panic(expected = "only the contract admin can pause the contract")]
+fn test_contract_pause_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env));
+
+    client.set_contract_paused(&true, &Address::generate(&env));
+}
@@ -62,12 +62,19 @@ pub const ACT_TRANSFERRED: &str = "transferred";
 pub const ACT_MEMBER_ADDED: &str = "member_added";
 pub const ACT_MEMBER_REMOVED: &str = "member_removed";
 pub const ACT_ROLE_UPDATED: &str = "role_updated";
+pub const ACT_ROLE_DEFINED: &str = "role_defined";
+pub const ACT_ROLE_ASSIGNED: &str = "role_assigned";
 pub const ACT_JOINED: &str = "joined";
+pub const ACT_PROFILE_UPDATED: &str = "profile_updated";
+pub const ACT_ANNOUNCED: &str = "announced";
+pub const ACT_BADGE_MINTED: &str = "badge_minted";
 
 // =========== Bounty-specific actions ===========
 
 pub const ACT_CLAIMED: &str = "claimed";
 pub const ACT_SUBMITTED: &str = "submitted";
+pub const ACT_UNASSIGNED: &str = "unassigned";
+pub const ACT_DISPUTED: &str = "disputed";
 
 // =========== Payment-specific actions ===========
 
@@ -77,9 +84,16 @@ pub const ACT_DISTRIBUTED: &str = "distributed";
 // =========== Governance-specific actions ===========
 
 pub const ACT_VOTED: &str = "voted";
+pub const ACT_COMMITTED: &str = "committed";
 pub const ACT_DELEGATED: &str = "delegated";
 pub const ACT_FINALIZED: &str = "finalized";
 pub const ACT_PROPOSED: &str = "proposed";
+pub const ACT_VETOED: &str = "vetoed";
+pub const ACT_COMMENTED: &str = "commented";
+pub const ACT_EXTENDED: &str = "extended";
+pub const ACT_BOND_LOCKED: &str = "bond_locked";
+pub const ACT_BOND_REFUNDED: &str = "bond_refunded";
+pub const ACT_BOND_FORFEITED: &str = "bond_forfeited";
 
 // =========== Milestone-specific actions ===========
 
@@ -123,9 +137,11 @@ pub const ACT_GRANTED: &str = "granted";
 pub const ACT_REVOKED: &str = "revoked";
 pub const ACT_INCREASED: &str = "increased";
 pub const ACT_DECREASED: &str = "decreased";
+pub const ACT_SPENT: &str = "spent";
 
 // =========== Upgrade-specific actions ===========
 
 pub const ACT_UPGRADE_PROPOSED: &str = "upgrade_proposed";
 pub const ACT_UPGRADE_EXECUTED: &str = "upgrade_executed";
 pub const ACT_EMERGENCY_UPGRADE: &str = "emerg_upgrade";
+pub const ACT_WASM_UPGRADED: &str = "wasm_upgraded";
@@ -0,0 +1,124 @@
+use crate::bounty::escrow::{lock_funds, release_funds};
+use crate::events::emit::emit_event;
+use crate::events::topics::{
+    ACT_BOND_FORFEITED, ACT_BOND_LOCKED, ACT_BOND_REFUNDED, MOD_GOVERNANCE,
+};
+use soroban_sdk::{Address, Env, String};
+
+use crate::governance::storage::{get_bond, get_proposal as load_proposal, store_bond};
+use crate::governance::types::{BondStatus, GovernanceConfig, ProposalBond, ProposalBondEvent};
+use crate::treasury::management::credit_held_funds;
+
+fn emit_bond_event(env: &Env, bond: &ProposalBond, action: &str) {
+    let event = ProposalBondEvent {
+        proposal_id: bond.proposal_id,
+        proposer: bond.proposer.clone(),
+        amount: bond.amount,
+        status: bond.status.clone(),
+    };
+    emit_event(env, MOD_GOVERNANCE, action, event);
+}
+
+/// Lock the configured bond from the proposer. No-op when bonding is disabled.
+pub fn lock_bond(env: &Env, cfg: &GovernanceConfig, proposal_id: u64, proposer: &Address) {
+    if cfg.proposal_bond <= 0 {
+        return;
+    }
+
+    let token = cfg
+        .bond_token
+        .clone()
+        .unwrap_or_else(|| panic!("bond token not configured"));
+    let treasury_id = cfg
+        .bond_treasury_id
+        .unwrap_or_else(|| panic!("bond treasury not configured"));
+
+    lock_funds(env, &token, proposer, cfg.proposal_bond);
+
+    let bond = ProposalBond {
+        proposal_id,
+        proposer: proposer.clone(),
+        token,
+        amount: cfg.proposal_bond,
+        treasury_id,
+        status: BondStatus::Locked,
+    };
+    store_bond(env, &bond);
+
+    emit_bond_event(env, &bond, ACT_BOND_LOCKED);
+}
+
+/// Settle a locked bond once the proposal is finalized: it becomes
+/// refundable if quorum was reached, otherwise it is forfeited to the treasury.
+pub fn settle_bond(env: &Env, proposal_id: u64, quorum_reached: bool) {
+    let mut bond = match get_bond(env, proposal_id) {
+        Some(b) => b,
+        None => return,
+    };
+    if !matches!(bond.status, BondStatus::Locked) {
+        return;
+    }
+
+    if quorum_reached {
+        bond.status = BondStatus::Refundable;
+        store_bond(env, &bond);
+        return;
+    }
+
+    credit_held_funds(
+        env,
+        bond.treasury_id,
+        bond.proposer.clone(),
+        bond.amount,
+        bond.token.clone(),
+        String::from_str(env, "forfeited proposal bond"),
+    );
+
+    bond.status = BondStatus::Forfeited;
+    store_bond(env, &bond);
+
+    emit_bond_event(env, &bond, ACT_BOND_FORFEITED);
+}
+
+/// Return a locked bond to the proposer immediately (used on cancellation).
+pub fn refund_bond(env: &Env, proposal_id: u64) {
+    let mut bond = match get_bond(env, proposal_id) {
+        Some(b) => b,
+        None => return,
+    };
+    if !matches!(bond.status, BondStatus::Locked | BondStatus::Refundable) {
+        return;
+    }
+
+    release_funds(env, &bond.token, &bond.proposer, bond.amount);
+
+    bond.status = BondStatus::Refunded;
+    store_bond(env, &bond);
+
+    emit_bond_event(env, &bond, ACT_BOND_REFUNDED);
+}
+
+pub fn claim_proposal_bond(env: &Env, proposal_id: u64, proposer: Address) -> bool {
+    proposer.require_auth();
+
+    let proposal = load_proposal(env, proposal_id).unwrap_or_else(|| panic!("proposal not found"));
+    if proposal.proposer != proposer {
+        panic!("only the proposer can claim the bond");
+    }
+
+    let bond = get_bond(env, proposal_id).unwrap_or_else(|| panic!("no bond for proposal"));
+    match bond.status {
+        BondStatus::Refundable => {}
+        BondStatus::Locked => panic!("bond not yet refundable"),
+        BondStatus::Refunded => panic!("bond already refunded"),
+        BondStatus::Forfeited => panic!("bond was forfeited"),
+    }
+
+    refund_bond(env, proposal_id);
+
+    true
+}
+
+pub fn get_proposal_bond(env: &Env, proposal_id: u64) -> Option<ProposalBond> {
+    get_bond(env, proposal_id)
+}
@@ -1,24 +1,81 @@
 use crate::events::emit::emit_event;
-use crate::events::topics::{ACT_EXECUTED, MOD_GOVERNANCE};
-use soroban_sdk::{Address, Env};
+use crate::events::topics::{ACT_EXECUTED, ACT_VETOED, MOD_GOVERNANCE};
+use soroban_sdk::{Address, Env, String};
 
 use crate::governance::proposals::get_proposal as load_proposal;
-use crate::governance::storage::store_proposal;
+use crate::governance::storage::{get_proposal_config, get_spend_data, store_proposal};
 use crate::governance::types::{
-    ExecutionPayload, Proposal, ProposalExecutedEvent, ProposalStatus, ProposalType,
+    ExecutionPayload, FinalizeExecuteOutcome, Proposal, ProposalExecutedEvent, ProposalStatus,
+    ProposalType, ProposalVetoedEvent,
 };
-use crate::governance::voting::finalize_proposal;
+use crate::governance::voting::{finalize_proposal, tally_closes_at};
+use crate::guild::storage as guild_storage;
+use crate::guild::types::Role;
+use crate::treasury::management::{apply_governance_config, execute_governance_spend};
 
-const EXECUTION_DEADLINE_SECONDS: u64 = 3 * 24 * 60 * 60; // 3 days after passing
+const EXECUTION_DEADLINE_SECONDS: u64 = 3 * 24 * 60 * 60; // 3 days after the timelock
+
+/// Timelock applied to a passed proposal before it may run. Only treasury
+/// spends, treasury config changes and rule changes are delayed.
+fn execution_delay(env: &Env, proposal: &Proposal) -> u64 {
+    match proposal.proposal_type {
+        ProposalType::TreasurySpend
+        | ProposalType::TreasuryConfigChange
+        | ProposalType::RuleChange => get_proposal_config(env, proposal).execution_delay_seconds,
+        _ => 0,
+    }
+}
 
 pub fn execute_proposal(env: &Env, proposal_id: u64, executor: Address) -> bool {
-    let mut proposal = load_proposal(env, proposal_id);
     executor.require_auth(); // Enforce the new auth check for security
+    run_proposal(env, proposal_id, executor)
+}
+
+/// Finalize a proposal whose voting has ended and, if it passed and is
+/// already unlocked, execute it in the same invocation. A proposal still
+/// inside its timelock is left `Passed` instead of reverting the finalize.
+pub fn finalize_and_execute(
+    env: &Env,
+    proposal_id: u64,
+    executor: Address,
+) -> FinalizeExecuteOutcome {
+    executor.require_auth();
+
+    ensure_not_executed(&load_proposal(env, proposal_id));
+    if !matches!(finalize_proposal(env, proposal_id), ProposalStatus::Passed) {
+        return FinalizeExecuteOutcome::Rejected;
+    }
+
+    let proposal = load_proposal(env, proposal_id);
+    if let Some(passed_at) = proposal.passed_at {
+        if env.ledger().timestamp() < passed_at + execution_delay(env, &proposal) {
+            return FinalizeExecuteOutcome::PassedNotExecuted;
+        }
+    }
+
+    if run_proposal(env, proposal_id, executor) {
+        FinalizeExecuteOutcome::Executed
+    } else if matches!(
+        load_proposal(env, proposal_id).status,
+        ProposalStatus::Failed
+    ) {
+        FinalizeExecuteOutcome::Failed
+    } else {
+        FinalizeExecuteOutcome::PassedNotExecuted
+    }
+}
+
+fn run_proposal(env: &Env, proposal_id: u64, executor: Address) -> bool {
+    let mut proposal = load_proposal(env, proposal_id);
+
+    // checked before any side effect so a repeat call can never re-spend
+    ensure_not_executed(&proposal);
 
     let now = env.ledger().timestamp();
-    if matches!(proposal.status, ProposalStatus::Active) && now >= proposal.voting_end {
+    if matches!(proposal.status, ProposalStatus::Active) && now >= tally_closes_at(&proposal) {
         let _status = finalize_proposal(env, proposal_id);
         proposal = load_proposal(env, proposal_id);
+        ensure_not_executed(&proposal);
         if !matches!(proposal.status, ProposalStatus::Passed) {
             panic!("proposal not passed");
         }
@@ -29,7 +86,11 @@ pub fn execute_proposal(env: &Env, proposal_id: u64, executor: Address) -> bool
     }
 
     if let Some(passed_at) = proposal.passed_at {
-        if now > passed_at + EXECUTION_DEADLINE_SECONDS {
+        let unlocks_at = passed_at + execution_delay(env, &proposal);
+        if now < unlocks_at {
+            panic!("timelock not elapsed");
+        }
+        if now > unlocks_at + EXECUTION_DEADLINE_SECONDS {
             proposal.status = ProposalStatus::Expired;
             store_proposal(env, &proposal);
             panic!("execution window expired");
@@ -38,14 +99,27 @@ pub fn execute_proposal(env: &Env, proposal_id: u64, executor: Address) -> bool
 
     let success = match (&proposal.proposal_type, &proposal.execution_payload) {
         (ProposalType::TreasurySpend, ExecutionPayload::TreasurySpend) => {
-            // High-security action: Relies on the new multisig flow.
-            true
+            disburse_spend(env, &proposal)
         }
         (ProposalType::RuleChange, ExecutionPayload::RuleChange) => {
             // High-security action
             true
         }
         (ProposalType::GeneralDecision, ExecutionPayload::GeneralDecision) => true,
+        (ProposalType::BountyDispute, ExecutionPayload::BountyDispute(_)) => true,
+        (ProposalType::TreasuryConfigChange, ExecutionPayload::TreasuryConfigChange(change)) => {
+            apply_governance_config(
+                env,
+                proposal.guild_id,
+                change.treasury_id,
+                change.approval_threshold,
+                change.signers.clone(),
+            )
+        }
+        (ProposalType::MultipleChoice, ExecutionPayload::MultipleChoice(_)) => {
+            // Signalling only: the selected option is recorded in `winning_option`
+            proposal.winning_option.is_some()
+        }
         _ => false,
     };
 
@@ -54,7 +128,16 @@ pub fn execute_proposal(env: &Env, proposal_id: u64, executor: Address) -> bool
         proposal_to_update.status = ProposalStatus::Executed;
         proposal_to_update.executed_at = Some(now);
         store_proposal(env, &proposal_to_update);
+        settle_bounty_dispute(env, &proposal_to_update);
+    } else if matches!(
+        proposal.proposal_type,
+        ProposalType::TreasurySpend | ProposalType::TreasuryConfigChange
+    ) {
+        // a spend or config change that could not be applied is not retried
+        proposal_to_update.status = ProposalStatus::Failed;
+        store_proposal(env, &proposal_to_update);
     }
+    guild_storage::touch_member(env, proposal.guild_id, &executor);
 
     let event = ProposalExecutedEvent {
         proposal_id,
@@ -64,3 +147,102 @@ pub fn execute_proposal(env: &Env, proposal_id: u64, executor: Address) -> bool
 
     success
 }
+
+/// Pay out a passed spend from the guild treasury named in its spend data.
+/// Spends without data, or that the treasury cannot cover, pay nothing.
+fn disburse_spend(env: &Env, proposal: &Proposal) -> bool {
+    let Some(spend) = get_spend_data(env, proposal.id) else {
+        return false;
+    };
+    execute_governance_spend(
+        env,
+        proposal.guild_id,
+        spend.treasury_id,
+        spend.recipient,
+        spend.amount,
+        spend.token,
+        spend.category,
+        spend.reason,
+    )
+    .is_some()
+}
+
+fn ensure_not_executed(proposal: &Proposal) {
+    if matches!(proposal.status, ProposalStatus::Executed) || proposal.executed_at.is_some() {
+        panic!("already executed");
+    }
+}
+
+/// Block a passed proposal before it is executed. Only guild owners may veto,
+/// and only while the proposal is still inside its execution window.
+pub fn veto_proposal(env: &Env, proposal_id: u64, owner: Address, reason: String) -> bool {
+    owner.require_auth();
+
+    let mut proposal = load_proposal(env, proposal_id);
+
+    let member = guild_storage::get_member(env, proposal.guild_id, &owner)
+        .unwrap_or_else(|| panic!("only guild owner can veto"));
+    if member.role != Role::Owner {
+        panic!("only guild owner can veto");
+    }
+
+    if !matches!(proposal.status, ProposalStatus::Passed) {
+        panic!("only passed proposals can be vetoed");
+    }
+    if matches!(proposal.proposal_type, ProposalType::BountyDispute) {
+        panic!("bounty disputes are settled only by vote");
+    }
+
+    let now = env.ledger().timestamp();
+    if let Some(passed_at) = proposal.passed_at {
+        if now > passed_at + execution_delay(env, &proposal) + EXECUTION_DEADLINE_SECONDS {
+            panic!("veto window expired");
+        }
+    }
+
+    proposal.status = ProposalStatus::Vetoed;
+    store_proposal(env, &proposal);
+    guild_storage::touch_member(env, proposal.guild_id, &owner);
+
+    let event = ProposalVetoedEvent {
+        proposal_id,
+        owner,
+        reason,
+    };
+    emit_event(env, MOD_GOVERNANCE, ACT_VETOED, event);
+
+    true
+}
+
+/// Mark a `Passed` proposal whose execution window has closed as `Expired`
+/// and settle any bounty escrow it held. Unlike `execute_proposal`, which
+/// reverts on an expired window, this persists the outcome. Returns whether
+/// the proposal was expired.
+pub fn expire_unexecuted(env: &Env, proposal: &Proposal) -> bool {
+    if !matches!(proposal.status, ProposalStatus::Passed) {
+        return false;
+    }
+    let Some(passed_at) = proposal.passed_at else {
+        return false;
+    };
+    let deadline = passed_at + execution_delay(env, proposal) + EXECUTION_DEADLINE_SECONDS;
+    if env.ledger().timestamp() <= deadline {
+        return false;
+    }
+
+    let mut expired = proposal.clone();
+    expired.status = ProposalStatus::Expired;
+    store_proposal(env, &expired);
+    settle_bounty_dispute(env, &expired);
+    true
+}
+
+/// Route a disputed bounty's escrow once its proposal is settled: an executed
+/// proposal pays the claimer, any other final outcome refunds the funder.
+/// No-op for other proposal types.
+pub fn settle_bounty_dispute(env: &Env, proposal: &Proposal) {
+    if let ExecutionPayload::BountyDispute(bounty_id) = proposal.execution_payload {
+        let release_to_claimer = matches!(proposal.status, ProposalStatus::Executed);
+        crate::bounty::resolve_dispute(env, bounty_id, release_to_claimer);
+    }
+}
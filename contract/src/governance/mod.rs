@@ -1,20 +1,32 @@
-﻿pub mod execution;
+﻿pub mod bond;
+pub mod execution;
 pub mod proposals;
 pub mod storage;
 pub mod types;
 pub mod voting;
 
 pub use types::{
-    ExecutionPayload, GovernanceConfig, Proposal, ProposalStatus, ProposalType, VoteDecision,
+    ExecutionPayload, FinalizeExecuteOutcome, GovernanceConfig, Proposal, ProposalBond,
+    ProposalComment, ProposalStatus, ProposalType, TreasuryConfigData, TreasurySpendData,
+    VoteDecision,
 };
 
 pub use proposals::{
-    cancel_proposal, create_proposal, get_active_proposals, get_proposal, update_governance_config,
+    add_proposal_comment, cancel_proposal, create_commit_reveal_proposal, create_proposal,
+    create_treasury_spend_proposal, default_payload, extend_voting_period, get_active_proposals,
+    get_active_proposals_by_type, get_all_proposals, get_proposal, get_proposal_comments,
+    get_proposal_config, get_proposal_count, get_treasury_spend, update_governance_config,
 };
 
-pub use voting::{delegate_vote, finalize_proposal, undelegate_vote, vote};
+pub use voting::{
+    check_early_rejection, commit_vote, delegate_vote, finalize_proposal, get_delegation_target,
+    get_delegators, get_snapshot_weight, get_votes_by_member, get_voting_weight, is_quorum_met,
+    reveal_vote, simulate_finalize, sweep_proposals, undelegate_vote, vote, vote_choice,
+};
+
+pub use bond::{claim_proposal_bond, get_proposal_bond};
 
-pub use execution::execute_proposal;
+pub use execution::{execute_proposal, finalize_and_execute, veto_proposal};
 
 #[cfg(test)]
 mod tests;
@@ -1,17 +1,29 @@
 use crate::events::emit::emit_event;
-use crate::events::topics::{ACT_CANCELLED, ACT_PROPOSED, ACT_UPDATED, MOD_GOVERNANCE};
+use crate::events::topics::{
+    ACT_CANCELLED, ACT_COMMENTED, ACT_EXTENDED, ACT_PROPOSED, ACT_UPDATED, MOD_GOVERNANCE,
+};
 use soroban_sdk::{Address, Env, String, Vec};
 
+use crate::governance::bond::{lock_bond, refund_bond};
 use crate::governance::storage::{
-    get_config, get_guild_proposals, get_next_proposal_id, get_proposal as load_proposal,
-    set_config, store_proposal,
+    append_comment, get_comments, get_config, get_guild_proposal_ids, get_guild_proposals,
+    get_guild_proposals_page, get_next_proposal_id, get_proposal as load_proposal,
+    get_proposal_config as load_proposal_config, get_spend_data, has_votes, set_config,
+    store_proposal, store_proposal_config, store_spend_data,
 };
 use crate::governance::types::{
-    ExecutionPayload, GovernanceConfig, GovernanceConfigUpdatedEvent, Proposal,
-    ProposalCreatedEvent, ProposalStatus, ProposalType,
+    ExecutionPayload, GovernanceConfig, GovernanceConfigUpdatedEvent, Proposal, ProposalComment,
+    ProposalCommentedEvent, ProposalCreatedEvent, ProposalStatus, ProposalType, QuorumMode,
+    TreasurySpendData, VotingPeriodExtendedEvent,
 };
+use crate::governance::voting::snapshot_voting_power;
 use crate::guild::storage as guild_storage;
-use crate::guild::types::Member;
+use crate::guild::types::{Member, Role};
+use crate::treasury::management::preview_governance_config;
+
+const MAX_CHOICE_OPTIONS: u32 = 10;
+const MAX_CHOICE_OPTION_LEN: u32 = 200;
+const MAX_COMMENT_URI_LEN: u32 = 256;
 
 fn validate_execution_payload(
     env: &Env,
@@ -26,6 +38,30 @@ fn validate_execution_payload(
         (ProposalType::RemoveMember, ExecutionPayload::RemoveMember) => {}
         (ProposalType::RuleChange, ExecutionPayload::RuleChange) => {}
         (ProposalType::GeneralDecision, ExecutionPayload::GeneralDecision) => {}
+        (ProposalType::BountyDispute, _) => {
+            panic!("bounty disputes are opened from the bounty");
+        }
+        (ProposalType::TreasuryConfigChange, ExecutionPayload::TreasuryConfigChange(change)) => {
+            if let Err(reason) = preview_governance_config(
+                env,
+                guild_id,
+                change.treasury_id,
+                change.approval_threshold,
+                change.signers.clone(),
+            ) {
+                panic!("{}", reason);
+            }
+        }
+        (ProposalType::MultipleChoice, ExecutionPayload::MultipleChoice(options)) => {
+            if options.len() < 2 || options.len() > MAX_CHOICE_OPTIONS {
+                panic!("invalid number of options");
+            }
+            for option in options.iter() {
+                if option.is_empty() || option.len() > MAX_CHOICE_OPTION_LEN {
+                    panic!("option length invalid");
+                }
+            }
+        }
         _ => {
             panic!("execution payload does not match proposal type");
         }
@@ -36,6 +72,23 @@ fn validate_execution_payload(
         guild_storage::get_guild(env, guild_id).unwrap_or_else(|| panic!("guild not found"));
 }
 
+/// Payload for proposal types that carry no extra data. Multiple-choice
+/// proposals need their options and must be built explicitly.
+pub fn default_payload(proposal_type: &ProposalType) -> ExecutionPayload {
+    match proposal_type {
+        ProposalType::TreasurySpend => ExecutionPayload::TreasurySpend,
+        ProposalType::AddMember => ExecutionPayload::AddMember,
+        ProposalType::RemoveMember => ExecutionPayload::RemoveMember,
+        ProposalType::RuleChange => ExecutionPayload::RuleChange,
+        ProposalType::GeneralDecision => ExecutionPayload::GeneralDecision,
+        ProposalType::MultipleChoice => panic!("multiple-choice proposals require options"),
+        ProposalType::BountyDispute => panic!("bounty disputes are opened from the bounty"),
+        ProposalType::TreasuryConfigChange => {
+            panic!("treasury config changes require the new settings")
+        }
+    }
+}
+
 fn get_member(env: &Env, guild_id: u64, address: &Address) -> Option<Member> {
     guild_storage::get_member(env, guild_id, address)
 }
@@ -47,6 +100,9 @@ fn ensure_guild_member(env: &Env, guild_id: u64, address: &Address) {
     }
 }
 
+/// Create a proposal. `voting_period_seconds` overrides the guild's default
+/// window and is clamped to the configured minimum and maximum.
+#[allow(clippy::too_many_arguments)]
 pub fn create_proposal(
     env: &Env,
     guild_id: u64,
@@ -55,6 +111,101 @@ pub fn create_proposal(
     title: String,
     description: String,
     execution_payload: ExecutionPayload,
+    voting_period_seconds: Option<u64>,
+) -> u64 {
+    new_proposal(
+        env,
+        guild_id,
+        proposer,
+        proposal_type,
+        title,
+        description,
+        execution_payload,
+        voting_period_seconds,
+        false,
+    )
+}
+
+/// Create a proposal whose votes are committed as hashes during the voting
+/// period and revealed afterwards, so the running tally stays hidden.
+pub fn create_commit_reveal_proposal(
+    env: &Env,
+    guild_id: u64,
+    proposer: Address,
+    proposal_type: ProposalType,
+    title: String,
+    description: String,
+    execution_payload: ExecutionPayload,
+) -> u64 {
+    if matches!(proposal_type, ProposalType::MultipleChoice) {
+        panic!("commit-reveal not supported for multiple-choice proposals");
+    }
+
+    new_proposal(
+        env,
+        guild_id,
+        proposer,
+        proposal_type,
+        title,
+        description,
+        execution_payload,
+        None,
+        true,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+/// Create a `TreasurySpend` proposal that pays `spend` out of one of the
+/// guild's treasuries once it passes and is executed.
+pub fn create_treasury_spend_proposal(
+    env: &Env,
+    guild_id: u64,
+    proposer: Address,
+    title: String,
+    description: String,
+    spend: TreasurySpendData,
+    voting_period_seconds: Option<u64>,
+) -> u64 {
+    if spend.amount <= 0 {
+        panic!("spend amount must be positive");
+    }
+    let treasury = crate::treasury::storage::get_treasury(env, spend.treasury_id)
+        .unwrap_or_else(|| panic!("treasury not found"));
+    if treasury.guild_id != guild_id {
+        panic!("treasury belongs to another guild");
+    }
+
+    let id = new_proposal(
+        env,
+        guild_id,
+        proposer,
+        ProposalType::TreasurySpend,
+        title,
+        description,
+        ExecutionPayload::TreasurySpend,
+        voting_period_seconds,
+        false,
+    );
+    store_spend_data(env, id, &spend);
+    id
+}
+
+/// Spend details of a `TreasurySpend` proposal, if any were attached.
+pub fn get_treasury_spend(env: &Env, proposal_id: u64) -> Option<TreasurySpendData> {
+    get_spend_data(env, proposal_id)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn new_proposal(
+    env: &Env,
+    guild_id: u64,
+    proposer: Address,
+    proposal_type: ProposalType,
+    title: String,
+    description: String,
+    execution_payload: ExecutionPayload,
+    voting_period_seconds: Option<u64>,
+    commit_reveal: bool,
 ) -> u64 {
     proposer.require_auth();
 
@@ -64,8 +215,12 @@ pub fn create_proposal(
 
     let cfg: GovernanceConfig = get_config(env, guild_id);
 
+    if !member.role.has_permission(&cfg.min_proposer_role) {
+        panic!("proposer role below guild minimum");
+    }
+
     // proposer reputation based on role weight
-    let reputation = crate::governance::types::role_weight(&member.role) as u32;
+    let reputation = cfg.role_weights.weight_of(&member.role) as u32;
     if reputation < cfg.min_proposer_reputation {
         panic!("insufficient reputation to create proposal");
     }
@@ -82,9 +237,14 @@ pub fn create_proposal(
 
     let id = get_next_proposal_id(env);
     let now = env.ledger().timestamp();
-    let voting_period_secs = (cfg.voting_period_days as u64) * 24 * 60 * 60;
+    let voting_end = now + cfg.voting_period_for(voting_period_seconds);
+    let reveal_end = if commit_reveal {
+        Some(voting_end + cfg.reveal_window_seconds)
+    } else {
+        None
+    };
 
-    let proposal = Proposal {
+    let mut proposal = Proposal {
         id,
         guild_id,
         proposer: proposer.clone(),
@@ -92,7 +252,7 @@ pub fn create_proposal(
         title,
         description,
         voting_start: now,
-        voting_end: now + voting_period_secs,
+        voting_end,
         status: ProposalStatus::Active,
         votes_for: 0,
         votes_against: 0,
@@ -100,9 +260,18 @@ pub fn create_proposal(
         execution_payload,
         passed_at: None,
         executed_at: None,
+        winning_option: None,
+        reveal_end,
+        extended: false,
+        total_eligible_weight: 0,
     };
+    proposal.total_eligible_weight = snapshot_voting_power(env, &proposal, &cfg);
 
     store_proposal(env, &proposal);
+    store_proposal_config(env, id, &cfg);
+    guild_storage::touch_member(env, guild_id, &proposer);
+
+    lock_bond(env, &cfg, id, &proposer);
 
     let event = ProposalCreatedEvent {
         proposal_id: id,
@@ -116,51 +285,263 @@ pub fn create_proposal(
     id
 }
 
+/// Open the guild vote arbitrating a disputed bounty. The bounty module has
+/// already authorized `opener` (its funder or claimer), who need not be a
+/// guild member; no proposal bond is taken.
+pub fn open_bounty_dispute(env: &Env, guild_id: u64, opener: Address, bounty_id: u64) -> u64 {
+    let cfg: GovernanceConfig = get_config(env, guild_id);
+
+    let id = get_next_proposal_id(env);
+    let now = env.ledger().timestamp();
+    let voting_period_secs = cfg.voting_period_for(None);
+
+    let mut proposal = Proposal {
+        id,
+        guild_id,
+        proposer: opener.clone(),
+        proposal_type: ProposalType::BountyDispute,
+        title: String::from_str(env, "Bounty dispute"),
+        description: String::from_str(
+            env,
+            "For pays the claimer; otherwise the funder is refunded",
+        ),
+        voting_start: now,
+        voting_end: now + voting_period_secs,
+        status: ProposalStatus::Active,
+        votes_for: 0,
+        votes_against: 0,
+        votes_abstain: 0,
+        execution_payload: ExecutionPayload::BountyDispute(bounty_id),
+        passed_at: None,
+        executed_at: None,
+        winning_option: None,
+        reveal_end: None,
+        extended: false,
+        total_eligible_weight: 0,
+    };
+    proposal.total_eligible_weight = snapshot_voting_power(env, &proposal, &cfg);
+
+    store_proposal(env, &proposal);
+    store_proposal_config(env, id, &cfg);
+
+    let event = ProposalCreatedEvent {
+        proposal_id: id,
+        guild_id,
+        proposer: opener,
+        proposal_type: ProposalType::BountyDispute,
+    };
+    emit_event(env, MOD_GOVERNANCE, ACT_PROPOSED, event);
+
+    id
+}
+
 pub fn cancel_proposal(env: &Env, proposal_id: u64, canceller: Address) -> bool {
     canceller.require_auth();
 
     let mut proposal =
         load_proposal(env, proposal_id).unwrap_or_else(|| panic!("proposal not found"));
 
-    if !matches!(
-        proposal.status,
-        ProposalStatus::Active | ProposalStatus::Draft
-    ) {
-        panic!("only active or draft proposals can be cancelled");
+    if !matches!(proposal.status, ProposalStatus::Active) {
+        panic!("only active proposals can be cancelled");
+    }
+    // the escrow goes to whichever side the vote favors; neither party
+    // (nor the owner) may settle it by pulling the proposal
+    if matches!(proposal.proposal_type, ProposalType::BountyDispute) {
+        panic!("bounty disputes are settled only by vote");
     }
 
-    // allow proposer or guild owner to cancel
-    let guild = guild_storage::get_guild(env, proposal.guild_id)
-        .unwrap_or_else(|| panic!("guild not found"));
+    let is_owner = get_member(env, proposal.guild_id, &canceller)
+        .map(|m| m.role == Role::Owner)
+        .unwrap_or(false);
 
-    if canceller != proposal.proposer && canceller != guild.owner {
-        panic!("only proposer or guild owner can cancel");
+    // owners may cancel any active proposal; proposers only until voting starts,
+    // so a proposal can't be pulled after it is seen to be losing
+    if !is_owner {
+        if canceller != proposal.proposer {
+            panic!("only proposer or guild owner can cancel");
+        }
+        if has_votes(env, proposal_id) {
+            panic!("proposal has votes; only a guild owner can cancel");
+        }
     }
 
     proposal.status = ProposalStatus::Cancelled;
     store_proposal(env, &proposal);
+    guild_storage::touch_member(env, proposal.guild_id, &canceller);
 
-    let event = crate::governance::types::ProposalCancelledEvent { proposal_id };
+    refund_bond(env, proposal_id);
+
+    let event = crate::governance::types::ProposalCancelledEvent {
+        proposal_id,
+        cancelled_by: canceller,
+    };
     emit_event(env, MOD_GOVERNANCE, ACT_CANCELLED, event);
 
     true
 }
 
+/// Push back the end of voting on an active proposal. Allowed once per
+/// proposal, by a guild owner or the proposer, and only while voting is still
+/// open; the whole period stays within the proposal's `max_voting_period_seconds`.
+pub fn extend_voting_period(
+    env: &Env,
+    proposal_id: u64,
+    additional_seconds: u64,
+    caller: Address,
+) -> u64 {
+    caller.require_auth();
+
+    let mut proposal =
+        load_proposal(env, proposal_id).unwrap_or_else(|| panic!("proposal not found"));
+
+    if !matches!(proposal.status, ProposalStatus::Active) {
+        panic!("only active proposals can be extended");
+    }
+    if env.ledger().timestamp() > proposal.voting_end {
+        panic!("voting period has ended");
+    }
+    if proposal.extended {
+        panic!("voting period already extended");
+    }
+
+    let is_owner = get_member(env, proposal.guild_id, &caller)
+        .map(|m| m.role == Role::Owner)
+        .unwrap_or(false);
+    if !is_owner && caller != proposal.proposer {
+        panic!("only proposer or guild owner can extend");
+    }
+
+    if additional_seconds == 0 {
+        panic!("extension must be positive");
+    }
+    let cfg = load_proposal_config(env, &proposal);
+    let new_voting_end = proposal.voting_end + additional_seconds;
+    if new_voting_end - proposal.voting_start > cfg.max_voting_period_seconds {
+        panic!("extension exceeds max voting period");
+    }
+
+    proposal.voting_end = new_voting_end;
+    proposal.reveal_end = proposal.reveal_end.map(|end| end + additional_seconds);
+    proposal.extended = true;
+    store_proposal(env, &proposal);
+    guild_storage::touch_member(env, proposal.guild_id, &caller);
+
+    let event = VotingPeriodExtendedEvent {
+        proposal_id,
+        extended_by: caller,
+        new_voting_end,
+    };
+    emit_event(env, MOD_GOVERNANCE, ACT_EXTENDED, event);
+
+    new_voting_end
+}
+
 pub fn get_proposal(env: &Env, proposal_id: u64) -> Proposal {
     load_proposal(env, proposal_id).unwrap_or_else(|| panic!("proposal not found"))
 }
 
-pub fn get_active_proposals(env: &Env, guild_id: u64) -> Vec<Proposal> {
-    let all = get_guild_proposals(env, guild_id);
+/// Governance rules a proposal is judged by, fixed when it was created.
+pub fn get_proposal_config(env: &Env, proposal_id: u64) -> GovernanceConfig {
+    load_proposal_config(env, &get_proposal(env, proposal_id))
+}
+
+/// Anchor a discussion comment to an active proposal. Only the content URI is
+/// stored; comments are append-only and cannot be edited or removed.
+pub fn add_proposal_comment(
+    env: &Env,
+    proposal_id: u64,
+    commenter: Address,
+    content_uri: String,
+) -> u32 {
+    commenter.require_auth();
+
+    let proposal = get_proposal(env, proposal_id);
+    if !matches!(proposal.status, ProposalStatus::Active) {
+        panic!("proposal not active");
+    }
+    if get_member(env, proposal.guild_id, &commenter).is_none() {
+        panic!("commenter must be guild member");
+    }
+    if content_uri.is_empty() || content_uri.len() > MAX_COMMENT_URI_LEN {
+        panic!("invalid content uri");
+    }
+
+    let comment = ProposalComment {
+        proposal_id,
+        author: commenter.clone(),
+        content_uri,
+        timestamp: env.ledger().timestamp(),
+    };
+    let index = append_comment(env, &comment);
+    guild_storage::touch_member(env, proposal.guild_id, &commenter);
+
+    let event = ProposalCommentedEvent {
+        proposal_id,
+        author: commenter,
+        index,
+    };
+    emit_event(env, MOD_GOVERNANCE, ACT_COMMENTED, event);
+
+    index
+}
+
+pub fn get_proposal_comments(env: &Env, proposal_id: u64) -> Vec<ProposalComment> {
+    get_comments(env, proposal_id)
+}
+
+/// Proposals of `guild_id` still open for voting: `Active` and not past
+/// `voting_end`. `proposal_type` narrows the scan to one type.
+fn scan_active_proposals(
+    env: &Env,
+    guild_id: u64,
+    proposal_type: Option<ProposalType>,
+) -> Vec<Proposal> {
+    let now = env.ledger().timestamp();
     let mut active = Vec::new(env);
-    for p in all.iter() {
-        if matches!(p.status, ProposalStatus::Active) {
-            active.push_back(p);
+    for p in get_guild_proposals(env, guild_id).iter() {
+        if !matches!(p.status, ProposalStatus::Active) || now > p.voting_end {
+            continue;
+        }
+        if proposal_type
+            .as_ref()
+            .is_some_and(|t| *t != p.proposal_type)
+        {
+            continue;
         }
+        active.push_back(p);
     }
     active
 }
 
+pub fn get_active_proposals(env: &Env, guild_id: u64) -> Vec<Proposal> {
+    scan_active_proposals(env, guild_id, None)
+}
+
+/// A guild's proposals in any status, newest first, at most `limit`. Pass
+/// the id of the last proposal of one page as `before_id` to get the next.
+pub fn get_all_proposals(
+    env: &Env,
+    guild_id: u64,
+    limit: u32,
+    before_id: Option<u64>,
+) -> Vec<Proposal> {
+    get_guild_proposals_page(env, guild_id, before_id, limit)
+}
+
+/// Number of proposals ever created in a guild, in any status.
+pub fn get_proposal_count(env: &Env, guild_id: u64) -> u64 {
+    get_guild_proposal_ids(env, guild_id).len() as u64
+}
+
+/// Active proposals of a single type, e.g. only `TreasurySpend`.
+pub fn get_active_proposals_by_type(
+    env: &Env,
+    guild_id: u64,
+    proposal_type: ProposalType,
+) -> Vec<Proposal> {
+    scan_active_proposals(env, guild_id, Some(proposal_type))
+}
+
 pub fn update_governance_config(
     env: &Env,
     guild_id: u64,
@@ -179,11 +560,55 @@ pub fn update_governance_config(
     if config.quorum_percentage == 0 || config.quorum_percentage > 100 {
         panic!("invalid quorum percentage");
     }
-    if config.approval_threshold == 0 || config.approval_threshold > 100 {
+    if config.pass_threshold_percentage == 0 || config.pass_threshold_percentage > 100 {
         panic!("invalid approval threshold");
     }
+    if let Some(overrides) = &config.type_thresholds {
+        for (_, thresholds) in overrides.iter() {
+            if let Some(q) = thresholds.quorum_percentage {
+                if q == 0 || q > 100 {
+                    panic!("invalid quorum percentage");
+                }
+            }
+            if let Some(a) = thresholds.approval_threshold {
+                if a == 0 || a > 100 {
+                    panic!("invalid approval threshold");
+                }
+            }
+        }
+    }
+    if config.min_voting_period_seconds == 0
+        || config.min_voting_period_seconds > config.max_voting_period_seconds
+    {
+        panic!("invalid voting period bounds");
+    }
+    if matches!(config.quorum_mode, QuorumMode::ActiveParticipation)
+        && config.active_window_seconds == 0
+    {
+        panic!("active window required for participation quorum");
+    }
+    if config.role_weights.owner == 0 {
+        panic!("invalid role weights");
+    }
+    if config.proposal_bond < 0 {
+        panic!("invalid proposal bond");
+    }
+    if config.proposal_bond > 0 {
+        if config.bond_token.is_none() {
+            panic!("bond token not configured");
+        }
+        let treasury_id = config
+            .bond_treasury_id
+            .unwrap_or_else(|| panic!("bond treasury not configured"));
+        let treasury = crate::treasury::storage::get_treasury(env, treasury_id)
+            .unwrap_or_else(|| panic!("treasury not found"));
+        if treasury.guild_id != guild_id {
+            panic!("bond treasury belongs to another guild");
+        }
+    }
 
     set_config(env, guild_id, &config);
+    guild_storage::touch_member(env, guild_id, &caller);
 
     let event = GovernanceConfigUpdatedEvent { guild_id };
     emit_event(env, MOD_GOVERNANCE, ACT_UPDATED, event);
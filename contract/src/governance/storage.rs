@@ -1,16 +1,34 @@
-﻿use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
+﻿use soroban_sdk::{symbol_short, Address, BytesN, Env, Map, Symbol, Vec};
 
-use crate::governance::types::{GovernanceConfig, Proposal, Vote};
+use crate::governance::types::{
+    ChoiceVote, GovernanceConfig, GovernanceConfigV2, Proposal, ProposalBond, ProposalComment,
+    ProposalExtras, ProposalType, StoredGovernanceConfig, StoredProposal, TreasurySpendData, Vote,
+    VoteDecision, VoterSnapshot,
+};
 
 const PROPOSALS_KEY: Symbol = symbol_short!("g_props");
+const PROPOSAL_EXTRAS_KEY: Symbol = symbol_short!("g_pextra");
 const PROPOSAL_COUNTER_KEY: Symbol = symbol_short!("g_pcnt");
 const GUILD_PROPOSALS_KEY: Symbol = symbol_short!("g_pidx");
+const SWEEP_CURSOR_KEY: Symbol = symbol_short!("g_sweep");
 
 const VOTES_KEY: Symbol = symbol_short!("g_votes");
+const MEMBER_VOTES_KEY: Symbol = symbol_short!("g_mvotes");
+const CHOICE_VOTES_KEY: Symbol = symbol_short!("g_cvotes");
+const COMMITMENTS_KEY: Symbol = symbol_short!("g_commit");
+
+const BONDS_KEY: Symbol = symbol_short!("g_bonds");
+
+const COMMENTS_KEY: Symbol = symbol_short!("g_cmnts");
 
 const DELEGATIONS_KEY: Symbol = symbol_short!("g_deleg");
+const TYPED_DELEGATIONS_KEY: Symbol = symbol_short!("g_tdeleg");
 
 const GOV_CONFIG_KEY: Symbol = symbol_short!("g_conf");
+const GOV_CONFIG_V2_KEY: Symbol = symbol_short!("g_conf_v2");
+const PROPOSAL_CONFIG_KEY: Symbol = symbol_short!("g_pconf");
+const VOTER_SNAPSHOTS_KEY: Symbol = symbol_short!("g_snap");
+const SPEND_DATA_KEY: Symbol = symbol_short!("g_spend");
 
 pub fn get_next_proposal_id(env: &Env) -> u64 {
     let current: u64 = env
@@ -23,15 +41,48 @@ pub fn get_next_proposal_id(env: &Env) -> u64 {
     next
 }
 
+/// Stored proposals and their side map of later state. Proposals are split so
+/// records written before that state existed still decode.
+struct ProposalMaps {
+    stored: Map<u64, StoredProposal>,
+    extras: Map<u64, ProposalExtras>,
+}
+
+impl ProposalMaps {
+    fn load(env: &Env) -> Self {
+        Self {
+            stored: env
+                .storage()
+                .persistent()
+                .get(&PROPOSALS_KEY)
+                .unwrap_or_else(|| Map::new(env)),
+            extras: env
+                .storage()
+                .persistent()
+                .get(&PROPOSAL_EXTRAS_KEY)
+                .unwrap_or_else(|| Map::new(env)),
+        }
+    }
+
+    fn get(&self, proposal_id: u64) -> Option<Proposal> {
+        let stored = self.stored.get(proposal_id)?;
+        let extras = self
+            .extras
+            .get(proposal_id)
+            .unwrap_or_else(ProposalExtras::default);
+        Some(Proposal::from_parts(stored, extras))
+    }
+}
+
 pub fn store_proposal(env: &Env, proposal: &Proposal) {
-    let mut proposals: Map<u64, Proposal> = env
-        .storage()
+    let mut maps = ProposalMaps::load(env);
+    let (stored, extras) = proposal.clone().into_parts();
+    maps.stored.set(proposal.id, stored);
+    maps.extras.set(proposal.id, extras);
+    env.storage().persistent().set(&PROPOSALS_KEY, &maps.stored);
+    env.storage()
         .persistent()
-        .get(&PROPOSALS_KEY)
-        .unwrap_or_else(|| Map::new(env));
-
-    proposals.set(proposal.id, proposal.clone());
-    env.storage().persistent().set(&PROPOSALS_KEY, &proposals);
+        .set(&PROPOSAL_EXTRAS_KEY, &maps.extras);
 
     // index by guild
     let mut index: Map<u64, Vec<u64>> = env
@@ -51,30 +102,72 @@ pub fn store_proposal(env: &Env, proposal: &Proposal) {
 }
 
 pub fn get_proposal(env: &Env, proposal_id: u64) -> Option<Proposal> {
-    let proposals: Map<u64, Proposal> = env
+    ProposalMaps::load(env).get(proposal_id)
+}
+
+/// Ids of every proposal created in a guild, oldest first.
+pub fn get_guild_proposal_ids(env: &Env, guild_id: u64) -> Vec<u64> {
+    let index: Map<u64, Vec<u64>> = env
         .storage()
         .persistent()
-        .get(&PROPOSALS_KEY)
+        .get(&GUILD_PROPOSALS_KEY)
         .unwrap_or_else(|| Map::new(env));
 
-    proposals.get(proposal_id)
+    index.get(guild_id).unwrap_or_else(|| Vec::new(env))
 }
 
-pub fn get_guild_proposals(env: &Env, guild_id: u64) -> Vec<Proposal> {
-    let index: Map<u64, Vec<u64>> = env
+/// Up to `limit` of a guild's proposals, newest first, keeping only ids
+/// below `before_id` when given.
+pub fn get_guild_proposals_page(
+    env: &Env,
+    guild_id: u64,
+    before_id: Option<u64>,
+    limit: u32,
+) -> Vec<Proposal> {
+    let proposals = ProposalMaps::load(env);
+
+    let mut page = Vec::new(env);
+    for id in get_guild_proposal_ids(env, guild_id).iter().rev() {
+        if page.len() >= limit {
+            break;
+        }
+        if before_id.is_some_and(|before| id >= before) {
+            continue;
+        }
+        if let Some(p) = proposals.get(id) {
+            page.push_back(p);
+        }
+    }
+    page
+}
+
+/// Position in a guild's proposal index before which every proposal is
+/// settled, so `sweep_proposals` can start past them.
+pub fn get_sweep_cursor(env: &Env, guild_id: u64) -> u32 {
+    let cursors: Map<u64, u32> = env
         .storage()
         .persistent()
-        .get(&GUILD_PROPOSALS_KEY)
+        .get(&SWEEP_CURSOR_KEY)
         .unwrap_or_else(|| Map::new(env));
 
-    let ids = index.get(guild_id).unwrap_or_else(|| Vec::new(env));
+    cursors.get(guild_id).unwrap_or(0)
+}
 
-    let proposals: Map<u64, Proposal> = env
+pub fn set_sweep_cursor(env: &Env, guild_id: u64, cursor: u32) {
+    let mut cursors: Map<u64, u32> = env
         .storage()
         .persistent()
-        .get(&PROPOSALS_KEY)
+        .get(&SWEEP_CURSOR_KEY)
         .unwrap_or_else(|| Map::new(env));
 
+    cursors.set(guild_id, cursor);
+    env.storage().persistent().set(&SWEEP_CURSOR_KEY, &cursors);
+}
+
+pub fn get_guild_proposals(env: &Env, guild_id: u64) -> Vec<Proposal> {
+    let ids = get_guild_proposal_ids(env, guild_id);
+    let proposals = ProposalMaps::load(env);
+
     let mut result = Vec::new(env);
     for id in ids.iter() {
         if let Some(p) = proposals.get(id) {
@@ -99,6 +192,39 @@ pub fn store_vote(env: &Env, vote: &Vote) {
     votes_map.set(vote.proposal_id, proposal_votes);
 
     env.storage().persistent().set(&VOTES_KEY, &votes_map);
+
+    if let Some(proposal) = get_proposal(env, vote.proposal_id) {
+        index_member_vote(env, proposal.guild_id, vote);
+    }
+}
+
+/// Per-voter index of decisions, keyed by proposal id so a re-vote replaces
+/// the earlier entry.
+fn index_member_vote(env: &Env, guild_id: u64, vote: &Vote) {
+    let mut index: Map<(u64, Address), Map<u64, VoteDecision>> = env
+        .storage()
+        .persistent()
+        .get(&MEMBER_VOTES_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let key = (guild_id, vote.voter.clone());
+    let mut member_votes = index.get(key.clone()).unwrap_or_else(|| Map::new(env));
+    member_votes.set(vote.proposal_id, vote.decision.clone());
+    index.set(key, member_votes);
+
+    env.storage().persistent().set(&MEMBER_VOTES_KEY, &index);
+}
+
+pub fn get_member_votes(env: &Env, guild_id: u64, voter: &Address) -> Map<u64, VoteDecision> {
+    let index: Map<(u64, Address), Map<u64, VoteDecision>> = env
+        .storage()
+        .persistent()
+        .get(&MEMBER_VOTES_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    index
+        .get((guild_id, voter.clone()))
+        .unwrap_or_else(|| Map::new(env))
 }
 
 #[allow(dead_code)]
@@ -123,6 +249,118 @@ pub fn get_all_votes(env: &Env, proposal_id: u64) -> Map<Address, Vote> {
     votes_map.get(proposal_id).unwrap_or_else(|| Map::new(env))
 }
 
+pub fn store_choice_vote(env: &Env, vote: &ChoiceVote) {
+    let mut votes_map: Map<u64, Map<Address, ChoiceVote>> = env
+        .storage()
+        .persistent()
+        .get(&CHOICE_VOTES_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let mut proposal_votes = votes_map
+        .get(vote.proposal_id)
+        .unwrap_or_else(|| Map::new(env));
+
+    proposal_votes.set(vote.voter.clone(), vote.clone());
+    votes_map.set(vote.proposal_id, proposal_votes);
+
+    env.storage()
+        .persistent()
+        .set(&CHOICE_VOTES_KEY, &votes_map);
+}
+
+pub fn get_all_choice_votes(env: &Env, proposal_id: u64) -> Map<Address, ChoiceVote> {
+    let votes_map: Map<u64, Map<Address, ChoiceVote>> = env
+        .storage()
+        .persistent()
+        .get(&CHOICE_VOTES_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    votes_map.get(proposal_id).unwrap_or_else(|| Map::new(env))
+}
+
+pub fn store_commitment(env: &Env, proposal_id: u64, voter: &Address, commitment: &BytesN<32>) {
+    let mut commitments: Map<u64, Map<Address, BytesN<32>>> = env
+        .storage()
+        .persistent()
+        .get(&COMMITMENTS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let mut proposal_commitments = commitments
+        .get(proposal_id)
+        .unwrap_or_else(|| Map::new(env));
+
+    proposal_commitments.set(voter.clone(), commitment.clone());
+    commitments.set(proposal_id, proposal_commitments);
+
+    env.storage()
+        .persistent()
+        .set(&COMMITMENTS_KEY, &commitments);
+}
+
+pub fn get_commitment(env: &Env, proposal_id: u64, voter: &Address) -> Option<BytesN<32>> {
+    let commitments: Map<u64, Map<Address, BytesN<32>>> = env
+        .storage()
+        .persistent()
+        .get(&COMMITMENTS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    commitments
+        .get(proposal_id)
+        .and_then(|proposal_commitments| proposal_commitments.get(voter.clone()))
+}
+
+pub fn remove_commitment(env: &Env, proposal_id: u64, voter: &Address) {
+    let mut commitments: Map<u64, Map<Address, BytesN<32>>> = env
+        .storage()
+        .persistent()
+        .get(&COMMITMENTS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    if let Some(mut proposal_commitments) = commitments.get(proposal_id) {
+        proposal_commitments.remove(voter.clone());
+        commitments.set(proposal_id, proposal_commitments);
+        env.storage()
+            .persistent()
+            .set(&COMMITMENTS_KEY, &commitments);
+    }
+}
+
+pub fn clear_commitments(env: &Env, proposal_id: u64) {
+    let mut commitments: Map<u64, Map<Address, BytesN<32>>> = env
+        .storage()
+        .persistent()
+        .get(&COMMITMENTS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    if commitments.contains_key(proposal_id) {
+        commitments.remove(proposal_id);
+        env.storage()
+            .persistent()
+            .set(&COMMITMENTS_KEY, &commitments);
+    }
+}
+
+/// Whether any vote, choice vote or commitment has been recorded for a proposal
+pub fn has_votes(env: &Env, proposal_id: u64) -> bool {
+    if !get_all_votes(env, proposal_id).is_empty() {
+        return true;
+    }
+    if !get_all_choice_votes(env, proposal_id).is_empty() {
+        return true;
+    }
+
+    let commitments: Map<u64, Map<Address, BytesN<32>>> = env
+        .storage()
+        .persistent()
+        .get(&COMMITMENTS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    commitments
+        .get(proposal_id)
+        .map(|c| !c.is_empty())
+        .unwrap_or(false)
+}
+
 pub fn set_delegation(env: &Env, guild_id: u64, delegator: &Address, delegate: &Address) {
     let mut delegations: Map<(u64, Address), Address> = env
         .storage()
@@ -151,6 +389,108 @@ pub fn remove_delegation(env: &Env, guild_id: u64, delegator: &Address) {
     }
 }
 
+/// Drop `member`'s own delegations in `guild_id`, general and type-scoped,
+/// and every delegation pointing at them, so those delegators fall back to
+/// voting for themselves.
+pub fn clear_member_delegations(env: &Env, guild_id: u64, member: &Address) {
+    let mut delegations: Map<(u64, Address), Address> = env
+        .storage()
+        .persistent()
+        .get(&DELEGATIONS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let mut changed = false;
+    for ((gid, delegator), delegate) in delegations.clone().iter() {
+        if gid == guild_id && (delegator == *member || delegate == *member) {
+            delegations.remove((gid, delegator));
+            changed = true;
+        }
+    }
+    if changed {
+        env.storage()
+            .persistent()
+            .set(&DELEGATIONS_KEY, &delegations);
+    }
+
+    let mut typed: Map<(u64, Address, ProposalType), Address> = env
+        .storage()
+        .persistent()
+        .get(&TYPED_DELEGATIONS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let mut typed_changed = false;
+    for ((gid, delegator, proposal_type), delegate) in typed.clone().iter() {
+        if gid == guild_id && (delegator == *member || delegate == *member) {
+            typed.remove((gid, delegator, proposal_type));
+            typed_changed = true;
+        }
+    }
+    if typed_changed {
+        env.storage()
+            .persistent()
+            .set(&TYPED_DELEGATIONS_KEY, &typed);
+    }
+}
+
+/// Delegation that applies only to proposals of `proposal_type`.
+pub fn set_typed_delegation(
+    env: &Env,
+    guild_id: u64,
+    delegator: &Address,
+    proposal_type: &ProposalType,
+    delegate: &Address,
+) {
+    let mut typed: Map<(u64, Address, ProposalType), Address> = env
+        .storage()
+        .persistent()
+        .get(&TYPED_DELEGATIONS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    typed.set(
+        (guild_id, delegator.clone(), proposal_type.clone()),
+        delegate.clone(),
+    );
+    env.storage()
+        .persistent()
+        .set(&TYPED_DELEGATIONS_KEY, &typed);
+}
+
+pub fn remove_typed_delegation(
+    env: &Env,
+    guild_id: u64,
+    delegator: &Address,
+    proposal_type: &ProposalType,
+) {
+    let mut typed: Map<(u64, Address, ProposalType), Address> = env
+        .storage()
+        .persistent()
+        .get(&TYPED_DELEGATIONS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let key = (guild_id, delegator.clone(), proposal_type.clone());
+    if typed.contains_key(key.clone()) {
+        typed.remove(key);
+        env.storage()
+            .persistent()
+            .set(&TYPED_DELEGATIONS_KEY, &typed);
+    }
+}
+
+pub fn get_typed_delegate(
+    env: &Env,
+    guild_id: u64,
+    delegator: &Address,
+    proposal_type: &ProposalType,
+) -> Option<Address> {
+    let typed: Map<(u64, Address, ProposalType), Address> = env
+        .storage()
+        .persistent()
+        .get(&TYPED_DELEGATIONS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    typed.get((guild_id, delegator.clone(), proposal_type.clone()))
+}
+
 pub fn get_delegate(env: &Env, guild_id: u64, delegator: &Address) -> Option<Address> {
     let delegations: Map<(u64, Address), Address> = env
         .storage()
@@ -161,25 +501,172 @@ pub fn get_delegate(env: &Env, guild_id: u64, delegator: &Address) -> Option<Add
     delegations.get((guild_id, delegator.clone()))
 }
 
+/// A guild's governance config. The original fields and the later settings
+/// are stored under separate keys so configs written before the settings
+/// existed still decode; a guild without V2 settings gets their defaults.
 pub fn get_config(env: &Env, guild_id: u64) -> GovernanceConfig {
-    let configs: Map<u64, GovernanceConfig> = env
+    let configs: Map<u64, StoredGovernanceConfig> = env
         .storage()
         .persistent()
         .get(&GOV_CONFIG_KEY)
         .unwrap_or_else(|| Map::new(env));
+    let Some(stored) = configs.get(guild_id) else {
+        return GovernanceConfig::default();
+    };
 
-    configs
+    let v2_configs: Map<u64, GovernanceConfigV2> = env
+        .storage()
+        .persistent()
+        .get(&GOV_CONFIG_V2_KEY)
+        .unwrap_or_else(|| Map::new(env));
+    let v2 = v2_configs
         .get(guild_id)
-        .unwrap_or_else(GovernanceConfig::default)
+        .unwrap_or_else(GovernanceConfigV2::default);
+
+    GovernanceConfig::from_parts(stored, v2)
 }
 
 pub fn set_config(env: &Env, guild_id: u64, config: &GovernanceConfig) {
-    let mut configs: Map<u64, GovernanceConfig> = env
+    let (stored, v2) = config.clone().into_parts();
+
+    let mut configs: Map<u64, StoredGovernanceConfig> = env
         .storage()
         .persistent()
         .get(&GOV_CONFIG_KEY)
         .unwrap_or_else(|| Map::new(env));
-
-    configs.set(guild_id, config.clone());
+    configs.set(guild_id, stored);
     env.storage().persistent().set(&GOV_CONFIG_KEY, &configs);
+
+    let mut v2_configs: Map<u64, GovernanceConfigV2> = env
+        .storage()
+        .persistent()
+        .get(&GOV_CONFIG_V2_KEY)
+        .unwrap_or_else(|| Map::new(env));
+    v2_configs.set(guild_id, v2);
+    env.storage()
+        .persistent()
+        .set(&GOV_CONFIG_V2_KEY, &v2_configs);
+}
+
+/// Record the governance rules a proposal was created under.
+pub fn store_proposal_config(env: &Env, proposal_id: u64, config: &GovernanceConfig) {
+    let mut configs: Map<u64, GovernanceConfig> = env
+        .storage()
+        .persistent()
+        .get(&PROPOSAL_CONFIG_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    configs.set(proposal_id, config.clone());
+    env.storage()
+        .persistent()
+        .set(&PROPOSAL_CONFIG_KEY, &configs);
+}
+
+/// Rules a proposal is bound by: its creation-time snapshot, or the live
+/// guild config for proposals created before snapshots were recorded.
+pub fn get_proposal_config(env: &Env, proposal: &Proposal) -> GovernanceConfig {
+    let configs: Map<u64, GovernanceConfig> = env
+        .storage()
+        .persistent()
+        .get(&PROPOSAL_CONFIG_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    configs
+        .get(proposal.id)
+        .unwrap_or_else(|| get_config(env, proposal.guild_id))
+}
+
+pub fn store_voter_snapshots(env: &Env, proposal_id: u64, voters: &Map<Address, VoterSnapshot>) {
+    let mut snapshots: Map<u64, Map<Address, VoterSnapshot>> = env
+        .storage()
+        .persistent()
+        .get(&VOTER_SNAPSHOTS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    snapshots.set(proposal_id, voters.clone());
+    env.storage()
+        .persistent()
+        .set(&VOTER_SNAPSHOTS_KEY, &snapshots);
+}
+
+pub fn get_voter_snapshots(env: &Env, proposal_id: u64) -> Option<Map<Address, VoterSnapshot>> {
+    let snapshots: Map<u64, Map<Address, VoterSnapshot>> = env
+        .storage()
+        .persistent()
+        .get(&VOTER_SNAPSHOTS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    snapshots.get(proposal_id)
+}
+
+pub fn store_spend_data(env: &Env, proposal_id: u64, spend: &TreasurySpendData) {
+    let mut spends: Map<u64, TreasurySpendData> = env
+        .storage()
+        .persistent()
+        .get(&SPEND_DATA_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    spends.set(proposal_id, spend.clone());
+    env.storage().persistent().set(&SPEND_DATA_KEY, &spends);
+}
+
+pub fn get_spend_data(env: &Env, proposal_id: u64) -> Option<TreasurySpendData> {
+    let spends: Map<u64, TreasurySpendData> = env
+        .storage()
+        .persistent()
+        .get(&SPEND_DATA_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    spends.get(proposal_id)
+}
+
+/// Append a comment to a proposal's thread, returning its index. Comments
+/// are never rewritten once stored.
+pub fn append_comment(env: &Env, comment: &ProposalComment) -> u32 {
+    let mut comments: Map<u64, Vec<ProposalComment>> = env
+        .storage()
+        .persistent()
+        .get(&COMMENTS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let mut thread = comments
+        .get(comment.proposal_id)
+        .unwrap_or_else(|| Vec::new(env));
+    let index = thread.len();
+    thread.push_back(comment.clone());
+    comments.set(comment.proposal_id, thread);
+    env.storage().persistent().set(&COMMENTS_KEY, &comments);
+
+    index
+}
+
+pub fn get_comments(env: &Env, proposal_id: u64) -> Vec<ProposalComment> {
+    let comments: Map<u64, Vec<ProposalComment>> = env
+        .storage()
+        .persistent()
+        .get(&COMMENTS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    comments.get(proposal_id).unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn store_bond(env: &Env, bond: &ProposalBond) {
+    let mut bonds: Map<u64, ProposalBond> = env
+        .storage()
+        .persistent()
+        .get(&BONDS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    bonds.set(bond.proposal_id, bond.clone());
+    env.storage().persistent().set(&BONDS_KEY, &bonds);
+}
+
+pub fn get_bond(env: &Env, proposal_id: u64) -> Option<ProposalBond> {
+    let bonds: Map<u64, ProposalBond> = env
+        .storage()
+        .persistent()
+        .get(&BONDS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    bonds.get(proposal_id)
 }
@@ -1,15 +1,20 @@
 ﻿#[cfg(test)]
 mod tests {
-    use crate::governance::{proposals, storage};
+    use crate::governance::{proposals, storage, voting};
     use crate::governance::types::{
-        ExecutionPayload, GovernanceConfig, Proposal, ProposalStatus, ProposalType, Vote,
+        BondStatus, ExecutionPayload, FinalizeExecuteOutcome, GovernanceConfig, Proposal,
+        ProposalFinalizedEvent, ProposalStatus, ProposalThresholds, ProposalType, QuorumMode,
+        RoleWeights, StoredGovernanceConfig, StoredProposal, TreasuryConfigData, TreasurySpendData, Vote,
         VoteDecision,
     };
+    use crate::treasury::types::WithdrawalCategory;
     use crate::guild::types::Role;
     use crate::StellarGuildsContract;
     use crate::StellarGuildsContractClient;
-    use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
-    use soroban_sdk::{Address, Env, String};
+    use soroban_sdk::testutils::{Address as _, Events as _, Ledger, LedgerInfo};
+    use soroban_sdk::{
+        symbol_short, token, Address, Bytes, BytesN, Env, FromVal, IntoVal, Map, String, Symbol, Vec,
+    };
 
     fn setup_env() -> Env {
         let env = Env::default();
@@ -81,6 +86,7 @@ mod tests {
             &ProposalType::GeneralDecision,
             &String::from_str(&env, "Test Proposal"),
             &String::from_str(&env, "Description"),
+            &None,
         );
 
         assert_eq!(proposal_id, 1);
@@ -91,6 +97,49 @@ mod tests {
         assert_eq!(proposal.status, ProposalStatus::Active);
     }
 
+    #[test]
+    #[should_panic(expected = "proposer role below guild minimum")]
+    fn test_min_proposer_role_blocks_contributor() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let (guild_id, _admin, member, contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+        client.update_governance_config(
+            &guild_id,
+            &owner,
+            &GovernanceConfig {
+                min_proposer_role: Role::Member,
+                ..GovernanceConfig::default()
+            },
+        );
+
+        // Members still meet the bar
+        client.create_proposal(
+            &guild_id,
+            &member,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Allowed"),
+            &String::from_str(&env, "Member proposal"),
+            &None,
+        );
+
+        client.create_proposal(
+            &guild_id,
+            &contributor,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Blocked"),
+            &String::from_str(&env, "Contributor proposal"),
+            &None,
+        );
+    }
+
     #[test]
     fn test_vote_weights_and_execution() {
         let env = setup_env();
@@ -111,6 +160,7 @@ mod tests {
             &ProposalType::GeneralDecision,
             &String::from_str(&env, "Test Proposal"),
             &String::from_str(&env, "Description"),
+            &None,
         );
 
         client.vote(&proposal_id, &owner, &VoteDecision::For);
@@ -138,6 +188,105 @@ mod tests {
         assert_eq!(final_proposal.status, ProposalStatus::Executed);
     }
 
+    fn choice_options(env: &Env) -> Vec<String> {
+        let mut options = Vec::new(env);
+        options.push_back(String::from_str(env, "Grant A"));
+        options.push_back(String::from_str(env, "Grant B"));
+        options.push_back(String::from_str(env, "Grant C"));
+        options
+    }
+
+    #[test]
+    fn test_multiple_choice_selects_highest_weight() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let (guild_id, admin, member, contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let proposal_id = client.create_choice_proposal(
+            &guild_id,
+            &owner,
+            &String::from_str(&env, "Which grant?"),
+            &String::from_str(&env, "Pick one grant to fund"),
+            &choice_options(&env),
+        );
+
+        client.vote_choice(&proposal_id, &owner, &1);
+        client.vote_choice(&proposal_id, &admin, &2);
+        client.vote_choice(&proposal_id, &member, &2);
+        client.vote_choice(&proposal_id, &contributor, &0);
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+
+        let status = client.finalize_proposal(&proposal_id);
+        assert_eq!(status, ProposalStatus::Passed);
+        assert_eq!(client.get_proposal(&proposal_id).winning_option, Some(1));
+
+        assert!(client.execute_proposal(&proposal_id, &owner));
+    }
+
+    #[test]
+    fn test_multiple_choice_tie_resolves_to_lowest_index() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        let co_owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+        client.add_member(&guild_id, &co_owner, &Role::Owner, &owner);
+
+        let proposal_id = client.create_choice_proposal(
+            &guild_id,
+            &owner,
+            &String::from_str(&env, "Which grant?"),
+            &String::from_str(&env, "Tie break"),
+            &choice_options(&env),
+        );
+
+        client.vote_choice(&proposal_id, &owner, &2);
+        client.vote_choice(&proposal_id, &co_owner, &1);
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+
+        assert_eq!(client.finalize_proposal(&proposal_id), ProposalStatus::Passed);
+        assert_eq!(client.get_proposal(&proposal_id).winning_option, Some(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid option index")]
+    fn test_vote_choice_rejects_out_of_range_option() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        let proposal_id = client.create_choice_proposal(
+            &guild_id,
+            &owner,
+            &String::from_str(&env, "Which grant?"),
+            &String::from_str(&env, "Out of range"),
+            &choice_options(&env),
+        );
+
+        client.vote_choice(&proposal_id, &owner, &3);
+    }
+
     #[test]
     fn test_vote_delegation_and_execution() {
         let env = setup_env();
@@ -152,17 +301,18 @@ mod tests {
         let (guild_id, admin, member, contributor) =
             setup_guild_with_members(&env, &client, &owner);
 
+        client.delegate_vote(&guild_id, &member, &admin, &None);
+        client.delegate_vote(&guild_id, &contributor, &member, &None);
+
         let proposal_id = client.create_proposal(
             &guild_id,
             &owner,
             &ProposalType::GeneralDecision,
             &String::from_str(&env, "Delegation Proposal"),
             &String::from_str(&env, "Delegation"),
+            &None,
         );
 
-        client.delegate_vote(&guild_id, &member, &admin);
-        client.delegate_vote(&guild_id, &contributor, &member);
-
         client.vote(&proposal_id, &admin, &VoteDecision::For);
 
         let proposal = client.get_proposal(&proposal_id);
@@ -180,6 +330,52 @@ mod tests {
         assert!(is_executed);
     }
 
+    #[test]
+    fn test_type_scoped_delegation_overrides_general() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let (guild_id, admin, member, contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let rule_change = Some(ProposalType::RuleChange);
+        client.delegate_vote(&guild_id, &member, &admin, &None);
+        client.delegate_vote(&guild_id, &member, &owner, &rule_change);
+        client.delegate_vote(&guild_id, &contributor, &owner, &rule_change);
+
+        let tally = |proposal_type: ProposalType| {
+            let proposal_id = client.create_proposal(
+                &guild_id,
+                &owner,
+                &proposal_type,
+                &String::from_str(&env, "Scoped"),
+                &String::from_str(&env, "Scoped delegation"),
+                &None,
+            );
+            client.vote(&proposal_id, &admin, &VoteDecision::For);
+            client.vote(&proposal_id, &owner, &VoteDecision::Against);
+            let end = client.get_proposal(&proposal_id).voting_end;
+            set_ledger_timestamp(&env, end + 1);
+            client.finalize_proposal(&proposal_id);
+            let proposal = client.get_proposal(&proposal_id);
+            (proposal.votes_for, proposal.votes_against)
+        };
+
+        // owner 10, admin 5, member 2, contributor 1
+        assert_eq!(tally(ProposalType::GeneralDecision), (7, 10));
+        assert_eq!(tally(ProposalType::RuleChange), (5, 13));
+
+        // clearing the scoped delegation falls back to the general one
+        client.undelegate_vote(&guild_id, &member, &rule_change);
+        assert_eq!(tally(ProposalType::RuleChange), (7, 11));
+    }
+
     #[test]
     #[should_panic(expected = "only passed proposals can be executed")]
     fn test_quorum_rejection_prevents_execution() {
@@ -201,6 +397,7 @@ mod tests {
             &ProposalType::GeneralDecision,
             &String::from_str(&env, "Low Quorum"),
             &String::from_str(&env, "Low quorum"),
+            &None,
         );
 
         client.vote(&proposal_id, &contributor, &VoteDecision::For);
@@ -216,6 +413,90 @@ mod tests {
         client.execute_proposal(&proposal_id, &owner);
     }
 
+    #[test]
+    fn test_config_in_original_layout_still_loads() {
+        let env = setup_env();
+        env.mock_all_auths();
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        env.as_contract(&contract_id, || {
+            let key = symbol_short!("g_conf");
+            let mut legacy: Map<u64, StoredGovernanceConfig> = Map::new(&env);
+            legacy.set(
+                guild_id,
+                StoredGovernanceConfig {
+                    quorum_percentage: 40,
+                    approval_threshold: 75,
+                    voting_period_days: 3,
+                    min_proposer_reputation: 0,
+                },
+            );
+            env.storage().persistent().set(&key, &legacy);
+
+            let mut cfg = storage::get_config(&env, guild_id);
+            assert_eq!(cfg.quorum_percentage, 40);
+            assert_eq!(cfg.pass_threshold_percentage, 75);
+            assert_eq!(cfg.proposal_bond, 0);
+            assert_eq!(cfg.role_weights, RoleWeights::default());
+
+            cfg.tenure_bonus_per_year = 2;
+            storage::set_config(&env, guild_id, &cfg);
+            let stored: Map<u64, StoredGovernanceConfig> =
+                env.storage().persistent().get(&key).unwrap();
+            assert_eq!(stored.get(guild_id).unwrap().approval_threshold, 75);
+            assert_eq!(storage::get_config(&env, guild_id), cfg);
+        });
+    }
+
+    #[test]
+    fn test_proposal_in_original_layout_still_loads() {
+        let env = setup_env();
+        env.mock_all_auths();
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        env.as_contract(&contract_id, || {
+            let mut legacy: Map<u64, StoredProposal> = Map::new(&env);
+            legacy.set(
+                7,
+                StoredProposal {
+                    id: 7,
+                    guild_id,
+                    proposer: owner.clone(),
+                    proposal_type: ProposalType::GeneralDecision,
+                    title: String::from_str(&env, "Legacy"),
+                    description: String::from_str(&env, "Stored before extras"),
+                    voting_start: 100,
+                    voting_end: 200,
+                    status: ProposalStatus::Active,
+                    votes_for: 0,
+                    votes_against: 0,
+                    votes_abstain: 0,
+                    execution_payload: ExecutionPayload::GeneralDecision,
+                    passed_at: None,
+                    executed_at: None,
+                },
+            );
+            env.storage()
+                .persistent()
+                .set(&symbol_short!("g_props"), &legacy);
+
+            let mut proposal = storage::get_proposal(&env, 7).unwrap();
+            assert_eq!(proposal.title, String::from_str(&env, "Legacy"));
+            assert_eq!(proposal.reveal_end, None);
+            assert!(!proposal.extended);
+
+            proposal.extended = true;
+            storage::store_proposal(&env, &proposal);
+            assert!(storage::get_proposal(&env, 7).unwrap().extended);
+        });
+    }
+
     #[test]
     fn test_storage_round_trip_for_votes_delegations_and_configs() {
         let env = setup_env();
@@ -245,6 +526,10 @@ mod tests {
                 execution_payload: ExecutionPayload::GeneralDecision,
                 passed_at: None,
                 executed_at: None,
+                winning_option: None,
+                reveal_end: None,
+                extended: false,
+                total_eligible_weight: 0,
             };
 
             storage::store_proposal(&env, &proposal);
@@ -274,15 +559,125 @@ mod tests {
             assert_eq!(storage::get_config(&env, guild_id), GovernanceConfig::default());
             let updated = GovernanceConfig {
                 quorum_percentage: 45,
-                approval_threshold: 70,
+                pass_threshold_percentage: 70,
                 voting_period_days: 5,
                 min_proposer_reputation: 2,
+                min_proposer_role: Role::Contributor,
+                proposal_bond: 0,
+                bond_token: None,
+                bond_treasury_id: None,
+                reveal_window_seconds: 86_400,
+                type_thresholds: None,
+                tenure_bonus_per_year: 0,
+                max_tenure_bonus: 0,
+                execution_delay_seconds: 0,
+                min_voting_period_seconds: 24 * 60 * 60,
+                max_voting_period_seconds: 30 * 24 * 60 * 60,
+                quorum_mode: QuorumMode::TotalMembership,
+                active_window_seconds: 30 * 24 * 60 * 60,
+                abstain_counts_for_quorum: true,
+                tie_breaks_for: false,
+                role_weights: RoleWeights::default(),
             };
             storage::set_config(&env, guild_id, &updated);
             assert_eq!(storage::get_config(&env, guild_id), updated);
         });
     }
 
+    #[test]
+    fn test_active_proposals_by_type_and_voting_window() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Decision"),
+            &String::from_str(&env, "not a spend"),
+            &None,
+        );
+        let spend = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::TreasurySpend,
+            &String::from_str(&env, "Spend"),
+            &String::from_str(&env, "pay someone"),
+            &None,
+        );
+
+        let spends = client.get_active_proposals_by_type(&guild_id, &ProposalType::TreasurySpend);
+        assert_eq!(spends.len(), 1);
+        assert_eq!(spends.get(0).unwrap().id, spend);
+        assert_eq!(client.get_active_proposals(&guild_id).len(), 2);
+        assert_eq!(
+            client
+                .get_active_proposals_by_type(&guild_id, &ProposalType::RuleChange)
+                .len(),
+            0
+        );
+
+        // past voting_end a proposal awaiting finalization is no longer active
+        let end = client.get_proposal(&spend).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+        assert_eq!(client.get_active_proposals(&guild_id).len(), 0);
+        assert_eq!(
+            client
+                .get_active_proposals_by_type(&guild_id, &ProposalType::TreasurySpend)
+                .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_all_proposals_pages_history_newest_first() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+        let other_guild = setup_guild(&client, &env, &owner);
+
+        let mut ids = Vec::new(&env);
+        for guild in [guild_id, other_guild, guild_id, guild_id] {
+            ids.push_back(client.create_proposal(
+                &guild,
+                &owner,
+                &ProposalType::GeneralDecision,
+                &String::from_str(&env, "Decision"),
+                &String::from_str(&env, "history"),
+                &None,
+            ));
+        }
+        client.cancel_proposal(&ids.get(0).unwrap(), &owner);
+        assert_eq!(client.get_proposal_count(&guild_id), 3);
+        assert_eq!(client.get_proposal_count(&other_guild), 1);
+
+        // finished proposals stay in the history
+        let first_page = client.get_all_proposals(&guild_id, &2, &None);
+        assert_eq!(first_page.len(), 2);
+        assert_eq!(first_page.get(0).unwrap().id, ids.get(3).unwrap());
+        assert_eq!(first_page.get(1).unwrap().id, ids.get(2).unwrap());
+
+        let cursor = first_page.get(1).unwrap().id;
+        let second_page = client.get_all_proposals(&guild_id, &2, &Some(cursor));
+        assert_eq!(second_page.len(), 1);
+        let cancelled = second_page.get(0).unwrap();
+        assert_eq!(cancelled.id, ids.get(0).unwrap());
+        assert_eq!(cancelled.status, ProposalStatus::Cancelled);
+    }
+
     #[test]
     fn test_cancel_proposal_updates_active_list_and_config() {
         let env = setup_env();
@@ -299,6 +694,7 @@ mod tests {
             &ProposalType::GeneralDecision,
             &String::from_str(&env, "A"),
             &String::from_str(&env, "first"),
+            &None,
         );
         let proposal_b = client.create_proposal(
             &guild_id,
@@ -306,6 +702,7 @@ mod tests {
             &ProposalType::GeneralDecision,
             &String::from_str(&env, "B"),
             &String::from_str(&env, "second"),
+            &None,
         );
 
         assert_eq!(client.get_active_proposals(&guild_id).len(), 2);
@@ -316,9 +713,25 @@ mod tests {
 
         let new_cfg = GovernanceConfig {
             quorum_percentage: 40,
-            approval_threshold: 66,
+            pass_threshold_percentage: 66,
             voting_period_days: 10,
             min_proposer_reputation: 1,
+            min_proposer_role: Role::Contributor,
+            proposal_bond: 0,
+            bond_token: None,
+            bond_treasury_id: None,
+            reveal_window_seconds: 86_400,
+            type_thresholds: None,
+            tenure_bonus_per_year: 0,
+            max_tenure_bonus: 0,
+            execution_delay_seconds: 0,
+            min_voting_period_seconds: 24 * 60 * 60,
+            max_voting_period_seconds: 30 * 24 * 60 * 60,
+            quorum_mode: QuorumMode::TotalMembership,
+            active_window_seconds: 30 * 24 * 60 * 60,
+            abstain_counts_for_quorum: true,
+            tie_breaks_for: false,
+            role_weights: RoleWeights::default(),
         };
         assert!(client.update_governance_config(&guild_id, &owner, &new_cfg));
 
@@ -347,6 +760,7 @@ mod tests {
                 String::from_str(&env, "Bad payload"),
                 String::from_str(&env, "mismatch"),
                 ExecutionPayload::GeneralDecision,
+                None,
             );
         });
     }
@@ -367,10 +781,2315 @@ mod tests {
             &owner,
             &GovernanceConfig {
                 quorum_percentage: 0,
-                approval_threshold: 60,
+                pass_threshold_percentage: 60,
                 voting_period_days: 7,
                 min_proposer_reputation: 0,
+                min_proposer_role: Role::Contributor,
+                proposal_bond: 0,
+                bond_token: None,
+                bond_treasury_id: None,
+                reveal_window_seconds: 86_400,
+                type_thresholds: None,
+                tenure_bonus_per_year: 0,
+                max_tenure_bonus: 0,
+                execution_delay_seconds: 0,
+                min_voting_period_seconds: 24 * 60 * 60,
+                max_voting_period_seconds: 30 * 24 * 60 * 60,
+                quorum_mode: QuorumMode::TotalMembership,
+                active_window_seconds: 30 * 24 * 60 * 60,
+                abstain_counts_for_quorum: true,
+                tie_breaks_for: false,
+                role_weights: RoleWeights::default(),
+            },
+        );
+    }
+
+    fn setup_bonded_guild(
+        env: &Env,
+        client: &StellarGuildsContractClient<'_>,
+        owner: &Address,
+        bond: i128,
+    ) -> (u64, Address, Address, Address, Address, u64) {
+        let (guild_id, admin, member, contributor) = setup_guild_with_members(env, client, owner);
+
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+        let token_admin = token::StellarAssetClient::new(env, &token);
+        token_admin.mint(owner, &1_000);
+        token_admin.mint(&admin, &1_000);
+
+        let mut signers = Vec::new(env);
+        signers.push_back(owner.clone());
+        let treasury_id = client.initialize_treasury(&guild_id, &signers, &1u32, &false, &false, &Vec::new(env));
+
+        client.update_governance_config(
+            &guild_id,
+            owner,
+            &GovernanceConfig {
+                proposal_bond: bond,
+                bond_token: Some(token.clone()),
+                bond_treasury_id: Some(treasury_id),
+                ..GovernanceConfig::default()
             },
         );
+
+        (guild_id, admin, member, contributor, token, treasury_id)
+    }
+
+    #[test]
+    fn test_proposal_bond_refunded_after_quorum() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor, token, _treasury_id) =
+            setup_bonded_guild(&env, &client, &owner, 100);
+        let balances = token::TokenClient::new(&env, &token);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &admin,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Bonded"),
+            &String::from_str(&env, "Needs a bond"),
+            &None,
+        );
+        assert_eq!(balances.balance(&admin), 900);
+        assert_eq!(
+            client.get_proposal_bond(&proposal_id).unwrap().status,
+            BondStatus::Locked
+        );
+
+        // owner votes against: quorum reached, proposal rejected, bond still refundable
+        client.vote(&proposal_id, &owner, &VoteDecision::Against);
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+        assert_eq!(client.finalize_proposal(&proposal_id), ProposalStatus::Rejected);
+        assert_eq!(
+            client.get_proposal_bond(&proposal_id).unwrap().status,
+            BondStatus::Refundable
+        );
+
+        assert!(client.claim_proposal_bond(&proposal_id, &admin));
+        assert_eq!(balances.balance(&admin), 1_000);
+        assert_eq!(
+            client.get_proposal_bond(&proposal_id).unwrap().status,
+            BondStatus::Refunded
+        );
+    }
+
+    #[test]
+    fn test_proposal_bond_forfeited_without_quorum() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor, token, treasury_id) =
+            setup_bonded_guild(&env, &client, &owner, 100);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &admin,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Spam"),
+            &String::from_str(&env, "Nobody votes"),
+            &None,
+        );
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+        assert_eq!(client.finalize_proposal(&proposal_id), ProposalStatus::Rejected);
+
+        assert_eq!(
+            client.get_proposal_bond(&proposal_id).unwrap().status,
+            BondStatus::Forfeited
+        );
+        assert_eq!(
+            client.get_treasury_balance(&treasury_id, &Some(token.clone())),
+            100
+        );
+        assert_eq!(token::TokenClient::new(&env, &token).balance(&admin), 900);
+    }
+
+    #[test]
+    fn test_cancelled_proposal_refunds_bond() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor, token, _treasury_id) =
+            setup_bonded_guild(&env, &client, &owner, 100);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &admin,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Withdrawn"),
+            &String::from_str(&env, "Cancelled by proposer"),
+            &None,
+        );
+        assert!(client.cancel_proposal(&proposal_id, &admin));
+
+        assert_eq!(token::TokenClient::new(&env, &token).balance(&admin), 1_000);
+        assert_eq!(
+            client.get_proposal_bond(&proposal_id).unwrap().status,
+            BondStatus::Refunded
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "bond was forfeited")]
+    fn test_claim_forfeited_bond_fails() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor, _token, _treasury_id) =
+            setup_bonded_guild(&env, &client, &owner, 100);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &admin,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Spam"),
+            &String::from_str(&env, "Nobody votes"),
+            &None,
+        );
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+        client.finalize_proposal(&proposal_id);
+
+        client.claim_proposal_bond(&proposal_id, &admin);
+    }
+
+    fn create_passed_proposal(
+        env: &Env,
+        client: &StellarGuildsContractClient<'_>,
+        guild_id: u64,
+        owner: &Address,
+        admin: &Address,
+    ) -> u64 {
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            admin,
+            &ProposalType::GeneralDecision,
+            &String::from_str(env, "Passes"),
+            &String::from_str(env, "Will be vetoed"),
+            &None,
+        );
+        client.vote(&proposal_id, owner, &VoteDecision::For);
+        client.vote(&proposal_id, admin, &VoteDecision::For);
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(env, end + 1);
+        assert_eq!(client.finalize_proposal(&proposal_id), ProposalStatus::Passed);
+        proposal_id
+    }
+
+    #[test]
+    #[should_panic(expected = "only passed proposals can be executed")]
+    fn test_veto_blocks_execution() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let proposal_id = create_passed_proposal(&env, &client, guild_id, &owner, &admin);
+
+        assert!(client.veto_proposal(
+            &proposal_id,
+            &owner,
+            &String::from_str(&env, "unsafe change")
+        ));
+        assert_eq!(client.get_proposal(&proposal_id).status, ProposalStatus::Vetoed);
+
+        client.execute_proposal(&proposal_id, &admin);
+    }
+
+    #[test]
+    #[should_panic(expected = "only guild owner can veto")]
+    fn test_veto_requires_owner_role() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let proposal_id = create_passed_proposal(&env, &client, guild_id, &owner, &admin);
+
+        client.veto_proposal(&proposal_id, &admin, &String::from_str(&env, "not mine"));
+    }
+
+    #[test]
+    #[should_panic(expected = "veto window expired")]
+    fn test_veto_rejected_after_execution_window() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let proposal_id = create_passed_proposal(&env, &client, guild_id, &owner, &admin);
+        let passed_at = client.get_proposal(&proposal_id).passed_at.unwrap();
+        set_ledger_timestamp(&env, passed_at + 3 * 24 * 60 * 60 + 1);
+
+        client.veto_proposal(&proposal_id, &owner, &String::from_str(&env, "too late"));
+    }
+
+    fn commitment(
+        env: &Env,
+        contract_id: &Address,
+        decision: &VoteDecision,
+        salt: &Bytes,
+    ) -> BytesN<32> {
+        env.as_contract(contract_id, || voting::vote_commitment(env, decision, salt))
+    }
+
+    #[test]
+    fn test_commit_reveal_counts_only_revealed_votes() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let proposal_id = client.create_commit_reveal_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Private vote"),
+            &String::from_str(&env, "Hidden tally"),
+        );
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.reveal_end, Some(proposal.voting_end + 86_400));
+
+        let owner_salt = Bytes::from_slice(&env, b"owner-salt");
+        let admin_salt = Bytes::from_slice(&env, b"admin-salt");
+        let member_salt = Bytes::from_slice(&env, b"member-salt");
+        client.commit_vote(
+            &proposal_id,
+            &owner,
+            &commitment(&env, &contract_id, &VoteDecision::For, &owner_salt),
+        );
+        client.commit_vote(
+            &proposal_id,
+            &admin,
+            &commitment(&env, &contract_id, &VoteDecision::For, &admin_salt),
+        );
+        client.commit_vote(
+            &proposal_id,
+            &member,
+            &commitment(&env, &contract_id, &VoteDecision::Against, &member_salt),
+        );
+
+        set_ledger_timestamp(&env, proposal.voting_end + 1);
+        client.reveal_vote(&proposal_id, &owner, &VoteDecision::For, &owner_salt);
+        client.reveal_vote(&proposal_id, &admin, &VoteDecision::For, &admin_salt);
+        // member never reveals: their commitment is discarded
+
+        set_ledger_timestamp(&env, proposal.reveal_end.unwrap() + 1);
+        assert_eq!(client.finalize_proposal(&proposal_id), ProposalStatus::Passed);
+
+        let finalized = client.get_proposal(&proposal_id);
+        assert_eq!(finalized.votes_for, 15);
+        assert_eq!(finalized.votes_against, 0);
+        env.as_contract(&contract_id, || {
+            assert_eq!(storage::get_commitment(&env, proposal_id, &member), None);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "commitment mismatch")]
+    fn test_reveal_with_wrong_decision_fails() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        let proposal_id = client.create_commit_reveal_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Private vote"),
+            &String::from_str(&env, "Mismatched reveal"),
+        );
+
+        let salt = Bytes::from_slice(&env, b"salt");
+        client.commit_vote(
+            &proposal_id,
+            &owner,
+            &commitment(&env, &contract_id, &VoteDecision::Against, &salt),
+        );
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+        client.reveal_vote(&proposal_id, &owner, &VoteDecision::For, &salt);
+    }
+
+    #[test]
+    #[should_panic(expected = "reveal phase not started")]
+    fn test_reveal_during_voting_fails() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        let proposal_id = client.create_commit_reveal_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Private vote"),
+            &String::from_str(&env, "Early reveal"),
+        );
+
+        let salt = Bytes::from_slice(&env, b"salt");
+        client.commit_vote(
+            &proposal_id,
+            &owner,
+            &commitment(&env, &contract_id, &VoteDecision::For, &salt),
+        );
+        client.reveal_vote(&proposal_id, &owner, &VoteDecision::For, &salt);
+    }
+
+    fn finalize_with_40_percent_turnout(proposal_type: ProposalType) -> ProposalStatus {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+        // two more contributors bring the total weight to 20
+        client.add_member(&guild_id, &Address::generate(&env), &Role::Contributor, &owner);
+        client.add_member(&guild_id, &Address::generate(&env), &Role::Contributor, &owner);
+
+        let mut overrides = Map::new(&env);
+        overrides.set(
+            ProposalType::TreasurySpend,
+            ProposalThresholds {
+                quorum_percentage: Some(60),
+                approval_threshold: None,
+            },
+        );
+        client.update_governance_config(
+            &guild_id,
+            &owner,
+            &GovernanceConfig {
+                type_thresholds: Some(overrides),
+                ..GovernanceConfig::default()
+            },
+        );
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &admin,
+            &proposal_type,
+            &String::from_str(&env, "Turnout"),
+            &String::from_str(&env, "40% of weight votes"),
+            &None,
+        );
+
+        // 5 + 2 + 1 = 8 of 20
+        client.vote(&proposal_id, &admin, &VoteDecision::For);
+        client.vote(&proposal_id, &member, &VoteDecision::For);
+        client.vote(&proposal_id, &contributor, &VoteDecision::For);
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+        client.finalize_proposal(&proposal_id)
+    }
+
+    #[test]
+    fn test_type_quorum_override_rejects_treasury_spend() {
+        assert_eq!(
+            finalize_with_40_percent_turnout(ProposalType::TreasurySpend),
+            ProposalStatus::Rejected
+        );
+    }
+
+    #[test]
+    fn test_general_decision_uses_global_quorum() {
+        assert_eq!(
+            finalize_with_40_percent_turnout(ProposalType::GeneralDecision),
+            ProposalStatus::Passed
+        );
+    }
+
+    #[test]
+    fn test_custom_role_weights_apply_to_new_proposals_only() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let title = String::from_str(&env, "Weights");
+        let description = String::from_str(&env, "custom role weights");
+        let before = client.create_proposal(
+            &guild_id,
+            &admin,
+            &ProposalType::GeneralDecision,
+            &title,
+            &description,
+            &None,
+        );
+
+        // a flat guild: every role carries the same weight
+        let flat = RoleWeights {
+            owner: 1,
+            admin: 1,
+            member: 1,
+            contributor: 1,
+        };
+        client.update_governance_config(
+            &guild_id,
+            &owner,
+            &GovernanceConfig {
+                role_weights: flat,
+                ..GovernanceConfig::default()
+            },
+        );
+        let after = client.create_proposal(
+            &guild_id,
+            &admin,
+            &ProposalType::GeneralDecision,
+            &title,
+            &description,
+            &None,
+        );
+
+        for proposal_id in [before, after] {
+            client.vote(&proposal_id, &owner, &VoteDecision::For);
+            client.vote(&proposal_id, &contributor, &VoteDecision::For);
+            client.vote(&proposal_id, &admin, &VoteDecision::Against);
+            client.vote(&proposal_id, &member, &VoteDecision::Against);
+        }
+
+        let end = client.get_proposal(&after).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+        assert_eq!(client.finalize_proposal(&before), ProposalStatus::Passed);
+        assert_eq!(client.finalize_proposal(&after), ProposalStatus::Rejected);
+
+        // the earlier proposal keeps the built-in 10 / 5 / 2 / 1 weights
+        let old = client.get_proposal(&before);
+        assert_eq!((old.votes_for, old.votes_against), (11, 7));
+        let new = client.get_proposal(&after);
+        assert_eq!((new.votes_for, new.votes_against), (2, 2));
+    }
+
+    #[test]
+    fn test_thresholds_for_falls_back_to_global() {
+        let env = setup_env();
+        let mut overrides = Map::new(&env);
+        overrides.set(
+            ProposalType::RuleChange,
+            ProposalThresholds {
+                quorum_percentage: None,
+                approval_threshold: Some(75),
+            },
+        );
+        let cfg = GovernanceConfig {
+            type_thresholds: Some(overrides),
+            ..GovernanceConfig::default()
+        };
+
+        assert_eq!(cfg.thresholds_for(&ProposalType::RuleChange), (30, 75));
+        assert_eq!(cfg.thresholds_for(&ProposalType::AddMember), (30, 60));
+    }
+
+    #[test]
+    fn test_tenure_bonus_uses_delegator_tenure() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        let veteran = Address::generate(&env);
+        let newcomer = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+        client.add_member(&guild_id, &veteran, &Role::Member, &owner);
+
+        client.update_governance_config(
+            &guild_id,
+            &owner,
+            &GovernanceConfig {
+                tenure_bonus_per_year: 3,
+                max_tenure_bonus: 5,
+                ..GovernanceConfig::default()
+            },
+        );
+
+        // two years later a new member joins and receives the veteran's delegation
+        set_ledger_timestamp(&env, 1000 + 2 * 365 * 24 * 60 * 60);
+        client.add_member(&guild_id, &newcomer, &Role::Member, &owner);
+        client.delegate_vote(&guild_id, &veteran, &newcomer, &None);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Tenure"),
+            &String::from_str(&env, "Delegated tenure"),
+            &None,
+        );
+        client.vote(&proposal_id, &newcomer, &VoteDecision::For);
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+        assert_eq!(client.finalize_proposal(&proposal_id), ProposalStatus::Passed);
+
+        // veteran: 2 + min(2 * 3, 5) = 7, newcomer: 2 + 0
+        assert_eq!(client.get_proposal(&proposal_id).votes_for, 9);
+    }
+
+    #[test]
+    fn test_get_voting_weight_aggregates_delegation_chain() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        assert_eq!(client.get_voting_weight(&guild_id, &admin), 5);
+
+        // contributor -> member -> admin
+        client.delegate_vote(&guild_id, &contributor, &member, &None);
+        client.delegate_vote(&guild_id, &member, &admin, &None);
+
+        assert_eq!(client.get_voting_weight(&guild_id, &admin), 8);
+        assert_eq!(client.get_voting_weight(&guild_id, &member), 0);
+        assert_eq!(client.get_voting_weight(&guild_id, &contributor), 0);
+        assert_eq!(client.get_voting_weight(&guild_id, &owner), 10);
+        assert_eq!(
+            client.get_voting_weight(&guild_id, &Address::generate(&env)),
+            0
+        );
+    }
+
+    #[test]
+    fn test_get_voting_weight_does_not_double_count_cycles() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        // write a cycle directly; delegate_vote itself refuses to create one
+        env.as_contract(&contract_id, || {
+            storage::set_delegation(&env, guild_id, &admin, &member);
+            storage::set_delegation(&env, guild_id, &member, &contributor);
+            storage::set_delegation(&env, guild_id, &contributor, &admin);
+        });
+
+        let cycle_total = client.get_voting_weight(&guild_id, &admin)
+            + client.get_voting_weight(&guild_id, &member)
+            + client.get_voting_weight(&guild_id, &contributor);
+        assert_eq!(cycle_total, 8);
+    }
+
+    #[test]
+    fn test_delegation_target_and_delegators() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        // contributor -> member -> admin, owner votes for themselves
+        client.delegate_vote(&guild_id, &contributor, &member, &None);
+        client.delegate_vote(&guild_id, &member, &admin, &None);
+
+        assert_eq!(client.get_delegation_target(&guild_id, &contributor), admin);
+        assert_eq!(client.get_delegation_target(&guild_id, &member), admin);
+        assert_eq!(client.get_delegation_target(&guild_id, &owner), owner);
+
+        let to_admin = client.get_delegators(&guild_id, &admin);
+        assert_eq!(to_admin.len(), 1);
+        assert_eq!(to_admin.get(0).unwrap(), member);
+        assert_eq!(client.get_delegators(&guild_id, &member).get(0).unwrap(), contributor);
+        assert_eq!(client.get_delegators(&guild_id, &owner).len(), 0);
+    }
+
+    #[test]
+    fn test_removing_delegate_restores_self_voting() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        client.delegate_vote(&guild_id, &member, &admin, &None);
+        client.delegate_vote(&guild_id, &admin, &owner, &None);
+        assert!(client.remove_member(&guild_id, &admin, &owner));
+
+        // both the incoming and the outgoing delegation are gone
+        assert_eq!(client.get_delegation_target(&guild_id, &member), member);
+        assert_eq!(client.get_delegators(&guild_id, &admin).len(), 0);
+        env.as_contract(&contract_id, || {
+            assert_eq!(storage::get_delegate(&env, guild_id, &admin), None);
+        });
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "After removal"),
+            &String::from_str(&env, "Member votes directly"),
+            &None,
+        );
+        client.vote(&proposal_id, &member, &VoteDecision::For);
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+        client.finalize_proposal(&proposal_id);
+        assert_eq!(client.get_proposal(&proposal_id).votes_for, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "delegation cycle detected")]
+    fn test_triangle_delegation_is_rejected() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        client.delegate_vote(&guild_id, &admin, &member, &None);
+        client.delegate_vote(&guild_id, &member, &contributor, &None);
+        // closing the triangle must fail
+        client.delegate_vote(&guild_id, &contributor, &admin, &None);
+    }
+
+    #[test]
+    #[should_panic(expected = "delegation chain too long")]
+    fn test_delegation_chain_length_is_bounded() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        let mut chain: Vec<Address> = Vec::new(&env);
+        for _ in 0..18 {
+            let addr = Address::generate(&env);
+            client.add_member(&guild_id, &addr, &Role::Member, &owner);
+            chain.push_back(addr);
+        }
+
+        // build the chain from its tail so each new link extends it by one
+        for i in (0..17).rev() {
+            client.delegate_vote(
+                &guild_id,
+                &chain.get(i).unwrap(),
+                &chain.get(i + 1).unwrap(),
+                &None,
+            );
+        }
+    }
+
+    fn create_passed_treasury_proposal(
+        env: &Env,
+        client: &StellarGuildsContractClient<'_>,
+        owner: &Address,
+        delay: u64,
+    ) -> u64 {
+        create_passed_spend(env, client, owner, delay, 400).0
+    }
+
+    /// Passed proposal spending `amount` from a treasury holding 1000;
+    /// returns the proposal and treasury ids.
+    fn create_passed_spend(
+        env: &Env,
+        client: &StellarGuildsContractClient<'_>,
+        owner: &Address,
+        delay: u64,
+        amount: i128,
+    ) -> (u64, u64) {
+        let (guild_id, admin, _member, _contributor) = setup_guild_with_members(env, client, owner);
+        let mut signers = Vec::new(env);
+        signers.push_back(owner.clone());
+        let treasury_id = client.initialize_treasury(&guild_id, &signers, &1u32, &false, &false, &Vec::new(env));
+        client.deposit_treasury(&treasury_id, owner, &1_000i128, &None, &None, &None);
+
+        client.update_governance_config(
+            &guild_id,
+            owner,
+            &GovernanceConfig {
+                execution_delay_seconds: delay,
+                min_voting_period_seconds: 24 * 60 * 60,
+                max_voting_period_seconds: 30 * 24 * 60 * 60,
+                quorum_mode: QuorumMode::TotalMembership,
+                active_window_seconds: 30 * 24 * 60 * 60,
+                ..GovernanceConfig::default()
+            },
+        );
+
+        let proposal_id = client.create_treasury_spend_proposal(
+            &guild_id,
+            &admin,
+            &String::from_str(env, "Spend"),
+            &String::from_str(env, "Timelocked spend"),
+            &TreasurySpendData {
+                treasury_id,
+                amount,
+                token: None,
+                recipient: Address::generate(env),
+                category: WithdrawalCategory::Grant,
+                reason: String::from_str(env, "grant"),
+            },
+            &None,
+        );
+        client.vote(&proposal_id, owner, &VoteDecision::For);
+        client.vote(&proposal_id, &admin, &VoteDecision::For);
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(env, end + 1);
+        assert_eq!(client.finalize_proposal(&proposal_id), ProposalStatus::Passed);
+        (proposal_id, treasury_id)
+    }
+
+    #[test]
+    fn test_treasury_spend_proposal_disburses_funds() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (proposal_id, treasury_id) = create_passed_spend(&env, &client, &owner, 0, 400);
+
+        assert!(client.execute_proposal(&proposal_id, &owner));
+        assert_eq!(client.get_proposal(&proposal_id).status, ProposalStatus::Executed);
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 600);
+
+        let spend = client.get_treasury_spend(&proposal_id).unwrap();
+        let history = client.get_transaction_history(&treasury_id, &10u32);
+        let paid = history.get(history.len() - 1).unwrap();
+        assert_eq!(paid.amount, 400);
+        assert_eq!(paid.recipient, Some(spend.recipient));
+        assert_eq!(paid.category, WithdrawalCategory::Grant);
+    }
+
+    #[test]
+    fn test_treasury_spend_over_balance_marks_failed() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (proposal_id, treasury_id) = create_passed_spend(&env, &client, &owner, 0, 5_000);
+
+        assert!(!client.execute_proposal(&proposal_id, &owner));
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.status, ProposalStatus::Failed);
+        assert!(proposal.executed_at.is_none());
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 1_000);
+
+        // a failed spend is final
+        assert!(client.try_execute_proposal(&proposal_id, &owner).is_err());
+    }
+
+    #[test]
+    fn test_treasury_spend_over_budget_marks_failed() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (proposal_id, treasury_id) = create_passed_spend(&env, &client, &owner, 0, 400);
+        client.set_budget(&treasury_id, &WithdrawalCategory::Grant, &300i128, &0u64, &owner);
+
+        assert_eq!(
+            client.finalize_and_execute(&proposal_id, &owner),
+            FinalizeExecuteOutcome::Failed
+        );
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 1_000);
+    }
+
+    #[test]
+    fn test_treasury_spend_to_unlisted_payee_marks_failed() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (proposal_id, treasury_id) = create_passed_spend(&env, &client, &owner, 0, 400);
+        let payees = Vec::from_array(&env, [Address::generate(&env)]);
+        client.set_payee_allowlist(&treasury_id, &payees, &owner);
+
+        assert!(!client.execute_proposal(&proposal_id, &owner));
+        assert_eq!(client.get_proposal(&proposal_id).status, ProposalStatus::Failed);
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 1_000);
+    }
+
+    #[test]
+    fn test_treasury_config_proposal_updates_threshold_after_vote() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+        let mut signers = Vec::new(&env);
+        signers.push_back(owner.clone());
+        let treasury_id = client.initialize_treasury(&guild_id, &signers, &1u32, &false, &false, &Vec::new(&env));
+
+        // a threshold the new signers cannot meet is refused up front
+        let mut new_signers = Vec::new(&env);
+        new_signers.push_back(owner.clone());
+        new_signers.push_back(admin.clone());
+        new_signers.push_back(member.clone());
+        let too_high = TreasuryConfigData {
+            treasury_id,
+            approval_threshold: 4,
+            signers: Some(new_signers.clone()),
+        };
+        assert!(client
+            .try_create_treasury_config_proposal(
+                &guild_id,
+                &admin,
+                &String::from_str(&env, "Signers"),
+                &String::from_str(&env, "Too strict"),
+                &too_high,
+                &None,
+            )
+            .is_err());
+
+        let proposal_id = client.create_treasury_config_proposal(
+            &guild_id,
+            &admin,
+            &String::from_str(&env, "Signers"),
+            &String::from_str(&env, "Add signers, 2-of-3"),
+            &TreasuryConfigData {
+                treasury_id,
+                approval_threshold: 2,
+                signers: Some(new_signers),
+            },
+            &None,
+        );
+        client.vote(&proposal_id, &owner, &VoteDecision::For);
+        client.vote(&proposal_id, &admin, &VoteDecision::For);
+
+        // nothing changes until the vote passes and the proposal runs
+        assert_eq!(client.get_treasury(&treasury_id).approval_threshold, 1);
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+        assert!(client.execute_proposal(&proposal_id, &owner));
+
+        let treasury = client.get_treasury(&treasury_id);
+        assert_eq!(treasury.approval_threshold, 2);
+        assert_eq!(treasury.signers.len(), 3);
+        assert!(treasury.signers.contains(&member));
+    }
+
+    #[test]
+    fn test_treasury_config_proposal_keeps_tiers_satisfiable() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+        let signers = Vec::from_array(&env, [owner.clone(), admin.clone(), member.clone()]);
+        let treasury_id =
+            client.initialize_treasury(&guild_id, &signers, &2u32, &false, &false, &Vec::new(&env));
+        client.set_approval_tiers(
+            &treasury_id,
+            &Vec::from_array(&env, [(100_000i128, 3u32)]),
+            &owner,
+        );
+
+        // dropping to two signers would leave the three-signature tier unreachable
+        let shrink = TreasuryConfigData {
+            treasury_id,
+            approval_threshold: 2,
+            signers: Some(Vec::from_array(&env, [owner.clone(), admin.clone()])),
+        };
+        assert!(client
+            .try_create_treasury_config_proposal(
+                &guild_id,
+                &admin,
+                &String::from_str(&env, "Signers"),
+                &String::from_str(&env, "Drop a signer"),
+                &shrink,
+                &None,
+            )
+            .is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "timelock not elapsed")]
+    fn test_execute_rejected_before_timelock() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let proposal_id = create_passed_treasury_proposal(&env, &client, &owner, 2 * 24 * 60 * 60);
+
+        client.execute_proposal(&proposal_id, &owner);
+    }
+
+    #[test]
+    fn test_execution_window_starts_after_timelock() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let delay = 5 * 24 * 60 * 60;
+        let proposal_id = create_passed_treasury_proposal(&env, &client, &owner, delay);
+
+        // past the 3-day deadline measured from pass time, but inside the window after the delay
+        let passed_at = client.get_proposal(&proposal_id).passed_at.unwrap();
+        set_ledger_timestamp(&env, passed_at + delay + 2 * 24 * 60 * 60);
+
+        assert!(client.execute_proposal(&proposal_id, &owner));
+        assert_eq!(client.get_proposal(&proposal_id).status, ProposalStatus::Executed);
+    }
+
+    #[test]
+    fn test_proposer_cancels_before_votes_and_owner_after() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let unvoted = client.create_proposal(
+            &guild_id,
+            &admin,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Unvoted"),
+            &String::from_str(&env, "Proposer withdraws"),
+            &None,
+        );
+        assert!(client.cancel_proposal(&unvoted, &admin));
+        assert_eq!(client.get_proposal(&unvoted).status, ProposalStatus::Cancelled);
+
+        let voted = client.create_proposal(
+            &guild_id,
+            &admin,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Voted"),
+            &String::from_str(&env, "Owner cancels"),
+            &None,
+        );
+        client.vote(&voted, &member, &VoteDecision::Against);
+        assert!(client.cancel_proposal(&voted, &owner));
+        assert_eq!(client.get_proposal(&voted).status, ProposalStatus::Cancelled);
+    }
+
+    #[test]
+    #[should_panic(expected = "proposal has votes; only a guild owner can cancel")]
+    fn test_proposer_cannot_cancel_after_votes() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &admin,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Losing"),
+            &String::from_str(&env, "Proposer wants out"),
+            &None,
+        );
+        client.vote(&proposal_id, &member, &VoteDecision::Against);
+
+        client.cancel_proposal(&proposal_id, &admin);
+    }
+
+    #[test]
+    #[should_panic(expected = "only active proposals can be cancelled")]
+    fn test_cancel_non_active_proposal_fails() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Twice"),
+            &String::from_str(&env, "Cancelled already"),
+            &None,
+        );
+        client.cancel_proposal(&proposal_id, &owner);
+        client.cancel_proposal(&proposal_id, &owner);
+    }
+
+    #[test]
+    fn test_extend_voting_period_keeps_votes() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &admin,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Needs time"),
+            &String::from_str(&env, "More deliberation"),
+            &None,
+        );
+        client.vote(&proposal_id, &member, &VoteDecision::For);
+        let old_end = client.get_proposal(&proposal_id).voting_end;
+
+        let new_end = client.extend_voting_period(&proposal_id, &(3 * 24 * 60 * 60), &admin);
+        assert_eq!(new_end, old_end + 3 * 24 * 60 * 60);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.voting_end, new_end);
+        assert!(proposal.extended);
+
+        // voting stays open past the original end
+        set_ledger_timestamp(&env, old_end + 1);
+        client.vote(&proposal_id, &owner, &VoteDecision::For);
+
+        set_ledger_timestamp(&env, new_end + 1);
+        client.finalize_proposal(&proposal_id);
+        assert_eq!(client.get_proposal(&proposal_id).votes_for, 12);
+    }
+
+    #[test]
+    #[should_panic(expected = "voting period already extended")]
+    fn test_extend_voting_period_only_once() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Twice"),
+            &String::from_str(&env, "Extended already"),
+            &None,
+        );
+        client.extend_voting_period(&proposal_id, &60, &owner);
+        client.extend_voting_period(&proposal_id, &60, &owner);
+    }
+
+    #[test]
+    #[should_panic(expected = "extension exceeds max voting period")]
+    fn test_extend_voting_period_bounded_by_max() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Forever"),
+            &String::from_str(&env, "Too long"),
+            &None,
+        );
+        // 7-day default period + 24 days exceeds the 30-day maximum
+        client.extend_voting_period(&proposal_id, &(24 * 24 * 60 * 60), &owner);
+    }
+
+    #[test]
+    #[should_panic(expected = "only proposer or guild owner can extend")]
+    fn test_extend_voting_period_rejects_other_members() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &admin,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Not yours"),
+            &String::from_str(&env, "Member tries to extend"),
+            &None,
+        );
+        client.extend_voting_period(&proposal_id, &60, &member);
+    }
+
+    #[test]
+    #[should_panic(expected = "voting period has ended")]
+    fn test_extend_expired_proposal_fails() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Late"),
+            &String::from_str(&env, "Already over"),
+            &None,
+        );
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+        client.extend_voting_period(&proposal_id, &60, &owner);
+    }
+
+    #[test]
+    fn test_get_votes_by_member_tracks_latest_decision() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let first = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "First"),
+            &String::from_str(&env, "History"),
+            &None,
+        );
+        let second = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Second"),
+            &String::from_str(&env, "History"),
+            &None,
+        );
+
+        client.vote(&first, &admin, &VoteDecision::For);
+        client.vote(&second, &admin, &VoteDecision::Abstain);
+        // changing the vote replaces the recorded decision
+        client.vote(&first, &admin, &VoteDecision::Against);
+
+        let history = client.get_votes_by_member(&guild_id, &admin);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.get(0).unwrap(), (first, VoteDecision::Against));
+        assert_eq!(history.get(1).unwrap(), (second, VoteDecision::Abstain));
+
+        assert_eq!(client.get_votes_by_member(&guild_id, &owner).len(), 0);
+    }
+
+    #[test]
+    fn test_simulate_finalize_previews_without_mutating() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Preview"),
+            &String::from_str(&env, "Dry run"),
+            &None,
+        );
+
+        // a lone contributor vote cannot reach quorum
+        client.vote(&proposal_id, &contributor, &VoteDecision::For);
+        assert_eq!(
+            client.simulate_finalize(&proposal_id),
+            ProposalStatus::Rejected
+        );
+
+        client.vote(&proposal_id, &owner, &VoteDecision::For);
+        client.vote(&proposal_id, &admin, &VoteDecision::For);
+        assert_eq!(client.simulate_finalize(&proposal_id), ProposalStatus::Passed);
+
+        // still mid-vote and untouched
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.status, ProposalStatus::Active);
+        assert_eq!(proposal.votes_for, 0);
+        assert!(proposal.passed_at.is_none());
+
+        set_ledger_timestamp(&env, proposal.voting_end + 1);
+        assert_eq!(
+            client.finalize_proposal(&proposal_id),
+            client.simulate_finalize(&proposal_id)
+        );
+    }
+
+    #[test]
+    fn test_is_quorum_met_tracks_votes_mid_vote() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Quorum"),
+            &String::from_str(&env, "Live check"),
+            &None,
+        );
+        assert!(!client.is_quorum_met(&proposal_id));
+
+        client.vote(&proposal_id, &contributor, &VoteDecision::For);
+        assert!(!client.is_quorum_met(&proposal_id));
+
+        // quorum is reached even though the outcome is a rejection
+        client.vote(&proposal_id, &owner, &VoteDecision::Against);
+        client.vote(&proposal_id, &admin, &VoteDecision::Against);
+        assert!(client.is_quorum_met(&proposal_id));
+        assert_eq!(
+            client.simulate_finalize(&proposal_id),
+            ProposalStatus::Rejected
+        );
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Active
+        );
+    }
+
+    #[test]
+    fn test_early_rejection_waits_while_against_votes_can_switch() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &admin,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Doomed"),
+            &String::from_str(&env, "Early rejection"),
+            &None,
+        );
+        assert!(!client.check_early_rejection(&proposal_id));
+
+        // 5 against, 13 still uncast: a 13 / 18 `For` finish would pass
+        client.vote(&proposal_id, &admin, &VoteDecision::Against);
+        assert!(!client.check_early_rejection(&proposal_id));
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Active
+        );
+
+        // 15 against, but both voters may still switch to `For`
+        client.vote(&proposal_id, &owner, &VoteDecision::Against);
+        assert!(!client.check_early_rejection(&proposal_id));
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Active
+        );
+
+        client.vote(&proposal_id, &owner, &VoteDecision::For);
+        client.vote(&proposal_id, &admin, &VoteDecision::For);
+        client.vote(&proposal_id, &member, &VoteDecision::For);
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+        assert_eq!(client.finalize_proposal(&proposal_id), ProposalStatus::Passed);
+    }
+
+    #[test]
+    fn test_sweep_finalizes_only_closed_active_proposals() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let create = |title: &str| {
+            client.create_proposal(
+                &guild_id,
+                &owner,
+                &ProposalType::GeneralDecision,
+                &String::from_str(&env, title),
+                &String::from_str(&env, "Sweep"),
+                &None,
+            )
+        };
+        let passing = create("Passing");
+        let finalized = create("Finalized");
+        client.vote(&passing, &owner, &VoteDecision::For);
+        client.vote(&passing, &admin, &VoteDecision::For);
+
+        set_ledger_timestamp(&env, 1000 + 3 * 24 * 60 * 60);
+        let still_open = create("Still open");
+
+        set_ledger_timestamp(&env, client.get_proposal(&passing).voting_end + 1);
+        client.finalize_proposal(&finalized);
+
+        assert_eq!(client.sweep_proposals(&guild_id), 1);
+        assert_eq!(
+            client.get_proposal(&passing).status,
+            ProposalStatus::Passed
+        );
+        assert_eq!(
+            client.get_proposal(&finalized).status,
+            ProposalStatus::Rejected
+        );
+        assert_eq!(
+            client.get_proposal(&still_open).status,
+            ProposalStatus::Active
+        );
+        assert_eq!(client.sweep_proposals(&guild_id), 0);
+    }
+
+    #[test]
+    fn test_sweep_cursor_skips_settled_proposals() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, _admin, _member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let create = |title: &str| {
+            client.create_proposal(
+                &guild_id,
+                &owner,
+                &ProposalType::GeneralDecision,
+                &String::from_str(&env, title),
+                &String::from_str(&env, "Cursor"),
+                &None,
+            )
+        };
+        let cancelled_a = create("Cancelled A");
+        let cancelled_b = create("Cancelled B");
+        let open = create("Open");
+        client.cancel_proposal(&cancelled_a, &owner);
+        client.cancel_proposal(&cancelled_b, &owner);
+
+        let cursor = || {
+            env.as_contract(&contract_id, || storage::get_sweep_cursor(&env, guild_id))
+        };
+
+        // settled proposals at the front are skipped; the open one holds the cursor
+        assert_eq!(client.sweep_proposals(&guild_id), 0);
+        assert_eq!(cursor(), 2);
+
+        set_ledger_timestamp(&env, client.get_proposal(&open).voting_end + 1);
+        assert_eq!(client.sweep_proposals(&guild_id), 1);
+        assert_eq!(
+            client.get_proposal(&open).status,
+            ProposalStatus::Rejected
+        );
+        assert_eq!(cursor(), 3);
+    }
+
+    fn finalized_events(env: &Env) -> Vec<ProposalFinalizedEvent> {
+        let topic = (
+            Symbol::new(env, "governance"),
+            Symbol::new(env, "finalized"),
+        )
+            .into_val(env);
+        let mut found = Vec::new(env);
+        for (_, topics, data) in env.events().all().iter() {
+            if topics == topic {
+                found.push_back(ProposalFinalizedEvent::from_val(env, &data));
+            }
+        }
+        found
+    }
+
+    #[test]
+    fn test_finalize_emits_full_tally_once() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Tally"),
+            &String::from_str(&env, "Report it"),
+            &None,
+        );
+        client.vote(&proposal_id, &owner, &VoteDecision::For);
+        client.vote(&proposal_id, &admin, &VoteDecision::Against);
+        client.vote(&proposal_id, &member, &VoteDecision::Abstain);
+
+        let proposal = client.get_proposal(&proposal_id);
+        set_ledger_timestamp(&env, proposal.voting_end + 1);
+        let status = client.finalize_proposal(&proposal_id);
+
+        let events = finalized_events(&env);
+        assert_eq!(events.len(), 1);
+        let event = events.get(0).unwrap();
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(event.proposal_id, proposal_id);
+        assert_eq!(event.status, status);
+        assert_eq!(event.votes_for, proposal.votes_for);
+        assert_eq!(event.votes_against, proposal.votes_against);
+        assert_eq!(event.votes_abstain, proposal.votes_abstain);
+        assert!(event.total_possible_weight > 0);
+        assert!(event.quorum_reached);
+
+        // a repeat finalize returns the stored status without re-emitting
+        assert_eq!(client.finalize_proposal(&proposal_id), status);
+        assert_eq!(finalized_events(&env).len(), 1);
+    }
+
+    #[test]
+    fn test_cached_total_weight_matches_membership_scan() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, _admin, _member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Cached"),
+            &String::from_str(&env, "Weight fixed at creation"),
+            &None,
+        );
+        let cached = client.get_proposal(&proposal_id).total_eligible_weight;
+
+        let scanned: i128 = env.as_contract(&contract_id, || {
+            crate::guild::storage::get_all_members(&env, guild_id)
+                .iter()
+                .map(|m| crate::governance::types::role_weight(&m.role))
+                .sum()
+        });
+        assert_eq!(cached, scanned);
+        assert_eq!(cached, 18);
+
+        // joining after creation changes neither the cache nor the quorum base
+        client.add_member(&guild_id, &Address::generate(&env), &Role::Admin, &owner);
+        client.vote(&proposal_id, &owner, &VoteDecision::For);
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.total_eligible_weight, cached);
+
+        set_ledger_timestamp(&env, proposal.voting_end + 1);
+        client.finalize_proposal(&proposal_id);
+        let event = finalized_events(&env).get(0).unwrap();
+        assert_eq!(event.total_possible_weight, cached);
+    }
+
+    #[test]
+    #[should_panic(expected = "already executed")]
+    fn test_execute_proposal_twice_is_rejected() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let proposal_id = create_passed_proposal(&env, &client, guild_id, &owner, &admin);
+
+        assert!(client.execute_proposal(&proposal_id, &admin));
+        client.execute_proposal(&proposal_id, &admin);
+    }
+
+    #[test]
+    #[should_panic(expected = "already executed")]
+    fn test_execute_after_implicit_finalize_is_idempotent() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &admin,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Implicit"),
+            &String::from_str(&env, "Finalized by execute"),
+            &None,
+        );
+        client.vote(&proposal_id, &owner, &VoteDecision::For);
+        client.vote(&proposal_id, &admin, &VoteDecision::For);
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+
+        // the first call finalizes and executes; a later explicit finalize is a no-op
+        assert!(client.execute_proposal(&proposal_id, &admin));
+        assert_eq!(
+            client.finalize_proposal(&proposal_id),
+            ProposalStatus::Executed
+        );
+        client.execute_proposal(&proposal_id, &admin);
+    }
+
+    #[test]
+    fn test_proposal_is_judged_by_config_at_creation() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Snapshot"),
+            &String::from_str(&env, "Rules fixed at creation"),
+            &None,
+        );
+        client.vote(&proposal_id, &owner, &VoteDecision::For);
+        client.vote(&proposal_id, &admin, &VoteDecision::For);
+
+        // 15 of 18 weight voted: enough for the default quorum, not for 100%
+        let mut stricter = GovernanceConfig::default();
+        stricter.quorum_percentage = 100;
+        client.update_governance_config(&guild_id, &owner, &stricter);
+
+        assert_eq!(
+            client.get_proposal_config(&proposal_id),
+            GovernanceConfig::default()
+        );
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+        assert_eq!(
+            client.finalize_proposal(&proposal_id),
+            ProposalStatus::Passed
+        );
+    }
+
+    #[test]
+    fn test_requested_voting_period_is_clamped_to_bounds() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        let day = 24 * 60 * 60;
+        let window = |requested: Option<u64>| {
+            let id = client.create_proposal(
+                &guild_id,
+                &owner,
+                &ProposalType::GeneralDecision,
+                &String::from_str(&env, "Window"),
+                &String::from_str(&env, "Clamped"),
+                &requested,
+            );
+            let proposal = client.get_proposal(&id);
+            proposal.voting_end - proposal.voting_start
+        };
+
+        assert_eq!(window(None), 7 * day);
+        assert_eq!(window(Some(14 * day)), 14 * day);
+        // a few minutes is raised to the one-day floor
+        assert_eq!(window(Some(300)), day);
+        assert_eq!(window(Some(365 * day)), 30 * day);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid voting period bounds")]
+    fn test_update_governance_config_rejects_inverted_voting_bounds() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        let mut cfg = GovernanceConfig::default();
+        cfg.min_voting_period_seconds = 10 * 24 * 60 * 60;
+        cfg.max_voting_period_seconds = 24 * 60 * 60;
+        client.update_governance_config(&guild_id, &owner, &cfg);
+    }
+
+    #[test]
+    fn test_participation_quorum_ignores_inactive_members() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let day = 24 * 60 * 60;
+        let active_guild = setup_guild(&client, &env, &owner);
+        let total_guild = setup_guild(&client, &env, &owner);
+        for guild_id in [active_guild, total_guild] {
+            // six idle admins outweigh the owner under full-membership quorum
+            for _ in 0..6 {
+                client.add_member(&guild_id, &Address::generate(&env), &Role::Admin, &owner);
+            }
+        }
+
+        set_ledger_timestamp(&env, 1000 + 60 * day);
+
+        let mut cfg = GovernanceConfig::default();
+        cfg.quorum_mode = QuorumMode::ActiveParticipation;
+        cfg.active_window_seconds = 7 * day;
+        client.update_governance_config(&active_guild, &owner, &cfg);
+
+        let mut outcomes = [ProposalStatus::Active, ProposalStatus::Active];
+        for (i, guild_id) in [active_guild, total_guild].iter().enumerate() {
+            let proposal_id = client.create_proposal(
+                guild_id,
+                &owner,
+                &ProposalType::GeneralDecision,
+                &String::from_str(&env, "Quorum"),
+                &String::from_str(&env, "Only the owner shows up"),
+                &None,
+            );
+            client.vote(&proposal_id, &owner, &VoteDecision::For);
+            let end = client.get_proposal(&proposal_id).voting_end;
+            set_ledger_timestamp(&env, end + 1);
+            outcomes[i] = client.finalize_proposal(&proposal_id);
+        }
+
+        assert_eq!(outcomes[0], ProposalStatus::Passed);
+        assert_eq!(outcomes[1], ProposalStatus::Rejected);
+    }
+
+    #[test]
+    #[should_panic(expected = "active window required for participation quorum")]
+    fn test_participation_quorum_requires_active_window() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        let mut cfg = GovernanceConfig::default();
+        cfg.quorum_mode = QuorumMode::ActiveParticipation;
+        cfg.active_window_seconds = 0;
+        client.update_governance_config(&guild_id, &owner, &cfg);
+    }
+
+    #[test]
+    fn test_mid_vote_promotion_and_delegation_use_snapshot() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let (guild_id, admin, member, contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Snapshot"),
+            &String::from_str(&env, "Weights are fixed at creation"),
+            &None,
+        );
+        let member_weight = client.get_snapshot_weight(&proposal_id, &member);
+
+        client.update_role(&guild_id, &member, &Role::Admin, &owner);
+        client.delegate_vote(&guild_id, &contributor, &admin, &None);
+        assert_eq!(
+            client.get_snapshot_weight(&proposal_id, &member),
+            member_weight
+        );
+
+        client.vote(&proposal_id, &member, &VoteDecision::For);
+        client.vote(&proposal_id, &admin, &VoteDecision::Against);
+        client.vote(&proposal_id, &contributor, &VoteDecision::Against);
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+        client.finalize_proposal(&proposal_id);
+
+        // the late delegation is ignored, so the contributor's own vote stands
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.votes_for, member_weight);
+        assert_eq!(
+            proposal.votes_against,
+            client.get_snapshot_weight(&proposal_id, &admin)
+                + client.get_snapshot_weight(&proposal_id, &contributor)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "voter not eligible for this proposal")]
+    fn test_member_joining_after_creation_cannot_vote() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Snapshot"),
+            &String::from_str(&env, "Late joiner"),
+            &None,
+        );
+
+        let late = Address::generate(&env);
+        client.add_member(&guild_id, &late, &Role::Member, &owner);
+        assert_eq!(client.get_snapshot_weight(&proposal_id, &late), 0);
+        client.vote(&proposal_id, &late, &VoteDecision::For);
+    }
+
+    #[test]
+    fn test_proposal_comments_are_appended_in_order() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let (guild_id, admin, member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Discuss"),
+            &String::from_str(&env, "Debate before voting"),
+            &None,
+        );
+
+        let first = String::from_str(&env, "ipfs://comment-1");
+        let second = String::from_str(&env, "ipfs://comment-2");
+        assert_eq!(client.add_proposal_comment(&proposal_id, &admin, &first), 0);
+        set_ledger_timestamp(&env, 1500);
+        assert_eq!(
+            client.add_proposal_comment(&proposal_id, &member, &second),
+            1
+        );
+
+        let comments = client.get_proposal_comments(&proposal_id);
+        assert_eq!(comments.len(), 2);
+        let c0 = comments.get(0).unwrap();
+        assert_eq!(c0.author, admin);
+        assert_eq!(c0.content_uri, first);
+        assert_eq!(c0.timestamp, 1000);
+        let c1 = comments.get(1).unwrap();
+        assert_eq!(c1.author, member);
+        assert_eq!(c1.timestamp, 1500);
+    }
+
+    #[test]
+    #[should_panic(expected = "commenter must be guild member")]
+    fn test_non_member_cannot_comment() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Discuss"),
+            &String::from_str(&env, "Members only"),
+            &None,
+        );
+
+        client.add_proposal_comment(
+            &proposal_id,
+            &Address::generate(&env),
+            &String::from_str(&env, "ipfs://outsider"),
+        );
+    }
+
+    #[test]
+    fn test_finalize_and_execute_reports_outcome() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+
+        let passing = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Adopt"),
+            &String::from_str(&env, "One-shot finalize and execute"),
+            &None,
+        );
+        client.vote(&passing, &owner, &VoteDecision::For);
+        client.vote(&passing, &admin, &VoteDecision::For);
+
+        let failing = client.create_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Reject"),
+            &String::from_str(&env, "Voted down"),
+            &None,
+        );
+        client.vote(&failing, &owner, &VoteDecision::Against);
+        client.vote(&failing, &admin, &VoteDecision::Against);
+
+        let end = client.get_proposal(&passing).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+
+        assert_eq!(
+            client.finalize_and_execute(&passing, &owner),
+            FinalizeExecuteOutcome::Executed
+        );
+        assert_eq!(
+            client.get_proposal(&passing).status,
+            ProposalStatus::Executed
+        );
+        assert_eq!(
+            client.finalize_and_execute(&failing, &owner),
+            FinalizeExecuteOutcome::Rejected
+        );
+        assert_eq!(
+            client.get_proposal(&failing).status,
+            ProposalStatus::Rejected
+        );
+    }
+
+    #[test]
+    fn test_finalize_and_execute_leaves_timelocked_proposal_passed() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, _member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+        client.update_governance_config(
+            &guild_id,
+            &owner,
+            &GovernanceConfig {
+                execution_delay_seconds: 2 * 24 * 60 * 60,
+                ..GovernanceConfig::default()
+            },
+        );
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &admin,
+            &ProposalType::TreasurySpend,
+            &String::from_str(&env, "Spend"),
+            &String::from_str(&env, "Timelocked spend"),
+            &None,
+        );
+        client.vote(&proposal_id, &owner, &VoteDecision::For);
+        client.vote(&proposal_id, &admin, &VoteDecision::For);
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+
+        assert_eq!(
+            client.finalize_and_execute(&proposal_id, &owner),
+            FinalizeExecuteOutcome::PassedNotExecuted
+        );
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.status, ProposalStatus::Passed);
+        assert!(proposal.executed_at.is_none());
+    }
+
+    /// Admin's 5 `For` against two members' 2 + 2 and the contributor's 1
+    /// `Against`: an exact 5-5 tie, i.e. 50% of the counted weight.
+    fn create_tied_proposal(
+        env: &Env,
+        client: &StellarGuildsContractClient<'_>,
+        owner: &Address,
+        pass_threshold_percentage: u32,
+        tie_breaks_for: bool,
+    ) -> u64 {
+        let (guild_id, admin, member, contributor) = setup_guild_with_members(env, client, owner);
+        let member2 = Address::generate(env);
+        client.add_member(&guild_id, &member2, &Role::Member, owner);
+        client.update_governance_config(
+            &guild_id,
+            owner,
+            &GovernanceConfig {
+                pass_threshold_percentage,
+                tie_breaks_for,
+                ..GovernanceConfig::default()
+            },
+        );
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &admin,
+            &ProposalType::GeneralDecision,
+            &String::from_str(env, "Tie"),
+            &String::from_str(env, "Evenly split"),
+            &None,
+        );
+        client.vote(&proposal_id, &admin, &VoteDecision::For);
+        client.vote(&proposal_id, &member, &VoteDecision::Against);
+        client.vote(&proposal_id, &member2, &VoteDecision::Against);
+        client.vote(&proposal_id, &contributor, &VoteDecision::Against);
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(env, end + 1);
+        proposal_id
+    }
+
+    #[test]
+    fn test_exact_tie_fails_unless_tie_breaks_for() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let proposal_id = create_tied_proposal(&env, &client, &owner, 50, false);
+
+        // 50% meets the threshold, but the tie rule takes precedence
+        assert_eq!(
+            client.finalize_proposal(&proposal_id),
+            ProposalStatus::Rejected
+        );
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.votes_for, 5);
+        assert_eq!(proposal.votes_against, 5);
+    }
+
+    #[test]
+    fn test_exact_tie_passes_when_tie_breaks_for() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let proposal_id = create_tied_proposal(&env, &client, &owner, 50, true);
+
+        assert_eq!(
+            client.finalize_proposal(&proposal_id),
+            ProposalStatus::Passed
+        );
+    }
+
+    #[test]
+    fn test_exact_tie_misses_higher_threshold_despite_tie_breaks_for() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let proposal_id = create_tied_proposal(&env, &client, &owner, 60, true);
+
+        // 50% is short of 60%, so the tie rule has nothing to decide
+        assert_eq!(
+            client.finalize_proposal(&proposal_id),
+            ProposalStatus::Rejected
+        );
+    }
+
+    /// Owner 10 + admin 5 + member 2 + contributor 1 = 18, so 30% quorum
+    /// needs 5 weight. The member's 2 `For` reaches it only with the admin's
+    /// 5 abstain counted.
+    fn create_abstain_boundary_proposal(
+        env: &Env,
+        client: &StellarGuildsContractClient<'_>,
+        owner: &Address,
+        abstain_counts_for_quorum: bool,
+    ) -> (u64, u64) {
+        let (guild_id, admin, member, _contributor) = setup_guild_with_members(env, client, owner);
+        client.update_governance_config(
+            &guild_id,
+            owner,
+            &GovernanceConfig {
+                abstain_counts_for_quorum,
+                ..GovernanceConfig::default()
+            },
+        );
+
+        let proposal_id = client.create_proposal(
+            &guild_id,
+            &member,
+            &ProposalType::GeneralDecision,
+            &String::from_str(env, "Turnout"),
+            &String::from_str(env, "Abstentions at the boundary"),
+            &None,
+        );
+        client.vote(&proposal_id, &member, &VoteDecision::For);
+        client.vote(&proposal_id, &admin, &VoteDecision::Abstain);
+        (guild_id, proposal_id)
+    }
+
+    #[test]
+    fn test_abstain_counts_toward_quorum_but_not_threshold() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (_guild_id, proposal_id) =
+            create_abstain_boundary_proposal(&env, &client, &owner, true);
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+
+        // 7 turnout meets quorum; approval is 2 / (2 + 0) with abstain excluded
+        assert_eq!(
+            client.finalize_proposal(&proposal_id),
+            ProposalStatus::Passed
+        );
+        assert_eq!(client.get_proposal(&proposal_id).votes_abstain, 5);
+    }
+
+    #[test]
+    fn test_abstain_ignored_for_quorum_uses_snapshotted_flag() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, proposal_id) =
+            create_abstain_boundary_proposal(&env, &client, &owner, false);
+
+        // flipping the guild setting later does not change the open proposal
+        client.update_governance_config(&guild_id, &owner, &GovernanceConfig::default());
+        assert!(
+            !client
+                .get_proposal_config(&proposal_id)
+                .abstain_counts_for_quorum
+        );
+
+        let end = client.get_proposal(&proposal_id).voting_end;
+        set_ledger_timestamp(&env, end + 1);
+
+        // only the member's 2 counts, short of the 5 needed
+        assert_eq!(
+            client.finalize_proposal(&proposal_id),
+            ProposalStatus::Rejected
+        );
     }
 }
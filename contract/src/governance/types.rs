@@ -1,6 +1,7 @@
-﻿use soroban_sdk::{contracttype, Address, String};
+﻿use soroban_sdk::{contracttype, Address, Map, String, Vec};
 
 use crate::guild::types::Role;
+use crate::treasury::types::WithdrawalCategory;
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -10,6 +11,9 @@ pub enum ProposalType {
     RemoveMember,
     RuleChange,
     GeneralDecision,
+    MultipleChoice,
+    BountyDispute,
+    TreasuryConfigChange,
 }
 
 #[contracttype]
@@ -22,6 +26,25 @@ pub enum ProposalStatus {
     Executed,
     Cancelled,
     Expired,
+    /// Blocked by a guild owner after passing; final
+    Vetoed,
+    /// Passed, but its action could not be carried out; final
+    Failed,
+}
+
+/// Result of `finalize_and_execute`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FinalizeExecuteOutcome {
+    /// The proposal passed and was executed in the same call
+    Executed,
+    /// The proposal passed but cannot run yet (timelock) or its payload
+    /// did not execute; it stays `Passed`
+    PassedNotExecuted,
+    /// The proposal passed but its action failed; it is now `Failed`
+    Failed,
+    /// The proposal did not pass
+    Rejected,
 }
 
 #[contracttype]
@@ -32,24 +55,251 @@ pub enum VoteDecision {
     Abstain,
 }
 
+/// What the quorum percentage is measured against.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum QuorumMode {
+    /// Combined weight of every guild member
+    TotalMembership,
+    /// Combined weight of members active within `active_window_seconds`
+    /// before voting opened
+    ActiveParticipation,
+}
+
+/// Quorum / pass threshold overrides for one proposal type. Unset values fall
+/// back to the guild-wide settings in `GovernanceConfig`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalThresholds {
+    pub quorum_percentage: Option<u32>,
+    pub approval_threshold: Option<u32>,
+}
+
+/// Base vote weight of each built-in role
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleWeights {
+    pub owner: u32,
+    pub admin: u32,
+    pub member: u32,
+    pub contributor: u32,
+}
+
+impl RoleWeights {
+    pub fn default() -> Self {
+        Self {
+            owner: 10,
+            admin: 5,
+            member: 2,
+            contributor: 1,
+        }
+    }
+
+    pub fn weight_of(&self, role: &Role) -> i128 {
+        let weight = match role {
+            Role::Owner => self.owner,
+            Role::Admin => self.admin,
+            Role::Member => self.member,
+            Role::Contributor => self.contributor,
+        };
+        weight as i128
+    }
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct GovernanceConfig {
     pub quorum_percentage: u32,
-    pub approval_threshold: u32,
+    /// Share of `For` weight among `For` + `Against` needed to pass; a
+    /// proposal passes when `votes_for * 100 / (votes_for + votes_against)`
+    /// is `>=` this value
+    pub pass_threshold_percentage: u32,
     pub voting_period_days: u32,
     pub min_proposer_reputation: u32,
+    /// Lowest role allowed to create proposals
+    pub min_proposer_role: Role,
+    /// Bond locked by the proposer at creation; 0 disables bonding
+    pub proposal_bond: i128,
+    /// Token the bond is paid in (required when `proposal_bond` > 0)
+    pub bond_token: Option<Address>,
+    /// Treasury that receives bonds forfeited for missing quorum
+    pub bond_treasury_id: Option<u64>,
+    /// Length of the reveal phase that follows voting on commit-reveal proposals
+    pub reveal_window_seconds: u64,
+    /// Per-proposal-type quorum and threshold overrides
+    pub type_thresholds: Option<Map<ProposalType, ProposalThresholds>>,
+    /// Extra vote weight per full year of membership; 0 disables tenure weighting
+    pub tenure_bonus_per_year: u32,
+    /// Upper bound on the total tenure bonus
+    pub max_tenure_bonus: u32,
+    /// Delay between passing and execution for treasury and rule-change proposals
+    pub execution_delay_seconds: u64,
+    /// Shortest voting window a proposer may request
+    pub min_voting_period_seconds: u64,
+    /// Longest voting window a proposer may request
+    pub max_voting_period_seconds: u64,
+    pub quorum_mode: QuorumMode,
+    /// Look-back used by `QuorumMode::ActiveParticipation`
+    pub active_window_seconds: u64,
+    /// Whether abstain weight counts toward quorum; it never counts toward
+    /// the pass threshold
+    pub abstain_counts_for_quorum: bool,
+    /// Outcome of an exact `votes_for == votes_against` tie when the pass
+    /// threshold is 50%; other thresholds decide a tie as usual
+    pub tie_breaks_for: bool,
+    /// Base vote weight per role; proposals keep the weights they were created under
+    pub role_weights: RoleWeights,
 }
 
 impl GovernanceConfig {
     pub fn default() -> Self {
         Self {
             quorum_percentage: 30,
-            approval_threshold: 60,
+            pass_threshold_percentage: 60,
             voting_period_days: 7,
             min_proposer_reputation: 0,
+            min_proposer_role: Role::Contributor,
+            proposal_bond: 0,
+            bond_token: None,
+            bond_treasury_id: None,
+            reveal_window_seconds: 24 * 60 * 60,
+            type_thresholds: None,
+            tenure_bonus_per_year: 0,
+            max_tenure_bonus: 0,
+            execution_delay_seconds: 0,
+            min_voting_period_seconds: 24 * 60 * 60,
+            max_voting_period_seconds: 30 * 24 * 60 * 60,
+            quorum_mode: QuorumMode::TotalMembership,
+            active_window_seconds: 30 * 24 * 60 * 60,
+            abstain_counts_for_quorum: true,
+            tie_breaks_for: false,
+            role_weights: RoleWeights::default(),
+        }
+    }
+
+    /// Voting window for a new proposal: the requested duration, or
+    /// `voting_period_days` when none is given, clamped to the configured bounds
+    pub fn voting_period_for(&self, requested_seconds: Option<u64>) -> u64 {
+        requested_seconds
+            .unwrap_or((self.voting_period_days as u64) * 24 * 60 * 60)
+            .max(self.min_voting_period_seconds)
+            .min(self.max_voting_period_seconds)
+    }
+
+    /// Effective `(quorum_percentage, pass_threshold_percentage)` for a proposal type
+    pub fn thresholds_for(&self, proposal_type: &ProposalType) -> (u32, u32) {
+        let overrides = self
+            .type_thresholds
+            .as_ref()
+            .and_then(|m| m.get(proposal_type.clone()));
+
+        match overrides {
+            Some(o) => (
+                o.quorum_percentage.unwrap_or(self.quorum_percentage),
+                o.approval_threshold
+                    .unwrap_or(self.pass_threshold_percentage),
+            ),
+            None => (self.quorum_percentage, self.pass_threshold_percentage),
+        }
+    }
+}
+
+/// Original `GovernanceConfig` layout, as stored per guild under `g_conf`.
+/// Its fields must not change or existing configs stop decoding; settings
+/// added later live in [`GovernanceConfigV2`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StoredGovernanceConfig {
+    pub quorum_percentage: u32,
+    pub approval_threshold: u32,
+    pub voting_period_days: u32,
+    pub min_proposer_reputation: u32,
+}
+
+/// Governance settings added after the original config, stored per guild
+/// under their own versioned key. Guilds without an entry use the defaults.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceConfigV2 {
+    pub min_proposer_role: Role,
+    pub proposal_bond: i128,
+    pub bond_token: Option<Address>,
+    pub bond_treasury_id: Option<u64>,
+    pub reveal_window_seconds: u64,
+    pub type_thresholds: Option<Map<ProposalType, ProposalThresholds>>,
+    pub tenure_bonus_per_year: u32,
+    pub max_tenure_bonus: u32,
+    pub execution_delay_seconds: u64,
+    pub min_voting_period_seconds: u64,
+    pub max_voting_period_seconds: u64,
+    pub quorum_mode: QuorumMode,
+    pub active_window_seconds: u64,
+    pub abstain_counts_for_quorum: bool,
+    pub tie_breaks_for: bool,
+    pub role_weights: RoleWeights,
+}
+
+impl GovernanceConfig {
+    /// Reassemble a config from its stored parts
+    pub fn from_parts(stored: StoredGovernanceConfig, v2: GovernanceConfigV2) -> Self {
+        Self {
+            quorum_percentage: stored.quorum_percentage,
+            pass_threshold_percentage: stored.approval_threshold,
+            voting_period_days: stored.voting_period_days,
+            min_proposer_reputation: stored.min_proposer_reputation,
+            min_proposer_role: v2.min_proposer_role,
+            proposal_bond: v2.proposal_bond,
+            bond_token: v2.bond_token,
+            bond_treasury_id: v2.bond_treasury_id,
+            reveal_window_seconds: v2.reveal_window_seconds,
+            type_thresholds: v2.type_thresholds,
+            tenure_bonus_per_year: v2.tenure_bonus_per_year,
+            max_tenure_bonus: v2.max_tenure_bonus,
+            execution_delay_seconds: v2.execution_delay_seconds,
+            min_voting_period_seconds: v2.min_voting_period_seconds,
+            max_voting_period_seconds: v2.max_voting_period_seconds,
+            quorum_mode: v2.quorum_mode,
+            active_window_seconds: v2.active_window_seconds,
+            abstain_counts_for_quorum: v2.abstain_counts_for_quorum,
+            tie_breaks_for: v2.tie_breaks_for,
+            role_weights: v2.role_weights,
         }
     }
+
+    /// Split a config into its original stored layout and the later settings
+    pub fn into_parts(self) -> (StoredGovernanceConfig, GovernanceConfigV2) {
+        let stored = StoredGovernanceConfig {
+            quorum_percentage: self.quorum_percentage,
+            approval_threshold: self.pass_threshold_percentage,
+            voting_period_days: self.voting_period_days,
+            min_proposer_reputation: self.min_proposer_reputation,
+        };
+        let v2 = GovernanceConfigV2 {
+            min_proposer_role: self.min_proposer_role,
+            proposal_bond: self.proposal_bond,
+            bond_token: self.bond_token,
+            bond_treasury_id: self.bond_treasury_id,
+            reveal_window_seconds: self.reveal_window_seconds,
+            type_thresholds: self.type_thresholds,
+            tenure_bonus_per_year: self.tenure_bonus_per_year,
+            max_tenure_bonus: self.max_tenure_bonus,
+            execution_delay_seconds: self.execution_delay_seconds,
+            min_voting_period_seconds: self.min_voting_period_seconds,
+            max_voting_period_seconds: self.max_voting_period_seconds,
+            quorum_mode: self.quorum_mode,
+            active_window_seconds: self.active_window_seconds,
+            abstain_counts_for_quorum: self.abstain_counts_for_quorum,
+            tie_breaks_for: self.tie_breaks_for,
+            role_weights: self.role_weights,
+        };
+        (stored, v2)
+    }
+}
+
+impl GovernanceConfigV2 {
+    pub fn default() -> Self {
+        GovernanceConfig::default().into_parts().1
+    }
 }
 
 /// Simple execution payload for Soroban compatibility.
@@ -57,7 +307,7 @@ impl GovernanceConfig {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ExecutionPayload {
-    /// Treasury spend: (treasury_id, amount, recipient_str, reason)
+    /// Treasury spend; the details are stored as `TreasurySpendData`
     TreasurySpend,
     /// Add member to guild
     AddMember,
@@ -67,6 +317,13 @@ pub enum ExecutionPayload {
     RuleChange,
     /// General decision (signalling only)
     GeneralDecision,
+    /// Multiple-choice decision: the options voters choose between
+    MultipleChoice(Vec<String>),
+    /// Disputed bounty: passing releases escrow to the claimer, any other
+    /// outcome refunds the funder
+    BountyDispute(u64),
+    /// New approval threshold and, optionally, signer set for a treasury
+    TreasuryConfigChange(TreasuryConfigData),
 }
 
 /// Detailed payload data stored separately for complex operations
@@ -77,9 +334,22 @@ pub struct TreasurySpendData {
     pub amount: i128,
     pub token: Option<Address>,
     pub recipient: Address,
+    /// Budget the spend is counted against
+    pub category: WithdrawalCategory,
     pub reason: String,
 }
 
+/// Treasury settings a `TreasuryConfigChange` proposal applies once executed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryConfigData {
+    pub treasury_id: u64,
+    pub approval_threshold: u32,
+    /// Replacement signer list; `None` keeps the current signers. Must be
+    /// `None` for role-based treasuries.
+    pub signers: Option<Vec<Address>>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AddMemberData {
@@ -124,6 +394,115 @@ pub struct Proposal {
     pub execution_payload: ExecutionPayload,
     pub passed_at: Option<u64>,
     pub executed_at: Option<u64>,
+    /// Index of the winning option once a multiple-choice proposal is finalized
+    pub winning_option: Option<u32>,
+    /// End of the reveal phase; `Some` only for commit-reveal proposals
+    pub reveal_end: Option<u64>,
+    /// Whether the voting period has already been extended once
+    pub extended: bool,
+    /// Combined role weight of the creation-time voter snapshot, cached so
+    /// finalization need not rescan the membership
+    pub total_eligible_weight: i128,
+}
+
+/// Original `Proposal` layout, as stored under `g_props`. Its fields must not
+/// change or existing proposals stop decoding; later per-proposal state lives
+/// in [`ProposalExtras`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StoredProposal {
+    pub id: u64,
+    pub guild_id: u64,
+    pub proposer: Address,
+    pub proposal_type: ProposalType,
+    pub title: String,
+    pub description: String,
+    pub voting_start: u64,
+    pub voting_end: u64,
+    pub status: ProposalStatus,
+    pub votes_for: i128,
+    pub votes_against: i128,
+    pub votes_abstain: i128,
+    pub execution_payload: ExecutionPayload,
+    pub passed_at: Option<u64>,
+    pub executed_at: Option<u64>,
+}
+
+/// Proposal state added after the original layout, stored in a side map keyed
+/// by proposal id. Proposals without an entry use the defaults.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalExtras {
+    pub winning_option: Option<u32>,
+    pub reveal_end: Option<u64>,
+    pub extended: bool,
+    pub total_eligible_weight: i128,
+}
+
+impl ProposalExtras {
+    pub fn default() -> Self {
+        Self {
+            winning_option: None,
+            reveal_end: None,
+            extended: false,
+            total_eligible_weight: 0,
+        }
+    }
+}
+
+impl Proposal {
+    /// Reassemble a proposal from its stored parts
+    pub fn from_parts(stored: StoredProposal, extras: ProposalExtras) -> Self {
+        Self {
+            id: stored.id,
+            guild_id: stored.guild_id,
+            proposer: stored.proposer,
+            proposal_type: stored.proposal_type,
+            title: stored.title,
+            description: stored.description,
+            voting_start: stored.voting_start,
+            voting_end: stored.voting_end,
+            status: stored.status,
+            votes_for: stored.votes_for,
+            votes_against: stored.votes_against,
+            votes_abstain: stored.votes_abstain,
+            execution_payload: stored.execution_payload,
+            passed_at: stored.passed_at,
+            executed_at: stored.executed_at,
+            winning_option: extras.winning_option,
+            reveal_end: extras.reveal_end,
+            extended: extras.extended,
+            total_eligible_weight: extras.total_eligible_weight,
+        }
+    }
+
+    /// Split a proposal into its original stored layout and the later state
+    pub fn into_parts(self) -> (StoredProposal, ProposalExtras) {
+        let stored = StoredProposal {
+            id: self.id,
+            guild_id: self.guild_id,
+            proposer: self.proposer,
+            proposal_type: self.proposal_type,
+            title: self.title,
+            description: self.description,
+            voting_start: self.voting_start,
+            voting_end: self.voting_end,
+            status: self.status,
+            votes_for: self.votes_for,
+            votes_against: self.votes_against,
+            votes_abstain: self.votes_abstain,
+            execution_payload: self.execution_payload,
+            passed_at: self.passed_at,
+            executed_at: self.executed_at,
+        };
+        let extras = ProposalExtras {
+            winning_option: self.winning_option,
+            reveal_end: self.reveal_end,
+            extended: self.extended,
+            total_eligible_weight: self.total_eligible_weight,
+        };
+        (stored, extras)
+    }
 }
 
 #[contracttype]
@@ -136,8 +515,71 @@ pub struct Vote {
     pub timestamp: u64,
 }
 
+/// A member's voting standing captured when a proposal is created
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoterSnapshot {
+    pub role: Role,
+    pub weight: i128,
+    /// End of the member's delegation chain at creation time
+    pub delegate: Address,
+}
+
+/// Vote cast on a multiple-choice proposal
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChoiceVote {
+    pub voter: Address,
+    pub proposal_id: u64,
+    pub option_index: u32,
+    pub weight: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BondStatus {
+    /// Held by the contract while the proposal is undecided
+    Locked,
+    /// Quorum was reached; the proposer may claim the bond back
+    Refundable,
+    Refunded,
+    /// Quorum was missed; the bond was moved to the guild treasury
+    Forfeited,
+}
+
+/// Anti-spam bond locked when a proposal is created
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalBond {
+    pub proposal_id: u64,
+    pub proposer: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub treasury_id: u64,
+    pub status: BondStatus,
+}
+
 // Events
 
+/// Discussion entry anchored to a proposal; the content itself lives off-chain
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalComment {
+    pub proposal_id: u64,
+    pub author: Address,
+    pub content_uri: String,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalCommentedEvent {
+    pub proposal_id: u64,
+    pub author: Address,
+    pub index: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProposalCreatedEvent {
@@ -155,6 +597,21 @@ pub struct VoteCastEvent {
     pub decision: VoteDecision,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteCommittedEvent {
+    pub proposal_id: u64,
+    pub voter: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChoiceVoteCastEvent {
+    pub proposal_id: u64,
+    pub voter: Address,
+    pub option_index: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct VoteDelegatedEvent {
@@ -170,6 +627,24 @@ pub struct VoteUndelegatedEvent {
     pub delegator: Address,
 }
 
+/// Delegation limited to proposals of one type
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScopedVoteDelegatedEvent {
+    pub guild_id: u64,
+    pub delegator: Address,
+    pub delegate: Address,
+    pub proposal_type: ProposalType,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScopedVoteUndelegatedEvent {
+    pub guild_id: u64,
+    pub delegator: Address,
+    pub proposal_type: ProposalType,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProposalFinalizedEvent {
@@ -178,6 +653,8 @@ pub struct ProposalFinalizedEvent {
     pub votes_for: i128,
     pub votes_against: i128,
     pub votes_abstain: i128,
+    pub total_possible_weight: i128,
+    pub quorum_reached: bool,
 }
 
 #[contracttype]
@@ -187,10 +664,27 @@ pub struct ProposalExecutedEvent {
     pub success: bool,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalVetoedEvent {
+    pub proposal_id: u64,
+    pub owner: Address,
+    pub reason: String,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProposalCancelledEvent {
     pub proposal_id: u64,
+    pub cancelled_by: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VotingPeriodExtendedEvent {
+    pub proposal_id: u64,
+    pub extended_by: Address,
+    pub new_voting_end: u64,
 }
 
 #[contracttype]
@@ -199,11 +693,16 @@ pub struct GovernanceConfigUpdatedEvent {
     pub guild_id: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalBondEvent {
+    pub proposal_id: u64,
+    pub proposer: Address,
+    pub amount: i128,
+    pub status: BondStatus,
+}
+
+/// Built-in weight of a role, used where no guild config applies
 pub fn role_weight(role: &Role) -> i128 {
-    match role {
-        Role::Owner => 10,
-        Role::Admin => 5,
-        Role::Member => 2,
-        Role::Contributor => 1,
-    }
+    RoleWeights::default().weight_of(role)
 }
@@ -1,53 +1,163 @@
 use crate::events::emit::emit_event;
-use crate::events::topics::{ACT_DELEGATED, ACT_FINALIZED, ACT_UPDATED, ACT_VOTED, MOD_GOVERNANCE};
-use soroban_sdk::{Address, Env};
+use crate::events::topics::{
+    ACT_COMMITTED, ACT_DELEGATED, ACT_FINALIZED, ACT_UPDATED, ACT_VOTED, MOD_GOVERNANCE,
+};
+use soroban_sdk::{Address, Bytes, BytesN, Env, Map, Vec};
 
+use crate::governance::bond::settle_bond;
+use crate::governance::execution::{expire_unexecuted, settle_bounty_dispute};
 use crate::governance::storage::{
-    get_all_votes, get_config, get_delegate, get_proposal as load_proposal, remove_delegation,
-    set_delegation, store_proposal, store_vote,
+    clear_commitments, get_all_choice_votes, get_all_votes, get_commitment, get_config,
+    get_delegate, get_guild_proposal_ids, get_member_votes, get_proposal as load_proposal,
+    get_proposal_config, get_sweep_cursor, get_typed_delegate, get_voter_snapshots,
+    remove_commitment, remove_delegation, remove_typed_delegation, set_delegation,
+    set_sweep_cursor, set_typed_delegation, store_choice_vote, store_commitment, store_proposal,
+    store_vote, store_voter_snapshots,
 };
-use crate::governance::types::role_weight;
 use crate::governance::types::{
-    Proposal, ProposalFinalizedEvent, ProposalStatus, Vote, VoteCastEvent, VoteDecision,
+    ChoiceVote, ChoiceVoteCastEvent, ExecutionPayload, GovernanceConfig, Proposal,
+    ProposalFinalizedEvent, ProposalStatus, ProposalType, QuorumMode, Vote, VoteCastEvent,
+    VoteCommittedEvent, VoteDecision, VoterSnapshot,
 };
 use crate::guild::storage as guild_storage;
-use crate::reputation::scoring::compute_governance_weight;
+use crate::guild::types::Member;
+use crate::reputation::scoring::reputation_bonus;
+
+const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// Extra weight for membership tenure: one `tenure_bonus_per_year` per full
+/// year since joining, capped at `max_tenure_bonus`.
+fn tenure_bonus(env: &Env, cfg: &GovernanceConfig, member: &Member) -> i128 {
+    if cfg.tenure_bonus_per_year == 0 {
+        return 0;
+    }
+    let now = env.ledger().timestamp();
+    let years = now.saturating_sub(member.joined_at) / SECONDS_PER_YEAR;
+    let bonus = (years as i128) * (cfg.tenure_bonus_per_year as i128);
+    bonus.min(cfg.max_tenure_bonus as i128)
+}
+
+/// Weight a member contributes to a tally: configured role weight and
+/// reputation plus tenure bonus.
+fn member_weight(env: &Env, guild_id: u64, cfg: &GovernanceConfig, member: &Member) -> i128 {
+    cfg.role_weights.weight_of(&member.role)
+        + reputation_bonus(env, &member.address, guild_id)
+        + tenure_bonus(env, cfg, member)
+}
+
+/// Longest delegation chain that is followed when resolving or creating a delegation
+const MAX_DELEGATION_DEPTH: u32 = 16;
+
+/// Most proposals a single `sweep_proposals` call inspects; call again for the rest
+const MAX_SWEEP_PROPOSALS: u32 = 50;
+
+/// Next hop of `addr`'s delegation for proposals of `scope`: a delegation
+/// limited to that type takes precedence over the member's general one.
+fn next_delegate(
+    env: &Env,
+    guild_id: u64,
+    addr: &Address,
+    scope: &Option<ProposalType>,
+) -> Option<Address> {
+    scope
+        .as_ref()
+        .and_then(|proposal_type| get_typed_delegate(env, guild_id, addr, proposal_type))
+        .or_else(|| get_delegate(env, guild_id, addr))
+}
 
-fn resolve_delegate(env: &Env, guild_id: u64, addr: &Address) -> Address {
+fn resolve_delegate(
+    env: &Env,
+    guild_id: u64,
+    addr: &Address,
+    scope: &Option<ProposalType>,
+) -> Address {
     let mut current = addr.clone();
-    // prevent infinite loops in case of unexpected cycles
-    for _ in 0..16 {
-        if let Some(next) = get_delegate(env, guild_id, &current) {
-            if next == current {
-                break;
+    // bounded walk: a chain that doesn't terminate in time (e.g. a cycle written
+    // before detection existed, or one closed by mixing general and type-scoped
+    // delegations) falls back to the member voting for themselves
+    for _ in 0..MAX_DELEGATION_DEPTH {
+        match next_delegate(env, guild_id, &current, scope) {
+            Some(next) if next != current => {
+                if next == *addr {
+                    return addr.clone();
+                }
+                current = next;
             }
-            current = next;
-        } else {
-            break;
+            _ => return current,
         }
     }
-    current
+    addr.clone()
+}
+
+/// Current weight, role and resolved delegate of every guild member, with
+/// delegations resolved for `proposal`'s type.
+fn capture_voters(
+    env: &Env,
+    proposal: &Proposal,
+    cfg: &GovernanceConfig,
+) -> Map<Address, VoterSnapshot> {
+    let guild_id = proposal.guild_id;
+    let scope = Some(proposal.proposal_type.clone());
+    let mut voters = Map::new(env);
+    for member in guild_storage::get_all_members(env, guild_id).iter() {
+        let snapshot = VoterSnapshot {
+            role: member.role,
+            weight: member_weight(env, guild_id, cfg, &member),
+            delegate: resolve_delegate(env, guild_id, &member.address, &scope),
+        };
+        voters.set(member.address, snapshot);
+    }
+    voters
+}
+
+/// Record the voting power of every member when a proposal is created, so
+/// promotions and delegations made while it is active cannot shift the vote.
+/// Returns the snapshot's combined role weight, the quorum base under
+/// `QuorumMode::TotalMembership`.
+pub fn snapshot_voting_power(env: &Env, proposal: &Proposal, cfg: &GovernanceConfig) -> i128 {
+    let voters = capture_voters(env, proposal, cfg);
+    store_voter_snapshots(env, proposal.id, &voters);
+    voters
+        .values()
+        .iter()
+        .map(|v| cfg.role_weights.weight_of(&v.role))
+        .sum()
+}
+
+/// Voters a proposal is tallied against: its creation-time snapshot, or the
+/// live membership for proposals created before snapshots were recorded.
+fn eligible_voters(env: &Env, proposal: &Proposal) -> Map<Address, VoterSnapshot> {
+    get_voter_snapshots(env, proposal.id).unwrap_or_else(|| {
+        let cfg = get_proposal_config(env, proposal);
+        capture_voters(env, proposal, &cfg)
+    })
+}
+
+/// Weight `voter` casts on `proposal`; members who joined after the
+/// snapshot was taken cannot vote on it.
+fn snapshot_weight(env: &Env, proposal: &Proposal, voter: &Address) -> i128 {
+    eligible_voters(env, proposal)
+        .get(voter.clone())
+        .map(|v| v.weight)
+        .unwrap_or_else(|| panic!("voter not eligible for this proposal"))
 }
 
 fn compute_total_weight_and_tallies(env: &Env, proposal: &Proposal) -> (i128, i128, i128, i128) {
     // returns (total_votes_weight, for_weight, against_weight, abstain_weight)
     let votes_map = get_all_votes(env, proposal.id);
-    let members = guild_storage::get_all_members(env, proposal.guild_id);
 
     let mut total_votes_weight: i128 = 0;
     let mut for_weight: i128 = 0;
     let mut against_weight: i128 = 0;
     let mut abstain_weight: i128 = 0;
 
-    for member in members.iter() {
-        let rep = resolve_delegate(env, proposal.guild_id, &member.address);
-        let weight =
-            compute_governance_weight(env, &member.address, proposal.guild_id, &member.role);
+    for (address, voter) in eligible_voters(env, proposal).iter() {
+        let weight = voter.weight;
 
-        let decision_opt = if rep == member.address {
-            votes_map.get(member.address.clone()).map(|v| v.decision)
+        let decision_opt = if voter.delegate == address {
+            votes_map.get(address).map(|v| v.decision)
         } else {
-            votes_map.get(rep).map(|v| v.decision)
+            votes_map.get(voter.delegate).map(|v| v.decision)
         };
 
         if let Some(decision) = decision_opt {
@@ -68,6 +178,34 @@ fn compute_total_weight_and_tallies(env: &Env, proposal: &Proposal) -> (i128, i1
     )
 }
 
+fn compute_choice_tallies(env: &Env, proposal: &Proposal, option_count: u32) -> (i128, Vec<i128>) {
+    // returns (total_votes_weight, weight per option index)
+    let votes_map = get_all_choice_votes(env, proposal.id);
+
+    let mut tallies: Vec<i128> = Vec::new(env);
+    for _ in 0..option_count {
+        tallies.push_back(0);
+    }
+    let mut total_votes_weight: i128 = 0;
+
+    for voter in eligible_voters(env, proposal).values().iter() {
+        if let Some(choice) = votes_map.get(voter.delegate) {
+            total_votes_weight += voter.weight;
+            let current = tallies.get(choice.option_index).unwrap_or(0);
+            tallies.set(choice.option_index, current + voter.weight);
+        }
+    }
+
+    (total_votes_weight, tallies)
+}
+
+fn choice_option_count(proposal: &Proposal) -> u32 {
+    match &proposal.execution_payload {
+        ExecutionPayload::MultipleChoice(options) => options.len(),
+        _ => panic!("proposal is not multiple choice"),
+    }
+}
+
 pub fn vote(env: &Env, proposal_id: u64, voter: Address, decision: VoteDecision) -> bool {
     voter.require_auth();
 
@@ -77,16 +215,186 @@ pub fn vote(env: &Env, proposal_id: u64, voter: Address, decision: VoteDecision)
         panic!("proposal not active");
     }
 
+    if matches!(proposal.proposal_type, ProposalType::MultipleChoice) {
+        panic!("use vote_choice for multiple-choice proposals");
+    }
+
+    if proposal.reveal_end.is_some() {
+        panic!("use commit_vote for commit-reveal proposals");
+    }
+
     let now = env.ledger().timestamp();
     if now < proposal.voting_start || now > proposal.voting_end {
         panic!("voting period closed");
     }
 
     // must be guild member
-    let member = guild_storage::get_member(env, proposal.guild_id, &voter)
-        .unwrap_or_else(|| panic!("voter must be guild member"));
+    if guild_storage::get_member(env, proposal.guild_id, &voter).is_none() {
+        panic!("voter must be guild member");
+    }
+
+    let weight = snapshot_weight(env, &proposal, &voter);
+
+    let vote = Vote {
+        voter: voter.clone(),
+        proposal_id,
+        decision: decision.clone(),
+        weight,
+        timestamp: now,
+    };
+
+    store_vote(env, &vote);
+    guild_storage::touch_member(env, proposal.guild_id, &voter);
+
+    let event = VoteCastEvent {
+        proposal_id,
+        voter,
+        decision,
+    };
+    emit_event(env, MOD_GOVERNANCE, ACT_VOTED, event);
+
+    true
+}
+
+pub fn vote_choice(env: &Env, proposal_id: u64, voter: Address, option_index: u32) -> bool {
+    voter.require_auth();
+
+    let proposal = load_proposal(env, proposal_id).unwrap_or_else(|| panic!("proposal not found"));
+
+    if !matches!(proposal.status, ProposalStatus::Active) {
+        panic!("proposal not active");
+    }
+
+    if option_index >= choice_option_count(&proposal) {
+        panic!("invalid option index");
+    }
+
+    let now = env.ledger().timestamp();
+    if now < proposal.voting_start || now > proposal.voting_end {
+        panic!("voting period closed");
+    }
+
+    if guild_storage::get_member(env, proposal.guild_id, &voter).is_none() {
+        panic!("voter must be guild member");
+    }
+
+    let weight = snapshot_weight(env, &proposal, &voter);
+
+    let vote = ChoiceVote {
+        voter: voter.clone(),
+        proposal_id,
+        option_index,
+        weight,
+        timestamp: now,
+    };
+
+    store_choice_vote(env, &vote);
+    guild_storage::touch_member(env, proposal.guild_id, &voter);
+
+    let event = ChoiceVoteCastEvent {
+        proposal_id,
+        voter,
+        option_index,
+    };
+    emit_event(env, MOD_GOVERNANCE, ACT_VOTED, event);
+
+    true
+}
+
+/// Time after which a proposal can be finalized: the end of voting, or the
+/// end of the reveal phase for commit-reveal proposals.
+pub fn tally_closes_at(proposal: &Proposal) -> u64 {
+    proposal.reveal_end.unwrap_or(proposal.voting_end)
+}
+
+/// Commitment hash for a commit-reveal vote: `sha256(decision_byte || salt)`,
+/// where the decision byte is 0 = For, 1 = Against, 2 = Abstain.
+pub fn vote_commitment(env: &Env, decision: &VoteDecision, salt: &Bytes) -> BytesN<32> {
+    let tag: u8 = match decision {
+        VoteDecision::For => 0,
+        VoteDecision::Against => 1,
+        VoteDecision::Abstain => 2,
+    };
+    let mut preimage = Bytes::new(env);
+    preimage.push_back(tag);
+    preimage.append(salt);
+    env.crypto().sha256(&preimage).into()
+}
+
+pub fn commit_vote(
+    env: &Env,
+    proposal_id: u64,
+    voter: Address,
+    commitment_hash: BytesN<32>,
+) -> bool {
+    voter.require_auth();
+
+    let proposal = load_proposal(env, proposal_id).unwrap_or_else(|| panic!("proposal not found"));
+
+    if !matches!(proposal.status, ProposalStatus::Active) {
+        panic!("proposal not active");
+    }
+
+    if proposal.reveal_end.is_none() {
+        panic!("proposal does not use commit-reveal voting");
+    }
+
+    let now = env.ledger().timestamp();
+    if now < proposal.voting_start || now > proposal.voting_end {
+        panic!("voting period closed");
+    }
+
+    if guild_storage::get_member(env, proposal.guild_id, &voter).is_none() {
+        panic!("voter must be guild member");
+    }
+
+    store_commitment(env, proposal_id, &voter, &commitment_hash);
+    guild_storage::touch_member(env, proposal.guild_id, &voter);
+
+    let event = VoteCommittedEvent { proposal_id, voter };
+    emit_event(env, MOD_GOVERNANCE, ACT_COMMITTED, event);
+
+    true
+}
+
+pub fn reveal_vote(
+    env: &Env,
+    proposal_id: u64,
+    voter: Address,
+    decision: VoteDecision,
+    salt: Bytes,
+) -> bool {
+    voter.require_auth();
+
+    let proposal = load_proposal(env, proposal_id).unwrap_or_else(|| panic!("proposal not found"));
+
+    if !matches!(proposal.status, ProposalStatus::Active) {
+        panic!("proposal not active");
+    }
+
+    let reveal_end = proposal
+        .reveal_end
+        .unwrap_or_else(|| panic!("proposal does not use commit-reveal voting"));
+
+    let now = env.ledger().timestamp();
+    if now <= proposal.voting_end {
+        panic!("reveal phase not started");
+    }
+    if now > reveal_end {
+        panic!("reveal phase closed");
+    }
+
+    let commitment = get_commitment(env, proposal_id, &voter)
+        .unwrap_or_else(|| panic!("no commitment for voter"));
+    if vote_commitment(env, &decision, &salt) != commitment {
+        panic!("commitment mismatch");
+    }
+
+    if guild_storage::get_member(env, proposal.guild_id, &voter).is_none() {
+        panic!("voter must be guild member");
+    }
 
-    let weight = compute_governance_weight(env, &voter, proposal.guild_id, &member.role);
+    let weight = snapshot_weight(env, &proposal, &voter);
 
     let vote = Vote {
         voter: voter.clone(),
@@ -97,6 +405,8 @@ pub fn vote(env: &Env, proposal_id: u64, voter: Address, decision: VoteDecision)
     };
 
     store_vote(env, &vote);
+    guild_storage::touch_member(env, proposal.guild_id, &voter);
+    remove_commitment(env, proposal_id, &voter);
 
     let event = VoteCastEvent {
         proposal_id,
@@ -108,7 +418,75 @@ pub fn vote(env: &Env, proposal_id: u64, voter: Address, decision: VoteDecision)
     true
 }
 
-pub fn delegate_vote(env: &Env, guild_id: u64, delegator: Address, delegate: Address) -> bool {
+/// Effective weight `address` would cast right now, resolved the same way as
+/// the finalize tally: its own weight (unless delegated away) plus the weight
+/// of every member whose delegation chain ends at `address`.
+pub fn get_voting_weight(env: &Env, guild_id: u64, address: Address) -> u32 {
+    if guild_storage::get_member(env, guild_id, &address).is_none() {
+        return 0;
+    }
+
+    let cfg = get_config(env, guild_id);
+    let members = guild_storage::get_all_members(env, guild_id);
+
+    // each member is visited once, so cycles cannot count weight twice
+    let mut total: i128 = 0;
+    for member in members.iter() {
+        if resolve_delegate(env, guild_id, &member.address, &None) == address {
+            total += member_weight(env, guild_id, &cfg, &member);
+        }
+    }
+
+    total as u32
+}
+
+/// Weight `address` was assigned in a proposal's creation-time snapshot, or 0
+/// if it was not a member then.
+pub fn get_snapshot_weight(env: &Env, proposal_id: u64, address: Address) -> i128 {
+    let proposal = load_proposal(env, proposal_id).unwrap_or_else(|| panic!("proposal not found"));
+    eligible_voters(env, &proposal)
+        .get(address)
+        .map(|v| v.weight)
+        .unwrap_or(0)
+}
+
+/// The address that actually casts `address`'s vote: the end of its
+/// delegation chain, or `address` itself if it has not delegated.
+pub fn get_delegation_target(env: &Env, guild_id: u64, address: Address) -> Address {
+    resolve_delegate(env, guild_id, &address, &None)
+}
+
+/// Members who delegated directly to `address`.
+pub fn get_delegators(env: &Env, guild_id: u64, address: Address) -> Vec<Address> {
+    let mut delegators = Vec::new(env);
+    for member in guild_storage::get_all_members(env, guild_id).iter() {
+        if get_delegate(env, guild_id, &member.address) == Some(address.clone()) {
+            delegators.push_back(member.address);
+        }
+    }
+    delegators
+}
+
+/// Proposals in a guild that `address` has voted on, with the decision
+/// currently recorded for each.
+pub fn get_votes_by_member(env: &Env, guild_id: u64, address: Address) -> Vec<(u64, VoteDecision)> {
+    let mut result = Vec::new(env);
+    for (proposal_id, decision) in get_member_votes(env, guild_id, &address).iter() {
+        result.push_back((proposal_id, decision));
+    }
+    result
+}
+
+/// Delegate `delegator`'s vote to `delegate`. With a `proposal_type` the
+/// delegation covers only proposals of that type and overrides any general
+/// delegation for them; `None` delegates for every type.
+pub fn delegate_vote(
+    env: &Env,
+    guild_id: u64,
+    delegator: Address,
+    delegate: Address,
+    proposal_type: Option<ProposalType>,
+) -> bool {
     delegator.require_auth();
 
     if delegator == delegate {
@@ -121,44 +499,81 @@ pub fn delegate_vote(env: &Env, guild_id: u64, delegator: Address, delegate: Add
     let _d2 = guild_storage::get_member(env, guild_id, &delegate)
         .unwrap_or_else(|| panic!("delegate must be guild member"));
 
-    // check for cycles: walk starting from delegate
+    // check for cycles: walk the existing chain starting from delegate
     let mut current = delegate.clone();
-    for _ in 0..16 {
+    let mut depth: u32 = 0;
+    loop {
         if current == delegator {
             panic!("delegation cycle detected");
         }
-        if let Some(next) = get_delegate(env, guild_id, &current) {
-            if next == current {
-                break;
+        match next_delegate(env, guild_id, &current, &proposal_type) {
+            Some(next) if next != current => {
+                depth += 1;
+                if depth >= MAX_DELEGATION_DEPTH {
+                    panic!("delegation chain too long");
+                }
+                current = next;
             }
-            current = next;
-        } else {
-            break;
+            _ => break,
         }
     }
 
-    set_delegation(env, guild_id, &delegator, &delegate);
-
-    let event = crate::governance::types::VoteDelegatedEvent {
-        guild_id,
-        delegator,
-        delegate,
-    };
-    emit_event(env, MOD_GOVERNANCE, ACT_DELEGATED, event);
+    guild_storage::touch_member(env, guild_id, &delegator);
+    match proposal_type {
+        Some(proposal_type) => {
+            set_typed_delegation(env, guild_id, &delegator, &proposal_type, &delegate);
+            let event = crate::governance::types::ScopedVoteDelegatedEvent {
+                guild_id,
+                delegator,
+                delegate,
+                proposal_type,
+            };
+            emit_event(env, MOD_GOVERNANCE, ACT_DELEGATED, event);
+        }
+        None => {
+            set_delegation(env, guild_id, &delegator, &delegate);
+            let event = crate::governance::types::VoteDelegatedEvent {
+                guild_id,
+                delegator,
+                delegate,
+            };
+            emit_event(env, MOD_GOVERNANCE, ACT_DELEGATED, event);
+        }
+    }
 
     true
 }
 
-pub fn undelegate_vote(env: &Env, guild_id: u64, delegator: Address) -> bool {
+/// Remove `delegator`'s delegation for `proposal_type`, or its general
+/// delegation for `None`; delegations of other scopes are kept.
+pub fn undelegate_vote(
+    env: &Env,
+    guild_id: u64,
+    delegator: Address,
+    proposal_type: Option<ProposalType>,
+) -> bool {
     delegator.require_auth();
 
-    remove_delegation(env, guild_id, &delegator);
-
-    let event = crate::governance::types::VoteUndelegatedEvent {
-        guild_id,
-        delegator,
-    };
-    emit_event(env, MOD_GOVERNANCE, ACT_UPDATED, event);
+    guild_storage::touch_member(env, guild_id, &delegator);
+    match proposal_type {
+        Some(proposal_type) => {
+            remove_typed_delegation(env, guild_id, &delegator, &proposal_type);
+            let event = crate::governance::types::ScopedVoteUndelegatedEvent {
+                guild_id,
+                delegator,
+                proposal_type,
+            };
+            emit_event(env, MOD_GOVERNANCE, ACT_UPDATED, event);
+        }
+        None => {
+            remove_delegation(env, guild_id, &delegator);
+            let event = crate::governance::types::VoteUndelegatedEvent {
+                guild_id,
+                delegator,
+            };
+            emit_event(env, MOD_GOVERNANCE, ACT_UPDATED, event);
+        }
+    }
 
     true
 }
@@ -172,56 +587,338 @@ pub fn finalize_proposal(env: &Env, proposal_id: u64) -> ProposalStatus {
     }
 
     let now = env.ledger().timestamp();
-    if now < proposal.voting_end {
+    if now < tally_closes_at(&proposal) {
         panic!("voting period not finished");
     }
 
-    let cfg = get_config(env, proposal.guild_id);
-
-    let members = guild_storage::get_all_members(env, proposal.guild_id);
-    let mut total_possible_weight: i128 = 0;
-    for member in members.iter() {
-        total_possible_weight += role_weight(&member.role);
+    // commitments that were never revealed carry no weight
+    if proposal.reveal_end.is_some() {
+        clear_commitments(env, proposal_id);
     }
 
-    let quorum_threshold: i128 = (total_possible_weight * (cfg.quorum_percentage as i128)) / 100;
+    let (quorum_reached, total_possible_weight) = resolve_outcome(env, &mut proposal, now);
+    record_finalization(env, &proposal, quorum_reached, total_possible_weight);
 
-    let (total_votes_weight, for_weight, against_weight, abstain_weight) =
-        compute_total_weight_and_tallies(env, &proposal);
+    proposal.status
+}
 
-    proposal.votes_for = for_weight;
-    proposal.votes_against = against_weight;
-    proposal.votes_abstain = abstain_weight;
+/// Persist a finalized proposal, settle its bond and any bounty dispute, and
+/// emit the finalization event.
+fn record_finalization(
+    env: &Env,
+    proposal: &Proposal,
+    quorum_reached: bool,
+    total_possible_weight: i128,
+) {
+    store_proposal(env, proposal);
 
-    if total_votes_weight < quorum_threshold {
-        proposal.status = ProposalStatus::Rejected;
-    } else {
-        let counted = for_weight + against_weight;
-        if counted == 0 {
-            proposal.status = ProposalStatus::Rejected;
-        } else {
-            let approval_pct = (for_weight * 100) / counted;
-            if approval_pct >= (cfg.approval_threshold as i128) {
-                proposal.status = ProposalStatus::Passed;
-                if proposal.passed_at.is_none() {
-                    proposal.passed_at = Some(now);
-                }
-            } else {
-                proposal.status = ProposalStatus::Rejected;
-            }
-        }
-    }
+    settle_bond(env, proposal.id, quorum_reached);
 
-    store_proposal(env, &proposal);
+    if matches!(proposal.status, ProposalStatus::Rejected) {
+        settle_bounty_dispute(env, proposal);
+    }
 
     let event = ProposalFinalizedEvent {
-        proposal_id,
+        proposal_id: proposal.id,
         status: proposal.status.clone(),
         votes_for: proposal.votes_for,
         votes_against: proposal.votes_against,
         votes_abstain: proposal.votes_abstain,
+        total_possible_weight,
+        quorum_reached,
     };
     emit_event(env, MOD_GOVERNANCE, ACT_FINALIZED, event);
+}
 
+/// Finalize every `Active` proposal of a guild whose tally window has closed,
+/// and expire every `Passed` one left unexecuted past its execution window
+/// (releasing any bounty escrow it froze).
+///
+/// Inspects at most `MAX_SWEEP_PROPOSALS` per call, oldest first, starting
+/// from a stored cursor that moves past proposals once they are settled for
+/// good, so earlier sweeps are never rescanned. Returns how many proposals
+/// were finalized or expired.
+pub fn sweep_proposals(env: &Env, guild_id: u64) -> u32 {
+    let now = env.ledger().timestamp();
+    let ids = get_guild_proposal_ids(env, guild_id);
+    let start = get_sweep_cursor(env, guild_id);
+    let end = ids.len().min(start.saturating_add(MAX_SWEEP_PROPOSALS));
+
+    let mut cursor = start;
+    let mut swept = 0u32;
+    for idx in start..end {
+        let status = ids
+            .get(idx)
+            .and_then(|id| load_proposal(env, id))
+            .map(|p| match p.status {
+                ProposalStatus::Active if now >= tally_closes_at(&p) => {
+                    swept += 1;
+                    finalize_proposal(env, p.id)
+                }
+                ProposalStatus::Passed if expire_unexecuted(env, &p) => {
+                    swept += 1;
+                    ProposalStatus::Expired
+                }
+                status => status,
+            });
+        // a proposal that can still be finalized or expired holds the cursor
+        let open = matches!(
+            status,
+            Some(ProposalStatus::Active) | Some(ProposalStatus::Passed)
+        );
+        if cursor == idx && !open {
+            cursor = idx + 1;
+        }
+    }
+    if cursor != start {
+        set_sweep_cursor(env, guild_id, cursor);
+    }
+    swept
+}
+
+/// Preview the outcome of finalizing a proposal against its current tally.
+///
+/// Runs the same quorum and threshold logic as `finalize_proposal`, but
+/// ignores `voting_end` and never writes state, settles bonds or emits events.
+pub fn simulate_finalize(env: &Env, proposal_id: u64) -> ProposalStatus {
+    let mut proposal =
+        load_proposal(env, proposal_id).unwrap_or_else(|| panic!("proposal not found"));
+
+    if !matches!(proposal.status, ProposalStatus::Active) {
+        return proposal.status;
+    }
+
+    resolve_outcome(env, &mut proposal, env.ledger().timestamp());
     proposal.status
 }
+
+/// Whether the weight cast so far reaches quorum, measured with the same
+/// quorum mode and percentage `finalize_proposal` uses. Read-only, and says
+/// nothing about whether the proposal would pass the approval threshold.
+pub fn is_quorum_met(env: &Env, proposal_id: u64) -> bool {
+    let mut proposal =
+        load_proposal(env, proposal_id).unwrap_or_else(|| panic!("proposal not found"));
+
+    let (quorum_reached, _) = resolve_outcome(env, &mut proposal, env.ledger().timestamp());
+    quorum_reached
+}
+
+/// Finalize an `Active` proposal as `Rejected` before `voting_end` when no
+/// remaining votes can save it: even if every eligible member voted `For`,
+/// quorum would still be missed. Votes already cast count as open, since
+/// voters may change them until voting ends, so a tally that is merely
+/// behind on the approval threshold is never rejected early.
+///
+/// Commit-reveal proposals are never rejected early, since their committed
+/// votes are hidden. Returns whether the proposal was rejected.
+pub fn check_early_rejection(env: &Env, proposal_id: u64) -> bool {
+    let mut proposal =
+        load_proposal(env, proposal_id).unwrap_or_else(|| panic!("proposal not found"));
+
+    if !matches!(proposal.status, ProposalStatus::Active) || proposal.reveal_end.is_some() {
+        return false;
+    }
+
+    let cfg = get_proposal_config(env, &proposal);
+    let is_choice = matches!(proposal.proposal_type, ProposalType::MultipleChoice);
+    let binary_votes = get_all_votes(env, proposal_id);
+    let choice_votes = get_all_choice_votes(env, proposal_id);
+    let active_since = active_since(&proposal, &cfg);
+
+    // weight still uncast, and the quorum base growth if all of it turned out
+    let mut uncast_weight: i128 = 0;
+    let mut base_growth: i128 = 0;
+    for (address, voter) in eligible_voters(env, &proposal).iter() {
+        let cast = if is_choice {
+            choice_votes.contains_key(voter.delegate.clone())
+        } else {
+            binary_votes.contains_key(voter.delegate.clone())
+        };
+        if cast {
+            continue;
+        }
+        uncast_weight += voter.weight;
+        if matches!(cfg.quorum_mode, QuorumMode::ActiveParticipation)
+            && !is_active_since(env, proposal.guild_id, &address, active_since)
+        {
+            base_growth += cfg.role_weights.weight_of(&voter.role);
+        }
+    }
+
+    let total_possible_weight = quorum_base(env, &proposal, &cfg);
+    let (quorum_percentage, _) = cfg.thresholds_for(&proposal.proposal_type);
+    let best_quorum_threshold =
+        ((total_possible_weight + base_growth) * (quorum_percentage as i128)) / 100;
+
+    // best case: every vote, cast or uncast, ends up `For` (or on any choice
+    // option), so abstentions count towards quorum as well
+    let cast_weight = if is_choice {
+        let option_count = choice_option_count(&proposal);
+        compute_choice_tallies(env, &proposal, option_count).0
+    } else {
+        let (cast_weight, for_weight, against_weight, abstain_weight) =
+            compute_total_weight_and_tallies(env, &proposal);
+        proposal.votes_for = for_weight;
+        proposal.votes_against = against_weight;
+        proposal.votes_abstain = abstain_weight;
+        cast_weight
+    };
+    let best_turnout = cast_weight + uncast_weight;
+    if best_turnout > 0 && best_turnout >= best_quorum_threshold {
+        return false;
+    }
+
+    proposal.status = ProposalStatus::Rejected;
+    // quorum was out of reach, so a bond is forfeited
+    record_finalization(env, &proposal, false, total_possible_weight);
+
+    true
+}
+
+/// Apply quorum and threshold rules to `proposal` in memory, setting its
+/// status. Returns whether quorum was reached and the total possible weight
+/// it was measured against.
+fn resolve_outcome(env: &Env, proposal: &mut Proposal, now: u64) -> (bool, i128) {
+    let cfg = get_proposal_config(env, proposal);
+    let total_possible_weight = quorum_base(env, proposal, &cfg);
+
+    let (quorum_percentage, approval_threshold) = cfg.thresholds_for(&proposal.proposal_type);
+    let quorum_threshold: i128 = (total_possible_weight * (quorum_percentage as i128)) / 100;
+
+    let quorum_reached = if matches!(proposal.proposal_type, ProposalType::MultipleChoice) {
+        let option_count = choice_option_count(proposal);
+        let (total_votes_weight, tallies) = compute_choice_tallies(env, proposal, option_count);
+
+        if total_votes_weight == 0 || total_votes_weight < quorum_threshold {
+            proposal.status = ProposalStatus::Rejected;
+            false
+        } else {
+            // strict comparison keeps the lowest index on ties
+            let mut winner: u32 = 0;
+            let mut best: i128 = tallies.get(0).unwrap_or(0);
+            for idx in 1..option_count {
+                let weight = tallies.get(idx).unwrap_or(0);
+                if weight > best {
+                    best = weight;
+                    winner = idx;
+                }
+            }
+            proposal.status = ProposalStatus::Passed;
+            proposal.winning_option = Some(winner);
+            if proposal.passed_at.is_none() {
+                proposal.passed_at = Some(now);
+            }
+            true
+        }
+    } else {
+        finalize_binary(
+            env,
+            proposal,
+            quorum_threshold,
+            approval_threshold,
+            cfg.abstain_counts_for_quorum,
+            cfg.tie_breaks_for,
+            now,
+        )
+    };
+    (quorum_reached, total_possible_weight)
+}
+
+/// Weight quorum is measured against.
+fn quorum_base(env: &Env, proposal: &Proposal, cfg: &GovernanceConfig) -> i128 {
+    // total membership was fixed by the snapshot; activity has to be read now.
+    // Voting itself refreshes activity, so voters always fall inside the window
+    match cfg.quorum_mode {
+        // proposals stored before the weight was cached fall back to a rescan
+        QuorumMode::TotalMembership if proposal.total_eligible_weight > 0 => {
+            proposal.total_eligible_weight
+        }
+        QuorumMode::TotalMembership => eligible_voters(env, proposal)
+            .values()
+            .iter()
+            .map(|v| cfg.role_weights.weight_of(&v.role))
+            .sum(),
+        QuorumMode::ActiveParticipation => {
+            let active_since = active_since(proposal, cfg);
+            let mut weight: i128 = 0;
+            for (address, voter) in eligible_voters(env, proposal).iter() {
+                if is_active_since(env, proposal.guild_id, &address, active_since) {
+                    weight += cfg.role_weights.weight_of(&voter.role);
+                }
+            }
+            weight
+        }
+    }
+}
+
+fn active_since(proposal: &Proposal, cfg: &GovernanceConfig) -> u64 {
+    proposal
+        .voting_start
+        .saturating_sub(cfg.active_window_seconds)
+}
+
+fn is_active_since(env: &Env, guild_id: u64, address: &Address, since: u64) -> bool {
+    guild_storage::get_member(env, guild_id, address)
+        .is_some_and(|m| guild_storage::get_last_active(env, guild_id, &m) >= since)
+}
+
+/// Whether a binary tally clears the approval threshold. The tie rule only
+/// decides an exact split under a 50% threshold, the one case it sits on.
+fn binary_passes(
+    for_weight: i128,
+    against_weight: i128,
+    approval_threshold: u32,
+    tie_breaks_for: bool,
+) -> bool {
+    let counted = for_weight + against_weight;
+    if counted == 0 {
+        false
+    } else if for_weight == against_weight && approval_threshold == 50 {
+        tie_breaks_for
+    } else {
+        // floor division keeps `>=` exact for whole-number thresholds
+        (for_weight * 100) / counted >= (approval_threshold as i128)
+    }
+}
+
+fn finalize_binary(
+    env: &Env,
+    proposal: &mut Proposal,
+    quorum_threshold: i128,
+    approval_threshold: u32,
+    abstain_counts_for_quorum: bool,
+    tie_breaks_for: bool,
+    now: u64,
+) -> bool {
+    let (mut total_votes_weight, for_weight, against_weight, abstain_weight) =
+        compute_total_weight_and_tallies(env, proposal);
+    if !abstain_counts_for_quorum {
+        total_votes_weight -= abstain_weight;
+    }
+
+    proposal.votes_for = for_weight;
+    proposal.votes_against = against_weight;
+    proposal.votes_abstain = abstain_weight;
+
+    let quorum_reached = total_votes_weight > 0 && total_votes_weight >= quorum_threshold;
+
+    if !quorum_reached {
+        proposal.status = ProposalStatus::Rejected;
+    } else {
+        if binary_passes(
+            for_weight,
+            against_weight,
+            approval_threshold,
+            tie_breaks_for,
+        ) {
+            proposal.status = ProposalStatus::Passed;
+            if proposal.passed_at.is_none() {
+                proposal.passed_at = Some(now);
+            }
+        } else {
+            proposal.status = ProposalStatus::Rejected;
+        }
+    }
+
+    quorum_reached
+}
@@ -1,14 +1,20 @@
 ﻿use crate::events::emit::emit_event;
 use crate::events::topics::{
-    ACT_CREATED, ACT_JOINED, ACT_MEMBER_ADDED, ACT_MEMBER_REMOVED, ACT_ROLE_UPDATED, MOD_GUILD,
+    ACT_ANNOUNCED, ACT_BADGE_MINTED, ACT_CREATED, ACT_JOINED, ACT_MEMBER_ADDED, ACT_MEMBER_REMOVED,
+    ACT_PROFILE_UPDATED, ACT_ROLE_ASSIGNED, ACT_ROLE_DEFINED, ACT_ROLE_UPDATED, MOD_GUILD,
 };
 use crate::guild::storage;
 use crate::guild::types::{
-    Guild, GuildCreatedEvent, GuildJoinedEvent, Member, MemberAddedEvent, MemberRemovedEvent, Role,
+    Announcement, AnnouncementPostedEvent, BadgeMintedEvent, CustomRole, CustomRoleAssignedEvent,
+    Guild, GuildCreatedEvent, GuildJoinedEvent, GuildSnapshot, Member, MemberAddedEvent,
+    MemberProfileUpdatedEvent, MemberRemovedEvent, MembershipBadge, PermissionConfig, Role,
     RoleUpdatedEvent,
 };
 use soroban_sdk::{Address, Env, String, Vec};
 
+/// Largest member list `export_guild` returns in one call
+pub const MAX_EXPORT_MEMBERS: u32 = 200;
+
 /// Create a new guild
 ///
 /// # Events emitted
@@ -112,25 +118,14 @@ pub fn add_member(
     let caller_member = storage::get_member(env, guild_id, &caller)
         .ok_or(String::from_str(env, "Caller is not a member of the guild"))?;
 
-    match role {
-        Role::Owner => {
-            if caller_member.role != Role::Owner {
-                return Err(String::from_str(env, "Only owner can add new owners"));
-            }
-        }
-        Role::Admin => {
-            if caller_member.role != Role::Owner && caller_member.role != Role::Admin {
-                return Err(String::from_str(env, "Only owner or admin can add admins"));
-            }
-        }
-        Role::Member | Role::Contributor => {
-            if !caller_member.role.has_permission(&Role::Member) {
-                return Err(String::from_str(
-                    env,
-                    "Insufficient permissions to add members",
-                ));
-            }
-        }
+    let required = storage::get_permission_config(env, guild_id)
+        .add_member
+        .required_for(&role);
+    if !has_permission(env, guild_id, caller_member.address.clone(), required) {
+        return Err(String::from_str(
+            env,
+            "Insufficient permissions to add members",
+        ));
     }
 
     let timestamp = env.ledger().timestamp();
@@ -140,6 +135,7 @@ pub fn add_member(
         joined_at: timestamp,
     };
     storage::store_member(env, guild_id, &member);
+    storage::touch_member(env, guild_id, &caller);
 
     let mut updated_guild = guild;
     updated_guild.member_count += 1;
@@ -196,40 +192,31 @@ pub fn remove_member(
         }
     }
 
-    if !is_self_removal {
+    if is_self_removal {
+        ensure_free_to_leave(env, &guild, &address)?;
+    } else {
         let caller_member = storage::get_member(env, guild_id, &caller)
             .ok_or(String::from_str(env, "Caller is not a member of the guild"))?;
 
-        match member.role {
-            Role::Owner => {
-                if caller_member.role != Role::Owner {
-                    return Err(String::from_str(env, "Only owner can remove owners"));
-                }
-                let owner_count = storage::count_owners(env, guild_id);
-                if owner_count <= 1 {
-                    return Err(String::from_str(env, "Cannot remove the last owner"));
-                }
-            }
-            Role::Admin => {
-                if caller_member.role != Role::Owner && caller_member.role != Role::Admin {
-                    return Err(String::from_str(
-                        env,
-                        "Only owner or admin can remove admins",
-                    ));
-                }
-            }
-            Role::Member | Role::Contributor => {
-                if !caller_member.role.has_permission(&Role::Member) {
-                    return Err(String::from_str(
-                        env,
-                        "Insufficient permissions to remove members",
-                    ));
-                }
-            }
+        let required = storage::get_permission_config(env, guild_id)
+            .remove_member
+            .required_for(&member.role);
+        if !has_permission(env, guild_id, caller_member.address.clone(), required) {
+            return Err(String::from_str(
+                env,
+                "Insufficient permissions to remove members",
+            ));
         }
     }
 
     storage::remove_member(env, guild_id, &address);
+    storage::set_custom_role_assignment(env, guild_id, &address, None);
+    storage::remove_badge(env, guild_id, &address);
+    storage::set_profile_uri(env, guild_id, &address, None);
+    storage::set_last_active(env, guild_id, &address, None);
+    storage::set_last_role_change(env, guild_id, &address, None);
+    crate::governance::storage::clear_member_delegations(env, guild_id, &address);
+    storage::touch_member(env, guild_id, &caller);
 
     let mut updated_guild = guild;
     updated_guild.member_count = updated_guild.member_count.saturating_sub(1);
@@ -245,6 +232,30 @@ pub fn remove_member(
     Ok(true)
 }
 
+/// Reject a self-removal the guild does not allow, or that would leave a
+/// treasury signer seat or an assigned bounty behind.
+fn ensure_free_to_leave(env: &Env, guild: &Guild, address: &Address) -> Result<(), String> {
+    if !storage::get_allow_self_removal(env, guild.id) {
+        return Err(String::from_str(
+            env,
+            "Self-removal is disabled; ask an admin to remove you",
+        ));
+    }
+    if crate::treasury::multisig::is_listed_signer(env, guild.id, address) {
+        return Err(String::from_str(
+            env,
+            "Cannot leave while a treasury signer; ask to be replaced first",
+        ));
+    }
+    if crate::bounty::has_assigned_bounty(env, guild.id, address) {
+        return Err(String::from_str(
+            env,
+            "Cannot leave with an assigned bounty; submit or release it first",
+        ));
+    }
+    Ok(())
+}
+
 /// Update a member's role
 ///
 /// # Events emitted
@@ -268,8 +279,7 @@ pub fn update_role(
     new_role: Role,
     caller: Address,
 ) -> Result<bool, String> {
-    let _guild =
-        storage::get_guild(env, guild_id).ok_or(String::from_str(env, "Guild not found"))?;
+    storage::get_guild(env, guild_id).ok_or(String::from_str(env, "Guild not found"))?;
 
     let member = storage::get_member(env, guild_id, &address)
         .ok_or(String::from_str(env, "Member not found"))?;
@@ -277,33 +287,30 @@ pub fn update_role(
     let caller_member = storage::get_member(env, guild_id, &caller)
         .ok_or(String::from_str(env, "Caller is not a member of the guild"))?;
 
-    match member.role {
-        Role::Owner => {
-            if caller_member.role != Role::Owner {
-                return Err(String::from_str(env, "Only owner can change owner role"));
-            }
-            if new_role != Role::Owner {
-                let owner_count = storage::count_owners(env, guild_id);
-                if owner_count <= 1 {
-                    return Err(String::from_str(env, "Cannot demote the last owner"));
-                }
-            }
+    let now = env.ledger().timestamp();
+    let cooldown = storage::get_role_change_cooldown(env, guild_id);
+    let last_change = storage::get_last_role_change(env, guild_id, &address);
+    if cooldown > 0 && last_change > 0 {
+        let allowed_at = last_change.saturating_add(cooldown);
+        if now < allowed_at {
+            return Err(cooldown_error(env, allowed_at));
         }
-        Role::Admin => {
-            if caller_member.role != Role::Owner && caller_member.role != Role::Admin {
-                return Err(String::from_str(
-                    env,
-                    "Only owner or admin can change admin role",
-                ));
-            }
-        }
-        Role::Member | Role::Contributor => {
-            if caller_member.role != Role::Owner && caller_member.role != Role::Admin {
-                return Err(String::from_str(
-                    env,
-                    "Insufficient permissions to change member role",
-                ));
-            }
+    }
+
+    let required = storage::get_permission_config(env, guild_id)
+        .update_role
+        .required_for(&member.role);
+    if !has_permission(env, guild_id, caller_member.address.clone(), required) {
+        return Err(String::from_str(
+            env,
+            "Insufficient permissions to change member role",
+        ));
+    }
+
+    if member.role == Role::Owner && new_role != Role::Owner {
+        let owner_count = storage::count_owners(env, guild_id);
+        if owner_count <= 1 {
+            return Err(String::from_str(env, "Cannot demote the last owner"));
         }
     }
 
@@ -315,6 +322,8 @@ pub fn update_role(
         joined_at: member.joined_at,
     };
     storage::store_member(env, guild_id, &updated_member);
+    storage::set_last_role_change(env, guild_id, &address, Some(now));
+    storage::touch_member(env, guild_id, &caller);
 
     emit_event(
         env,
@@ -331,6 +340,146 @@ pub fn update_role(
     Ok(true)
 }
 
+/// `"Role change on cooldown until <timestamp>"`
+fn cooldown_error(env: &Env, allowed_at: u64) -> String {
+    const PREFIX: &[u8] = b"Role change on cooldown until ";
+    let mut buf = [0u8; PREFIX.len() + 20];
+    buf[..PREFIX.len()].copy_from_slice(PREFIX);
+
+    let mut digits = [0u8; 20];
+    let mut len = 0;
+    let mut n = allowed_at;
+    loop {
+        digits[len] = b'0' + (n % 10) as u8;
+        len += 1;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    for i in 0..len {
+        buf[PREFIX.len() + i] = digits[len - 1 - i];
+    }
+
+    String::from_bytes(env, &buf[..PREFIX.len() + len])
+}
+
+/// Set the minimum time between role changes of the same member
+///
+/// Only a guild owner may change it. A cooldown of 0 disables the check.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `cooldown_seconds` - Seconds a member's role stays locked after `update_role`
+/// * `caller` - The address making the request (must be an owner)
+///
+/// # Returns
+/// `Ok(true)` on success, or an error message
+pub fn set_role_change_cooldown(
+    env: &Env,
+    guild_id: u64,
+    cooldown_seconds: u64,
+    caller: Address,
+) -> Result<bool, String> {
+    storage::get_guild(env, guild_id).ok_or(String::from_str(env, "Guild not found"))?;
+
+    let caller_member = storage::get_member(env, guild_id, &caller)
+        .ok_or(String::from_str(env, "Caller is not a member of the guild"))?;
+    if caller_member.role != Role::Owner {
+        return Err(String::from_str(
+            env,
+            "Only owner can set the role change cooldown",
+        ));
+    }
+
+    storage::set_role_change_cooldown(env, guild_id, cooldown_seconds);
+    storage::touch_member(env, guild_id, &caller);
+
+    Ok(true)
+}
+
+/// Allow or forbid members to remove themselves from the guild
+///
+/// Only a guild owner may change it. Admin removals are unaffected.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `allowed` - Whether `remove_member` accepts a member removing themselves
+/// * `caller` - The address making the request (must be an owner)
+///
+/// # Returns
+/// `Ok(true)` on success, or an error message
+pub fn set_allow_self_removal(
+    env: &Env,
+    guild_id: u64,
+    allowed: bool,
+    caller: Address,
+) -> Result<bool, String> {
+    storage::get_guild(env, guild_id).ok_or(String::from_str(env, "Guild not found"))?;
+
+    let caller_member = storage::get_member(env, guild_id, &caller)
+        .ok_or(String::from_str(env, "Caller is not a member of the guild"))?;
+    if caller_member.role != Role::Owner {
+        return Err(String::from_str(
+            env,
+            "Only owner can change the self-removal setting",
+        ));
+    }
+
+    storage::set_allow_self_removal(env, guild_id, allowed);
+    storage::touch_member(env, guild_id, &caller);
+
+    Ok(true)
+}
+
+/// Whether members of the guild may remove themselves
+pub fn get_allow_self_removal(env: &Env, guild_id: u64) -> Result<bool, String> {
+    storage::get_guild(env, guild_id).ok_or(String::from_str(env, "Guild not found"))?;
+    Ok(storage::get_allow_self_removal(env, guild_id))
+}
+
+/// Replace a guild's permission matrix for adding, removing and re-ranking members
+///
+/// Only a guild owner may change it. Callers must still be guild members, and
+/// the last owner can never be removed or demoted.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `config` - Minimum caller role per action and target role
+/// * `caller` - The address making the request (must be an owner)
+///
+/// # Returns
+/// `Ok(true)` on success, or an error message
+pub fn set_permission_config(
+    env: &Env,
+    guild_id: u64,
+    config: PermissionConfig,
+    caller: Address,
+) -> Result<bool, String> {
+    storage::get_guild(env, guild_id).ok_or(String::from_str(env, "Guild not found"))?;
+
+    let caller_member = storage::get_member(env, guild_id, &caller)
+        .ok_or(String::from_str(env, "Caller is not a member of the guild"))?;
+    if caller_member.role != Role::Owner {
+        return Err(String::from_str(
+            env,
+            "Only owner can set the permission config",
+        ));
+    }
+
+    storage::store_permission_config(env, guild_id, &config);
+    storage::touch_member(env, guild_id, &caller);
+
+    Ok(true)
+}
+
+pub fn get_permission_config(env: &Env, guild_id: u64) -> PermissionConfig {
+    storage::get_permission_config(env, guild_id)
+}
+
 /// Self-join a guild
 ///
 /// Allows any address to add themselves to an existing guild as a `Member`.
@@ -389,24 +538,435 @@ pub fn join_guild(env: &Env, guild_id: u64, caller: Address) -> Result<bool, Str
     Ok(true)
 }
 
+/// Mint a soul-bound membership badge for a member of a guild
+///
+/// The badge records the member's current role. Minting again re-issues it
+/// with the role held at that time. Badges cannot be transferred and are
+/// revoked when the member is removed.
+///
+/// # Events emitted
+/// - `(guild, badge_minted)` → `BadgeMintedEvent`
+///
+/// # Arguments
+/// * `env`      - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `address`  - The member receiving the badge
+///
+/// # Errors
+/// - Guild not found
+/// - Address is not a member of the guild
+pub fn mint_membership_badge(
+    env: &Env,
+    guild_id: u64,
+    address: Address,
+) -> Result<MembershipBadge, String> {
+    storage::get_guild(env, guild_id).ok_or(String::from_str(env, "Guild not found"))?;
+    let member = storage::get_member(env, guild_id, &address)
+        .ok_or(String::from_str(env, "Member not found"))?;
+
+    let badge = MembershipBadge {
+        guild_id,
+        holder: address.clone(),
+        role: member.role,
+        issued_at: env.ledger().timestamp(),
+    };
+    storage::store_badge(env, &badge);
+    storage::touch_member(env, guild_id, &address);
+
+    emit_event(
+        env,
+        MOD_GUILD,
+        ACT_BADGE_MINTED,
+        BadgeMintedEvent {
+            guild_id,
+            holder: address,
+            role: badge.role,
+            issued_at: badge.issued_at,
+        },
+    );
+
+    Ok(badge)
+}
+
+pub fn get_badge(env: &Env, guild_id: u64, address: Address) -> Option<MembershipBadge> {
+    storage::get_badge(env, guild_id, &address)
+}
+
+/// Profile metadata URI `address` has set in the guild, if any
+pub fn get_member_profile(env: &Env, guild_id: u64, address: Address) -> Option<String> {
+    storage::get_profile_uri(env, guild_id, &address)
+}
+
+/// Whether `address` holds a membership badge of the guild. Badges are
+/// deleted on removal, so a badge always belongs to a current member.
+pub fn verify_badge(env: &Env, guild_id: u64, address: Address) -> bool {
+    storage::get_badge(env, guild_id, &address).is_some()
+}
+
+/// Set the caller's own profile metadata URI in a guild
+///
+/// An empty `uri` clears the profile. The member must sign the transaction.
+///
+/// # Events emitted
+/// - `(guild, profile_updated)` → `MemberProfileUpdatedEvent`
+///
+/// # Arguments
+/// * `env`      - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `address`  - The member updating their profile (must sign the transaction)
+/// * `uri`      - The profile metadata URI (max 256 chars)
+///
+/// # Errors
+/// - `"Member not found"` — `address` is not a member of the guild.
+/// - `"Profile URI must be at most 256 characters"`
+pub fn set_member_profile(
+    env: &Env,
+    guild_id: u64,
+    address: Address,
+    uri: String,
+) -> Result<bool, String> {
+    address.require_auth();
+
+    if !storage::has_member(env, guild_id, &address) {
+        return Err(String::from_str(env, "Member not found"));
+    }
+    if uri.len() > 256 {
+        return Err(String::from_str(
+            env,
+            "Profile URI must be at most 256 characters",
+        ));
+    }
+
+    let uri = if uri.is_empty() { None } else { Some(uri) };
+    storage::set_profile_uri(env, guild_id, &address, uri.clone());
+    storage::touch_member(env, guild_id, &address);
+
+    emit_event(
+        env,
+        MOD_GUILD,
+        ACT_PROFILE_UPDATED,
+        MemberProfileUpdatedEvent {
+            guild_id,
+            address,
+            metadata_uri: uri,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Define or re-rank a custom role for a guild
+///
+/// # Events emitted
+/// - `(guild, role_defined)` → `CustomRole`
+///
+/// # Arguments
+/// * `env`      - The contract environment
+/// * `guild_id` - The ID of the guild
+/// * `name`     - The custom role name
+/// * `level`    - Permission level, compared against `Role::level`
+/// * `caller`   - The address making the request (must be admin or above)
+///
+/// # Errors
+/// - Guild not found
+/// - Caller lacks admin permission
+/// - Level is zero or not below the caller's own level
+pub fn define_custom_role(
+    env: &Env,
+    guild_id: u64,
+    name: String,
+    level: u32,
+    caller: Address,
+) -> Result<bool, String> {
+    storage::get_guild(env, guild_id).ok_or(String::from_str(env, "Guild not found"))?;
+
+    if !has_permission(env, guild_id, caller.clone(), Role::Admin) {
+        return Err(String::from_str(
+            env,
+            "Only owner or admin can define custom roles",
+        ));
+    }
+    if name.is_empty() {
+        return Err(String::from_str(env, "Custom role name cannot be empty"));
+    }
+    let caller_level = permission_level(env, guild_id, &caller).unwrap_or(0);
+    if level == 0 || level >= caller_level {
+        return Err(String::from_str(
+            env,
+            "Custom role level must be below the caller's level",
+        ));
+    }
+
+    storage::store_custom_role(env, guild_id, &name, level);
+    storage::touch_member(env, guild_id, &caller);
+
+    emit_event(env, MOD_GUILD, ACT_ROLE_DEFINED, CustomRole { name, level });
+
+    Ok(true)
+}
+
+/// Assign a custom role to a member, or clear it with `None`
+///
+/// # Events emitted
+/// - `(guild, role_assigned)` → `CustomRoleAssignedEvent`
+///
+/// # Arguments
+/// * `env`       - The contract environment
+/// * `guild_id`  - The ID of the guild
+/// * `address`   - The member receiving the role
+/// * `role_name` - The custom role to assign, or `None` to clear it
+/// * `caller`    - The address making the request (must be admin or above)
+///
+/// # Errors
+/// - Guild, member or custom role not found
+/// - Caller lacks admin permission or does not outrank the role
+pub fn assign_custom_role(
+    env: &Env,
+    guild_id: u64,
+    address: Address,
+    role_name: Option<String>,
+    caller: Address,
+) -> Result<bool, String> {
+    storage::get_guild(env, guild_id).ok_or(String::from_str(env, "Guild not found"))?;
+
+    if !storage::has_member(env, guild_id, &address) {
+        return Err(String::from_str(env, "Member not found"));
+    }
+    if !has_permission(env, guild_id, caller.clone(), Role::Admin) {
+        return Err(String::from_str(
+            env,
+            "Only owner or admin can assign custom roles",
+        ));
+    }
+    if let Some(ref name) = role_name {
+        let level = storage::get_custom_roles(env, guild_id)
+            .get(name.clone())
+            .ok_or(String::from_str(env, "Custom role not found"))?;
+        if level >= permission_level(env, guild_id, &caller).unwrap_or(0) {
+            return Err(String::from_str(
+                env,
+                "Cannot assign a custom role at or above the caller's level",
+            ));
+        }
+    }
+
+    storage::set_custom_role_assignment(env, guild_id, &address, role_name.clone());
+    storage::touch_member(env, guild_id, &caller);
+
+    emit_event(
+        env,
+        MOD_GUILD,
+        ACT_ROLE_ASSIGNED,
+        CustomRoleAssignedEvent {
+            guild_id,
+            address,
+            role_name,
+        },
+    );
+
+    Ok(true)
+}
+
+/// Post an official announcement to a guild
+///
+/// Announcements are append-only: they cannot be edited, only superseded
+/// by newer posts.
+///
+/// # Events emitted
+/// - `(guild, announced)` → `AnnouncementPostedEvent`
+///
+/// # Arguments
+/// * `env`         - The contract environment
+/// * `guild_id`    - The ID of the guild
+/// * `caller`      - The address posting (must be admin or above)
+/// * `content_uri` - URI of the announcement content (1–256 chars)
+///
+/// # Errors
+/// - Guild not found
+/// - Caller lacks admin permission
+/// - Content URI is empty or longer than 256 characters
+pub fn post_announcement(
+    env: &Env,
+    guild_id: u64,
+    caller: Address,
+    content_uri: String,
+) -> Result<bool, String> {
+    storage::get_guild(env, guild_id).ok_or(String::from_str(env, "Guild not found"))?;
+
+    if !has_permission(env, guild_id, caller.clone(), Role::Admin) {
+        return Err(String::from_str(
+            env,
+            "Only owner or admin can post announcements",
+        ));
+    }
+    if content_uri.is_empty() || content_uri.len() > 256 {
+        return Err(String::from_str(
+            env,
+            "Announcement URI must be between 1 and 256 characters",
+        ));
+    }
+
+    let posted_at = env.ledger().timestamp();
+    let announcement = Announcement {
+        author: caller.clone(),
+        content_uri: content_uri.clone(),
+        posted_at,
+    };
+    storage::append_announcement(env, guild_id, &announcement);
+    storage::touch_member(env, guild_id, &caller);
+
+    emit_event(
+        env,
+        MOD_GUILD,
+        ACT_ANNOUNCED,
+        AnnouncementPostedEvent {
+            guild_id,
+            author: caller,
+            content_uri,
+            posted_at,
+        },
+    );
+
+    Ok(true)
+}
+
 // ─── Query helpers (no events) ────────────────────────────────────────────────
 
+/// Up to `limit` most recent announcements of a guild, newest first
+pub fn get_announcements(env: &Env, guild_id: u64, limit: u32) -> Vec<Announcement> {
+    let all = storage::get_announcements(env, guild_id);
+    let mut out = Vec::new(env);
+    for announcement in all.iter().rev() {
+        if out.len() >= limit {
+            break;
+        }
+        out.push_back(announcement);
+    }
+    out
+}
+
+/// A guild's member record, or `None` for non-members and unknown guilds
+pub fn try_get_member(env: &Env, guild_id: u64, address: Address) -> Option<Member> {
+    storage::get_member(env, guild_id, &address)
+}
+
 pub fn get_member(env: &Env, guild_id: u64, address: Address) -> Result<Member, String> {
-    storage::get_member(env, guild_id, &address).ok_or(String::from_str(env, "Member not found"))
+    try_get_member(env, guild_id, address).ok_or(String::from_str(env, "Member not found"))
+}
+
+/// Timestamp of a member's last state-changing participation in the guild
+pub fn get_member_last_active(env: &Env, guild_id: u64, address: Address) -> Result<u64, String> {
+    let member = get_member(env, guild_id, address)?;
+    Ok(storage::get_last_active(env, guild_id, &member))
+}
+
+pub fn get_guild(env: &Env, guild_id: u64) -> Result<Guild, String> {
+    storage::get_guild(env, guild_id).ok_or(String::from_str(env, "Guild not found"))
+}
+
+pub fn get_guild_count(env: &Env) -> u64 {
+    storage::get_guild_count(env)
 }
 
 pub fn get_all_members(env: &Env, guild_id: u64) -> Vec<Member> {
     storage::get_all_members(env, guild_id)
 }
 
+/// Up to `limit` members starting at `offset`, in storage order.
+pub fn get_members_page(env: &Env, guild_id: u64, offset: u32, limit: u32) -> Vec<Member> {
+    let mut page = Vec::new(env);
+    for member in storage::get_all_members(env, guild_id)
+        .iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+    {
+        page.push_back(member);
+    }
+    page
+}
+
+/// Bundle a guild's metadata, members and governance config into one
+/// consistent snapshot.
+///
+/// # Errors
+/// Returns `Err` if the guild does not exist, or has more than
+/// `MAX_EXPORT_MEMBERS` members (callers should page with `get_members_page`).
+pub fn export_guild(env: &Env, guild_id: u64) -> Result<GuildSnapshot, String> {
+    let guild = get_guild(env, guild_id)?;
+    if guild.member_count > MAX_EXPORT_MEMBERS {
+        return Err(String::from_str(env, "too large, use pagination"));
+    }
+
+    Ok(GuildSnapshot {
+        members: storage::get_all_members(env, guild_id),
+        governance_config: crate::governance::storage::get_config(env, guild_id),
+        taken_at: env.ledger().timestamp(),
+        guild,
+    })
+}
+
+/// Members whose last state-changing participation predates `inactive_since`.
+pub fn get_inactive_members(env: &Env, guild_id: u64, inactive_since: u64) -> Vec<Address> {
+    let mut inactive = Vec::new(env);
+    for member in storage::get_all_members(env, guild_id).iter() {
+        if storage::get_last_active(env, guild_id, &member) < inactive_since {
+            inactive.push_back(member.address);
+        }
+    }
+    inactive
+}
+
 pub fn is_member(env: &Env, guild_id: u64, address: Address) -> bool {
     storage::has_member(env, guild_id, &address)
 }
 
+/// Effective permission level of a member: the higher of their built-in role
+/// level and the level of any custom role assigned to them.
+pub fn permission_level(env: &Env, guild_id: u64, address: &Address) -> Option<u32> {
+    let member = storage::get_member(env, guild_id, address)?;
+    let custom_level = storage::get_custom_role_assignment(env, guild_id, address)
+        .and_then(|name| storage::get_custom_roles(env, guild_id).get(name))
+        .unwrap_or(0);
+    Some(member.role.level().max(custom_level))
+}
+
 pub fn has_permission(env: &Env, guild_id: u64, address: Address, required_role: Role) -> bool {
-    if let Some(member) = storage::get_member(env, guild_id, &address) {
-        member.role.has_permission(&required_role)
-    } else {
-        false
+    match permission_level(env, guild_id, &address) {
+        Some(level) => level >= required_role.level(),
+        None => false,
+    }
+}
+
+/// Whether `address` is a member of the guild ranking at or above `min_role`.
+///
+/// This is the stable check for other contracts gating features on guild
+/// roles: it never panics, and unknown guilds or non-members are simply
+/// `false`, unlike `get_member`.
+pub fn verify_role(env: &Env, guild_id: u64, address: Address, min_role: Role) -> bool {
+    permission_level(env, guild_id, &address).is_some_and(|level| level >= min_role.level())
+}
+
+/// Whether a member ranks at or above the level of a custom role. Unknown
+/// role names never grant permission.
+pub fn has_custom_role_permission(
+    env: &Env,
+    guild_id: u64,
+    address: Address,
+    role_name: String,
+) -> bool {
+    let Some(required) = storage::get_custom_roles(env, guild_id).get(role_name) else {
+        return false;
+    };
+    match permission_level(env, guild_id, &address) {
+        Some(level) => level >= required,
+        None => false,
+    }
+}
+
+pub fn get_custom_roles(env: &Env, guild_id: u64) -> Vec<CustomRole> {
+    let mut roles = Vec::new(env);
+    for (name, level) in storage::get_custom_roles(env, guild_id).iter() {
+        roles.push_back(CustomRole { name, level });
     }
+    roles
 }
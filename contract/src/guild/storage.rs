@@ -1,10 +1,20 @@
-﻿use crate::guild::types::{Guild, Member, Role};
-use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
+﻿use crate::guild::types::{Announcement, Guild, Member, MembershipBadge, PermissionConfig, Role};
+use soroban_sdk::{symbol_short, Address, Env, Map, String, Symbol, Vec};
 
 // Storage keys as symbols for efficient lookup
 const GUILDS_KEY: Symbol = symbol_short!("guilds");
 const MEMBERS_KEY: Symbol = symbol_short!("members");
 const GUILD_COUNTER_KEY: Symbol = symbol_short!("guild_cnt");
+const CUSTOM_ROLES_KEY: Symbol = symbol_short!("c_roles");
+const ROLE_ASSIGN_KEY: Symbol = symbol_short!("c_assign");
+const ANNOUNCEMENTS_KEY: Symbol = symbol_short!("g_annc");
+const PERMISSIONS_KEY: Symbol = symbol_short!("g_perms");
+const BADGES_KEY: Symbol = symbol_short!("g_badge");
+const PROFILES_KEY: Symbol = symbol_short!("g_prof");
+const ACTIVITY_KEY: Symbol = symbol_short!("g_active");
+const ROLE_COOLDOWN_KEY: Symbol = symbol_short!("g_rcool");
+const ROLE_CHANGED_KEY: Symbol = symbol_short!("g_rchg");
+const SELF_REMOVAL_KEY: Symbol = symbol_short!("g_selfrm");
 
 /// Initialize storage for guilds and members
 /// This should be called during contract initialization
@@ -36,6 +46,14 @@ pub fn get_next_guild_id(env: &Env) -> u64 {
     next_id
 }
 
+/// Number of guilds created so far; ids run from 1 to this value
+pub fn get_guild_count(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&GUILD_COUNTER_KEY)
+        .unwrap_or(0u64)
+}
+
 /// Store a guild
 pub fn store_guild(env: &Env, guild: &Guild) {
     let mut guilds: Map<u64, Guild> = env
@@ -87,6 +105,41 @@ pub fn get_member(env: &Env, guild_id: u64, address: &Address) -> Option<Member>
     guild_members.get(address.clone())
 }
 
+/// Timestamp of a member's last state-changing participation, falling back to
+/// when they joined. Kept apart from `Member` so members stored before
+/// activity tracking still decode.
+pub fn get_last_active(env: &Env, guild_id: u64, member: &Member) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&(ACTIVITY_KEY, guild_id, member.address.clone()))
+        .unwrap_or(member.joined_at)
+}
+
+/// Set a member's last activity timestamp, or clear it with `None`
+pub fn set_last_active(env: &Env, guild_id: u64, address: &Address, timestamp: Option<u64>) {
+    let key = (ACTIVITY_KEY, guild_id, address.clone());
+    match timestamp {
+        Some(timestamp) => env.storage().persistent().set(&key, &timestamp),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Stamp a member's last activity with the current ledger time. No-op for
+/// addresses that are not members of the guild.
+pub fn touch_member(env: &Env, guild_id: u64, address: &Address) {
+    if has_member(env, guild_id, address) {
+        set_last_active(env, guild_id, address, Some(env.ledger().timestamp()));
+    }
+}
+
+/// [`touch_member`] in every guild the address belongs to, for participation
+/// not tied to a single guild such as multisig signing.
+pub fn touch_member_in_all_guilds(env: &Env, address: &Address) {
+    for guild_id in 1..=get_guild_count(env) {
+        touch_member(env, guild_id, address);
+    }
+}
+
 /// Remove a member from a guild
 pub fn remove_member(env: &Env, guild_id: u64, address: &Address) -> bool {
     let mut members_map: Map<u64, Map<Address, Member>> = env
@@ -162,3 +215,154 @@ pub fn count_owners(env: &Env, guild_id: u64) -> u32 {
 
     count
 }
+
+/// Custom role levels defined by a guild, keyed by role name
+pub fn get_custom_roles(env: &Env, guild_id: u64) -> Map<String, u32> {
+    env.storage()
+        .persistent()
+        .get(&(CUSTOM_ROLES_KEY, guild_id))
+        .unwrap_or_else(|| Map::new(env))
+}
+
+/// Define or re-rank a custom role
+pub fn store_custom_role(env: &Env, guild_id: u64, name: &String, level: u32) {
+    let mut roles = get_custom_roles(env, guild_id);
+    roles.set(name.clone(), level);
+    env.storage()
+        .persistent()
+        .set(&(CUSTOM_ROLES_KEY, guild_id), &roles);
+}
+
+/// Name of the custom role assigned to a member, if any
+pub fn get_custom_role_assignment(env: &Env, guild_id: u64, address: &Address) -> Option<String> {
+    env.storage()
+        .persistent()
+        .get(&(ROLE_ASSIGN_KEY, guild_id, address.clone()))
+}
+
+/// Assign a custom role to a member, or clear it with `None`
+pub fn set_custom_role_assignment(
+    env: &Env,
+    guild_id: u64,
+    address: &Address,
+    role_name: Option<String>,
+) {
+    let key = (ROLE_ASSIGN_KEY, guild_id, address.clone());
+    match role_name {
+        Some(name) => env.storage().persistent().set(&key, &name),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Permission matrix of a guild, falling back to the built-in rules
+pub fn get_permission_config(env: &Env, guild_id: u64) -> PermissionConfig {
+    env.storage()
+        .persistent()
+        .get(&(PERMISSIONS_KEY, guild_id))
+        .unwrap_or_else(PermissionConfig::default_matrix)
+}
+
+pub fn store_permission_config(env: &Env, guild_id: u64, config: &PermissionConfig) {
+    env.storage()
+        .persistent()
+        .set(&(PERMISSIONS_KEY, guild_id), config);
+}
+
+pub fn get_badge(env: &Env, guild_id: u64, holder: &Address) -> Option<MembershipBadge> {
+    env.storage()
+        .persistent()
+        .get(&(BADGES_KEY, guild_id, holder.clone()))
+}
+
+pub fn store_badge(env: &Env, badge: &MembershipBadge) {
+    env.storage()
+        .persistent()
+        .set(&(BADGES_KEY, badge.guild_id, badge.holder.clone()), badge);
+}
+
+pub fn remove_badge(env: &Env, guild_id: u64, holder: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&(BADGES_KEY, guild_id, holder.clone()));
+}
+
+/// Profile metadata URI a member has set, if any. Kept apart from `Member`
+/// so members stored before profiles existed still decode.
+pub fn get_profile_uri(env: &Env, guild_id: u64, address: &Address) -> Option<String> {
+    env.storage()
+        .persistent()
+        .get(&(PROFILES_KEY, guild_id, address.clone()))
+}
+
+/// Set a member's profile URI, or clear it with `None`
+pub fn set_profile_uri(env: &Env, guild_id: u64, address: &Address, uri: Option<String>) {
+    let key = (PROFILES_KEY, guild_id, address.clone());
+    match uri {
+        Some(uri) => env.storage().persistent().set(&key, &uri),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Minimum seconds between role changes of the same member (0 disables).
+/// Kept apart from `Guild` so guilds stored before the cooldown still decode.
+pub fn get_role_change_cooldown(env: &Env, guild_id: u64) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&(ROLE_COOLDOWN_KEY, guild_id))
+        .unwrap_or(0)
+}
+
+pub fn set_role_change_cooldown(env: &Env, guild_id: u64, cooldown_seconds: u64) {
+    env.storage()
+        .persistent()
+        .set(&(ROLE_COOLDOWN_KEY, guild_id), &cooldown_seconds);
+}
+
+/// Whether members may leave the guild on their own (default true). Kept
+/// apart from `Guild` so guilds stored before the setting still decode.
+pub fn get_allow_self_removal(env: &Env, guild_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .get(&(SELF_REMOVAL_KEY, guild_id))
+        .unwrap_or(true)
+}
+
+pub fn set_allow_self_removal(env: &Env, guild_id: u64, allowed: bool) {
+    env.storage()
+        .persistent()
+        .set(&(SELF_REMOVAL_KEY, guild_id), &allowed);
+}
+
+/// Timestamp of a member's last `update_role` (0 if never changed)
+pub fn get_last_role_change(env: &Env, guild_id: u64, address: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&(ROLE_CHANGED_KEY, guild_id, address.clone()))
+        .unwrap_or(0)
+}
+
+/// Record when a member's role last changed, or clear it with `None`
+pub fn set_last_role_change(env: &Env, guild_id: u64, address: &Address, timestamp: Option<u64>) {
+    let key = (ROLE_CHANGED_KEY, guild_id, address.clone());
+    match timestamp {
+        Some(timestamp) => env.storage().persistent().set(&key, &timestamp),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Announcements posted to a guild, oldest first
+pub fn get_announcements(env: &Env, guild_id: u64) -> Vec<Announcement> {
+    env.storage()
+        .persistent()
+        .get(&(ANNOUNCEMENTS_KEY, guild_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Append an announcement; existing entries are never rewritten
+pub fn append_announcement(env: &Env, guild_id: u64, announcement: &Announcement) {
+    let mut announcements = get_announcements(env, guild_id);
+    announcements.push_back(announcement.clone());
+    env.storage()
+        .persistent()
+        .set(&(ANNOUNCEMENTS_KEY, guild_id), &announcements);
+}
@@ -1,14 +1,21 @@
-//! Guild membership tests — join_guild
+//! Guild membership tests — join_guild and the guild registry
 //!
 //! Covers authorized self-join, duplicate join rejection, join on a
-//! non-existent guild, and unauthorized join (missing signature).
+//! non-existent guild, unauthorized join (missing signature), guild
+//! count / lookup queries, level-based custom role permissions,
+//! activity tracking, the role-change cooldown, self-removal rules,
+//! permission configs, membership badges, member profiles,
+//! announcements, guild exports, and role checks from other contracts.
 
 #![cfg(test)]
 
-use crate::guild::types::Role;
+use crate::governance::{GovernanceConfig, ProposalType};
+use crate::guild::membership::MAX_EXPORT_MEMBERS;
+use crate::guild::types::{PermissionConfig, Role};
+use crate::interfaces::{guild, ContractCallResult};
 use crate::{StellarGuildsContract, StellarGuildsContractClient};
-use soroban_sdk::testutils::Address as _;
-use soroban_sdk::{Address, Env, String};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{contract, contractimpl, Address, Env, String};
 
 // ─── Helpers ──────────────────────────────────────────────────────────────────
 
@@ -33,6 +40,21 @@ fn create_test_guild(client: &StellarGuildsContractClient<'_>, env: &Env, owner:
     )
 }
 
+/// External contract that gates a feature on the caller being a guild admin.
+#[contract]
+struct AdminGatedContract;
+
+#[contractimpl]
+impl AdminGatedContract {
+    pub fn is_allowed(env: Env, guilds: Address, guild_id: u64, caller: Address) -> bool {
+        let call = guild::GuildContractCall::VerifyRole(guild_id, caller, Role::Admin);
+        matches!(
+            guild::invoke(&env, &guilds, call),
+            Ok(ContractCallResult::Bool(true))
+        )
+    }
+}
+
 // ─── Tests ────────────────────────────────────────────────────────────────────
 
 /// An address that signs its own transaction can successfully join a guild
@@ -125,3 +147,555 @@ fn test_join_guild_unauthorized_panics() {
     // No mock_all_auths → require_auth() inside join_guild panics.
     client.join_guild(&guild_id, &joiner);
 }
+
+/// Guilds are enumerable by id up to `get_guild_count`.
+#[test]
+fn test_guild_registry_count_and_lookup() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    assert_eq!(client.get_guild_count(), 0);
+
+    let owner = Address::generate(&env);
+    let first = create_test_guild(&client, &env, &owner);
+    let second = create_test_guild(&client, &env, &owner);
+    assert_eq!(client.get_guild_count(), 2);
+
+    for guild_id in [first, second] {
+        let guild = client.get_guild(&guild_id);
+        assert_eq!(guild.id, guild_id);
+        assert_eq!(guild.owner, owner);
+        assert_eq!(guild.name, String::from_str(&env, "Test Guild"));
+    }
+}
+
+/// `find_member` returns `None` instead of panicking for non-members.
+#[test]
+fn test_find_member_is_graceful() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let member = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    client.add_member(&guild_id, &member, &Role::Member, &owner);
+
+    let found = client.find_member(&guild_id, &member).unwrap();
+    assert_eq!(found.address, member);
+    assert_eq!(found.role, Role::Member);
+    assert!(client.find_member(&guild_id, &stranger).is_none());
+    assert!(client.find_member(&999u64, &member).is_none());
+}
+
+/// Looking up an unknown guild id must panic with "guild not found".
+#[test]
+#[should_panic(expected = "guild not found")]
+fn test_get_nonexistent_guild_panics() {
+    let env = setup_env();
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    client.get_guild(&42u64);
+}
+
+/// A "Treasurer" custom role at level 250 sits between Member and Admin.
+#[test]
+fn test_custom_role_sits_between_member_and_admin() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    let treasurer = Address::generate(&env);
+    client.add_member(&guild_id, &treasurer, &Role::Contributor, &owner);
+
+    let name = String::from_str(&env, "Treasurer");
+    client.define_custom_role(&guild_id, &name, &250u32, &owner);
+    client.assign_custom_role(&guild_id, &treasurer, &Some(name.clone()), &owner);
+
+    assert!(client.has_permission(&guild_id, &treasurer, &Role::Member));
+    assert!(!client.has_permission(&guild_id, &treasurer, &Role::Admin));
+    assert!(client.has_custom_role_permission(&guild_id, &treasurer, &name));
+    assert!(client.has_custom_role_permission(&guild_id, &owner, &name));
+    assert_eq!(client.get_custom_roles(&guild_id).len(), 1);
+
+    client.assign_custom_role(&guild_id, &treasurer, &None, &owner);
+    assert!(!client.has_permission(&guild_id, &treasurer, &Role::Member));
+}
+
+/// An admin cannot define a custom role that outranks themselves.
+#[test]
+#[should_panic(expected = "Custom role level must be below the caller's level")]
+fn test_custom_role_cannot_outrank_creator() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    let admin = Address::generate(&env);
+    client.add_member(&guild_id, &admin, &Role::Admin, &owner);
+
+    client.define_custom_role(
+        &guild_id,
+        &String::from_str(&env, "Steward"),
+        &350u32,
+        &admin,
+    );
+}
+
+/// Only state-changing participation refreshes a member's last activity.
+#[test]
+fn test_inactive_members_tracks_participation_only() {
+    let env = setup_env();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    let active = Address::generate(&env);
+    let idle = Address::generate(&env);
+    client.add_member(&guild_id, &active, &Role::Member, &owner);
+    client.add_member(&guild_id, &idle, &Role::Member, &owner);
+
+    env.ledger().with_mut(|l| l.timestamp = 5_000);
+    client.create_proposal(
+        &guild_id,
+        &active,
+        &ProposalType::GeneralDecision,
+        &String::from_str(&env, "Ping"),
+        &String::from_str(&env, "Activity"),
+        &None,
+    );
+    // reads leave activity untouched
+    client.get_member(&guild_id, &idle);
+    client.has_permission(&guild_id, &idle, &Role::Member);
+
+    assert_eq!(client.get_member_last_active(&guild_id, &active), 5_000);
+    assert_eq!(client.get_member_last_active(&guild_id, &idle), 1_000);
+
+    let inactive = client.get_inactive_members(&guild_id, &5_000);
+    assert_eq!(inactive.len(), 2);
+    assert!(inactive.contains(&owner));
+    assert!(inactive.contains(&idle));
+    assert_eq!(client.get_inactive_members(&guild_id, &1_000).len(), 0);
+}
+
+/// A member's role stays locked for the configured cooldown after a change.
+#[test]
+fn test_role_change_cooldown_blocks_rapid_flips() {
+    let env = setup_env();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    let member = Address::generate(&env);
+    client.add_member(&guild_id, &member, &Role::Member, &owner);
+    client.set_role_change_cooldown(&guild_id, &3_600, &owner);
+
+    client.update_role(&guild_id, &member, &Role::Admin, &owner);
+    assert_eq!(client.get_member(&guild_id, &member).role, Role::Admin);
+
+    env.ledger().with_mut(|l| l.timestamp = 2_000);
+    assert!(client
+        .try_update_role(&guild_id, &member, &Role::Member, &owner)
+        .is_err());
+
+    env.ledger().with_mut(|l| l.timestamp = 4_600);
+    client.update_role(&guild_id, &member, &Role::Member, &owner);
+    assert_eq!(client.get_member(&guild_id, &member).role, Role::Member);
+}
+
+#[test]
+#[should_panic(expected = "Role change on cooldown until 4600")]
+fn test_role_change_cooldown_reports_when_allowed() {
+    let env = setup_env();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    let member = Address::generate(&env);
+    client.add_member(&guild_id, &member, &Role::Member, &owner);
+    client.set_role_change_cooldown(&guild_id, &3_600, &owner);
+
+    client.update_role(&guild_id, &member, &Role::Admin, &owner);
+    client.update_role(&guild_id, &member, &Role::Member, &owner);
+}
+
+/// Without a cooldown roles can change back to back.
+#[test]
+fn test_zero_cooldown_allows_consecutive_role_changes() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    let member = Address::generate(&env);
+    client.add_member(&guild_id, &member, &Role::Member, &owner);
+
+    client.update_role(&guild_id, &member, &Role::Admin, &owner);
+    client.update_role(&guild_id, &member, &Role::Contributor, &owner);
+    assert_eq!(
+        client.get_member(&guild_id, &member).role,
+        Role::Contributor
+    );
+}
+
+/// Self-removal can be switched off per guild, and is refused for members
+/// still holding a treasury signer seat; admins can always remove them.
+#[test]
+fn test_self_removal_setting_and_signer_obligation() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    let member = Address::generate(&env);
+    let signer = Address::generate(&env);
+    client.add_member(&guild_id, &member, &Role::Member, &owner);
+    client.add_member(&guild_id, &signer, &Role::Admin, &owner);
+    assert!(client.get_allow_self_removal(&guild_id));
+
+    client.set_allow_self_removal(&guild_id, &false, &owner);
+    assert!(client
+        .try_remove_member(&guild_id, &member, &member)
+        .is_err());
+    assert!(client.remove_member(&guild_id, &member, &owner));
+
+    client.set_allow_self_removal(&guild_id, &true, &owner);
+    let mut signers = soroban_sdk::Vec::new(&env);
+    signers.push_back(owner.clone());
+    signers.push_back(signer.clone());
+    client.initialize_treasury(
+        &guild_id,
+        &signers,
+        &2u32,
+        &false,
+        &false,
+        &soroban_sdk::Vec::new(&env),
+    );
+    assert!(client
+        .try_remove_member(&guild_id, &signer, &signer)
+        .is_err());
+    assert!(client.is_member(&guild_id, &signer));
+}
+
+/// Guilds start on the built-in matrix and can tighten it: once adding members
+/// requires an admin, a plain member can no longer add anyone.
+#[test]
+fn test_permission_config_can_be_tightened() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    let member = Address::generate(&env);
+    client.add_member(&guild_id, &member, &Role::Member, &owner);
+
+    let mut config = client.get_permission_config(&guild_id);
+    assert_eq!(config, PermissionConfig::default_matrix());
+
+    client.add_member(
+        &guild_id,
+        &Address::generate(&env),
+        &Role::Contributor,
+        &member,
+    );
+
+    config.add_member.member = Role::Admin;
+    config.add_member.contributor = Role::Admin;
+    client.set_permission_config(&guild_id, &config, &owner);
+
+    assert!(client
+        .try_add_member(
+            &guild_id,
+            &Address::generate(&env),
+            &Role::Contributor,
+            &member
+        )
+        .is_err());
+    client.add_member(
+        &guild_id,
+        &Address::generate(&env),
+        &Role::Contributor,
+        &owner,
+    );
+}
+
+/// Loosened rules let members manage admins; only owners may change the config.
+#[test]
+fn test_permission_config_can_be_loosened_by_owner_only() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    let member = Address::generate(&env);
+    let admin = Address::generate(&env);
+    client.add_member(&guild_id, &member, &Role::Member, &owner);
+
+    let mut config = PermissionConfig::default_matrix();
+    config.add_member.admin = Role::Member;
+    config.remove_member.admin = Role::Member;
+    assert!(client
+        .try_set_permission_config(&guild_id, &config, &member)
+        .is_err());
+    assert!(client
+        .try_add_member(&guild_id, &admin, &Role::Admin, &member)
+        .is_err());
+
+    client.set_permission_config(&guild_id, &config, &owner);
+    client.add_member(&guild_id, &admin, &Role::Admin, &member);
+    assert_eq!(client.get_member(&guild_id, &admin).role, Role::Admin);
+    client.remove_member(&guild_id, &admin, &member);
+    assert!(!client.is_member(&guild_id, &admin));
+}
+
+/// A badge records the role at mint time and disappears with the membership.
+#[test]
+fn test_membership_badge_is_revoked_on_removal() {
+    let env = setup_env();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    let member = Address::generate(&env);
+    client.add_member(&guild_id, &member, &Role::Admin, &owner);
+
+    assert!(!client.verify_badge(&guild_id, &member));
+    assert!(client
+        .try_mint_membership_badge(&guild_id, &Address::generate(&env))
+        .is_err());
+
+    let badge = client.mint_membership_badge(&guild_id, &member);
+    assert_eq!(badge.role, Role::Admin);
+    assert_eq!(badge.issued_at, 1_000);
+    assert_eq!(client.get_badge(&guild_id, &member), Some(badge));
+    assert!(client.verify_badge(&guild_id, &member));
+
+    client.remove_member(&guild_id, &member, &owner);
+    assert!(!client.verify_badge(&guild_id, &member));
+    assert_eq!(client.get_badge(&guild_id, &member), None);
+}
+
+/// An export carries metadata, members and governance config together and
+/// refuses guilds too large for a single read.
+#[test]
+fn test_export_guild_snapshot() {
+    let env = setup_env();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 2_000);
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    let member = Address::generate(&env);
+    client.add_member(&guild_id, &member, &Role::Member, &owner);
+
+    let snapshot = client.export_guild(&guild_id);
+    assert_eq!(snapshot.guild.id, guild_id);
+    assert_eq!(snapshot.members.len(), 2);
+    assert_eq!(snapshot.governance_config, GovernanceConfig::default());
+    assert_eq!(snapshot.taken_at, 2_000);
+    assert!(client.try_export_guild(&999).is_err());
+
+    assert_eq!(client.get_members_page(&guild_id, &1, &10).len(), 1);
+    assert_eq!(client.get_members_page(&guild_id, &5, &10).len(), 0);
+
+    for _ in 0..MAX_EXPORT_MEMBERS {
+        client.add_member(
+            &guild_id,
+            &Address::generate(&env),
+            &Role::Contributor,
+            &owner,
+        );
+    }
+    assert!(client.try_export_guild(&guild_id).is_err());
+    assert_eq!(client.get_members_page(&guild_id, &0, &50).len(), 50);
+}
+
+/// Another contract can gate on guild roles without risking a panic for
+/// non-members or unknown guilds.
+#[test]
+fn test_verify_role_from_another_contract() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let gate_id = env.register_contract(None, AdminGatedContract);
+    let gate = AdminGatedContractClient::new(&env, &gate_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    let admin = Address::generate(&env);
+    let member = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    client.add_member(&guild_id, &admin, &Role::Admin, &owner);
+    client.add_member(&guild_id, &member, &Role::Member, &owner);
+
+    assert!(client.verify_role(&guild_id, &member, &Role::Contributor));
+    assert!(!client.verify_role(&guild_id, &outsider, &Role::Contributor));
+
+    assert!(gate.is_allowed(&contract_id, &guild_id, &owner));
+    assert!(gate.is_allowed(&contract_id, &guild_id, &admin));
+    assert!(!gate.is_allowed(&contract_id, &guild_id, &member));
+    assert!(!gate.is_allowed(&contract_id, &guild_id, &outsider));
+    assert!(!gate.is_allowed(&contract_id, &999, &admin));
+}
+
+/// Members set their own profile URI; it survives role changes and is
+/// cleared by an empty URI or by leaving the guild.
+#[test]
+fn test_set_member_profile() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    let member = Address::generate(&env);
+    client.add_member(&guild_id, &member, &Role::Member, &owner);
+    assert_eq!(client.get_member_profile(&guild_id, &member), None);
+
+    let uri = String::from_str(&env, "ipfs://profile");
+    assert!(client.set_member_profile(&guild_id, &member, &uri));
+    client.update_role(&guild_id, &member, &Role::Admin, &owner);
+    assert_eq!(
+        client.get_member_profile(&guild_id, &member),
+        Some(uri.clone())
+    );
+
+    client.set_member_profile(&guild_id, &member, &String::from_str(&env, ""));
+    assert_eq!(client.get_member_profile(&guild_id, &member), None);
+
+    client.set_member_profile(&guild_id, &member, &uri);
+    client.remove_member(&guild_id, &member, &owner);
+    assert_eq!(client.get_member_profile(&guild_id, &member), None);
+}
+
+/// Only members of the guild can set a profile there.
+#[test]
+#[should_panic(expected = "Member not found")]
+fn test_set_member_profile_non_member_panics() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    let outsider = Address::generate(&env);
+    client.set_member_profile(&guild_id, &outsider, &String::from_str(&env, "ipfs://x"));
+}
+
+/// Profile URIs are capped at 256 characters.
+#[test]
+#[should_panic(expected = "Profile URI must be at most 256 characters")]
+fn test_set_member_profile_rejects_long_uri() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    let uri = String::from_bytes(&env, &[b'a'; 257]);
+    client.set_member_profile(&guild_id, &owner, &uri);
+}
+
+/// Admins post announcements; readers get the newest first, capped at `limit`.
+#[test]
+fn test_post_and_get_announcements() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    let admin = Address::generate(&env);
+    client.add_member(&guild_id, &admin, &Role::Admin, &owner);
+
+    env.ledger().with_mut(|l| l.timestamp = 100);
+    client.post_announcement(&guild_id, &owner, &String::from_str(&env, "ipfs://policy"));
+    env.ledger().with_mut(|l| l.timestamp = 200);
+    client.post_announcement(
+        &guild_id,
+        &admin,
+        &String::from_str(&env, "ipfs://schedule"),
+    );
+
+    let latest = client.get_announcements(&guild_id, &1);
+    assert_eq!(latest.len(), 1);
+    let first = latest.get(0).unwrap();
+    assert_eq!(first.author, admin);
+    assert_eq!(first.content_uri, String::from_str(&env, "ipfs://schedule"));
+    assert_eq!(first.posted_at, 200);
+
+    let all = client.get_announcements(&guild_id, &10);
+    assert_eq!(all.len(), 2);
+    assert_eq!(all.get(1).unwrap().author, owner);
+}
+
+/// Regular members cannot post announcements.
+#[test]
+#[should_panic(expected = "Only owner or admin can post announcements")]
+fn test_member_cannot_post_announcement() {
+    let env = setup_env();
+    env.mock_all_auths();
+
+    let contract_id = register_and_init(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let guild_id = create_test_guild(&client, &env, &owner);
+    let member = Address::generate(&env);
+    client.add_member(&guild_id, &member, &Role::Member, &owner);
+
+    client.post_announcement(&guild_id, &member, &String::from_str(&env, "ipfs://x"));
+}
@@ -1,4 +1,5 @@
-﻿use soroban_sdk::{contracttype, Address};
+﻿use crate::governance::types::GovernanceConfig;
+use soroban_sdk::{contracttype, Address};
 
 /// Role enum for guild members
 /// - Owner: Full control over the guild
@@ -15,18 +16,21 @@ pub enum Role {
 }
 
 impl Role {
+    /// Numeric permission level of a built-in role. The gaps leave room for
+    /// guild-defined custom roles to rank between them.
+    pub fn level(&self) -> u32 {
+        match self {
+            Role::Owner => 400,
+            Role::Admin => 300,
+            Role::Member => 200,
+            Role::Contributor => 100,
+        }
+    }
+
     /// Check if a role has permission for a required role level
     /// Hierarchy: Owner > Admin > Member > Contributor
     pub fn has_permission(&self, required_role: &Role) -> bool {
-        match (self, required_role) {
-            (Role::Owner, _) => true,
-            (Role::Admin, Role::Admin)
-            | (Role::Admin, Role::Member)
-            | (Role::Admin, Role::Contributor) => true,
-            (Role::Member, Role::Member) | (Role::Member, Role::Contributor) => true,
-            (Role::Contributor, Role::Contributor) => true,
-            _ => false,
-        }
+        self.level() >= required_role.level()
     }
 }
 
@@ -72,6 +76,98 @@ pub struct Member {
     pub joined_at: u64,
 }
 
+/// Guild-defined role ranked by a numeric permission level, e.g. a
+/// "Treasurer" at 250 sits between Member (200) and Admin (300)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CustomRole {
+    pub name: soroban_sdk::String,
+    pub level: u32,
+}
+
+/// Soul-bound proof of guild membership. There is deliberately no way to
+/// transfer one; it is deleted when the holder leaves the guild.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MembershipBadge {
+    pub guild_id: u64,
+    pub holder: Address,
+    /// Role held when the badge was minted
+    pub role: Role,
+    pub issued_at: u64,
+}
+
+/// Point-in-time view of a guild for backups and migrations
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct GuildSnapshot {
+    pub guild: Guild,
+    pub members: soroban_sdk::Vec<Member>,
+    pub governance_config: GovernanceConfig,
+    /// Ledger timestamp the snapshot was taken at
+    pub taken_at: u64,
+}
+
+/// Minimum caller role needed to act on a member, by the target member's role
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RolePermissions {
+    pub owner: Role,
+    pub admin: Role,
+    pub member: Role,
+    pub contributor: Role,
+}
+
+impl RolePermissions {
+    /// Minimum role required to act on a member holding `target`
+    pub fn required_for(&self, target: &Role) -> Role {
+        match target {
+            Role::Owner => self.owner,
+            Role::Admin => self.admin,
+            Role::Member => self.member,
+            Role::Contributor => self.contributor,
+        }
+    }
+}
+
+/// Per-guild permission matrix consulted by `add_member`, `remove_member`
+/// and `update_role`. For `update_role` the target is the member's current role.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PermissionConfig {
+    pub add_member: RolePermissions,
+    pub remove_member: RolePermissions,
+    pub update_role: RolePermissions,
+}
+
+impl PermissionConfig {
+    /// The built-in rules: owners manage owners, admins manage admins, members
+    /// may add or remove members and contributors, and only admins change
+    /// their roles.
+    pub fn default_matrix() -> Self {
+        let by_target = |lower: Role| RolePermissions {
+            owner: Role::Owner,
+            admin: Role::Admin,
+            member: lower,
+            contributor: lower,
+        };
+        PermissionConfig {
+            add_member: by_target(Role::Member),
+            remove_member: by_target(Role::Member),
+            update_role: by_target(Role::Admin),
+        }
+    }
+}
+
+/// Official guild announcement; the content itself lives off-chain at `content_uri`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Announcement {
+    pub author: Address,
+    pub content_uri: soroban_sdk::String,
+    pub posted_at: u64,
+}
+
 /// Event emitted when a guild is created
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -101,6 +197,36 @@ pub struct MemberAddedEvent {
     pub joined_at: u64,
 }
 
+/// Event emitted when a member updates their profile URI
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MemberProfileUpdatedEvent {
+    pub guild_id: u64,
+    pub address: Address,
+    /// New profile URI, `None` when the profile was cleared
+    pub metadata_uri: Option<soroban_sdk::String>,
+}
+
+/// Event emitted when a member mints their membership badge
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BadgeMintedEvent {
+    pub guild_id: u64,
+    pub holder: Address,
+    pub role: Role,
+    pub issued_at: u64,
+}
+
+/// Event emitted when an announcement is posted to a guild
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AnnouncementPostedEvent {
+    pub guild_id: u64,
+    pub author: Address,
+    pub content_uri: soroban_sdk::String,
+    pub posted_at: u64,
+}
+
 /// Event emitted when a member is removed
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -109,6 +235,15 @@ pub struct MemberRemovedEvent {
     pub address: Address,
 }
 
+/// Event emitted when a custom role is assigned to or cleared from a member
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CustomRoleAssignedEvent {
+    pub guild_id: u64,
+    pub address: Address,
+    pub role_name: Option<soroban_sdk::String>,
+}
+
 /// Event emitted when a member's role is updated
 #[contracttype]
 #[derive(Clone, Debug)]
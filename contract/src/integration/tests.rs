@@ -366,8 +366,18 @@ mod tests {
             &guild_id,
             &Vec::from_array(&env, [admin.clone()]),
             &1u32,
+            &false,
+            &false,
+            &Vec::new(&env),
         );
-        assert!(treasury_client.deposit_treasury(&treasury_id, &admin, &750i128, &None,));
+        assert!(treasury_client.deposit_treasury(
+            &treasury_id,
+            &admin,
+            &750i128,
+            &None,
+            &None,
+            &None
+        ));
 
         let pool_id = payment_client.create_payment_pool(
             &500i128,
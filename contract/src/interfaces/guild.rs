@@ -10,6 +10,7 @@ pub enum GuildContractCall {
     GetAllMembers(u64),
     IsMember(u64, Address),
     HasPermission(u64, Address, Role),
+    VerifyRole(u64, Address, Role),
 }
 
 pub fn invoke(
@@ -68,5 +69,21 @@ pub fn invoke(
                 _ => Err(IntegrationErrorCode::CrossContractCallFailed),
             }
         }
+        GuildContractCall::VerifyRole(guild_id, address, min_role) => {
+            let result = env.try_invoke_contract::<bool, soroban_sdk::InvokeError>(
+                contract_address,
+                &Symbol::new(env, "verify_role"),
+                vec![
+                    env,
+                    guild_id.into_val(env),
+                    address.into_val(env),
+                    min_role.into_val(env),
+                ],
+            );
+            match result {
+                Ok(Ok(verified)) => Ok(ContractCallResult::Bool(verified)),
+                _ => Err(IntegrationErrorCode::CrossContractCallFailed),
+            }
+        }
     }
 }
@@ -12,7 +12,9 @@ mod tests {
     use crate::payment::types::DistributionStatus;
     use crate::reputation::types::ReputationProfile;
     use crate::subscription::types::{MembershipTier, Subscription, SubscriptionStatus};
-    use crate::treasury::types::{Transaction, TransactionStatus, TransactionType, Treasury};
+    use crate::treasury::types::{
+        Transaction, TransactionStatus, TransactionType, Treasury, WithdrawalCategory,
+    };
     use soroban_sdk::testutils::Address as _;
     use soroban_sdk::{contract, contractimpl, Address, Env, Map, String, Vec};
 
@@ -128,6 +130,10 @@ mod tests {
                 execution_payload: ExecutionPayload::GeneralDecision,
                 passed_at: None,
                 executed_at: None,
+                winning_option: None,
+                reveal_end: None,
+                extended: false,
+                total_eligible_weight: 18,
             }
         }
 
@@ -200,7 +206,9 @@ mod tests {
                 guild_id: 7,
                 owner: Address::generate(&env),
                 signers: Vec::new(&env),
+                role_based_signers: false,
                 approval_threshold: 2,
+                auto_threshold: false,
                 high_value_threshold: 1000,
                 balance_xlm: 500,
                 token_balances: Map::new(&env),
@@ -229,7 +237,12 @@ mod tests {
                     status: TransactionStatus::Executed,
                     created_at: 1,
                     expires_at: 2,
+                    category: WithdrawalCategory::Other,
                     reason: String::from_str(&env, "seed"),
+                    fee: 0,
+                    net_amount: 300,
+                    memo: None,
+                    on_behalf_of: None,
                 }],
             )
         }
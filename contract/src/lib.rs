@@ -1,6 +1,6 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, Address, Env, String, Vec};
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, Map, String, Vec};
 
 mod events;
 mod guild;
@@ -8,26 +8,54 @@ mod integration;
 mod interfaces;
 mod utils;
 use guild::membership::{
-    add_member, create_guild, get_all_members, get_member, has_permission, is_member, join_guild,
-    remove_member, update_role,
+    add_member, assign_custom_role, create_guild, define_custom_role, get_all_members,
+    get_allow_self_removal, get_announcements, get_badge, get_custom_roles, get_guild, get_guild_count, get_inactive_members,
+    export_guild, get_member, get_member_last_active, get_member_profile, get_members_page, get_permission_config, has_custom_role_permission, has_permission, is_member,
+    join_guild, mint_membership_badge, post_announcement, remove_member, set_allow_self_removal,
+    set_member_profile, set_permission_config, set_role_change_cooldown, try_get_member,
+    update_role, verify_badge, verify_role,
 };
 use guild::storage;
-use guild::types::{Member, Role};
+use guild::types::{
+    Announcement, CustomRole, Guild, GuildSnapshot, Member, MembershipBadge, PermissionConfig,
+    Role,
+};
 
 mod bounty;
 use bounty::{
-    approve_bounty, approve_completion, cancel_bounty, claim_bounty, claim_payout, create_bounty,
-    expire_bounty, fund_bounty, get_bounty_data, get_guild_bounties_list, release_escrow,
-    submit_work, Bounty,
+    approve_bounty, approve_bounty_milestone, approve_completion, cancel_bounty, claim_bounty,
+    claim_payout, create_bounty, create_milestone_bounty, dispute_bounty, expire_bounty,
+    fund_bounty, get_bounty_data, get_bounty_milestones, get_claim_window, get_failed_count,
+    get_guild_bounties_list, get_reputation as bounty_get_reputation,
+    refund_expired_bounty, release_escrow, set_claim_window, submit_work, unassign_claimer, Bounty,
+    BountyMilestone,
 };
 
 mod treasury;
 use treasury::{
     approve_transaction as core_approve_transaction, deposit as core_deposit,
     emergency_pause as core_emergency_pause, execute_transaction as core_execute_transaction,
-    get_balance as core_get_balance, get_transaction_history as core_get_transaction_history,
+    get_approval_tiers as core_get_approval_tiers,
+    get_balance as core_get_balance, get_depositor_total as core_get_depositor_total,
+    get_payee_allowlist as core_get_payee_allowlist,
+    get_reserve_floor as core_get_reserve_floor, get_top_depositors as core_get_top_depositors,
+    get_transaction_history as core_get_transaction_history,
+    get_transaction_history_paged as core_get_transaction_history_paged,
+    get_transaction_timeout as core_get_transaction_timeout,
+    get_treasuries_for_guild as core_get_treasuries_for_guild,
+    get_treasury_co_guilds as core_get_treasury_co_guilds,
+    get_treasury_guild as core_get_treasury_guild,
     grant_allowance as core_grant_allowance, initialize_treasury as core_initialize_treasury,
-    propose_withdrawal as core_propose_withdrawal, set_budget as core_set_budget, Transaction,
+    is_operation_blocked as core_is_operation_blocked, is_payee_allowed as core_is_payee_allowed,
+    propose_treasury_transfer as core_propose_treasury_transfer,
+    propose_withdrawal as core_propose_withdrawal, revoke_approval as core_revoke_approval,
+    set_approval_tiers as core_set_approval_tiers, set_budget as core_set_budget,
+    set_operation_pause as core_set_operation_pause,
+    set_payee_allowlist as core_set_payee_allowlist,
+    set_reserve_floor as core_set_reserve_floor,
+    set_transaction_timeout as core_set_transaction_timeout,
+    set_withdrawal_fee_bps as core_set_withdrawal_fee_bps, spend_allowance as core_spend_allowance,
+    Transaction, TreasuryOperation, WithdrawalCategory,
 };
 
 mod analytics;
@@ -47,12 +75,32 @@ use reputation::{
 
 mod governance;
 use governance::{
-    cancel_proposal as gov_cancel_proposal, create_proposal as gov_create_proposal,
+    add_proposal_comment as gov_add_proposal_comment, cancel_proposal as gov_cancel_proposal,
+    check_early_rejection as gov_check_early_rejection,
+    claim_proposal_bond as gov_claim_proposal_bond, commit_vote as gov_commit_vote,
+    create_commit_reveal_proposal as gov_create_commit_reveal_proposal,
+    create_proposal as gov_create_proposal,
+    create_treasury_spend_proposal as gov_create_treasury_spend_proposal,
+    default_payload as gov_default_payload,
     delegate_vote as gov_delegate_vote, execute_proposal as gov_execute_proposal,
-    finalize_proposal as gov_finalize_proposal, get_active_proposals as gov_get_active_proposals,
-    get_proposal as gov_get_proposal, undelegate_vote as gov_undelegate_vote,
-    update_governance_config as gov_update_governance_config, vote as gov_vote, ExecutionPayload,
-    GovernanceConfig, Proposal, ProposalStatus, ProposalType, VoteDecision,
+    extend_voting_period as gov_extend_voting_period,
+    finalize_and_execute as gov_finalize_and_execute, finalize_proposal as gov_finalize_proposal,
+    get_active_proposals as gov_get_active_proposals,
+    get_active_proposals_by_type as gov_get_active_proposals_by_type,
+    get_all_proposals as gov_get_all_proposals,
+    get_delegation_target as gov_get_delegation_target, get_delegators as gov_get_delegators,
+    get_proposal as gov_get_proposal, get_proposal_bond as gov_get_proposal_bond,
+    get_proposal_comments as gov_get_proposal_comments,
+    get_proposal_config as gov_get_proposal_config,
+    get_proposal_count as gov_get_proposal_count, get_snapshot_weight as gov_get_snapshot_weight,
+    get_treasury_spend as gov_get_treasury_spend,
+    get_votes_by_member as gov_get_votes_by_member, get_voting_weight as gov_get_voting_weight,
+    is_quorum_met as gov_is_quorum_met, reveal_vote as gov_reveal_vote, simulate_finalize as gov_simulate_finalize,
+    sweep_proposals as gov_sweep_proposals, undelegate_vote as gov_undelegate_vote,
+    update_governance_config as gov_update_governance_config, veto_proposal as gov_veto_proposal,
+    vote as gov_vote, vote_choice as gov_vote_choice, ExecutionPayload, FinalizeExecuteOutcome,
+    GovernanceConfig, Proposal, ProposalBond, ProposalComment, ProposalStatus, ProposalType,
+    TreasuryConfigData, TreasurySpendData, VoteDecision,
 };
 
 mod milestone;
@@ -106,8 +154,9 @@ use allowance::{
 
 mod emergency;
 use emergency::{
+    ensure_not_paused, is_contract_paused as emerg_is_contract_paused,
     is_paused as emerg_is_paused, pause_contract as emerg_pause_contract,
-    resume_contract as emerg_resume_contract,
+    resume_contract as emerg_resume_contract, set_contract_paused as emerg_set_contract_paused,
 };
 
 mod multisig;
@@ -116,36 +165,61 @@ use multisig::{
     ms_add_signer as internal_add_signer,
     // Signing aliases
     ms_cancel_operation as internal_cancel_operation,
+    ms_clear_proxy as internal_clear_proxy,
     ms_check_and_expire as internal_check_and_expire,
     ms_emergency_expire_operation as internal_emergency_expire_operation,
     ms_emergency_extend_timeout as internal_emergency_extend_timeout,
     ms_execute_operation as internal_execute_operation,
     ms_freeze_account as internal_freeze_account,
     // Policy aliases
+    ms_get_operation_count as internal_get_operation_count,
     ms_get_operation_policy as internal_get_operation_policy,
+    ms_get_operation_progress as internal_get_operation_progress,
     ms_get_operation_status as internal_get_operation_status,
+    ms_get_operations as internal_get_operations,
+    ms_get_proxy as internal_get_proxy,
+    ms_get_operations_awaiting_signer as internal_get_operations_awaiting_signer,
     ms_get_pending_operations as internal_get_pending_operations,
+    ms_get_account_count as internal_get_account_count,
+    ms_get_inactive_signers as internal_get_inactive_signers,
     ms_get_safe_account as internal_get_safe_account,
+    ms_get_signers as internal_get_signers,
+    ms_is_signer as internal_is_signer,
+    ms_list_accounts_by_guild as internal_list_accounts_by_guild,
     ms_list_accounts_by_owner as internal_list_accounts_by_owner,
     ms_propose_operation as internal_propose_operation,
+    ms_recover_account as internal_recover_account,
     ms_register_account as internal_register_account,
+    ms_register_account_with_policies as internal_register_account_with_policies,
     ms_remove_signer as internal_remove_signer,
+    ms_resign_signer as internal_resign_signer,
     ms_require_executed_operation as internal_require_executed_operation,
     ms_reset_operation_policy as internal_reset_operation_policy,
     ms_rotate_signer as internal_rotate_signer,
     ms_set_operation_policy as internal_set_operation_policy,
+    ms_set_proxy as internal_set_proxy,
+    ms_set_signer_weights as internal_set_signer_weights,
+    ms_set_threshold_percentage as internal_set_threshold_percentage,
+    ms_set_timeout_bounds as internal_set_timeout_bounds,
 
+    ms_sign_as_proxy as internal_sign_as_proxy,
     ms_sign_operation as internal_sign_operation,
+    ms_sign_operations_batch as internal_sign_operations_batch,
     ms_sweep_expired_operations as internal_sweep_expired_operations,
 
+    ms_transfer_account_ownership as internal_transfer_account_ownership,
     ms_unfreeze_account as internal_unfreeze_account,
     ms_update_threshold as internal_update_threshold,
 
     // Types
     MultiSigAccount,
+    MultiSigError,
     MultiSigOperation,
+    OperationPayload,
     OperationPolicy,
+    OperationStatus,
     OperationType,
+    SignerProxy,
 };
 
 mod upgrade;
@@ -211,6 +285,17 @@ impl StellarGuildsContract {
         String::from_str(&_env, "0.1.0")
     }
 
+    /// Pause or resume every mutating entry point across all guilds (contract
+    /// admin only). Reads, contract upgrades and emergency stops stay available.
+    pub fn set_contract_paused(env: Env, paused: bool, admin: Address) -> bool {
+        emerg_set_contract_paused(&env, paused, admin)
+    }
+
+    /// Whether the contract-wide pause switch is on.
+    pub fn is_contract_paused(env: Env) -> bool {
+        emerg_is_contract_paused(&env)
+    }
+
     // ============ Integration Layer ============
 
     pub fn register_contract(
@@ -220,6 +305,7 @@ impl StellarGuildsContract {
         version: Version,
         caller: Address,
     ) -> bool {
+        ensure_not_paused(&env);
         integration::auth::require_admin(&env, &caller);
         integration::registry::register_contract(&env, contract_type, address, version)
     }
@@ -235,6 +321,7 @@ impl StellarGuildsContract {
         new_version: Version,
         caller: Address,
     ) -> bool {
+        ensure_not_paused(&env);
         integration::auth::require_admin(&env, &caller);
         integration::registry::update_contract(&env, contract_type, new_address, new_version)
     }
@@ -251,6 +338,7 @@ impl StellarGuildsContract {
         schema_version: u32,
         caller: Address,
     ) -> bool {
+        ensure_not_paused(&env);
         integration::auth::require_admin(&env, &caller);
         integration::events::emit_event(&env, event_type, source_contract, data, schema_version)
     }
@@ -265,6 +353,7 @@ impl StellarGuildsContract {
     }
 
     pub fn subscribe_to_events(env: Env, subscriber: Address, event_types: Vec<EventType>) -> bool {
+        ensure_not_paused(&env);
         subscriber.require_auth();
         integration::events::subscribe_to_events(&env, subscriber, event_types)
     }
@@ -274,6 +363,7 @@ impl StellarGuildsContract {
         caller: Address,
         call: GuildContractCall,
     ) -> ContractCallResponse {
+        ensure_not_paused(&env);
         let address = integration::registry::get_contract_address(&env, ContractType::Guild);
         if !integration::auth::verify_cross_contract_auth(
             &env,
@@ -305,6 +395,9 @@ impl StellarGuildsContract {
                         required_role,
                     )))
                 }
+                GuildContractCall::VerifyRole(guild_id, address, min_role) => Ok(
+                    ContractCallResult::Bool(verify_role(&env, guild_id, address, min_role)),
+                ),
             }
         } else {
             interfaces::guild::invoke(&env, &address, call)
@@ -316,6 +409,7 @@ impl StellarGuildsContract {
         caller: Address,
         call: BountyContractCall,
     ) -> ContractCallResponse {
+        ensure_not_paused(&env);
         let address = integration::registry::get_contract_address(&env, ContractType::Bounty);
         if !integration::auth::verify_cross_contract_auth(
             &env,
@@ -348,6 +442,7 @@ impl StellarGuildsContract {
         caller: Address,
         call: PaymentContractCall,
     ) -> ContractCallResponse {
+        ensure_not_paused(&env);
         let address = integration::registry::get_contract_address(&env, ContractType::Payment);
         if !integration::auth::verify_cross_contract_auth(
             &env,
@@ -402,6 +497,7 @@ impl StellarGuildsContract {
     }
 
     pub fn create_event_id(env: Env) -> u128 {
+        ensure_not_paused(&env);
         integration::events::create_event_id(&env)
     }
 
@@ -415,6 +511,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// The ID of the newly created guild
     pub fn create_guild(env: Env, name: String, description: String, owner: Address) -> u64 {
+        ensure_not_paused(&env);
         owner.require_auth();
         match create_guild(&env, name, description, owner) {
             Ok(id) => id,
@@ -439,6 +536,7 @@ impl StellarGuildsContract {
         role: Role,
         caller: Address,
     ) -> bool {
+        ensure_not_paused(&env);
         caller.require_auth();
         match add_member(&env, guild_id, address, role, caller) {
             Ok(result) => result,
@@ -456,10 +554,11 @@ impl StellarGuildsContract {
     /// # Returns
     /// true if successful, panics with error message otherwise
     pub fn remove_member(env: Env, guild_id: u64, address: Address, caller: Address) -> bool {
+        ensure_not_paused(&env);
         caller.require_auth();
         match remove_member(&env, guild_id, address, caller) {
             Ok(result) => result,
-            Err(_) => panic!("remove_member error"),
+            Err(e) => panic!("{:?}", e),
         }
     }
 
@@ -480,13 +579,134 @@ impl StellarGuildsContract {
         new_role: Role,
         caller: Address,
     ) -> bool {
+        ensure_not_paused(&env);
         caller.require_auth();
         match update_role(&env, guild_id, address, new_role, caller) {
             Ok(result) => result,
-            Err(_) => panic!("update_role error"),
+            Err(e) => panic!("{:?}", e),
+        }
+    }
+
+    /// Set how long a member's role stays locked after it is changed
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `cooldown_seconds` - Minimum seconds between role changes (0 disables)
+    /// * `caller` - The address making the request (must be an owner)
+    ///
+    /// # Returns
+    /// true if successful, panics with error message otherwise
+    pub fn set_role_change_cooldown(
+        env: Env,
+        guild_id: u64,
+        cooldown_seconds: u64,
+        caller: Address,
+    ) -> bool {
+        ensure_not_paused(&env);
+        caller.require_auth();
+        match set_role_change_cooldown(&env, guild_id, cooldown_seconds, caller) {
+            Ok(result) => result,
+            Err(e) => panic!("{:?}", e),
+        }
+    }
+
+    /// Allow or forbid members to remove themselves from a guild
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `allowed` - Whether members may leave on their own (default true)
+    /// * `caller` - The address making the request (must be an owner)
+    ///
+    /// # Returns
+    /// true if successful, panics with error message otherwise
+    pub fn set_allow_self_removal(
+        env: Env,
+        guild_id: u64,
+        allowed: bool,
+        caller: Address,
+    ) -> bool {
+        ensure_not_paused(&env);
+        caller.require_auth();
+        match set_allow_self_removal(&env, guild_id, allowed, caller) {
+            Ok(result) => result,
+            Err(e) => panic!("{:?}", e),
+        }
+    }
+
+    /// Check whether members may remove themselves from a guild
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    ///
+    /// # Returns
+    /// true unless an owner has disabled self-removal
+    pub fn get_allow_self_removal(env: Env, guild_id: u64) -> bool {
+        match get_allow_self_removal(&env, guild_id) {
+            Ok(allowed) => allowed,
+            Err(e) => panic!("{:?}", e),
+        }
+    }
+
+    /// Replace the minimum roles required to add, remove or re-rank members
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `config` - Minimum caller role per action, keyed by the target's role
+    /// * `caller` - The address making the request (must be an owner)
+    ///
+    /// # Returns
+    /// true if successful, panics with error message otherwise
+    pub fn set_permission_config(
+        env: Env,
+        guild_id: u64,
+        config: PermissionConfig,
+        caller: Address,
+    ) -> bool {
+        ensure_not_paused(&env);
+        caller.require_auth();
+        match set_permission_config(&env, guild_id, config, caller) {
+            Ok(result) => result,
+            Err(e) => panic!("{:?}", e),
+        }
+    }
+
+    /// Get a guild's permission matrix (the built-in rules unless overridden)
+    pub fn get_permission_config(env: Env, guild_id: u64) -> PermissionConfig {
+        get_permission_config(&env, guild_id)
+    }
+
+    /// Post an official announcement to a guild
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `caller` - The address posting (must be admin or above)
+    /// * `content_uri` - URI of the announcement content (1–256 chars)
+    ///
+    /// # Returns
+    /// true if successful, panics with error message otherwise
+    pub fn post_announcement(
+        env: Env,
+        guild_id: u64,
+        caller: Address,
+        content_uri: String,
+    ) -> bool {
+        ensure_not_paused(&env);
+        caller.require_auth();
+        match post_announcement(&env, guild_id, caller, content_uri) {
+            Ok(result) => result,
+            Err(e) => panic!("{:?}", e),
         }
     }
 
+    /// Get a guild's most recent announcements, newest first
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `limit` - Maximum number of announcements to return
+    pub fn get_announcements(env: Env, guild_id: u64, limit: u32) -> Vec<Announcement> {
+        get_announcements(&env, guild_id, limit)
+    }
+
     /// Get a member from a guild
     ///
     /// # Arguments
@@ -502,6 +722,44 @@ impl StellarGuildsContract {
         }
     }
 
+    /// Look up a member without panicking
+    ///
+    /// Named apart from `get_member` because generated clients already expose
+    /// `try_get_member` as its fallible variant.
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `address` - The address to look up
+    ///
+    /// # Returns
+    /// The Member, or `None` if the address is not a member or the guild
+    /// does not exist
+    pub fn find_member(env: Env, guild_id: u64, address: Address) -> Option<Member> {
+        try_get_member(&env, guild_id, address)
+    }
+
+    /// Get a guild by ID
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    ///
+    /// # Returns
+    /// The Guild if found, panics with "guild not found" otherwise
+    pub fn get_guild(env: Env, guild_id: u64) -> Guild {
+        match get_guild(&env, guild_id) {
+            Ok(guild) => guild,
+            Err(_) => panic!("guild not found"),
+        }
+    }
+
+    /// Get the total number of guilds created
+    ///
+    /// # Returns
+    /// The highest guild ID issued so far; guilds are numbered from 1
+    pub fn get_guild_count(env: Env) -> u64 {
+        get_guild_count(&env)
+    }
+
     /// Get all members of a guild
     ///
     /// # Arguments
@@ -513,6 +771,62 @@ impl StellarGuildsContract {
         get_all_members(&env, guild_id)
     }
 
+    /// Get a page of a guild's members
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `offset` - Number of members to skip
+    /// * `limit` - Maximum number of members to return
+    pub fn get_members_page(env: Env, guild_id: u64, offset: u32, limit: u32) -> Vec<Member> {
+        get_members_page(&env, guild_id, offset, limit)
+    }
+
+    /// Export a guild's metadata, members and governance config in one call
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    ///
+    /// # Returns
+    /// A point-in-time `GuildSnapshot`; panics for guilds too large to export
+    /// at once, which should be read with `get_members_page` instead
+    pub fn export_guild(env: Env, guild_id: u64) -> GuildSnapshot {
+        match export_guild(&env, guild_id) {
+            Ok(snapshot) => snapshot,
+            Err(e) => panic!("{:?}", e),
+        }
+    }
+
+    /// List members with no state-changing participation since a timestamp
+    ///
+    /// Voting, proposing, delegating, multisig signing and member management
+    /// count as activity; read-only calls do not.
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `inactive_since_ts` - Members last active before this time are listed
+    ///
+    /// # Returns
+    /// Addresses of the inactive members
+    pub fn get_inactive_members(env: Env, guild_id: u64, inactive_since_ts: u64) -> Vec<Address> {
+        get_inactive_members(&env, guild_id, inactive_since_ts)
+    }
+
+    /// Get when a member last took part in the guild
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `address` - The member's address
+    ///
+    /// # Returns
+    /// Timestamp of the member's last state-changing participation, or their
+    /// join time if they have not participated since
+    pub fn get_member_last_active(env: Env, guild_id: u64, address: Address) -> u64 {
+        match get_member_last_active(&env, guild_id, address) {
+            Ok(timestamp) => timestamp,
+            Err(e) => panic!("{:?}", e),
+        }
+    }
+
     /// Check if an address is a member of a guild
     ///
     /// # Arguments
@@ -537,13 +851,35 @@ impl StellarGuildsContract {
     /// # Returns
     /// true if successful, panics otherwise
     pub fn join_guild(env: Env, guild_id: u64, caller: Address) -> bool {
+        ensure_not_paused(&env);
         match join_guild(&env, guild_id, caller) {
             Ok(result) => result,
             Err(e) => panic!("{:?}", e),
         }
     }
 
-    /// Check if a member has permission for a required role
+    /// Set the caller's profile metadata URI in a guild
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `address`  - The member updating their profile (must auth)
+    /// * `uri`      - The profile metadata URI (max 256 chars, empty clears it)
+    ///
+    /// # Returns
+    /// true if successful, panics otherwise
+    pub fn set_member_profile(env: Env, guild_id: u64, address: Address, uri: String) -> bool {
+        ensure_not_paused(&env);
+        match set_member_profile(&env, guild_id, address, uri) {
+            Ok(result) => result,
+            Err(e) => panic!("{:?}", e),
+        }
+    }
+
+    /// Get the profile metadata URI a member has set in a guild, if any
+    pub fn get_member_profile(env: Env, guild_id: u64, address: Address) -> Option<String> {
+        get_member_profile(&env, guild_id, address)
+    }
+
     ///
     /// # Arguments
     /// * `guild_id` - The ID of the guild
@@ -556,6 +892,116 @@ impl StellarGuildsContract {
         has_permission(&env, guild_id, address, required_role)
     }
 
+    /// Check that an address is a guild member holding at least `min_role`
+    ///
+    /// Stable entry point for cross-contract integrations: it never panics,
+    /// returning false for unknown guilds and non-members, so callers need not
+    /// depend on `get_member`.
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `address` - The address to check
+    /// * `min_role` - The lowest role that satisfies the check
+    ///
+    /// # Returns
+    /// true if the address is a member at or above `min_role`
+    pub fn verify_role(env: Env, guild_id: u64, address: Address, min_role: Role) -> bool {
+        verify_role(&env, guild_id, address, min_role)
+    }
+
+    /// Define or re-rank a custom role with a numeric permission level
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `name` - The custom role name
+    /// * `level` - Permission level (Contributor=100, Member=200, Admin=300, Owner=400)
+    /// * `caller` - The address making the request (must be admin or above)
+    ///
+    /// # Returns
+    /// true if successful, panics with error message otherwise
+    pub fn define_custom_role(
+        env: Env,
+        guild_id: u64,
+        name: String,
+        level: u32,
+        caller: Address,
+    ) -> bool {
+        ensure_not_paused(&env);
+        caller.require_auth();
+        match define_custom_role(&env, guild_id, name, level, caller) {
+            Ok(result) => result,
+            Err(e) => panic!("{:?}", e),
+        }
+    }
+
+    /// Assign a custom role to a member, or clear it with `None`
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `address` - The member receiving the role
+    /// * `role_name` - The custom role to assign, or `None` to clear it
+    /// * `caller` - The address making the request (must be admin or above)
+    ///
+    /// # Returns
+    /// true if successful, panics with error message otherwise
+    pub fn assign_custom_role(
+        env: Env,
+        guild_id: u64,
+        address: Address,
+        role_name: Option<String>,
+        caller: Address,
+    ) -> bool {
+        ensure_not_paused(&env);
+        caller.require_auth();
+        match assign_custom_role(&env, guild_id, address, role_name, caller) {
+            Ok(result) => result,
+            Err(e) => panic!("{:?}", e),
+        }
+    }
+
+    /// List the custom roles registered for a guild
+    pub fn get_custom_roles(env: Env, guild_id: u64) -> Vec<CustomRole> {
+        get_custom_roles(&env, guild_id)
+    }
+
+    /// Check if a member ranks at or above a custom role's level
+    pub fn has_custom_role_permission(
+        env: Env,
+        guild_id: u64,
+        address: Address,
+        role_name: String,
+    ) -> bool {
+        has_custom_role_permission(&env, guild_id, address, role_name)
+    }
+
+    /// Mint a non-transferable membership badge for the calling member
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `address` - The member receiving the badge (must sign)
+    ///
+    /// # Returns
+    /// The badge, recording the member's current role and the issue time
+    pub fn mint_membership_badge(env: Env, guild_id: u64, address: Address) -> MembershipBadge {
+        ensure_not_paused(&env);
+        address.require_auth();
+        match mint_membership_badge(&env, guild_id, address) {
+            Ok(badge) => badge,
+            Err(e) => panic!("{:?}", e),
+        }
+    }
+
+    /// Get the membership badge an address holds in a guild, if any
+    pub fn get_badge(env: Env, guild_id: u64, address: Address) -> Option<MembershipBadge> {
+        get_badge(&env, guild_id, address)
+    }
+
+    /// Check whether an address holds a badge of a guild; badges are revoked
+    /// on removal, so this never panics and is safe for cross-contract calls
+    pub fn verify_badge(env: Env, guild_id: u64, address: Address) -> bool {
+        verify_badge(&env, guild_id, address)
+    }
+
     // ============ Payment Functions ============
 
     pub fn create_payment_pool(
@@ -565,6 +1011,7 @@ impl StellarGuildsContract {
         rule: DistributionRule,
         creator: Address,
     ) -> u64 {
+        ensure_not_paused(&env);
         match pay_create_payment_pool(&env, total_amount, token, rule, creator) {
             Ok(id) => id,
             Err(e) => {
@@ -594,6 +1041,7 @@ impl StellarGuildsContract {
         share: u32,
         caller: Address,
     ) -> bool {
+        ensure_not_paused(&env);
         match pay_add_recipient(&env, pool_id, recipient, share, caller) {
             Ok(result) => result,
             Err(e) => {
@@ -663,6 +1111,7 @@ impl StellarGuildsContract {
     }
 
     pub fn cancel_distribution(env: Env, pool_id: u64, caller: Address) -> bool {
+        ensure_not_paused(&env);
         match pay_cancel_distribution(&env, pool_id, caller) {
             Ok(result) => result,
             Err(e) => {
@@ -717,6 +1166,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if distribution was successful
     pub fn execute_distribution(env: Env, pool_id: u64, caller: Address) -> bool {
+        ensure_not_paused(&env);
         match pay_execute_distribution(&env, pool_id, caller) {
             Ok(result) => result,
             Err(e) => {
@@ -748,6 +1198,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// Vector of results (true for success, false for failure) for each pool
     pub fn batch_distribute(env: Env, pool_ids: Vec<u64>, caller: Address) -> Vec<bool> {
+        ensure_not_paused(&env);
         pay_batch_distribute(&env, pool_ids, caller)
     }
 
@@ -772,6 +1223,7 @@ impl StellarGuildsContract {
         reason: String,
         evidence_url: String,
     ) -> u64 {
+        ensure_not_paused(&env);
         dispute_create_dispute(
             &env,
             reference_id,
@@ -789,6 +1241,7 @@ impl StellarGuildsContract {
         party: Address,
         evidence_url: String,
     ) -> bool {
+        ensure_not_paused(&env);
         dispute_submit_evidence(&env, dispute_id, party, evidence_url)
     }
 
@@ -799,6 +1252,7 @@ impl StellarGuildsContract {
         voter: Address,
         decision: dispute::types::VoteDecision,
     ) -> bool {
+        ensure_not_paused(&env);
         dispute_cast_vote(&env, dispute_id, voter, decision)
     }
 
@@ -814,6 +1268,7 @@ impl StellarGuildsContract {
 
     /// Resolve a dispute and execute fund distribution
     pub fn resolve_dispute(env: Env, dispute_id: u64) -> dispute::types::Resolution {
+        ensure_not_paused(&env);
         dispute_resolve_dispute(&env, dispute_id)
     }
 
@@ -822,6 +1277,7 @@ impl StellarGuildsContract {
         env: Env,
         dispute_id: u64,
     ) -> Vec<dispute::types::FundDistribution> {
+        ensure_not_paused(&env);
         dispute_execute_resolution(&env, dispute_id)
     }
 
@@ -838,6 +1294,12 @@ impl StellarGuildsContract {
     /// * `guild_id` - The ID of the guild
     /// * `signers` - Vector of signer addresses (first is owner)
     /// * `approval_threshold` - Number of approvals required for transactions
+    /// * `role_based_signers` - Resolve signers from guild members at Admin
+    ///   level or above instead of `signers` (whose first entry stays owner)
+    /// * `auto_threshold` - Ignore `approval_threshold` and require a majority
+    ///   of signers, tracking the admin count in role-based mode
+    /// * `co_guild_ids` - Other guilds that must each have an admin approve
+    ///   every transaction; empty for a single-guild treasury
     ///
     /// # Returns
     /// The ID of the newly created treasury
@@ -846,8 +1308,20 @@ impl StellarGuildsContract {
         guild_id: u64,
         signers: Vec<Address>,
         approval_threshold: u32,
+        role_based_signers: bool,
+        auto_threshold: bool,
+        co_guild_ids: Vec<u64>,
     ) -> u64 {
-        core_initialize_treasury(&env, guild_id, signers, approval_threshold)
+        ensure_not_paused(&env);
+        core_initialize_treasury(
+            &env,
+            guild_id,
+            signers,
+            approval_threshold,
+            role_based_signers,
+            auto_threshold,
+            co_guild_ids,
+        )
     }
 
     /// Deposit funds into a treasury
@@ -857,6 +1331,9 @@ impl StellarGuildsContract {
     /// * `depositor` - Address making the deposit
     /// * `amount` - Amount to deposit
     /// * `token` - Token address (None for XLM)
+    /// * `memo` - Optional note recorded on the transaction
+    /// * `on_behalf_of` - Contributor to credit instead of `depositor`, who
+    ///   still pays
     ///
     /// # Returns
     /// `true` if deposit was successful
@@ -866,8 +1343,11 @@ impl StellarGuildsContract {
         depositor: Address,
         amount: i128,
         token: Option<Address>,
+        memo: Option<String>,
+        on_behalf_of: Option<Address>,
     ) -> bool {
-        core_deposit(&env, treasury_id, depositor, amount, token)
+        ensure_not_paused(&env);
+        core_deposit(&env, treasury_id, depositor, amount, token, memo, on_behalf_of)
     }
 
     /// Propose a withdrawal from treasury
@@ -878,7 +1358,8 @@ impl StellarGuildsContract {
     /// * `recipient` - Address to receive the funds
     /// * `amount` - Amount to withdraw
     /// * `token` - Token address (None for XLM)
-    /// * `reason` - Reason for the withdrawal
+    /// * `category` - Budget category the withdrawal is charged to
+    /// * `reason` - Free-form detail for the withdrawal
     ///
     /// # Returns
     /// The ID of the proposed transaction
@@ -889,8 +1370,10 @@ impl StellarGuildsContract {
         recipient: Address,
         amount: i128,
         token: Option<Address>,
+        category: WithdrawalCategory,
         reason: String,
     ) -> u64 {
+        ensure_not_paused(&env);
         core_propose_withdrawal(
             &env,
             treasury_id,
@@ -898,12 +1381,53 @@ impl StellarGuildsContract {
             recipient,
             amount,
             token,
+            category,
+            reason,
+        )
+    }
+
+    /// Propose moving funds between two treasuries held by this contract
+    ///
+    /// # Arguments
+    /// * `from_treasury` - The ID of the source treasury
+    /// * `to_treasury` - The ID of the destination treasury
+    /// * `amount` - Amount to transfer
+    /// * `token` - Token address (None for XLM)
+    /// * `proposer` - Signer of the source treasury proposing the transfer
+    /// * `category` - Budget category the transfer is charged to
+    /// * `reason` - Reason for the transfer
+    ///
+    /// # Returns
+    /// The ID of the proposed transaction
+    #[allow(clippy::too_many_arguments)]
+    pub fn propose_treasury_transfer(
+        env: Env,
+        from_treasury: u64,
+        to_treasury: u64,
+        amount: i128,
+        token: Option<Address>,
+        proposer: Address,
+        category: WithdrawalCategory,
+        reason: String,
+    ) -> u64 {
+        ensure_not_paused(&env);
+        core_propose_treasury_transfer(
+            &env,
+            from_treasury,
+            to_treasury,
+            amount,
+            token,
+            proposer,
+            category,
             reason,
         )
     }
 
     /// Approve a proposed transaction
     ///
+    /// On a treasury shared with co-guilds, an admin of a co-guild may approve
+    /// on that guild's behalf; every co-guild must approve before execution.
+    ///
     /// # Arguments
     /// * `tx_id` - The ID of the transaction to approve
     /// * `approver` - Address approving the transaction
@@ -911,9 +1435,23 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if approval was successful
     pub fn approve_transaction(env: Env, tx_id: u64, approver: Address) -> bool {
+        ensure_not_paused(&env);
         core_approve_transaction(&env, tx_id, approver)
     }
 
+    /// Revoke an approval given to a transaction that has not executed
+    ///
+    /// # Arguments
+    /// * `tx_id` - The ID of the transaction
+    /// * `signer` - Signer withdrawing their approval
+    ///
+    /// # Returns
+    /// `true` if the approval was revoked
+    pub fn revoke_approval(env: Env, tx_id: u64, signer: Address) -> bool {
+        ensure_not_paused(&env);
+        core_revoke_approval(&env, tx_id, signer)
+    }
+
     /// Execute an approved transaction
     ///
     /// # Arguments
@@ -923,6 +1461,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if execution was successful
     pub fn execute_transaction(env: Env, tx_id: u64, executor: Address) -> bool {
+        ensure_not_paused(&env);
         core_execute_transaction(&env, tx_id, executor)
     }
 
@@ -930,7 +1469,7 @@ impl StellarGuildsContract {
     ///
     /// # Arguments
     /// * `treasury_id` - The ID of the treasury
-    /// * `category` - Budget category name
+    /// * `category` - Budget category
     /// * `amount` - Budget amount
     /// * `period_seconds` - Budget period in seconds
     /// * `caller` - Address making the request (must be signer)
@@ -940,31 +1479,192 @@ impl StellarGuildsContract {
     pub fn set_budget(
         env: Env,
         treasury_id: u64,
-        category: String,
+        category: WithdrawalCategory,
         amount: i128,
         period_seconds: u64,
         caller: Address,
     ) -> bool {
+        ensure_not_paused(&env);
         core_set_budget(&env, treasury_id, caller, category, amount, period_seconds)
     }
 
-    /// Get treasury balance for a token
+    /// Set the minimum balance a treasury must keep of a token
     ///
     /// # Arguments
     /// * `treasury_id` - The ID of the treasury
     /// * `token` - Token address (None for XLM)
+    /// * `floor` - Balance no withdrawal may go below (0 removes the floor)
+    /// * `caller` - Address making the request (must be an admin signer)
     ///
     /// # Returns
-    /// The balance amount
-    pub fn get_treasury_balance(env: Env, treasury_id: u64, token: Option<Address>) -> i128 {
-        core_get_balance(&env, treasury_id, token)
+    /// `true` if the floor was set successfully
+    pub fn set_reserve_floor(
+        env: Env,
+        treasury_id: u64,
+        token: Option<Address>,
+        floor: i128,
+        caller: Address,
+    ) -> bool {
+        ensure_not_paused(&env);
+        core_set_reserve_floor(&env, treasury_id, token, floor, caller)
     }
 
-    pub fn get_treasury(env: Env, treasury_id: u64) -> treasury::types::Treasury {
-        crate::treasury::storage::get_treasury(&env, treasury_id)
+    /// Get the reserve floor of a treasury for a token (0 if unset)
+    pub fn get_reserve_floor(env: Env, treasury_id: u64, token: Option<Address>) -> i128 {
+        core_get_reserve_floor(&env, treasury_id, token)
+    }
+
+    /// Require more approvals for larger treasury outflows
+    ///
+    /// Tiers only raise the requirement; an outflow never needs fewer
+    /// approvals than the treasury threshold would ask without them.
+    ///
+    /// # Arguments
+    /// * `treasury_id` - The ID of the treasury
+    /// * `tiers` - `(amount_threshold, required_sigs)` pairs, ascending by threshold
+    /// * `caller` - Address making the request (must be an admin signer)
+    ///
+    /// # Returns
+    /// `true` if the tiers were set successfully
+    pub fn set_approval_tiers(
+        env: Env,
+        treasury_id: u64,
+        tiers: Vec<(i128, u32)>,
+        caller: Address,
+    ) -> bool {
+        ensure_not_paused(&env);
+        core_set_approval_tiers(&env, treasury_id, tiers, caller)
+    }
+
+    /// Get the amount-based approval tiers of a treasury
+    pub fn get_approval_tiers(env: Env, treasury_id: u64) -> Vec<(i128, u32)> {
+        core_get_approval_tiers(&env, treasury_id)
+    }
+
+    /// Set how long a treasury's new transactions stay open for approval
+    ///
+    /// # Arguments
+    /// * `treasury_id` - The ID of the treasury
+    /// * `seconds` - Approval window, clamped to between 1 hour and 30 days
+    /// * `caller` - Address making the request (must be an admin signer)
+    ///
+    /// # Returns
+    /// The timeout actually stored after clamping
+    pub fn set_transaction_timeout(
+        env: Env,
+        treasury_id: u64,
+        seconds: u64,
+        caller: Address,
+    ) -> u64 {
+        ensure_not_paused(&env);
+        core_set_transaction_timeout(&env, treasury_id, seconds, caller)
+    }
+
+    /// Get the approval window used for a treasury's new transactions
+    pub fn get_transaction_timeout(env: Env, treasury_id: u64) -> u64 {
+        core_get_transaction_timeout(&env, treasury_id)
+    }
+
+    /// Restrict a treasury's payouts to pre-approved recipients
+    ///
+    /// Applies to withdrawals, allowance spends, milestone payments and
+    /// governance spends alike.
+    ///
+    /// # Arguments
+    /// * `treasury_id` - The ID of the treasury
+    /// * `payees` - Allowed recipients (empty removes the restriction)
+    /// * `caller` - Address making the request (must be the guild owner)
+    ///
+    /// # Returns
+    /// `true` if the allowlist was set successfully
+    pub fn set_payee_allowlist(
+        env: Env,
+        treasury_id: u64,
+        payees: Vec<Address>,
+        caller: Address,
+    ) -> bool {
+        ensure_not_paused(&env);
+        core_set_payee_allowlist(&env, treasury_id, payees, caller)
+    }
+
+    /// Get the payee allowlist of a treasury (empty if unrestricted)
+    pub fn get_payee_allowlist(env: Env, treasury_id: u64) -> Vec<Address> {
+        core_get_payee_allowlist(&env, treasury_id)
+    }
+
+    /// Check whether the treasury may pay an address
+    pub fn is_payee_allowed(env: Env, treasury_id: u64, address: Address) -> bool {
+        core_is_payee_allowed(&env, treasury_id, address)
+    }
+
+    /// Get treasury balance for a token
+    ///
+    /// # Arguments
+    /// * `treasury_id` - The ID of the treasury
+    /// * `token` - Token address (None for XLM)
+    ///
+    /// # Returns
+    /// The balance amount
+    pub fn get_treasury_balance(env: Env, treasury_id: u64, token: Option<Address>) -> i128 {
+        core_get_balance(&env, treasury_id, token)
+    }
+
+    pub fn get_treasury(env: Env, treasury_id: u64) -> treasury::types::Treasury {
+        crate::treasury::storage::get_treasury(&env, treasury_id)
             .unwrap_or_else(|| panic!("treasury not found"))
     }
 
+    /// Get the IDs of all treasuries created for a guild
+    pub fn get_treasuries_for_guild(env: Env, guild_id: u64) -> Vec<u64> {
+        core_get_treasuries_for_guild(&env, guild_id)
+    }
+
+    /// Get the ID of the guild a treasury belongs to
+    pub fn get_treasury_guild(env: Env, treasury_id: u64) -> u64 {
+        core_get_treasury_guild(&env, treasury_id)
+    }
+
+    /// Get the other guilds that must each approve a treasury's transactions
+    pub fn get_treasury_co_guilds(env: Env, treasury_id: u64) -> Vec<u64> {
+        core_get_treasury_co_guilds(&env, treasury_id)
+    }
+
+    /// Get the cumulative amount an address has deposited into a treasury
+    ///
+    /// # Arguments
+    /// * `treasury_id` - The ID of the treasury
+    /// * `depositor` - The depositing address
+    /// * `token` - Token address (None for XLM)
+    ///
+    /// # Returns
+    /// Total deposited; withdrawals do not reduce it
+    pub fn get_depositor_total(
+        env: Env,
+        treasury_id: u64,
+        depositor: Address,
+        token: Option<Address>,
+    ) -> i128 {
+        core_get_depositor_total(&env, treasury_id, depositor, token)
+    }
+
+    /// Get the largest depositors of a treasury in one token
+    ///
+    /// # Arguments
+    /// * `treasury_id` - The ID of the treasury
+    /// * `token` - Token address (None for XLM)
+    /// * `limit` - Maximum number of depositors to return
+    ///
+    /// # Returns
+    /// `(depositor, total)` pairs, largest total first
+    pub fn get_top_depositors(
+        env: Env,
+        treasury_id: u64,
+        token: Option<Address>,
+        limit: u32,
+    ) -> Vec<(Address, i128)> {
+        core_get_top_depositors(&env, treasury_id, token, limit)
+    }
+
     /// Get transaction history for a treasury
     ///
     /// # Arguments
@@ -977,8 +1677,28 @@ impl StellarGuildsContract {
         core_get_transaction_history(&env, treasury_id, limit)
     }
 
+    /// Page through a treasury's transaction history, newest first
+    ///
+    /// # Arguments
+    /// * `treasury_id` - The ID of the treasury
+    /// * `before_id` - Only return transactions with a smaller id (None for the newest)
+    /// * `limit` - Maximum number of transactions to return
+    ///
+    /// # Returns
+    /// Up to `limit` transactions; pass the smallest id as the next `before_id`
+    pub fn get_transaction_history_paged(
+        env: Env,
+        treasury_id: u64,
+        before_id: Option<u64>,
+        limit: u32,
+    ) -> Vec<Transaction> {
+        core_get_transaction_history_paged(&env, treasury_id, before_id, limit)
+    }
+
     /// Grant an allowance to an admin
     ///
+    /// Treasuries shared with co-guilds do not support allowances.
+    ///
     /// # Arguments
     /// * `treasury_id` - The ID of the treasury
     /// * `admin` - Address to grant allowance to
@@ -998,6 +1718,7 @@ impl StellarGuildsContract {
         period_seconds: u64,
         owner: Address,
     ) -> bool {
+        ensure_not_paused(&env);
         core_grant_allowance(
             &env,
             treasury_id,
@@ -1009,6 +1730,29 @@ impl StellarGuildsContract {
         )
     }
 
+    /// Spend from a granted allowance without a multisig proposal
+    ///
+    /// # Arguments
+    /// * `treasury_id` - The ID of the treasury
+    /// * `grantee` - Address holding the allowance
+    /// * `recipient` - Address receiving the funds
+    /// * `amount` - Amount to spend
+    /// * `token` - Token address (None for XLM)
+    ///
+    /// # Returns
+    /// The ID of the recorded withdrawal transaction
+    pub fn spend_allowance(
+        env: Env,
+        treasury_id: u64,
+        grantee: Address,
+        recipient: Address,
+        amount: i128,
+        token: Option<Address>,
+    ) -> u64 {
+        ensure_not_paused(&env);
+        core_spend_allowance(&env, treasury_id, grantee, recipient, amount, token)
+    }
+
     /// Emergency pause treasury operations
     ///
     /// # Arguments
@@ -1022,6 +1766,50 @@ impl StellarGuildsContract {
         core_emergency_pause(&env, treasury_id, signer, paused)
     }
 
+    /// Pause or resume a single kind of treasury operation
+    ///
+    /// # Arguments
+    /// * `treasury_id` - The ID of the treasury
+    /// * `op_kind` - Deposits, withdrawals or allowance spends
+    /// * `paused` - Whether to pause or unpause
+    /// * `caller` - Address making the request (must be an admin signer)
+    ///
+    /// # Returns
+    /// `true` if pause state was changed successfully
+    pub fn set_operation_pause(
+        env: Env,
+        treasury_id: u64,
+        op_kind: TreasuryOperation,
+        paused: bool,
+        caller: Address,
+    ) -> bool {
+        core_set_operation_pause(&env, treasury_id, op_kind, paused, caller)
+    }
+
+    /// Whether an operation kind is blocked, by its own pause or the emergency pause
+    pub fn is_operation_blocked(env: Env, treasury_id: u64, op_kind: TreasuryOperation) -> bool {
+        core_is_operation_blocked(&env, treasury_id, op_kind)
+    }
+
+    /// Set the protocol fee taken from executed treasury withdrawals
+    ///
+    /// # Arguments
+    /// * `bps` - Fee in basis points (0 disables the fee)
+    /// * `fee_recipient` - Address receiving the fee
+    /// * `admin` - Contract admin address
+    ///
+    /// # Returns
+    /// `true` if the fee was updated
+    pub fn set_withdrawal_fee_bps(
+        env: Env,
+        bps: u32,
+        fee_recipient: Address,
+        admin: Address,
+    ) -> bool {
+        ensure_not_paused(&env);
+        core_set_withdrawal_fee_bps(&env, bps, fee_recipient, admin)
+    }
+
     // ============ Token Allowance Functions ============
 
     /// Approve a token allowance from owner to spender.
@@ -1045,6 +1833,7 @@ impl StellarGuildsContract {
         expires_at: u64,
         operation: AllowanceOperation,
     ) -> bool {
+        ensure_not_paused(&env);
         allowance_approve(&env, owner, spender, token, amount, expires_at, operation)
             .unwrap_or_else(|e| {
                 let msg = match e {
@@ -1065,6 +1854,7 @@ impl StellarGuildsContract {
         token: Option<Address>,
         delta: i128,
     ) -> bool {
+        ensure_not_paused(&env);
         allowance_increase(&env, owner, spender, token, delta).unwrap_or_else(|e| {
             let msg = match e {
                 allowance::AllowanceError::NotFound => "allowance not found",
@@ -1085,6 +1875,7 @@ impl StellarGuildsContract {
         token: Option<Address>,
         delta: i128,
     ) -> bool {
+        ensure_not_paused(&env);
         allowance_decrease(&env, owner, spender, token, delta).unwrap_or_else(|e| {
             let msg = match e {
                 allowance::AllowanceError::NotFound => "allowance not found",
@@ -1104,6 +1895,7 @@ impl StellarGuildsContract {
         spender: Address,
         token: Option<Address>,
     ) -> bool {
+        ensure_not_paused(&env);
         allowance_revoke(&env, owner, spender, token).unwrap_or_else(|e| {
             let msg = match e {
                 allowance::AllowanceError::NotFound => "allowance not found",
@@ -1233,10 +2025,11 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if snapshot was recorded
     pub fn record_treasury_snapshot(env: Env, treasury_id: u64, caller: Address) -> bool {
+        ensure_not_paused(&env);
         caller.require_auth();
         let treasury =
             treasury::storage::get_treasury(&env, treasury_id).expect("treasury not found");
-        treasury::multisig::ensure_is_signer(&treasury, &caller);
+        treasury::multisig::ensure_is_signer(&env, &treasury, &caller);
 
         let index = analytics::get_snapshot_count(&env, treasury_id);
         let snapshot = TreasurySnapshot {
@@ -1262,6 +2055,7 @@ impl StellarGuildsContract {
         contribution_type: ContributionType,
         reference_id: u64,
     ) {
+        ensure_not_paused(&env);
         contributor.require_auth();
         rep_record_contribution(
             &env,
@@ -1330,6 +2124,7 @@ impl StellarGuildsContract {
         token: Option<Address>,
         is_sequential: bool,
     ) -> u64 {
+        ensure_not_paused(&env);
         ms_create_project(
             &env,
             guild_id,
@@ -1363,6 +2158,7 @@ impl StellarGuildsContract {
         deadline: u64,
         caller: Address,
     ) -> u64 {
+        ensure_not_paused(&env);
         ms_add_milestone(
             &env,
             project_id,
@@ -1383,6 +2179,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if successful
     pub fn start_milestone(env: Env, milestone_id: u64, contributor: Address) -> bool {
+        ensure_not_paused(&env);
         ms_start_milestone(&env, milestone_id, contributor)
     }
 
@@ -1395,6 +2192,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if successful
     pub fn submit_milestone(env: Env, milestone_id: u64, proof_url: String) -> bool {
+        ensure_not_paused(&env);
         ms_submit_milestone(&env, milestone_id, proof_url)
     }
 
@@ -1407,6 +2205,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if successful
     pub fn approve_milestone(env: Env, milestone_id: u64, approver: Address) -> bool {
+        ensure_not_paused(&env);
         ms_approve_milestone(&env, milestone_id, approver)
     }
 
@@ -1425,6 +2224,7 @@ impl StellarGuildsContract {
         approver: Address,
         reason: String,
     ) -> bool {
+        ensure_not_paused(&env);
         ms_reject_milestone(&env, milestone_id, approver, reason)
     }
 
@@ -1458,6 +2258,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if successful
     pub fn release_milestone_payment(env: Env, milestone_id: u64) -> bool {
+        ensure_not_paused(&env);
         ms_release_payment(&env, milestone_id)
     }
 
@@ -1476,6 +2277,7 @@ impl StellarGuildsContract {
         new_deadline: u64,
         caller: Address,
     ) -> bool {
+        ensure_not_paused(&env);
         ms_extend_deadline(&env, milestone_id, new_deadline, caller)
     }
 
@@ -1488,6 +2290,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if successful
     pub fn cancel_project(env: Env, project_id: u64, caller: Address) -> bool {
+        ensure_not_paused(&env);
         ms_cancel_project(&env, project_id, caller)
     }
 
@@ -1501,6 +2304,8 @@ impl StellarGuildsContract {
     /// * `proposal_type` - Type of the proposal
     /// * `title` - Proposal title
     /// * `description` - Detailed description
+    /// * `voting_period_seconds` - Voting window, or `None` for the guild
+    ///   default; clamped to the configured minimum and maximum
     ///
     /// # Returns
     /// The ID of the newly created proposal
@@ -1511,7 +2316,10 @@ impl StellarGuildsContract {
         proposal_type: ProposalType,
         title: String,
         description: String,
+        voting_period_seconds: Option<u64>,
     ) -> u64 {
+        ensure_not_paused(&env);
+        let payload = gov_default_payload(&proposal_type);
         gov_create_proposal(
             &env,
             guild_id,
@@ -1519,10 +2327,164 @@ impl StellarGuildsContract {
             proposal_type,
             title,
             description,
-            ExecutionPayload::GeneralDecision,
+            payload,
+            voting_period_seconds,
+        )
+    }
+
+    /// Create a proposal that uses commit-reveal voting
+    ///
+    /// Votes are submitted as hashes with `commit_vote` during the voting
+    /// period and opened with `reveal_vote` during the reveal window.
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `proposer` - Address of the proposer
+    /// * `proposal_type` - Type of the proposal
+    /// * `title` - Proposal title
+    /// * `description` - Detailed description
+    ///
+    /// # Returns
+    /// The ID of the newly created proposal
+    pub fn create_commit_reveal_proposal(
+        env: Env,
+        guild_id: u64,
+        proposer: Address,
+        proposal_type: ProposalType,
+        title: String,
+        description: String,
+    ) -> u64 {
+        ensure_not_paused(&env);
+        let payload = gov_default_payload(&proposal_type);
+        gov_create_commit_reveal_proposal(
+            &env,
+            guild_id,
+            proposer,
+            proposal_type,
+            title,
+            description,
+            payload,
+        )
+    }
+
+    /// Create a multiple-choice proposal
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `proposer` - Address of the proposer
+    /// * `title` - Proposal title
+    /// * `description` - Detailed description
+    /// * `options` - The options voters choose between (2-10)
+    ///
+    /// # Returns
+    /// The ID of the newly created proposal
+    pub fn create_choice_proposal(
+        env: Env,
+        guild_id: u64,
+        proposer: Address,
+        title: String,
+        description: String,
+        options: Vec<String>,
+    ) -> u64 {
+        ensure_not_paused(&env);
+        gov_create_proposal(
+            &env,
+            guild_id,
+            proposer,
+            ProposalType::MultipleChoice,
+            title,
+            description,
+            ExecutionPayload::MultipleChoice(options),
+            None,
+        )
+    }
+
+    /// Create a treasury spend proposal
+    ///
+    /// Executing the proposal once it passes pays the spend out of the named
+    /// guild treasury; if the treasury cannot cover it the proposal fails.
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `proposer` - Address of the proposer
+    /// * `title` - Proposal title
+    /// * `description` - Detailed description
+    /// * `spend` - Treasury, recipient, amount, token and budget category
+    /// * `voting_period_seconds` - Voting window, or `None` for the guild
+    ///   default
+    ///
+    /// # Returns
+    /// The ID of the newly created proposal
+    pub fn create_treasury_spend_proposal(
+        env: Env,
+        guild_id: u64,
+        proposer: Address,
+        title: String,
+        description: String,
+        spend: TreasurySpendData,
+        voting_period_seconds: Option<u64>,
+    ) -> u64 {
+        ensure_not_paused(&env);
+        gov_create_treasury_spend_proposal(
+            &env,
+            guild_id,
+            proposer,
+            title,
+            description,
+            spend,
+            voting_period_seconds,
         )
     }
 
+    /// Create a proposal to change a treasury's approval threshold and signers
+    ///
+    /// Executing the proposal once it passes updates the treasury; if the
+    /// change no longer fits the treasury at that point the proposal fails.
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `proposer` - Address of the proposer
+    /// * `title` - Proposal title
+    /// * `description` - Detailed description
+    /// * `change` - Treasury, new threshold and optional replacement signers
+    /// * `voting_period_seconds` - Voting window, or `None` for the guild
+    ///   default
+    ///
+    /// # Returns
+    /// The ID of the newly created proposal
+    pub fn create_treasury_config_proposal(
+        env: Env,
+        guild_id: u64,
+        proposer: Address,
+        title: String,
+        description: String,
+        change: TreasuryConfigData,
+        voting_period_seconds: Option<u64>,
+    ) -> u64 {
+        ensure_not_paused(&env);
+        gov_create_proposal(
+            &env,
+            guild_id,
+            proposer,
+            ProposalType::TreasuryConfigChange,
+            title,
+            description,
+            ExecutionPayload::TreasuryConfigChange(change),
+            voting_period_seconds,
+        )
+    }
+
+    /// Get the spend details attached to a treasury spend proposal
+    ///
+    /// # Arguments
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// The spend data, or `None` if the proposal has none
+    pub fn get_treasury_spend(env: Env, proposal_id: u64) -> Option<TreasurySpendData> {
+        gov_get_treasury_spend(&env, proposal_id)
+    }
+
     /// Get a proposal by ID
     ///
     /// # Arguments
@@ -1534,6 +2496,20 @@ impl StellarGuildsContract {
         gov_get_proposal(&env, proposal_id)
     }
 
+    /// Get the governance rules a proposal is bound by
+    ///
+    /// Quorum, thresholds, weights and timelock are fixed when the proposal is
+    /// created; later config updates do not affect it.
+    ///
+    /// # Arguments
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// The GovernanceConfig snapshot taken at creation
+    pub fn get_proposal_config(env: Env, proposal_id: u64) -> GovernanceConfig {
+        gov_get_proposal_config(&env, proposal_id)
+    }
+
     /// Get all active proposals for a guild
     ///
     /// # Arguments
@@ -1545,6 +2521,52 @@ impl StellarGuildsContract {
         gov_get_active_proposals(&env, guild_id)
     }
 
+    /// Get a guild's proposals in any status, newest first
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `limit` - Maximum number of proposals to return
+    /// * `before_id` - Only return proposals with a lower ID; pass the last ID
+    ///   of the previous page, or `None` to start from the newest
+    ///
+    /// # Returns
+    /// Vector of proposals
+    pub fn get_all_proposals(
+        env: Env,
+        guild_id: u64,
+        limit: u32,
+        before_id: Option<u64>,
+    ) -> Vec<Proposal> {
+        gov_get_all_proposals(&env, guild_id, limit, before_id)
+    }
+
+    /// Get the number of proposals ever created in a guild
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    ///
+    /// # Returns
+    /// The proposal count, across all statuses
+    pub fn get_proposal_count(env: Env, guild_id: u64) -> u64 {
+        gov_get_proposal_count(&env, guild_id)
+    }
+
+    /// Get the active proposals of one type for a guild
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `proposal_type` - Type of proposal to return
+    ///
+    /// # Returns
+    /// Vector of active proposals of that type
+    pub fn get_active_proposals_by_type(
+        env: Env,
+        guild_id: u64,
+        proposal_type: ProposalType,
+    ) -> Vec<Proposal> {
+        gov_get_active_proposals_by_type(&env, guild_id, proposal_type)
+    }
+
     /// Cast a vote on a proposal
     ///
     /// # Arguments
@@ -1555,20 +2577,148 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if successful
     pub fn vote(env: Env, proposal_id: u64, voter: Address, decision: VoteDecision) -> bool {
+        ensure_not_paused(&env);
         gov_vote(&env, proposal_id, voter, decision)
     }
 
+    /// Cast a vote for one option of a multiple-choice proposal
+    ///
+    /// # Arguments
+    /// * `proposal_id` - The ID of the proposal
+    /// * `voter` - Address of the voter
+    /// * `option_index` - Index of the chosen option
+    ///
+    /// # Returns
+    /// `true` if successful
+    pub fn vote_choice(env: Env, proposal_id: u64, voter: Address, option_index: u32) -> bool {
+        ensure_not_paused(&env);
+        gov_vote_choice(&env, proposal_id, voter, option_index)
+    }
+
+    /// Commit a hidden vote on a commit-reveal proposal
+    ///
+    /// # Arguments
+    /// * `proposal_id` - The ID of the proposal
+    /// * `voter` - Address of the voter
+    /// * `commitment_hash` - `sha256(decision_byte || salt)`
+    ///
+    /// # Returns
+    /// `true` if successful
+    pub fn commit_vote(
+        env: Env,
+        proposal_id: u64,
+        voter: Address,
+        commitment_hash: BytesN<32>,
+    ) -> bool {
+        ensure_not_paused(&env);
+        gov_commit_vote(&env, proposal_id, voter, commitment_hash)
+    }
+
+    /// Reveal a previously committed vote after voting has ended
+    ///
+    /// # Arguments
+    /// * `proposal_id` - The ID of the proposal
+    /// * `voter` - Address of the voter
+    /// * `decision` - The committed decision
+    /// * `salt` - The salt used to build the commitment
+    ///
+    /// # Returns
+    /// `true` if the reveal matched and the vote was counted
+    pub fn reveal_vote(
+        env: Env,
+        proposal_id: u64,
+        voter: Address,
+        decision: VoteDecision,
+        salt: Bytes,
+    ) -> bool {
+        ensure_not_paused(&env);
+        gov_reveal_vote(&env, proposal_id, voter, decision, salt)
+    }
+
+    /// Get a member's current effective voting weight, including weight
+    /// delegated to them and any tenure bonus
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `address` - Address of the member
+    ///
+    /// # Returns
+    /// The weight the member's vote would carry, or 0 for non-members
+    pub fn get_voting_weight(env: Env, guild_id: u64, address: Address) -> u32 {
+        gov_get_voting_weight(&env, guild_id, address)
+    }
+
+    /// Get the weight a member was assigned when a proposal was created
+    ///
+    /// # Arguments
+    /// * `proposal_id` - The ID of the proposal
+    /// * `address` - Address of the member
+    ///
+    /// # Returns
+    /// The member's snapshotted weight, or 0 if they were not a member then
+    pub fn get_snapshot_weight(env: Env, proposal_id: u64, address: Address) -> i128 {
+        gov_get_snapshot_weight(&env, proposal_id, address)
+    }
+
+    /// Follow a member's delegation chain to the address that actually votes
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `address` - Address of the member
+    ///
+    /// # Returns
+    /// The terminal delegate, or `address` itself if it has not delegated
+    pub fn get_delegation_target(env: Env, guild_id: u64, address: Address) -> Address {
+        gov_get_delegation_target(&env, guild_id, address)
+    }
+
+    /// List the members who delegated directly to an address
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `address` - Address of the delegate
+    ///
+    /// # Returns
+    /// The direct delegators of `address`
+    pub fn get_delegators(env: Env, guild_id: u64, address: Address) -> Vec<Address> {
+        gov_get_delegators(&env, guild_id, address)
+    }
+
+    /// Get the proposals a member has voted on in a guild
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `address` - Address of the member
+    ///
+    /// # Returns
+    /// `(proposal_id, decision)` pairs ordered by proposal ID
+    pub fn get_votes_by_member(
+        env: Env,
+        guild_id: u64,
+        address: Address,
+    ) -> Vec<(u64, VoteDecision)> {
+        gov_get_votes_by_member(&env, guild_id, address)
+    }
+
     /// Delegate voting power to another member
     ///
     /// # Arguments
     /// * `guild_id` - The ID of the guild
     /// * `delegator` - Address delegating their vote
     /// * `delegate` - Address receiving the delegation
+    /// * `proposal_type` - Limit the delegation to one proposal type (None for all)
     ///
     /// # Returns
     /// `true` if successful
-    pub fn delegate_vote(env: Env, guild_id: u64, delegator: Address, delegate: Address) -> bool {
-        gov_delegate_vote(&env, guild_id, delegator, delegate)
+    pub fn delegate_vote(
+        env: Env,
+        guild_id: u64,
+        delegator: Address,
+        delegate: Address,
+        proposal_type: Option<ProposalType>,
+    ) -> bool {
+        ensure_not_paused(&env);
+        gov_delegate_vote(&env, guild_id, delegator, delegate, proposal_type)
     }
 
     /// Remove vote delegation
@@ -1576,11 +2726,18 @@ impl StellarGuildsContract {
     /// # Arguments
     /// * `guild_id` - The ID of the guild
     /// * `delegator` - Address removing their delegation
+    /// * `proposal_type` - Type-scoped delegation to remove (None for the general one)
     ///
     /// # Returns
     /// `true` if successful
-    pub fn undelegate_vote(env: Env, guild_id: u64, delegator: Address) -> bool {
-        gov_undelegate_vote(&env, guild_id, delegator)
+    pub fn undelegate_vote(
+        env: Env,
+        guild_id: u64,
+        delegator: Address,
+        proposal_type: Option<ProposalType>,
+    ) -> bool {
+        ensure_not_paused(&env);
+        gov_undelegate_vote(&env, guild_id, delegator, proposal_type)
     }
 
     /// Finalize a proposal after voting period ends
@@ -1591,9 +2748,65 @@ impl StellarGuildsContract {
     /// # Returns
     /// The final status of the proposal
     pub fn finalize_proposal(env: Env, proposal_id: u64) -> ProposalStatus {
+        ensure_not_paused(&env);
         gov_finalize_proposal(&env, proposal_id)
     }
 
+    /// Finalize a guild's proposals whose voting period has ended, and expire
+    /// passed proposals left unexecuted past their execution window. Each call
+    /// resumes from a stored cursor and inspects a bounded batch of proposals.
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    ///
+    /// # Returns
+    /// The number of proposals finalized or expired
+    pub fn sweep_proposals(env: Env, guild_id: u64) -> u32 {
+        ensure_not_paused(&env);
+        gov_sweep_proposals(&env, guild_id)
+    }
+
+    /// Reject a proposal before its voting period ends when quorum is out of
+    /// reach even if every vote, including ones already cast, ends up `For`
+    ///
+    /// # Arguments
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// true if the proposal was rejected early
+    pub fn check_early_rejection(env: Env, proposal_id: u64) -> bool {
+        ensure_not_paused(&env);
+        gov_check_early_rejection(&env, proposal_id)
+    }
+
+    /// Preview the status `finalize_proposal` would produce right now
+    ///
+    /// Read-only: does not require the voting period to have ended and does
+    /// not change the stored proposal.
+    ///
+    /// # Arguments
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// The status the proposal would finalize to against the current tally
+    pub fn simulate_finalize(env: Env, proposal_id: u64) -> ProposalStatus {
+        gov_simulate_finalize(&env, proposal_id)
+    }
+
+    /// Check whether the votes cast so far reach the proposal's quorum
+    ///
+    /// Read-only and usable mid-vote; uses the same quorum rules as
+    /// `finalize_proposal` but ignores the approval threshold.
+    ///
+    /// # Arguments
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// true if quorum is currently met
+    pub fn is_quorum_met(env: Env, proposal_id: u64) -> bool {
+        gov_is_quorum_met(&env, proposal_id)
+    }
+
     /// Execute a passed proposal
     ///
     /// # Arguments
@@ -1603,19 +2816,129 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if execution was successful
     pub fn execute_proposal(env: Env, proposal_id: u64, executor: Address) -> bool {
+        ensure_not_paused(&env);
         gov_execute_proposal(&env, proposal_id, executor)
     }
 
-    /// Cancel a proposal
+    /// Finalize a proposal and, if it passed and is unlocked, execute it
+    ///
+    /// # Arguments
+    /// * `proposal_id` - The ID of the proposal
+    /// * `executor` - Address executing the proposal
+    ///
+    /// # Returns
+    /// Whether the proposal was executed, passed but is not yet executable,
+    /// or was rejected
+    pub fn finalize_and_execute(
+        env: Env,
+        proposal_id: u64,
+        executor: Address,
+    ) -> FinalizeExecuteOutcome {
+        ensure_not_paused(&env);
+        gov_finalize_and_execute(&env, proposal_id, executor)
+    }
+
+    /// Veto a passed proposal before it is executed
+    ///
+    /// Bounty disputes cannot be vetoed; only their vote decides the escrow.
+    ///
+    /// # Arguments
+    /// * `proposal_id` - The ID of the proposal
+    /// * `owner` - Address of a guild owner
+    /// * `reason` - Why the proposal is being vetoed
+    ///
+    /// # Returns
+    /// `true` if the proposal was vetoed
+    pub fn veto_proposal(env: Env, proposal_id: u64, owner: Address, reason: String) -> bool {
+        ensure_not_paused(&env);
+        gov_veto_proposal(&env, proposal_id, owner, reason)
+    }
+
+    /// Cancel an active proposal
+    ///
+    /// The proposer may cancel until the first vote is cast; guild owners may
+    /// cancel at any time while the proposal is active. Bounty disputes
+    /// cannot be cancelled.
+    ///
+    /// # Arguments
+    /// * `proposal_id` - The ID of the proposal
+    /// * `caller` - Address making the request (proposer or owner)
+    ///
+    /// # Returns
+    /// `true` if successful
+    pub fn cancel_proposal(env: Env, proposal_id: u64, caller: Address) -> bool {
+        ensure_not_paused(&env);
+        gov_cancel_proposal(&env, proposal_id, caller)
+    }
+
+    /// Extend the voting period of an active proposal
+    ///
+    /// Allowed once per proposal, by the proposer or a guild owner, while
+    /// voting is still open. Existing votes are kept.
+    ///
+    /// # Arguments
+    /// * `proposal_id` - The ID of the proposal
+    /// * `additional_seconds` - Seconds to add to the voting period
+    /// * `caller` - Address making the request (proposer or owner)
+    ///
+    /// # Returns
+    /// The new end of the voting period
+    pub fn extend_voting_period(
+        env: Env,
+        proposal_id: u64,
+        additional_seconds: u64,
+        caller: Address,
+    ) -> u64 {
+        ensure_not_paused(&env);
+        gov_extend_voting_period(&env, proposal_id, additional_seconds, caller)
+    }
+
+    /// Anchor a discussion comment to an active proposal
+    ///
+    /// # Arguments
+    /// * `proposal_id` - The ID of the proposal
+    /// * `commenter` - Guild member posting the comment
+    /// * `content_uri` - URI of the off-chain comment content
+    ///
+    /// # Returns
+    /// The index of the comment in the proposal's thread
+    pub fn add_proposal_comment(
+        env: Env,
+        proposal_id: u64,
+        commenter: Address,
+        content_uri: String,
+    ) -> u32 {
+        ensure_not_paused(&env);
+        gov_add_proposal_comment(&env, proposal_id, commenter, content_uri)
+    }
+
+    /// Get all comments posted on a proposal, oldest first
+    pub fn get_proposal_comments(env: Env, proposal_id: u64) -> Vec<ProposalComment> {
+        gov_get_proposal_comments(&env, proposal_id)
+    }
+
+    /// Reclaim the bond of a proposal that reached quorum
+    ///
+    /// # Arguments
+    /// * `proposal_id` - The ID of the proposal
+    /// * `proposer` - Address of the original proposer
+    ///
+    /// # Returns
+    /// `true` if the bond was refunded
+    pub fn claim_proposal_bond(env: Env, proposal_id: u64, proposer: Address) -> bool {
+        ensure_not_paused(&env);
+        gov_claim_proposal_bond(&env, proposal_id, proposer)
+    }
+
+    /// Get the bond locked for a proposal, if any
     ///
     /// # Arguments
     /// * `proposal_id` - The ID of the proposal
-    /// * `caller` - Address making the request (must be proposer or admin)
     ///
     /// # Returns
-    /// `true` if successful
-    pub fn cancel_proposal(env: Env, proposal_id: u64, caller: Address) -> bool {
-        gov_cancel_proposal(&env, proposal_id, caller)
+    /// The proposal bond, or `None` if no bond was required
+    pub fn get_proposal_bond(env: Env, proposal_id: u64) -> Option<ProposalBond> {
+        gov_get_proposal_bond(&env, proposal_id)
     }
 
     /// Update governance configuration
@@ -1633,6 +2956,7 @@ impl StellarGuildsContract {
         caller: Address,
         config: GovernanceConfig,
     ) -> bool {
+        ensure_not_paused(&env);
         gov_update_governance_config(&env, guild_id, caller, config)
     }
 
@@ -1661,6 +2985,7 @@ impl StellarGuildsContract {
         token: Address,
         expiry: u64,
     ) -> u64 {
+        ensure_not_paused(&env);
         create_bounty(
             &env,
             guild_id,
@@ -1673,6 +2998,66 @@ impl StellarGuildsContract {
         )
     }
 
+    /// Create a bounty that pays out in stages as milestones are approved
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild creating the bounty
+    /// * `funder` - Address funding the bounty (must be guild admin/owner)
+    /// * `title` - Short title for the bounty
+    /// * `description` - Detailed description of the work
+    /// * `milestones` - Amount released for each milestone; their sum is locked up front
+    /// * `token` - Token contract used for payouts
+    /// * `expiry` - Absolute timestamp when the bounty expires
+    ///
+    /// # Returns
+    /// The ID of the newly created bounty
+    pub fn create_milestone_bounty(
+        env: Env,
+        guild_id: u64,
+        funder: Address,
+        title: String,
+        description: String,
+        milestones: Vec<i128>,
+        token: Address,
+        expiry: u64,
+    ) -> u64 {
+        ensure_not_paused(&env);
+        create_milestone_bounty(
+            &env,
+            guild_id,
+            funder,
+            title,
+            description,
+            milestones,
+            token,
+            expiry,
+        )
+    }
+
+    /// Approve one milestone of a claimed bounty and pay its amount to the claimer
+    ///
+    /// # Arguments
+    /// * `bounty_id` - The ID of the milestone bounty
+    /// * `milestone_index` - Zero-based index of the milestone
+    /// * `caller` - Address approving (must be guild admin/owner)
+    ///
+    /// # Returns
+    /// `true` if the milestone payment was released
+    pub fn approve_bounty_milestone(
+        env: Env,
+        bounty_id: u64,
+        milestone_index: u32,
+        caller: Address,
+    ) -> bool {
+        ensure_not_paused(&env);
+        approve_bounty_milestone(&env, bounty_id, milestone_index, caller)
+    }
+
+    /// Get the milestones of a bounty (empty for single-payout bounties)
+    pub fn get_bounty_milestones(env: Env, bounty_id: u64) -> Vec<BountyMilestone> {
+        get_bounty_milestones(&env, bounty_id)
+    }
+
     /// Fund a bounty with tokens
     ///
     /// # Arguments
@@ -1683,6 +3068,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if funding was successful
     pub fn fund_bounty(env: Env, bounty_id: u64, funder: Address, amount: i128) -> bool {
+        ensure_not_paused(&env);
         fund_bounty(&env, bounty_id, funder, amount)
     }
 
@@ -1695,6 +3081,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if claiming was successful
     pub fn claim_bounty(env: Env, bounty_id: u64, claimer: Address) -> bool {
+        ensure_not_paused(&env);
         claim_bounty(&env, bounty_id, claimer)
     }
 
@@ -1708,6 +3095,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if approval was successful
     pub fn approve_bounty(env: Env, bounty_id: u64, approver: Address, claimer: Address) -> bool {
+        ensure_not_paused(&env);
         approve_bounty(&env, bounty_id, approver, claimer)
     }
 
@@ -1720,6 +3108,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if submission was successful
     pub fn submit_work(env: Env, bounty_id: u64, submission_url: String) -> bool {
+        ensure_not_paused(&env);
         submit_work(&env, bounty_id, submission_url)
     }
 
@@ -1732,6 +3121,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if approval was successful
     pub fn approve_completion(env: Env, bounty_id: u64, approver: Address) -> bool {
+        ensure_not_paused(&env);
         approve_completion(&env, bounty_id, approver)
     }
 
@@ -1743,6 +3133,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if release was successful
     pub fn release_escrow(env: Env, bounty_id: u64) -> bool {
+        ensure_not_paused(&env);
         release_escrow(&env, bounty_id)
     }
 
@@ -1755,6 +3146,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if cancellation was successful
     pub fn cancel_bounty(env: Env, bounty_id: u64, canceller: Address) -> bool {
+        ensure_not_paused(&env);
         cancel_bounty(&env, bounty_id, canceller)
     }
 
@@ -1766,9 +3158,68 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if bounty was expired and refunded
     pub fn expire_bounty(env: Env, bounty_id: u64) -> bool {
+        ensure_not_paused(&env);
         expire_bounty(&env, bounty_id)
     }
 
+    /// Refund the creator of a bounty whose deadline passed without approved work
+    ///
+    /// # Arguments
+    /// * `bounty_id` - The ID of the expired bounty
+    /// * `funder` - Address of the bounty creator receiving the refund
+    ///
+    /// # Returns
+    /// `true` if the escrowed funds were refunded
+    pub fn refund_expired_bounty(env: Env, bounty_id: u64, funder: Address) -> bool {
+        ensure_not_paused(&env);
+        refund_expired_bounty(&env, bounty_id, funder)
+    }
+
+    /// Remove a claimer who has not submitted work and reopen the bounty
+    ///
+    /// # Arguments
+    /// * `bounty_id` - The ID of the claimed bounty
+    /// * `caller` - Address of the bounty creator or a guild admin
+    ///
+    /// # Returns
+    /// `true` if the bounty was reopened
+    pub fn unassign_claimer(env: Env, bounty_id: u64, caller: Address) -> bool {
+        ensure_not_paused(&env);
+        unassign_claimer(&env, bounty_id, caller)
+    }
+
+    /// Set how long a claimer holds a guild's bounties exclusively
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `seconds` - How long after claiming the claimer cannot be unassigned (0 disables)
+    /// * `caller` - Address of a guild admin or owner
+    ///
+    /// # Returns
+    /// `true` if the window was set
+    pub fn set_claim_window(env: Env, guild_id: u64, seconds: u64, caller: Address) -> bool {
+        ensure_not_paused(&env);
+        set_claim_window(&env, guild_id, seconds, caller)
+    }
+
+    /// Get a guild's exclusive claim window in seconds (0 if none)
+    pub fn get_claim_window(env: Env, guild_id: u64) -> u64 {
+        get_claim_window(&env, guild_id)
+    }
+
+    /// Escalate a bounty under review to a guild governance vote
+    ///
+    /// # Arguments
+    /// * `bounty_id` - The ID of the bounty under review
+    /// * `caller` - Address of the funder (creator) or the claimer
+    ///
+    /// # Returns
+    /// The ID of the `BountyDispute` proposal deciding the escrow
+    pub fn dispute_bounty(env: Env, bounty_id: u64, caller: Address) -> u64 {
+        ensure_not_paused(&env);
+        dispute_bounty(&env, bounty_id, caller)
+    }
+
     /// Claim bounty payout - claimer pulls funds from escrow to their own address
     ///
     /// This function allows an approved claimer to claim their payout after the bounty
@@ -1782,6 +3233,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// `true` if payout claim was successful
     pub fn claim_payout(env: Env, bounty_id: u64, claimer: Address) -> bool {
+        ensure_not_paused(&env);
         claim_payout(&env, bounty_id, claimer)
     }
 
@@ -1807,12 +3259,31 @@ impl StellarGuildsContract {
         get_guild_bounties_list(&env, guild_id)
     }
 
+    /// Get a claimer's bounty record within a guild
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `address` - The claimer
+    ///
+    /// # Returns
+    /// `(completed, earned)`: bounties completed and total escrow paid out
+    pub fn get_bounty_reputation(env: Env, guild_id: u64, address: Address) -> (u32, i128) {
+        bounty_get_reputation(&env, guild_id, address)
+    }
+
+    /// Count bounties in a guild that a claimer let expire while assigned or
+    /// lost in a dispute
+    pub fn get_bounty_failures(env: Env, guild_id: u64, address: Address) -> u32 {
+        get_failed_count(&env, guild_id, address)
+    }
+
     // Ã¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢Â
     //  Multi-Signature Framework
     //  Provides M-of-N signing, configurable policies, and emergency controls.
     // Ã¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢ÂÃ¢â€¢Â
 
-    /// Register a new multi-signature safe account.
+    /// Register a new multi-signature safe account. A `guild_id` links it to
+    /// a guild the owner administers.
     pub fn ms_register_account(
         env: Env,
         owner: Address,
@@ -1820,34 +3291,67 @@ impl StellarGuildsContract {
         threshold: u32,
         guild_id: Option<u64>,
         timeout_seconds: u64,
-    ) -> u64 {
-        match internal_register_account(&env, owner, signers, threshold, guild_id, timeout_seconds)
-        {
-            Ok(id) => id,
-            Err(e) => panic!("ms_register_account error: {}", e as u32),
-        }
+    ) -> Result<u64, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_register_account(&env, owner, signers, threshold, guild_id, timeout_seconds)
+    }
+
+    /// Register a multi-signature account and its operation policies in one
+    /// call. Fails without creating the account if any policy is invalid for
+    /// the signer set. (`ms_register_account_with_policies` exceeds the
+    /// contract function name limit.)
+    pub fn ms_register_with_policies(
+        env: Env,
+        owner: Address,
+        signers: Vec<Address>,
+        threshold: u32,
+        guild_id: Option<u64>,
+        policies: Vec<(OperationType, OperationPolicy)>,
+    ) -> Result<u64, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_register_account_with_policies(
+            &env, owner, signers, threshold, guild_id, policies,
+        )
     }
 
     /// Add a new signer to a multi-sig account (owner only).
-    pub fn ms_add_signer(env: Env, account_id: u64, new_signer: Address, caller: Address) -> bool {
-        match internal_add_signer(&env, account_id, new_signer, caller) {
-            Ok(()) => true,
-            Err(e) => panic!("ms_add_signer error: {}", e as u32),
-        }
+    pub fn ms_add_signer(
+        env: Env,
+        account_id: u64,
+        new_signer: Address,
+        caller: Address,
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_add_signer(&env, account_id, new_signer, caller)?;
+        Ok(true)
     }
 
     /// Remove a signer from a multi-sig account (owner only).
+    /// Fails with the matching `Unsatisfiable*Policy` error if a stored policy
+    /// would then need more signatures than signers remain.
     pub fn ms_remove_signer(
         env: Env,
         account_id: u64,
         signer: Address,
         caller: Address,
         new_threshold: u32,
-    ) -> bool {
-        match internal_remove_signer(&env, account_id, signer, caller, new_threshold) {
-            Ok(()) => true,
-            Err(e) => panic!("ms_remove_signer error: {}", e as u32),
-        }
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_remove_signer(&env, account_id, signer, caller, new_threshold)?;
+        Ok(true)
+    }
+
+    /// Remove yourself as a signer of a multi-sig account (not the owner).
+    /// Applies the same threshold and policy guards as `ms_remove_signer`.
+    pub fn ms_resign_signer(
+        env: Env,
+        account_id: u64,
+        signer: Address,
+        new_threshold: u32,
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_resign_signer(&env, account_id, signer, new_threshold)?;
+        Ok(true)
     }
 
     /// Atomically replace a compromised signer key with a new one (owner only).
@@ -1857,11 +3361,34 @@ impl StellarGuildsContract {
         old_signer: Address,
         new_signer: Address,
         caller: Address,
-    ) -> bool {
-        match internal_rotate_signer(&env, account_id, old_signer, new_signer, caller) {
-            Ok(()) => true,
-            Err(e) => panic!("ms_rotate_signer error: {}", e as u32),
-        }
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_rotate_signer(&env, account_id, old_signer, new_signer, caller)?;
+        Ok(true)
+    }
+
+    /// Transfer ownership of a multi-sig account to one of its signers (owner only).
+    pub fn ms_transfer_account_ownership(
+        env: Env,
+        account_id: u64,
+        new_owner: Address,
+        caller: Address,
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_transfer_account_ownership(&env, account_id, new_owner, caller)?;
+        Ok(true)
+    }
+
+    /// Assign signing weights to existing signers (owner only). Unlisted signers weigh 1.
+    pub fn ms_set_signer_weights(
+        env: Env,
+        account_id: u64,
+        weights: Map<Address, u32>,
+        caller: Address,
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_set_signer_weights(&env, account_id, weights, caller)?;
+        Ok(true)
     }
 
     /// Update the signing threshold for an account (owner only).
@@ -1870,35 +3397,65 @@ impl StellarGuildsContract {
         account_id: u64,
         new_threshold: u32,
         caller: Address,
-    ) -> bool {
-        match internal_update_threshold(&env, account_id, new_threshold, caller) {
-            Ok(()) => true,
-            Err(e) => panic!("ms_update_threshold error: {}", e as u32),
-        }
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_update_threshold(&env, account_id, new_threshold, caller)?;
+        Ok(true)
+    }
+
+    /// Make the signing threshold a percentage of the current signers, or
+    /// clear it with `None` (owner only). The stricter of the percentage and
+    /// the absolute threshold applies.
+    pub fn ms_set_threshold_percentage(
+        env: Env,
+        account_id: u64,
+        percentage: Option<u32>,
+        caller: Address,
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_set_threshold_percentage(&env, account_id, percentage, caller)?;
+        Ok(true)
+    }
+
+    /// Replace the signer set and threshold of an account whose signers are lost
+    /// (owner only). All pending operations are cancelled.
+    pub fn ms_recover_account(
+        env: Env,
+        account_id: u64,
+        new_signers: Vec<Address>,
+        new_threshold: u32,
+        owner: Address,
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_recover_account(&env, account_id, new_signers, new_threshold, owner)?;
+        Ok(true)
     }
 
     /// Freeze a multi-sig account, blocking all new operations (owner only).
-    pub fn ms_freeze_account(env: Env, account_id: u64, caller: Address) -> bool {
-        match internal_freeze_account(&env, account_id, caller) {
-            Ok(()) => true,
-            Err(e) => panic!("ms_freeze_account error: {}", e as u32),
-        }
+    pub fn ms_freeze_account(
+        env: Env,
+        account_id: u64,
+        caller: Address,
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_freeze_account(&env, account_id, caller)?;
+        Ok(true)
     }
 
     /// Unfreeze a previously frozen account (owner only).
-    pub fn ms_unfreeze_account(env: Env, account_id: u64, caller: Address) -> bool {
-        match internal_unfreeze_account(&env, account_id, caller) {
-            Ok(()) => true,
-            Err(e) => panic!("ms_unfreeze_account error: {}", e as u32),
-        }
+    pub fn ms_unfreeze_account(
+        env: Env,
+        account_id: u64,
+        caller: Address,
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_unfreeze_account(&env, account_id, caller)?;
+        Ok(true)
     }
 
     /// Retrieve a multi-sig account by ID.
-    pub fn ms_get_account(env: Env, account_id: u64) -> MultiSigAccount {
-        match internal_get_safe_account(&env, account_id) {
-            Ok(a) => a,
-            Err(e) => panic!("ms_get_account error: {}", e as u32),
-        }
+    pub fn ms_get_account(env: Env, account_id: u64) -> Result<MultiSigAccount, MultiSigError> {
+        internal_get_safe_account(&env, account_id)
     }
 
     /// List all multi-sig accounts owned by a given address.
@@ -1906,36 +3463,142 @@ impl StellarGuildsContract {
         internal_list_accounts_by_owner(&env, owner)
     }
 
+    /// List the IDs of the multi-sig accounts registered for a guild.
+    pub fn ms_list_accounts_by_guild(env: Env, guild_id: u64) -> Vec<u64> {
+        internal_list_accounts_by_guild(&env, guild_id)
+    }
+
+    /// Check whether an address is a signer on a multi-sig account.
+    pub fn ms_is_signer(env: Env, account_id: u64, address: Address) -> bool {
+        internal_is_signer(&env, account_id, address)
+    }
+
+    /// List the signers of a multi-sig account.
+    pub fn ms_get_signers(env: Env, account_id: u64) -> Vec<Address> {
+        internal_get_signers(&env, account_id)
+    }
+
+    /// List signers of a multi-sig account who have not signed since
+    /// `since_ts`, so dormant keys can be rotated out.
+    pub fn ms_get_inactive_signers(env: Env, account_id: u64, since_ts: u64) -> Vec<Address> {
+        internal_get_inactive_signers(&env, account_id, since_ts)
+    }
+
     // Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬ Multi-Sig Operations Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬
 
     /// Propose a new operation requiring multi-sig approval.
+    ///
+    /// `content_hash` optionally pins the off-chain document the operation
+    /// refers to; it cannot be changed afterwards.
     pub fn ms_propose_operation(
         env: Env,
         account_id: u64,
         operation_type: OperationType,
         description: String,
         proposer: Address,
-    ) -> u64 {
-        match internal_propose_operation(&env, account_id, operation_type, description, proposer) {
-            Ok(id) => id,
-            Err(e) => panic!("ms_propose_operation error: {}", e as u32),
-        }
+        content_hash: Option<BytesN<32>>,
+    ) -> Result<u64, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_propose_operation(
+            &env,
+            account_id,
+            operation_type,
+            description,
+            OperationPayload::None,
+            proposer,
+            content_hash,
+        )
+    }
+
+    /// Propose an operation whose effect is applied when it is executed,
+    /// binding the collected signatures to that exact action.
+    pub fn ms_propose_with_payload(
+        env: Env,
+        account_id: u64,
+        operation_type: OperationType,
+        description: String,
+        payload: OperationPayload,
+        proposer: Address,
+        content_hash: Option<BytesN<32>>,
+    ) -> Result<u64, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_propose_operation(
+            &env,
+            account_id,
+            operation_type,
+            description,
+            payload,
+            proposer,
+            content_hash,
+        )
     }
 
     /// Submit a signature for a pending operation.
-    pub fn ms_sign_operation(env: Env, operation_id: u64, signer: Address) -> u32 {
-        match internal_sign_operation(&env, operation_id, signer) {
-            Ok(n) => n,
-            Err(e) => panic!("ms_sign_operation error: {}", e as u32),
-        }
+    pub fn ms_sign_operation(
+        env: Env,
+        operation_id: u64,
+        signer: Address,
+    ) -> Result<u32, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_sign_operation(&env, operation_id, signer)
+    }
+
+    /// Delegate signing authority to `proxy` until `expires_at` (signer only).
+    pub fn ms_set_proxy(
+        env: Env,
+        account_id: u64,
+        signer: Address,
+        proxy: Address,
+        expires_at: u64,
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_set_proxy(&env, account_id, signer, proxy, expires_at)?;
+        Ok(true)
+    }
+
+    /// Revoke a signer's proxy immediately (signer only).
+    pub fn ms_clear_proxy(
+        env: Env,
+        account_id: u64,
+        signer: Address,
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_clear_proxy(&env, account_id, signer)?;
+        Ok(true)
+    }
+
+    /// Get the proxy currently registered for a signer, if any.
+    pub fn ms_get_proxy(env: Env, account_id: u64, signer: Address) -> Option<SignerProxy> {
+        internal_get_proxy(&env, account_id, signer)
+    }
+
+    /// Sign a pending operation on behalf of `signer` through their active proxy.
+    pub fn ms_sign_as_proxy(
+        env: Env,
+        operation_id: u64,
+        signer: Address,
+        proxy: Address,
+    ) -> Result<u32, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_sign_as_proxy(&env, operation_id, signer, proxy)
+    }
+
+    /// Sign several pending operations at once, skipping any the signer cannot sign.
+    /// Returns the number of operations signed.
+    pub fn ms_sign_operations_batch(env: Env, operation_ids: Vec<u64>, signer: Address) -> u32 {
+        ensure_not_paused(&env);
+        internal_sign_operations_batch(&env, operation_ids, signer)
     }
 
     /// Execute a fully-signed operation.
-    pub fn ms_execute_operation(env: Env, operation_id: u64, executor: Address) -> bool {
-        match internal_execute_operation(&env, operation_id, executor) {
-            Ok(()) => true,
-            Err(e) => panic!("ms_execute_operation error: {}", e as u32),
-        }
+    pub fn ms_execute_operation(
+        env: Env,
+        operation_id: u64,
+        executor: Address,
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_execute_operation(&env, operation_id, executor)?;
+        Ok(true)
     }
 
     /// Execute a treasury withdrawal proposal only after a multisig treasury operation is executed.
@@ -1948,25 +3611,26 @@ impl StellarGuildsContract {
         recipient: Address,
         amount: i128,
         token: Option<Address>,
+        category: WithdrawalCategory,
         reason: String,
-    ) -> u64 {
-        if let Err(e) = internal_require_executed_operation(
+    ) -> Result<u64, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_require_executed_operation(
             &env,
             multisig_operation_id,
             OperationType::TreasuryWithdrawal,
-        ) {
-            panic!("ms_propose_treasury_withdrawal gate error: {}", e as u32);
-        }
+        )?;
 
-        core_propose_withdrawal(
+        Ok(core_propose_withdrawal(
             &env,
             treasury_id,
             proposer,
             recipient,
             amount,
             token,
+            category,
             reason,
-        )
+        ))
     }
 
     /// Execute a governance proposal only after an executed governance multisig operation.
@@ -1975,44 +3639,76 @@ impl StellarGuildsContract {
         multisig_operation_id: u64,
         proposal_id: u64,
         executor: Address,
-    ) -> bool {
-        if let Err(e) = internal_require_executed_operation(
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_require_executed_operation(
             &env,
             multisig_operation_id,
             OperationType::GovernanceUpdate,
-        ) {
-            panic!("ms_execute_governance_proposal gate error: {}", e as u32);
-        }
-        gov_execute_proposal(&env, proposal_id, executor)
+        )?;
+        Ok(gov_execute_proposal(&env, proposal_id, executor))
     }
 
     /// Cancel a pending operation (proposer or account owner only).
-    pub fn ms_cancel_operation(env: Env, operation_id: u64, caller: Address) -> bool {
-        match internal_cancel_operation(&env, operation_id, caller) {
-            Ok(()) => true,
-            Err(e) => panic!("ms_cancel_operation error: {}", e as u32),
-        }
+    pub fn ms_cancel_operation(
+        env: Env,
+        operation_id: u64,
+        caller: Address,
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_cancel_operation(&env, operation_id, caller)?;
+        Ok(true)
     }
 
     /// Lazily check and mark a single operation as expired if its timeout passed.
-    pub fn ms_check_and_expire(env: Env, operation_id: u64) -> bool {
-        match internal_check_and_expire(&env, operation_id) {
-            Ok(expired) => expired,
-            Err(e) => panic!("ms_check_and_expire error: {}", e as u32),
-        }
+    pub fn ms_check_and_expire(env: Env, operation_id: u64) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_check_and_expire(&env, operation_id)
     }
 
     /// Sweep all pending operations for an account and expire timed-out ones.
     pub fn ms_sweep_expired(env: Env, account_id: u64) -> u32 {
+        ensure_not_paused(&env);
         internal_sweep_expired_operations(&env, account_id)
     }
 
     /// Retrieve the full state of an operation (with lazy expiry applied).
-    pub fn ms_get_operation(env: Env, operation_id: u64) -> MultiSigOperation {
-        match internal_get_operation_status(&env, operation_id) {
-            Ok(op) => op,
-            Err(e) => panic!("ms_get_operation error: {}", e as u32),
-        }
+    pub fn ms_get_operation(
+        env: Env,
+        operation_id: u64,
+    ) -> Result<MultiSigOperation, MultiSigError> {
+        internal_get_operation_status(&env, operation_id)
+    }
+
+    /// Signatures collected and required for an operation, plus whether the
+    /// account owner has signed.
+    pub fn ms_get_operation_progress(
+        env: Env,
+        operation_id: u64,
+    ) -> Result<(u32, u32, bool), MultiSigError> {
+        internal_get_operation_progress(&env, operation_id)
+    }
+
+    /// Highest multi-sig operation id issued so far. Ids start at 1 and are
+    /// never reused, so `1..=count` bounds any scan over operations.
+    pub fn ms_get_operation_count(env: Env) -> u64 {
+        internal_get_operation_count(&env)
+    }
+
+    /// Highest multi-sig account id issued so far. Ids start at 1 and are
+    /// never reused, so `1..=count` bounds any scan over accounts.
+    pub fn ms_get_account_count(env: Env) -> u64 {
+        internal_get_account_count(&env)
+    }
+
+    /// List an account's operations newest-first, optionally filtered by status.
+    pub fn ms_get_operations(
+        env: Env,
+        account_id: u64,
+        status_filter: Option<OperationStatus>,
+        limit: u32,
+    ) -> Vec<MultiSigOperation> {
+        internal_get_operations(&env, account_id, status_filter, limit)
     }
 
     /// List all currently pending (non-expired) operations for an account.
@@ -2020,6 +3716,11 @@ impl StellarGuildsContract {
         internal_get_pending_operations(&env, account_id)
     }
 
+    /// List pending operations, across all accounts, that `signer` can still sign.
+    pub fn ms_get_ops_awaiting_signer(env: Env, signer: Address) -> Vec<u64> {
+        internal_get_operations_awaiting_signer(&env, signer)
+    }
+
     // Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬ Emergency Controls Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬
 
     /// Extend or shorten the expiry of a pending operation (owner only).
@@ -2028,19 +3729,21 @@ impl StellarGuildsContract {
         operation_id: u64,
         new_timeout_seconds: u64,
         owner: Address,
-    ) -> bool {
-        match internal_emergency_extend_timeout(&env, operation_id, new_timeout_seconds, owner) {
-            Ok(()) => true,
-            Err(e) => panic!("ms_emergency_extend_timeout error: {}", e as u32),
-        }
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_emergency_extend_timeout(&env, operation_id, new_timeout_seconds, owner)?;
+        Ok(true)
     }
 
     /// Immediately expire a pending operation (owner-only kill-switch).
-    pub fn ms_emergency_expire(env: Env, operation_id: u64, owner: Address) -> bool {
-        match internal_emergency_expire_operation(&env, operation_id, owner) {
-            Ok(()) => true,
-            Err(e) => panic!("ms_emergency_expire error: {}", e as u32),
-        }
+    pub fn ms_emergency_expire(
+        env: Env,
+        operation_id: u64,
+        owner: Address,
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_emergency_expire_operation(&env, operation_id, owner)?;
+        Ok(true)
     }
 
     // Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬ Policy Management Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬
@@ -2054,9 +3757,11 @@ impl StellarGuildsContract {
         require_all_signers: bool,
         timeout_seconds: u64,
         require_owner_signature: bool,
+        max_amount: i128,
         caller: Address,
-    ) -> bool {
-        match internal_set_operation_policy(
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_set_operation_policy(
             &env,
             account_id,
             operation_type,
@@ -2064,11 +3769,32 @@ impl StellarGuildsContract {
             require_all_signers,
             timeout_seconds,
             require_owner_signature,
+            max_amount,
             caller,
-        ) {
-            Ok(()) => true,
-            Err(e) => panic!("ms_set_policy error: {}", e as u32),
-        }
+        )?;
+        Ok(true)
+    }
+
+    /// Override the allowed timeout range for an operation type (owner only).
+    /// Bounds must lie within 1 hour and 30 days.
+    pub fn ms_set_timeout_bounds(
+        env: Env,
+        account_id: u64,
+        operation_type: OperationType,
+        min_timeout: u64,
+        max_timeout: u64,
+        caller: Address,
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_set_timeout_bounds(
+            &env,
+            account_id,
+            operation_type,
+            min_timeout,
+            max_timeout,
+            caller,
+        )?;
+        Ok(true)
     }
 
     /// Get the effective policy for an operation type (returns default if none set).
@@ -2086,11 +3812,10 @@ impl StellarGuildsContract {
         account_id: u64,
         operation_type: OperationType,
         caller: Address,
-    ) -> bool {
-        match internal_reset_operation_policy(&env, account_id, operation_type, caller) {
-            Ok(()) => true,
-            Err(e) => panic!("ms_reset_policy error: {}", e as u32),
-        }
+    ) -> Result<bool, MultiSigError> {
+        ensure_not_paused(&env);
+        internal_reset_operation_policy(&env, account_id, operation_type, caller)?;
+        Ok(true)
     }
 
     // ============ Subscription Functions ============
@@ -2122,6 +3847,7 @@ impl StellarGuildsContract {
         benefits: Vec<String>,
         created_by: Address,
     ) -> u64 {
+        ensure_not_paused(&env);
         created_by.require_auth();
         match sub_create_plan(
             &env,
@@ -2150,6 +3876,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// The ID of the newly created subscription
     pub fn subscribe(env: Env, plan_id: u64, subscriber: Address, auto_renew: bool) -> u64 {
+        ensure_not_paused(&env);
         subscriber.require_auth();
         match sub_subscribe(&env, plan_id, subscriber, auto_renew) {
             Ok(id) => id,
@@ -2165,6 +3892,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// true if payment was successful
     pub fn process_subscription_payment(env: Env, subscription_id: u64) -> bool {
+        ensure_not_paused(&env);
         match sub_process_payment(&env, subscription_id, 0) {
             Ok(result) => result,
             Err(e) => panic!("process_payment error: {}", e as u32),
@@ -2179,6 +3907,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// true if payment was successful
     pub fn retry_subscription_payment(env: Env, subscription_id: u64) -> bool {
+        ensure_not_paused(&env);
         match sub_retry_payment(&env, subscription_id) {
             Ok(result) => result,
             Err(e) => panic!("retry_payment error: {}", e as u32),
@@ -2194,6 +3923,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// true if successful
     pub fn pause_subscription(env: Env, subscription_id: u64, caller: Address) -> bool {
+        ensure_not_paused(&env);
         caller.require_auth();
         match sub_pause_subscription(&env, subscription_id, caller) {
             Ok(result) => result,
@@ -2210,6 +3940,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// true if successful
     pub fn resume_subscription(env: Env, subscription_id: u64, caller: Address) -> bool {
+        ensure_not_paused(&env);
         caller.require_auth();
         match sub_resume_subscription(&env, subscription_id, caller) {
             Ok(result) => result,
@@ -2232,6 +3963,7 @@ impl StellarGuildsContract {
         caller: Address,
         reason: Option<String>,
     ) -> bool {
+        ensure_not_paused(&env);
         caller.require_auth();
         match sub_cancel_subscription(&env, subscription_id, caller, reason) {
             Ok(result) => result,
@@ -2256,6 +3988,7 @@ impl StellarGuildsContract {
         effective_immediately: bool,
         caller: Address,
     ) -> i128 {
+        ensure_not_paused(&env);
         caller.require_auth();
         let change = SubscriptionChange {
             new_plan_id,
@@ -2312,6 +4045,7 @@ impl StellarGuildsContract {
     /// # Returns
     /// Number of subscriptions processed
     pub fn process_due_subscriptions(env: Env, limit: u32) -> u32 {
+        ensure_not_paused(&env);
         sub_process_due_subscriptions(&env, limit)
     }
 
@@ -2325,6 +4059,7 @@ impl StellarGuildsContract {
         initial_version_patch: u32,
         governance_address: Address,
     ) -> bool {
+        ensure_not_paused(&env);
         let version = Version::new(
             initial_version_major,
             initial_version_minor,
@@ -2344,6 +4079,7 @@ impl StellarGuildsContract {
         target_version_patch: u32,
         description: String,
     ) -> u64 {
+        ensure_not_paused(&env);
         let target_version = Version::new(
             target_version_major,
             target_version_minor,
@@ -2365,6 +4101,7 @@ impl StellarGuildsContract {
         proposal_id: u64,
         vote_for: bool,
     ) -> bool {
+        ensure_not_paused(&env);
         match upgrade_logic::vote_on_proposal(&env, &voter, proposal_id, vote_for) {
             Ok(_) => true,
             Err(_) => false,
@@ -2373,6 +4110,7 @@ impl StellarGuildsContract {
 
     /// Execute an approved upgrade
     pub fn execute_upgrade_proposal(env: Env, executor: Address, proposal_id: u64) -> bool {
+        ensure_not_paused(&env);
         match upgrade_logic::execute_upgrade(&env, &executor, proposal_id) {
             Ok(_) => true,
             Err(_) => false,
@@ -2388,6 +4126,7 @@ impl StellarGuildsContract {
         new_version_minor: u32,
         new_version_patch: u32,
     ) -> bool {
+        ensure_not_paused(&env);
         let new_version = Version::new(new_version_major, new_version_minor, new_version_patch);
         match upgrade_logic::emergency_upgrade(&env, &caller, &new_contract_address, &new_version) {
             Ok(_) => true,
@@ -2397,12 +4136,43 @@ impl StellarGuildsContract {
 
     /// Toggle emergency upgrades on/off
     pub fn toggle_emergency_upgrades(env: Env, caller: Address, enable: bool) -> bool {
+        ensure_not_paused(&env);
         match upgrade_logic::toggle_emergency_upgrades(&env, &caller, enable) {
             Ok(_) => true,
             Err(_) => false,
         }
     }
 
+    /// Upgrade the contract code in place to an uploaded wasm (contract admin only).
+    /// Stored guild data and the contract address are carried over as-is, so
+    /// the new code must read the existing storage layouts.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> bool {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("contract admin not initialized");
+        admin.require_auth();
+
+        upgrade_logic::upgrade_wasm(&env, new_wasm_hash);
+        true
+    }
+
+    /// Upgrade the contract code only after an executed `GuildConfigChange`
+    /// multisig operation (contract admin only).
+    pub fn ms_upgrade(
+        env: Env,
+        multisig_operation_id: u64,
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<bool, MultiSigError> {
+        internal_require_executed_operation(
+            &env,
+            multisig_operation_id,
+            OperationType::GuildConfigChange,
+        )?;
+        Ok(Self::upgrade(env, new_wasm_hash))
+    }
+
     /// Get current contract version
     pub fn get_current_version(env: Env) -> Version {
         upgrade_storage::get_current_version(&env)
@@ -2422,6 +4192,7 @@ impl StellarGuildsContract {
         migration_function_selector: soroban_sdk::Symbol,
         estimated_gas: u64,
     ) -> bool {
+        ensure_not_paused(&env);
         let from_version = Version::new(from_version_major, from_version_minor, from_version_patch);
         let to_version = Version::new(to_version_major, to_version_minor, to_version_patch);
         let migration_plan = upgrade::types::MigrationPlan {
@@ -2440,12 +4211,14 @@ impl StellarGuildsContract {
 
     /// Initialize proxy functionality
     pub fn initialize_proxy(env: Env, initial_implementation: Address, admin: Address) -> bool {
+        ensure_not_paused(&env);
         proxy_storage::initialize(&env, initial_implementation, admin);
         true
     }
 
     /// Upgrade the proxy to a new implementation
     pub fn proxy_upgrade(env: Env, caller: Address, new_implementation: Address) -> bool {
+        ensure_not_paused(&env);
         match proxy_impl::upgrade(&env, &caller, &new_implementation) {
             Ok(_) => true,
             Err(_) => false,
@@ -2454,6 +4227,7 @@ impl StellarGuildsContract {
 
     /// Transfer admin rights of the proxy
     pub fn proxy_transfer_admin(env: Env, caller: Address, new_admin: Address) -> bool {
+        ensure_not_paused(&env);
         match proxy_impl::transfer_admin(&env, &caller, &new_admin) {
             Ok(_) => true,
             Err(_) => false,
@@ -2480,6 +4254,7 @@ impl StellarGuildsContract {
 
     /// Resume proxy after emergency stop
     pub fn proxy_resume(env: Env, caller: Address) -> bool {
+        ensure_not_paused(&env);
         match proxy_impl::resume(&env, &caller) {
             Ok(_) => true,
             Err(_) => false,
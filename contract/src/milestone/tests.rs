@@ -73,8 +73,9 @@ fn create_treasury_with_funds(
     signers.push_back(signer1.clone());
     signers.push_back(signer2.clone());
 
-    let treasury_id = client.initialize_treasury(&guild_id, &signers, &2u32);
-    client.deposit_treasury(&treasury_id, owner, &amount, &None);
+    let treasury_id =
+        client.initialize_treasury(&guild_id, &signers, &2u32, &false, &false, &Vec::new(env));
+    client.deposit_treasury(&treasury_id, owner, &amount, &None, &None, &None);
     treasury_id
 }
 
@@ -503,6 +504,57 @@ fn test_approve_milestone_success() {
     assert_eq!(milestone.status, MilestoneStatus::Approved);
 }
 
+#[test]
+#[should_panic(expected = "recipient not on payee allowlist")]
+fn test_approve_milestone_pays_only_allowed_payees() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    add_admin(&client, &env, guild_id, &owner, &admin);
+
+    let treasury_id = create_treasury_with_funds(&client, &env, guild_id, &owner, 5000i128);
+    let payees = Vec::from_array(&env, [Address::generate(&env)]);
+    client.set_payee_allowlist(&treasury_id, &payees, &owner);
+
+    let now = env.ledger().timestamp();
+    let mut milestones: Vec<MilestoneInput> = Vec::new(&env);
+
+    milestones.push_back(MilestoneInput {
+        title: String::from_str(&env, "M1"),
+        description: String::from_str(&env, ""),
+        payment_amount: 1000,
+        deadline: now + 86400,
+    });
+
+    client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &1000i128,
+        &treasury_id,
+        &None,
+        &false,
+    );
+
+    let milestone_id = 1u64;
+    client.start_milestone(&milestone_id, &contributor);
+    client.submit_milestone(
+        &milestone_id,
+        &String::from_str(&env, "https://github.com/pr/123"),
+    );
+
+    client.approve_milestone(&milestone_id, &admin);
+}
+
 #[test]
 #[should_panic(expected = "approver must be guild admin")]
 fn test_approve_milestone_non_admin_fails() {
@@ -1,4 +1,5 @@
 ﻿pub mod policy;
+pub mod proxy;
 pub mod registrar;
 pub mod signing;
 pub mod storage;
@@ -8,6 +9,7 @@ pub mod types;
 pub mod tests;
 
 pub use policy::*;
+pub use proxy::*;
 pub use registrar::*;
 pub use signing::*;
 pub use types::*;
@@ -1,9 +1,49 @@
 ﻿use crate::multisig::storage::{get_account, get_policy, store_policy};
 use crate::multisig::types::{
-    OperationPolicy, OperationType, DEFAULT_TIMEOUT, TIMEOUT_24H, TIMEOUT_48H,
+    MultiSigError, OperationPolicy, OperationType, DEFAULT_TIMEOUT, MAX_TIMEOUT_BOUND,
+    MIN_TIMEOUT_BOUND,
 };
 use soroban_sdk::{Address, Env};
 
+/// Whether `min_signatures` is a usable quorum for `signer_count` signers;
+/// any value is accepted when every signer must sign anyway.
+fn valid_min_signatures(min_signatures: u32, require_all_signers: bool, signer_count: u32) -> bool {
+    let min_safe = (signer_count / 2) + 1;
+    require_all_signers || (min_signatures >= min_safe && min_signatures <= signer_count)
+}
+
+/// Check a complete policy against an account with `signer_count` signers,
+/// returning it with its timeout defaulted and clamped into its bounds.
+pub(crate) fn validated_policy(
+    policy: OperationPolicy,
+    signer_count: u32,
+) -> Result<OperationPolicy, MultiSigError> {
+    if !valid_min_signatures(
+        policy.min_signatures,
+        policy.require_all_signers,
+        signer_count,
+    ) || policy.max_amount < 0
+    {
+        return Err(MultiSigError::InvalidPolicy);
+    }
+    let (min_timeout, max_timeout) = policy.timeout_bounds();
+    if min_timeout < MIN_TIMEOUT_BOUND
+        || max_timeout > MAX_TIMEOUT_BOUND
+        || min_timeout > max_timeout
+    {
+        return Err(MultiSigError::InvalidPolicy);
+    }
+    let timeout = if policy.timeout_seconds == 0 {
+        DEFAULT_TIMEOUT
+    } else {
+        policy.timeout_seconds
+    };
+    Ok(OperationPolicy {
+        timeout_seconds: policy.clamp_timeout(timeout),
+        ..policy
+    })
+}
+
 pub fn ms_set_operation_policy(
     env: &Env,
     account_id: u64,
@@ -12,24 +52,28 @@ pub fn ms_set_operation_policy(
     require_all_signers: bool,
     timeout_seconds: u64,
     require_owner_signature: bool,
+    max_amount: i128,
     caller: Address,
-) -> Result<(), u32> {
+) -> Result<(), MultiSigError> {
     caller.require_auth();
-    let account = get_account(env, account_id).ok_or(1u32)?;
+    let account = get_account(env, account_id).ok_or(MultiSigError::AccountNotFound)?;
 
     if account.owner != caller {
-        return Err(2u32);
+        return Err(MultiSigError::NotAuthorized);
     }
 
-    let min_safe = (account.signers.len() / 2) + 1;
-    if !require_all_signers && (min_signatures < min_safe || min_signatures > account.signers.len())
-    {
-        return Err(1u32);
+    if !valid_min_signatures(min_signatures, require_all_signers, account.signers.len()) {
+        return Err(MultiSigError::InvalidPolicy);
+    }
+    if max_amount < 0 {
+        return Err(MultiSigError::InvalidPolicy);
     }
+    // keep any timeout bounds already configured for this operation type
+    let current = ms_get_operation_policy(env, account_id, operation_type.clone());
     let timeout = if timeout_seconds == 0 {
-        DEFAULT_TIMEOUT
+        current.clamp_timeout(DEFAULT_TIMEOUT)
     } else {
-        timeout_seconds.clamp(TIMEOUT_24H, TIMEOUT_48H)
+        current.clamp_timeout(timeout_seconds)
     };
 
     let policy = OperationPolicy {
@@ -37,6 +81,9 @@ pub fn ms_set_operation_policy(
         require_all_signers,
         timeout_seconds: timeout,
         require_owner_signature,
+        max_amount,
+        min_timeout: current.min_timeout,
+        max_timeout: current.max_timeout,
     };
 
     store_policy(env, account_id, operation_type, &policy);
@@ -53,19 +100,53 @@ pub fn ms_get_operation_policy(
         require_all_signers: false,
         timeout_seconds: DEFAULT_TIMEOUT,
         require_owner_signature: false,
+        max_amount: 0,
+        min_timeout: 0,
+        max_timeout: 0,
     })
 }
 
+/// Override the timeout bounds for an operation type, within
+/// `MIN_TIMEOUT_BOUND..=MAX_TIMEOUT_BOUND`. The policy's current timeout is
+/// re-clamped into the new bounds.
+pub fn ms_set_timeout_bounds(
+    env: &Env,
+    account_id: u64,
+    operation_type: OperationType,
+    min_timeout: u64,
+    max_timeout: u64,
+    caller: Address,
+) -> Result<(), MultiSigError> {
+    caller.require_auth();
+    let account = get_account(env, account_id).ok_or(MultiSigError::AccountNotFound)?;
+    if account.owner != caller {
+        return Err(MultiSigError::NotAuthorized);
+    }
+    if min_timeout < MIN_TIMEOUT_BOUND
+        || max_timeout > MAX_TIMEOUT_BOUND
+        || min_timeout > max_timeout
+    {
+        return Err(MultiSigError::InvalidPolicy);
+    }
+
+    let mut policy = ms_get_operation_policy(env, account_id, operation_type.clone());
+    policy.min_timeout = min_timeout;
+    policy.max_timeout = max_timeout;
+    policy.timeout_seconds = policy.clamp_timeout(policy.timeout_seconds);
+    store_policy(env, account_id, operation_type, &policy);
+    Ok(())
+}
+
 pub fn ms_reset_operation_policy(
     env: &Env,
     account_id: u64,
     operation_type: OperationType,
     caller: Address,
-) -> Result<(), u32> {
+) -> Result<(), MultiSigError> {
     caller.require_auth();
-    let account = get_account(env, account_id).ok_or(1u32)?;
+    let account = get_account(env, account_id).ok_or(MultiSigError::AccountNotFound)?;
     if account.owner != caller {
-        return Err(2u32);
+        return Err(MultiSigError::NotAuthorized);
     }
 
     let default_policy = OperationPolicy {
@@ -73,6 +154,9 @@ pub fn ms_reset_operation_policy(
         require_all_signers: false,
         timeout_seconds: DEFAULT_TIMEOUT,
         require_owner_signature: false,
+        max_amount: 0,
+        min_timeout: 0,
+        max_timeout: 0,
     };
     store_policy(env, account_id, operation_type, &default_policy);
     Ok(())
@@ -0,0 +1,57 @@
+use crate::multisig::signing::add_signature;
+use crate::multisig::storage::{get_account, get_operation, get_proxy, remove_proxy, store_proxy};
+use crate::multisig::types::{MultiSigError, SignerProxy};
+use soroban_sdk::{Address, Env};
+
+/// Let `proxy` sign on behalf of `signer` until `expires_at` (signer only).
+pub fn ms_set_proxy(
+    env: &Env,
+    account_id: u64,
+    signer: Address,
+    proxy: Address,
+    expires_at: u64,
+) -> Result<(), MultiSigError> {
+    signer.require_auth();
+    let account = get_account(env, account_id).ok_or(MultiSigError::AccountNotFound)?;
+    if !account.signers.contains(&signer) || proxy == signer {
+        return Err(MultiSigError::NotAuthorized);
+    }
+    if expires_at <= env.ledger().timestamp() {
+        return Err(MultiSigError::ProxyExpired);
+    }
+    store_proxy(env, account_id, signer, &SignerProxy { proxy, expires_at });
+    Ok(())
+}
+
+/// Revoke a signer's proxy immediately (signer only).
+pub fn ms_clear_proxy(env: &Env, account_id: u64, signer: Address) -> Result<(), MultiSigError> {
+    signer.require_auth();
+    get_account(env, account_id).ok_or(MultiSigError::AccountNotFound)?;
+    remove_proxy(env, account_id, signer);
+    Ok(())
+}
+
+pub fn ms_get_proxy(env: &Env, account_id: u64, signer: Address) -> Option<SignerProxy> {
+    get_proxy(env, account_id, signer)
+}
+
+/// Sign an operation as `signer` through their active proxy. The signature is
+/// recorded under `signer`, so it counts once even if the signer also signs.
+pub fn ms_sign_as_proxy(
+    env: &Env,
+    op_id: u64,
+    signer: Address,
+    proxy: Address,
+) -> Result<u32, MultiSigError> {
+    proxy.require_auth();
+    let operation = get_operation(env, op_id).ok_or(MultiSigError::OperationNotFound)?;
+    let delegation =
+        get_proxy(env, operation.account_id, signer.clone()).ok_or(MultiSigError::ProxyNotFound)?;
+    if delegation.proxy != proxy {
+        return Err(MultiSigError::NotAuthorized);
+    }
+    if env.ledger().timestamp() > delegation.expires_at {
+        return Err(MultiSigError::ProxyExpired);
+    }
+    add_signature(env, op_id, signer)
+}
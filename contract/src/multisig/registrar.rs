@@ -1,22 +1,80 @@
-﻿use crate::multisig::storage::{get_account, next_account_id, store_account};
-use crate::multisig::types::{AccountStatus, MultiSigAccount};
-use soroban_sdk::{Address, Env, Vec};
+﻿use crate::events::emit::emit_event;
+use crate::events::topics::{ACT_TRANSFERRED, MOD_MULTISIG};
+use crate::guild::membership::has_permission;
+use crate::guild::types::Role;
+use crate::multisig::policy::validated_policy;
+use crate::multisig::signing::cancel_pending_operations;
+use crate::multisig::storage::{
+    add_guild_account, get_account, get_account_count, get_custom_operation_types,
+    get_guild_accounts, get_last_signed, get_policy, next_account_id, remove_last_signed,
+    set_last_signed, store_account, store_policy,
+};
+use crate::multisig::types::{
+    AccountOwnershipTransferredEvent, AccountStatus, MultiSigAccount, MultiSigError,
+    OperationPolicy, OperationType,
+};
+use soroban_sdk::{Address, Env, Map, Vec};
 
+/// An absolute threshold must be a strict majority of `signer_count`. In
+/// percentage mode it is only a floor, since the percentage already keeps
+/// the effective threshold a majority.
+fn valid_threshold(account: &MultiSigAccount, threshold: u32, signer_count: u32) -> bool {
+    let min_safe = match account.threshold_percentage {
+        Some(_) => 0,
+        None => (signer_count / 2) + 1,
+    };
+    threshold >= min_safe && threshold <= signer_count
+}
+
+/// Error for the first operation type whose stored policy needs more
+/// signatures than `signer_count`.
+fn unsatisfiable_policy(env: &Env, account_id: u64, signer_count: u32) -> Option<MultiSigError> {
+    let built_in = [
+        OperationType::TreasuryWithdrawal,
+        OperationType::GovernanceUpdate,
+        OperationType::GuildConfigChange,
+        OperationType::EmergencyAction,
+    ];
+    let custom = get_custom_operation_types(env, account_id);
+    let op_type = built_in
+        .into_iter()
+        .chain(custom.iter().map(OperationType::Custom))
+        .find(|op_type| {
+            get_policy(env, account_id, op_type.clone())
+                .map(|p| !p.require_all_signers && p.min_signatures > signer_count)
+                .unwrap_or(false)
+        })?;
+    Some(match op_type {
+        OperationType::TreasuryWithdrawal => MultiSigError::UnsatisfiableTreasuryPolicy,
+        OperationType::GovernanceUpdate => MultiSigError::UnsatisfiableGovernancePolicy,
+        OperationType::GuildConfigChange => MultiSigError::UnsatisfiableConfigPolicy,
+        OperationType::EmergencyAction => MultiSigError::UnsatisfiableEmergencyPolicy,
+        OperationType::Custom(_) => MultiSigError::UnsatisfiableCustomPolicy,
+    })
+}
+
+/// Register a new account. When `guild_id` is given the owner must be an
+/// admin or owner of that guild, and the account is listed under it.
 pub fn ms_register_account(
     env: &Env,
     owner: Address,
     mut signers: Vec<Address>,
     threshold: u32,
-    _guild_id: Option<u64>,
+    guild_id: Option<u64>,
     _timeout_seconds: u64,
-) -> Result<u64, u32> {
+) -> Result<u64, MultiSigError> {
     owner.require_auth();
     if !signers.contains(&owner) {
         signers.push_back(owner.clone());
     }
     let min_safe_threshold = (signers.len() / 2) + 1;
     if threshold < min_safe_threshold || threshold > signers.len() {
-        return Err(1u32);
+        return Err(MultiSigError::InvalidThreshold);
+    }
+    if let Some(guild_id) = guild_id {
+        if !has_permission(env, guild_id, owner.clone(), Role::Admin) {
+            return Err(MultiSigError::NotAuthorized);
+        }
     }
     let account_id = next_account_id(env);
     let account = MultiSigAccount {
@@ -26,27 +84,72 @@ pub fn ms_register_account(
         threshold,
         status: AccountStatus::Active,
         nonce: 0,
+        signer_weights: Map::new(env),
+        threshold_percentage: None,
     };
     store_account(env, account_id, &account);
+    let now = env.ledger().timestamp();
+    for signer in account.signers.iter() {
+        set_last_signed(env, account_id, signer, now);
+    }
+    if let Some(guild_id) = guild_id {
+        add_guild_account(env, guild_id, account_id);
+    }
+    Ok(account_id)
+}
+
+/// Register an account together with its operation policies, so it never
+/// exists with only the defaults. Every policy is validated against the
+/// final signer set before anything is stored; one invalid or repeated
+/// operation type fails the whole call.
+pub fn ms_register_account_with_policies(
+    env: &Env,
+    owner: Address,
+    signers: Vec<Address>,
+    threshold: u32,
+    guild_id: Option<u64>,
+    policies: Vec<(OperationType, OperationPolicy)>,
+) -> Result<u64, MultiSigError> {
+    let signer_count = if signers.contains(&owner) {
+        signers.len()
+    } else {
+        signers.len() + 1
+    };
+    let mut validated: Vec<(OperationType, OperationPolicy)> = Vec::new(env);
+    for (op_type, policy) in policies.iter() {
+        if validated.iter().any(|(seen, _)| seen == op_type) {
+            return Err(MultiSigError::InvalidPolicy);
+        }
+        validated.push_back((op_type, validated_policy(policy, signer_count)?));
+    }
+
+    let account_id = ms_register_account(env, owner, signers, threshold, guild_id, 0)?;
+    for (op_type, policy) in validated.iter() {
+        store_policy(env, account_id, op_type, &policy);
+    }
     Ok(account_id)
 }
 
-pub fn ms_freeze_account(env: &Env, account_id: u64, caller: Address) -> Result<(), u32> {
+pub fn ms_freeze_account(env: &Env, account_id: u64, caller: Address) -> Result<(), MultiSigError> {
     caller.require_auth();
-    let mut account = get_account(env, account_id).ok_or(2u32)?;
+    let mut account = get_account(env, account_id).ok_or(MultiSigError::AccountNotFound)?;
     if account.owner != caller {
-        return Err(3u32);
+        return Err(MultiSigError::NotAuthorized);
     }
     account.status = AccountStatus::Frozen;
     store_account(env, account_id, &account);
     Ok(())
 }
 
-pub fn ms_unfreeze_account(env: &Env, account_id: u64, caller: Address) -> Result<(), u32> {
+pub fn ms_unfreeze_account(
+    env: &Env,
+    account_id: u64,
+    caller: Address,
+) -> Result<(), MultiSigError> {
     caller.require_auth();
-    let mut account = get_account(env, account_id).ok_or(2u32)?;
+    let mut account = get_account(env, account_id).ok_or(MultiSigError::AccountNotFound)?;
     if account.owner != caller {
-        return Err(3u32);
+        return Err(MultiSigError::NotAuthorized);
     }
     account.status = AccountStatus::Active;
     store_account(env, account_id, &account);
@@ -58,13 +161,20 @@ pub fn ms_add_signer(
     account_id: u64,
     new_signer: Address,
     caller: Address,
-) -> Result<(), u32> {
+) -> Result<(), MultiSigError> {
     caller.require_auth();
-    let mut account = get_account(env, account_id).ok_or(2u32)?;
+    let mut account = get_account(env, account_id).ok_or(MultiSigError::AccountNotFound)?;
     if account.owner != caller {
-        return Err(3u32);
+        return Err(MultiSigError::NotAuthorized);
     }
     if !account.signers.contains(&new_signer) {
+        // a new signer is not flagged inactive before they had a chance to sign
+        set_last_signed(
+            env,
+            account_id,
+            new_signer.clone(),
+            env.ledger().timestamp(),
+        );
         account.signers.push_back(new_signer);
         store_account(env, account_id, &account);
     }
@@ -77,25 +187,63 @@ pub fn ms_remove_signer(
     signer: Address,
     caller: Address,
     new_threshold: u32,
-) -> Result<(), u32> {
+) -> Result<(), MultiSigError> {
     caller.require_auth();
-    let mut account = get_account(env, account_id).ok_or(2u32)?;
+    let mut account = get_account(env, account_id).ok_or(MultiSigError::AccountNotFound)?;
     if account.owner != caller || account.owner == signer {
-        return Err(3u32);
+        return Err(MultiSigError::NotAuthorized);
     }
     if let Some(idx) = account.signers.first_index_of(&signer) {
-        account.signers.remove(idx);
-        if account.signers.is_empty() {
-            return Err(1u32);
-        }
-        let min_safe = (account.signers.len() / 2) + 1;
-        if new_threshold < min_safe || new_threshold > account.signers.len() {
-            return Err(1u32);
-        }
-        account.threshold = new_threshold;
-        account.nonce += 1;
-        store_account(env, account_id, &account);
+        drop_signer(env, account_id, &mut account, idx, signer, new_threshold)?;
+    }
+    Ok(())
+}
+
+/// Let a signer step down on their own authority. The owner must transfer
+/// ownership before resigning.
+pub fn ms_resign_signer(
+    env: &Env,
+    account_id: u64,
+    signer: Address,
+    new_threshold: u32,
+) -> Result<(), MultiSigError> {
+    signer.require_auth();
+    let mut account = get_account(env, account_id).ok_or(MultiSigError::AccountNotFound)?;
+    if account.owner == signer {
+        return Err(MultiSigError::NotAuthorized);
     }
+    let idx = account
+        .signers
+        .first_index_of(&signer)
+        .ok_or(MultiSigError::SignerNotFound)?;
+    drop_signer(env, account_id, &mut account, idx, signer, new_threshold)
+}
+
+/// Remove the signer at `idx` and apply `new_threshold`, keeping the signer
+/// set non-empty, the threshold a safe majority and every policy satisfiable.
+fn drop_signer(
+    env: &Env,
+    account_id: u64,
+    account: &mut MultiSigAccount,
+    idx: u32,
+    signer: Address,
+    new_threshold: u32,
+) -> Result<(), MultiSigError> {
+    account.signers.remove(idx);
+    account.signer_weights.remove(signer.clone());
+    if account.signers.is_empty() {
+        return Err(MultiSigError::InvalidThreshold);
+    }
+    if !valid_threshold(account, new_threshold, account.signers.len()) {
+        return Err(MultiSigError::InvalidThreshold);
+    }
+    if let Some(err) = unsatisfiable_policy(env, account_id, account.signers.len()) {
+        return Err(err);
+    }
+    account.threshold = new_threshold;
+    account.nonce += 1;
+    store_account(env, account_id, account);
+    remove_last_signed(env, account_id, signer);
     Ok(())
 }
 
@@ -105,16 +253,28 @@ pub fn ms_rotate_signer(
     old_signer: Address,
     new_signer: Address,
     caller: Address,
-) -> Result<(), u32> {
+) -> Result<(), MultiSigError> {
     caller.require_auth();
-    let mut account = get_account(env, account_id).ok_or(2u32)?;
+    let mut account = get_account(env, account_id).ok_or(MultiSigError::AccountNotFound)?;
     if account.owner != caller {
-        return Err(3u32);
+        return Err(MultiSigError::NotAuthorized);
     }
     if account.signers.contains(&new_signer) {
-        return Err(1u32);
+        return Err(MultiSigError::SignerAlreadyExists);
     }
     if let Some(idx) = account.signers.first_index_of(&old_signer) {
+        // the replacement key inherits the rotated key's weight
+        if let Some(weight) = account.signer_weights.get(old_signer.clone()) {
+            account.signer_weights.remove(old_signer.clone());
+            account.signer_weights.set(new_signer.clone(), weight);
+        }
+        remove_last_signed(env, account_id, old_signer.clone());
+        set_last_signed(
+            env,
+            account_id,
+            new_signer.clone(),
+            env.ledger().timestamp(),
+        );
         account.signers.set(idx, new_signer);
         if account.owner == old_signer {
             account.owner = account.signers.get(idx).unwrap();
@@ -123,7 +283,39 @@ pub fn ms_rotate_signer(
         store_account(env, account_id, &account);
         return Ok(());
     }
-    Err(4u32)
+    Err(MultiSigError::SignerNotFound)
+}
+
+/// Hand account ownership to an existing signer. The previous owner stays a
+/// signer.
+pub fn ms_transfer_account_ownership(
+    env: &Env,
+    account_id: u64,
+    new_owner: Address,
+    caller: Address,
+) -> Result<(), MultiSigError> {
+    caller.require_auth();
+    let mut account = get_account(env, account_id).ok_or(MultiSigError::AccountNotFound)?;
+    if account.owner != caller {
+        return Err(MultiSigError::NotAuthorized);
+    }
+    if new_owner == account.owner {
+        return Err(MultiSigError::AlreadyOwner);
+    }
+    if !account.signers.contains(&new_owner) {
+        return Err(MultiSigError::SignerNotFound);
+    }
+    account.owner = new_owner.clone();
+    account.nonce += 1;
+    store_account(env, account_id, &account);
+
+    let event = AccountOwnershipTransferredEvent {
+        account_id,
+        previous_owner: caller,
+        new_owner,
+    };
+    emit_event(env, MOD_MULTISIG, ACT_TRANSFERRED, event);
+    Ok(())
 }
 
 pub fn ms_update_threshold(
@@ -131,33 +323,131 @@ pub fn ms_update_threshold(
     account_id: u64,
     new_threshold: u32,
     caller: Address,
-) -> Result<(), u32> {
+) -> Result<(), MultiSigError> {
+    caller.require_auth();
+    let mut account = get_account(env, account_id).ok_or(MultiSigError::AccountNotFound)?;
+    if account.owner != caller {
+        return Err(MultiSigError::NotAuthorized);
+    }
+    if !valid_threshold(&account, new_threshold, account.signers.len()) {
+        return Err(MultiSigError::InvalidThreshold);
+    }
+    account.threshold = new_threshold;
+    account.nonce += 1;
+    store_account(env, account_id, &account);
+    Ok(())
+}
+
+/// Make the threshold track a percentage of the signer set (owner only), or
+/// clear it with `None`. The percentage must be a majority (51-100); clearing
+/// requires the absolute threshold to be a safe majority on its own.
+pub fn ms_set_threshold_percentage(
+    env: &Env,
+    account_id: u64,
+    percentage: Option<u32>,
+    caller: Address,
+) -> Result<(), MultiSigError> {
     caller.require_auth();
-    let mut account = get_account(env, account_id).ok_or(2u32)?;
+    let mut account = get_account(env, account_id).ok_or(MultiSigError::AccountNotFound)?;
     if account.owner != caller {
-        return Err(3u32);
+        return Err(MultiSigError::NotAuthorized);
+    }
+    match percentage {
+        Some(pct) if !(51..=100).contains(&pct) => return Err(MultiSigError::InvalidThreshold),
+        Some(_) => {}
+        None => {
+            let min_safe = (account.signers.len() / 2) + 1;
+            if account.threshold < min_safe {
+                return Err(MultiSigError::InvalidThreshold);
+            }
+        }
+    }
+    account.threshold_percentage = percentage;
+    account.nonce += 1;
+    store_account(env, account_id, &account);
+    Ok(())
+}
+
+pub fn ms_set_signer_weights(
+    env: &Env,
+    account_id: u64,
+    weights: Map<Address, u32>,
+    caller: Address,
+) -> Result<(), MultiSigError> {
+    caller.require_auth();
+    let mut account = get_account(env, account_id).ok_or(MultiSigError::AccountNotFound)?;
+    if account.owner != caller {
+        return Err(MultiSigError::NotAuthorized);
+    }
+    for (signer, weight) in weights.iter() {
+        if !account.signers.contains(&signer) {
+            return Err(MultiSigError::SignerNotFound);
+        }
+        if weight == 0 {
+            return Err(MultiSigError::InvalidWeight);
+        }
+    }
+    account.signer_weights = weights;
+    account.nonce += 1;
+    store_account(env, account_id, &account);
+    Ok(())
+}
+
+/// Last-resort recovery for an account whose signers are lost: the owner
+/// replaces the whole signer set and threshold, and every in-flight
+/// operation is cancelled so it cannot be completed under the old set.
+pub fn ms_recover_account(
+    env: &Env,
+    account_id: u64,
+    mut new_signers: Vec<Address>,
+    new_threshold: u32,
+    owner: Address,
+) -> Result<(), MultiSigError> {
+    owner.require_auth();
+    let mut account = get_account(env, account_id).ok_or(MultiSigError::AccountNotFound)?;
+    if account.owner != owner {
+        return Err(MultiSigError::NotAuthorized);
+    }
+    if !new_signers.contains(&owner) {
+        new_signers.push_back(owner.clone());
+    }
+    let min_safe = (new_signers.len() / 2) + 1;
+    if new_threshold < min_safe || new_threshold > new_signers.len() {
+        return Err(MultiSigError::InvalidThreshold);
     }
-    let min_safe = (account.signers.len() / 2) + 1;
-    if new_threshold < min_safe || new_threshold > account.signers.len() {
-        return Err(1u32);
+    let now = env.ledger().timestamp();
+    for signer in account.signers.iter() {
+        if !new_signers.contains(&signer) {
+            remove_last_signed(env, account_id, signer);
+        }
+    }
+    for signer in new_signers.iter() {
+        if !account.signers.contains(&signer) {
+            set_last_signed(env, account_id, signer, now);
+        }
     }
+    account.signers = new_signers;
     account.threshold = new_threshold;
+    account.signer_weights = Map::new(env);
     account.nonce += 1;
     store_account(env, account_id, &account);
+    cancel_pending_operations(env, account_id);
     Ok(())
 }
 
-pub fn ms_get_safe_account(env: &Env, account_id: u64) -> Result<MultiSigAccount, u32> {
-    get_account(env, account_id).ok_or(2u32)
+pub fn ms_get_safe_account(env: &Env, account_id: u64) -> Result<MultiSigAccount, MultiSigError> {
+    get_account(env, account_id).ok_or(MultiSigError::AccountNotFound)
+}
+
+/// Number of accounts ever registered. Account ids are assigned in order
+/// starting at 1, so every account has an id in `1..=count`.
+pub fn ms_get_account_count(env: &Env) -> u64 {
+    get_account_count(env)
 }
 
 pub fn ms_list_accounts_by_owner(env: &Env, _owner: Address) -> Vec<MultiSigAccount> {
     let mut out = Vec::new(env);
-    let max_id: u64 = env
-        .storage()
-        .instance()
-        .get(&crate::multisig::storage::DataKey::AccountCounter)
-        .unwrap_or(0);
+    let max_id = get_account_count(env);
     for id in 1..=max_id {
         if let Some(account) = get_account(env, id) {
             if account.owner == _owner {
@@ -167,3 +457,36 @@ pub fn ms_list_accounts_by_owner(env: &Env, _owner: Address) -> Vec<MultiSigAcco
     }
     out
 }
+
+/// IDs of the accounts registered for `guild_id`, oldest first.
+pub fn ms_list_accounts_by_guild(env: &Env, guild_id: u64) -> Vec<u64> {
+    get_guild_accounts(env, guild_id)
+}
+
+/// Whether `address` signs for `account_id`; `false` for unknown accounts.
+pub fn ms_is_signer(env: &Env, account_id: u64, address: Address) -> bool {
+    get_account(env, account_id)
+        .map(|account| account.signers.contains(&address))
+        .unwrap_or(false)
+}
+
+/// Signers of `account_id` who have not signed or proposed anything since
+/// `since_ts`. Signers count as active from the time they were added;
+/// those with no recorded activity at all are reported as inactive.
+pub fn ms_get_inactive_signers(env: &Env, account_id: u64, since_ts: u64) -> Vec<Address> {
+    let mut inactive = Vec::new(env);
+    for signer in ms_get_signers(env, account_id).iter() {
+        let last_signed = get_last_signed(env, account_id, signer.clone()).unwrap_or(0);
+        if last_signed < since_ts {
+            inactive.push_back(signer);
+        }
+    }
+    inactive
+}
+
+/// Current signer set of `account_id`; empty for unknown accounts.
+pub fn ms_get_signers(env: &Env, account_id: u64) -> Vec<Address> {
+    get_account(env, account_id)
+        .map(|account| account.signers)
+        .unwrap_or_else(|| Vec::new(env))
+}
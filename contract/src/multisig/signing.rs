@@ -1,118 +1,209 @@
-﻿use crate::multisig::policy::ms_get_operation_policy;
+﻿use crate::guild::storage::touch_member_in_all_guilds;
+use crate::multisig::policy::ms_get_operation_policy;
 use crate::multisig::storage::{
-    get_account, get_operation, next_operation_id, store_account, store_operation, DataKey,
+    get_account, get_operation, get_operation_count, get_policy, next_operation_id,
+    set_last_signed, store_account, store_operation,
 };
 use crate::multisig::types::{
-    AccountStatus, MultiSigOperation, OperationStatus, OperationType, TIMEOUT_24H, TIMEOUT_48H,
+    AccountStatus, MultiSigAccount, MultiSigError, MultiSigOperation, OperationPayload,
+    OperationPolicy, OperationStatus, OperationType, MAX_AWAITING_SCAN,
 };
-use soroban_sdk::{Address, Env, String, Vec};
+use crate::treasury::management::propose_withdrawal_authorized;
+use soroban_sdk::{Address, Bytes, BytesN, Env, String, Vec};
+
+/// Total weight of the collected signatures; signers without an explicit weight count as 1.
+fn signature_weight(account: &MultiSigAccount, signatures: &Vec<Address>) -> u32 {
+    let mut total = 0u32;
+    for signer in signatures.iter() {
+        total += account.signer_weights.get(signer).unwrap_or(1);
+    }
+    total
+}
+
+/// `(collected, required)` signatures for an operation under the policy that
+/// applies to it. Unanimous policies count signers; otherwise both sides are weights.
+fn signature_progress(
+    env: &Env,
+    account: &MultiSigAccount,
+    operation: &MultiSigOperation,
+    policy: &OperationPolicy,
+) -> (u32, u32) {
+    // spends above the policy cap escalate to unanimity
+    let over_cap = policy.max_amount > 0 && operation.payload.amount() > policy.max_amount;
+    if policy.require_all_signers || over_cap {
+        return (operation.signatures.len(), account.signers.len());
+    }
+    // an explicitly configured policy overrides the account threshold
+    let required_weight = match get_policy(env, account.id, operation.op_type.clone()) {
+        Some(p) if p.min_signatures > 0 => p.min_signatures,
+        _ => account.effective_threshold(),
+    };
+    (
+        signature_weight(account, &operation.signatures),
+        required_weight,
+    )
+}
 
 pub fn ms_propose_operation(
     env: &Env,
     account_id: u64,
     op_type: OperationType,
     description: String,
+    payload: OperationPayload,
     proposer: Address,
-) -> Result<u64, u32> {
+    content_hash: Option<BytesN<32>>,
+) -> Result<u64, MultiSigError> {
     proposer.require_auth();
-    let mut account = get_account(env, account_id).ok_or(1u32)?;
-    if !account.signers.contains(&proposer) || account.status == AccountStatus::Frozen {
-        return Err(2u32);
+    let mut account = get_account(env, account_id).ok_or(MultiSigError::AccountNotFound)?;
+    if !account.signers.contains(&proposer) {
+        return Err(MultiSigError::NotAuthorized);
+    }
+    if account.status == AccountStatus::Frozen {
+        return Err(MultiSigError::AccountFrozen);
+    }
+    if !payload.matches(&op_type) {
+        return Err(MultiSigError::OperationTypeMismatch);
     }
     let policy = ms_get_operation_policy(env, account_id, op_type.clone());
     let op_id = next_operation_id(env);
     let current_time = env.ledger().timestamp();
     let mut signatures = Vec::new(env);
     signatures.push_back(proposer.clone());
-    let timeout = policy.timeout_seconds.clamp(TIMEOUT_24H, TIMEOUT_48H);
+    let timeout = policy.clamp_timeout(policy.timeout_seconds);
     let nonce = account.nonce;
     account.nonce += 1;
     store_account(env, account.id, &account);
+    touch_member_in_all_guilds(env, &proposer);
+    set_last_signed(env, account_id, proposer.clone(), current_time);
     let operation = MultiSigOperation {
         id: op_id,
         account_id,
         op_type,
         description,
+        payload,
         proposer,
         signatures,
         nonce,
         created_at: current_time,
         expires_at: current_time + timeout,
         status: OperationStatus::Pending,
+        content_hash: content_hash.map(Bytes::from),
     };
     store_operation(env, op_id, &operation);
     Ok(op_id)
 }
 
-pub fn ms_sign_operation(env: &Env, op_id: u64, signer: Address) -> Result<u32, u32> {
+pub fn ms_sign_operation(env: &Env, op_id: u64, signer: Address) -> Result<u32, MultiSigError> {
     signer.require_auth();
-    let mut operation = get_operation(env, op_id).ok_or(3u32)?;
-    let account = get_account(env, operation.account_id).ok_or(1u32)?;
+    add_signature(env, op_id, signer)
+}
+
+/// Sign every listed operation the signer is still eligible for, skipping the
+/// rest. Returns how many operations were signed.
+pub fn ms_sign_operations_batch(env: &Env, op_ids: Vec<u64>, signer: Address) -> u32 {
+    signer.require_auth();
+    let mut signed = 0u32;
+    for op_id in op_ids.iter() {
+        if add_signature(env, op_id, signer.clone()).is_ok() {
+            signed += 1;
+        }
+    }
+    signed
+}
+
+pub fn add_signature(env: &Env, op_id: u64, signer: Address) -> Result<u32, MultiSigError> {
+    let mut operation = get_operation(env, op_id).ok_or(MultiSigError::OperationNotFound)?;
+    let account = get_account(env, operation.account_id).ok_or(MultiSigError::AccountNotFound)?;
     if operation.status != OperationStatus::Pending {
-        return Err(4u32);
+        return Err(MultiSigError::OperationNotPending);
     }
     if env.ledger().timestamp() > operation.expires_at {
         operation.status = OperationStatus::Expired;
         store_operation(env, op_id, &operation);
-        return Err(5u32);
+        return Err(MultiSigError::OperationExpired);
     }
-    if !account.signers.contains(&signer) || operation.signatures.contains(&signer) {
-        return Err(6u32);
+    if !account.signers.contains(&signer) {
+        return Err(MultiSigError::NotAuthorized);
     }
+    if operation.signatures.contains(&signer) {
+        return Err(MultiSigError::AlreadySigned);
+    }
+    touch_member_in_all_guilds(env, &signer);
+    set_last_signed(
+        env,
+        operation.account_id,
+        signer.clone(),
+        env.ledger().timestamp(),
+    );
     operation.signatures.push_back(signer);
     let sig_count = operation.signatures.len();
     store_operation(env, op_id, &operation);
     Ok(sig_count)
 }
 
-pub fn ms_execute_operation(env: &Env, op_id: u64, executor: Address) -> Result<(), u32> {
+pub fn ms_execute_operation(env: &Env, op_id: u64, executor: Address) -> Result<(), MultiSigError> {
     executor.require_auth();
-    let mut operation = get_operation(env, op_id).ok_or(3u32)?;
-    let account = get_account(env, operation.account_id).ok_or(1u32)?;
+    let mut operation = get_operation(env, op_id).ok_or(MultiSigError::OperationNotFound)?;
+    let account = get_account(env, operation.account_id).ok_or(MultiSigError::AccountNotFound)?;
     if operation.status != OperationStatus::Pending {
-        return Err(4u32);
+        return Err(MultiSigError::OperationNotPending);
     }
     if env.ledger().timestamp() > operation.expires_at {
         operation.status = OperationStatus::Expired;
         store_operation(env, op_id, &operation);
-        return Err(5u32);
+        return Err(MultiSigError::OperationExpired);
     }
     let policy = ms_get_operation_policy(env, account.id, operation.op_type.clone());
-    let required_sigs = if policy.require_all_signers {
-        account.signers.len()
-    } else if policy.min_signatures > 0 {
-        policy.min_signatures
-    } else {
-        account.threshold
-    };
-    if operation.signatures.len() < required_sigs {
-        return Err(7u32);
+    let (collected, required) = signature_progress(env, &account, &operation, &policy);
+    if collected < required {
+        return Err(MultiSigError::ThresholdNotMet);
     }
     if policy.require_owner_signature && !operation.signatures.contains(&account.owner) {
-        return Err(8u32);
+        return Err(MultiSigError::OwnerSignatureRequired);
     }
     operation.status = OperationStatus::Executed;
     store_operation(env, op_id, &operation);
+    dispatch_payload(env, &operation, executor);
     Ok(())
 }
 
-pub fn ms_cancel_operation(env: &Env, op_id: u64, caller: Address) -> Result<(), u32> {
+/// Apply the effect bound to an executed operation, so the signatures cover
+/// the exact action rather than only its description.
+fn dispatch_payload(env: &Env, operation: &MultiSigOperation, executor: Address) {
+    match &operation.payload {
+        OperationPayload::None => {}
+        OperationPayload::TreasuryWithdrawal(p) => {
+            propose_withdrawal_authorized(
+                env,
+                p.treasury_id,
+                executor,
+                p.recipient.clone(),
+                p.amount,
+                p.token.clone(),
+                p.category.clone(),
+                operation.description.clone(),
+            );
+        }
+    }
+}
+
+pub fn ms_cancel_operation(env: &Env, op_id: u64, caller: Address) -> Result<(), MultiSigError> {
     caller.require_auth();
-    let mut op = get_operation(env, op_id).ok_or(3u32)?;
-    let account = get_account(env, op.account_id).ok_or(1u32)?;
+    let mut op = get_operation(env, op_id).ok_or(MultiSigError::OperationNotFound)?;
+    let account = get_account(env, op.account_id).ok_or(MultiSigError::AccountNotFound)?;
     if op.proposer != caller && account.owner != caller {
-        return Err(2u32);
+        return Err(MultiSigError::NotAuthorized);
     }
     if op.status != OperationStatus::Pending {
-        return Err(4u32);
+        return Err(MultiSigError::OperationNotPending);
     }
     op.status = OperationStatus::Cancelled;
     store_operation(env, op_id, &op);
     Ok(())
 }
 
-pub fn ms_check_and_expire(env: &Env, op_id: u64) -> Result<bool, u32> {
-    let mut op = get_operation(env, op_id).ok_or(3u32)?;
+pub fn ms_check_and_expire(env: &Env, op_id: u64) -> Result<bool, MultiSigError> {
+    let mut op = get_operation(env, op_id).ok_or(MultiSigError::OperationNotFound)?;
     if op.status == OperationStatus::Pending && env.ledger().timestamp() > op.expires_at {
         op.status = OperationStatus::Expired;
         store_operation(env, op_id, &op);
@@ -121,12 +212,16 @@ pub fn ms_check_and_expire(env: &Env, op_id: u64) -> Result<bool, u32> {
     Ok(false)
 }
 
-pub fn ms_emergency_expire_operation(env: &Env, op_id: u64, owner: Address) -> Result<(), u32> {
+pub fn ms_emergency_expire_operation(
+    env: &Env,
+    op_id: u64,
+    owner: Address,
+) -> Result<(), MultiSigError> {
     owner.require_auth();
-    let mut operation = get_operation(env, op_id).ok_or(3u32)?;
-    let account = get_account(env, operation.account_id).ok_or(1u32)?;
+    let mut operation = get_operation(env, op_id).ok_or(MultiSigError::OperationNotFound)?;
+    let account = get_account(env, operation.account_id).ok_or(MultiSigError::AccountNotFound)?;
     if account.owner != owner {
-        return Err(2u32);
+        return Err(MultiSigError::NotAuthorized);
     }
     operation.status = OperationStatus::Expired;
     store_operation(env, op_id, &operation);
@@ -138,45 +233,57 @@ pub fn ms_emergency_extend_timeout(
     op_id: u64,
     new_timeout_seconds: u64,
     owner: Address,
-) -> Result<(), u32> {
+) -> Result<(), MultiSigError> {
     owner.require_auth();
-    let mut op = get_operation(env, op_id).ok_or(3u32)?;
-    let account = get_account(env, op.account_id).ok_or(1u32)?;
+    let mut op = get_operation(env, op_id).ok_or(MultiSigError::OperationNotFound)?;
+    let account = get_account(env, op.account_id).ok_or(MultiSigError::AccountNotFound)?;
     if account.owner != owner {
-        return Err(2u32);
+        return Err(MultiSigError::NotAuthorized);
     }
-    let timeout = new_timeout_seconds.clamp(TIMEOUT_24H, TIMEOUT_48H);
+    let policy = ms_get_operation_policy(env, account.id, op.op_type.clone());
+    let timeout = policy.clamp_timeout(new_timeout_seconds);
     op.expires_at = env.ledger().timestamp() + timeout;
     store_operation(env, op_id, &op);
     Ok(())
 }
 
-pub fn ms_get_operation_status(env: &Env, op_id: u64) -> Result<MultiSigOperation, u32> {
-    get_operation(env, op_id).ok_or(3u32)
+pub fn ms_get_operation_status(env: &Env, op_id: u64) -> Result<MultiSigOperation, MultiSigError> {
+    get_operation(env, op_id).ok_or(MultiSigError::OperationNotFound)
+}
+
+/// Signatures collected versus required for an operation, and whether the
+/// account owner has signed, using the same rules as `ms_execute_operation`.
+pub fn ms_get_operation_progress(env: &Env, op_id: u64) -> Result<(u32, u32, bool), MultiSigError> {
+    let operation = get_operation(env, op_id).ok_or(MultiSigError::OperationNotFound)?;
+    let account = get_account(env, operation.account_id).ok_or(MultiSigError::AccountNotFound)?;
+    let policy = ms_get_operation_policy(env, account.id, operation.op_type.clone());
+    let (collected, required) = signature_progress(env, &account, &operation, &policy);
+    let owner_signed = operation.signatures.contains(&account.owner);
+    Ok((collected, required, owner_signed))
 }
 
 pub fn ms_require_executed_operation(
     env: &Env,
     op_id: u64,
     expected_type: OperationType,
-) -> Result<(), u32> {
-    let op = get_operation(env, op_id).ok_or(3u32)?;
+) -> Result<(), MultiSigError> {
+    let op = get_operation(env, op_id).ok_or(MultiSigError::OperationNotFound)?;
     if op.status != OperationStatus::Executed {
-        return Err(4u32);
+        return Err(MultiSigError::OperationNotExecuted);
     }
     if op.op_type != expected_type {
-        return Err(9u32);
+        return Err(MultiSigError::OperationTypeMismatch);
+    }
+    // payload-bound operations already applied their effect on execution
+    if op.payload != OperationPayload::None {
+        return Err(MultiSigError::PayloadAlreadyApplied);
     }
     Ok(())
 }
 
 pub fn ms_get_pending_operations(env: &Env, account_id: u64) -> Vec<MultiSigOperation> {
     let now = env.ledger().timestamp();
-    let max_id: u64 = env
-        .storage()
-        .instance()
-        .get(&DataKey::OperationCounter)
-        .unwrap_or(0);
+    let max_id = get_operation_count(env);
     let mut out = Vec::new(env);
     for op_id in 1..=max_id {
         if let Some(op) = get_operation(env, op_id) {
@@ -191,13 +298,84 @@ pub fn ms_get_pending_operations(env: &Env, account_id: u64) -> Vec<MultiSigOper
     out
 }
 
+/// IDs of pending, unexpired operations on accounts where `signer` is a
+/// signer and which `signer` has not signed yet, newest first. Only the
+/// latest `MAX_AWAITING_SCAN` operations are scanned.
+pub fn ms_get_operations_awaiting_signer(env: &Env, signer: Address) -> Vec<u64> {
+    let now = env.ledger().timestamp();
+    let max_id = get_operation_count(env);
+    let min_id = max_id.saturating_sub(MAX_AWAITING_SCAN) + 1;
+    let mut out = Vec::new(env);
+    for op_id in (min_id..=max_id).rev() {
+        let Some(op) = get_operation(env, op_id) else {
+            continue;
+        };
+        if op.status != OperationStatus::Pending
+            || now > op.expires_at
+            || op.signatures.contains(&signer)
+        {
+            continue;
+        }
+        if get_account(env, op.account_id).is_some_and(|a| a.signers.contains(&signer)) {
+            out.push_back(op_id);
+        }
+    }
+    out
+}
+
+/// Cancel every pending operation of an account, returning how many were cancelled.
+pub fn cancel_pending_operations(env: &Env, account_id: u64) -> u32 {
+    let max_id = get_operation_count(env);
+    let mut cancelled = 0u32;
+    for op_id in 1..=max_id {
+        if let Some(mut op) = get_operation(env, op_id) {
+            if op.account_id == account_id && op.status == OperationStatus::Pending {
+                op.status = OperationStatus::Cancelled;
+                store_operation(env, op_id, &op);
+                cancelled += 1;
+            }
+        }
+    }
+    cancelled
+}
+
+/// Number of operations ever proposed. Operation ids are assigned in order
+/// starting at 1, so every existing operation has an id in `1..=count`.
+pub fn ms_get_operation_count(env: &Env) -> u64 {
+    get_operation_count(env)
+}
+
+/// Operations of an account in any (or the given) status, newest first, at most `limit`.
+pub fn ms_get_operations(
+    env: &Env,
+    account_id: u64,
+    status_filter: Option<OperationStatus>,
+    limit: u32,
+) -> Vec<MultiSigOperation> {
+    let max_id = get_operation_count(env);
+    let mut out = Vec::new(env);
+    for op_id in (1..=max_id).rev() {
+        if out.len() >= limit {
+            break;
+        }
+        if let Some(op) = get_operation(env, op_id) {
+            if op.account_id != account_id {
+                continue;
+            }
+            if let Some(status) = &status_filter {
+                if op.status != *status {
+                    continue;
+                }
+            }
+            out.push_back(op);
+        }
+    }
+    out
+}
+
 pub fn ms_sweep_expired_operations(env: &Env, account_id: u64) -> u32 {
     let now = env.ledger().timestamp();
-    let max_id: u64 = env
-        .storage()
-        .instance()
-        .get(&DataKey::OperationCounter)
-        .unwrap_or(0);
+    let max_id = get_operation_count(env);
     let mut expired = 0u32;
     for op_id in 1..=max_id {
         if let Some(mut op) = get_operation(env, op_id) {
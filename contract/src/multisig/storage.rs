@@ -1,35 +1,52 @@
-﻿use crate::multisig::types::{MultiSigAccount, MultiSigOperation, OperationPolicy, OperationType};
-use soroban_sdk::{contracttype, Env};
+﻿use crate::multisig::types::{
+    AccountWeighting, MultiSigAccount, MultiSigOperation, OperationContent, OperationPolicy,
+    OperationType, PolicyLimits, SignerProxy, StoredMultiSigAccount, StoredMultiSigOperation,
+    StoredOperationPolicy,
+};
+use soroban_sdk::{contracttype, Address, Env, Vec};
 
 #[contracttype]
 pub enum DataKey {
     MultiSigAccount(u64),
     MultiSigOperation(u64),
     OperationPolicy(u64, OperationType),
+    CustomOperationTypes(u64),
+    SignerProxy(u64, Address),
+    SignerLastSigned(u64, Address),
+    GuildAccounts(u64),
     AccountCounter,
     OperationCounter,
+    AccountWeighting(u64),
+    OperationContent(u64),
+    PolicyLimits(u64, OperationType),
 }
 
-pub fn next_account_id(env: &Env) -> u64 {
-    let mut count: u64 = env
-        .storage()
+/// Highest account id issued so far; ids run from 1 to this value.
+pub fn get_account_count(env: &Env) -> u64 {
+    env.storage()
         .instance()
         .get(&DataKey::AccountCounter)
-        .unwrap_or(0);
-    count += 1;
+        .unwrap_or(0)
+}
+
+pub fn next_account_id(env: &Env) -> u64 {
+    let count = get_account_count(env) + 1;
     env.storage()
         .instance()
         .set(&DataKey::AccountCounter, &count);
     count
 }
 
-pub fn next_operation_id(env: &Env) -> u64 {
-    let mut count: u64 = env
-        .storage()
+/// Highest operation id issued so far; ids run from 1 to this value.
+pub fn get_operation_count(env: &Env) -> u64 {
+    env.storage()
         .instance()
         .get(&DataKey::OperationCounter)
-        .unwrap_or(0);
-    count += 1;
+        .unwrap_or(0)
+}
+
+pub fn next_operation_id(env: &Env) -> u64 {
+    let count = get_operation_count(env) + 1;
     env.storage()
         .instance()
         .set(&DataKey::OperationCounter, &count);
@@ -37,37 +54,138 @@ pub fn next_operation_id(env: &Env) -> u64 {
 }
 
 pub fn store_account(env: &Env, id: u64, account: &MultiSigAccount) {
+    let (stored, weighting) = account.clone().into_parts();
     env.storage()
         .persistent()
-        .set(&DataKey::MultiSigAccount(id), account);
+        .set(&DataKey::MultiSigAccount(id), &stored);
+    env.storage()
+        .persistent()
+        .set(&DataKey::AccountWeighting(id), &weighting);
 }
 
 pub fn get_account(env: &Env, id: u64) -> Option<MultiSigAccount> {
+    let stored: StoredMultiSigAccount = env
+        .storage()
+        .persistent()
+        .get(&DataKey::MultiSigAccount(id))?;
+    let weighting: Option<AccountWeighting> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::AccountWeighting(id));
+    Some(MultiSigAccount::from_parts(env, stored, weighting))
+}
+
+/// Record `account_id` under the guild it was registered for.
+pub fn add_guild_account(env: &Env, guild_id: u64, account_id: u64) {
+    let mut ids = get_guild_accounts(env, guild_id);
+    ids.push_back(account_id);
     env.storage()
         .persistent()
-        .get(&DataKey::MultiSigAccount(id))
+        .set(&DataKey::GuildAccounts(guild_id), &ids);
+}
+
+pub fn get_guild_accounts(env: &Env, guild_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::GuildAccounts(guild_id))
+        .unwrap_or_else(|| Vec::new(env))
 }
 
 pub fn store_operation(env: &Env, id: u64, operation: &MultiSigOperation) {
+    let (stored, content) = operation.clone().into_parts();
+    env.storage()
+        .persistent()
+        .set(&DataKey::MultiSigOperation(id), &stored);
     env.storage()
         .persistent()
-        .set(&DataKey::MultiSigOperation(id), operation);
+        .set(&DataKey::OperationContent(id), &content);
 }
 
 pub fn get_operation(env: &Env, id: u64) -> Option<MultiSigOperation> {
-    env.storage()
+    let stored: StoredMultiSigOperation = env
+        .storage()
+        .persistent()
+        .get(&DataKey::MultiSigOperation(id))?;
+    let content: Option<OperationContent> = env
+        .storage()
         .persistent()
-        .get(&DataKey::MultiSigOperation(id))
+        .get(&DataKey::OperationContent(id));
+    Some(MultiSigOperation::from_parts(stored, content))
 }
 
 pub fn store_policy(env: &Env, account_id: u64, op_type: OperationType, policy: &OperationPolicy) {
+    if let OperationType::Custom(type_id) = op_type {
+        let mut type_ids = get_custom_operation_types(env, account_id);
+        if !type_ids.contains(type_id) {
+            type_ids.push_back(type_id);
+            env.storage()
+                .persistent()
+                .set(&DataKey::CustomOperationTypes(account_id), &type_ids);
+        }
+    }
+    let (stored, limits) = policy.clone().into_parts();
+    env.storage().persistent().set(
+        &DataKey::OperationPolicy(account_id, op_type.clone()),
+        &stored,
+    );
     env.storage()
         .persistent()
-        .set(&DataKey::OperationPolicy(account_id, op_type), policy);
+        .set(&DataKey::PolicyLimits(account_id, op_type), &limits);
+}
+
+/// Ids of the custom operation types an account has stored a policy for.
+pub fn get_custom_operation_types(env: &Env, account_id: u64) -> Vec<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CustomOperationTypes(account_id))
+        .unwrap_or_else(|| Vec::new(env))
 }
 
 pub fn get_policy(env: &Env, account_id: u64, op_type: OperationType) -> Option<OperationPolicy> {
+    let stored: StoredOperationPolicy = env
+        .storage()
+        .persistent()
+        .get(&DataKey::OperationPolicy(account_id, op_type.clone()))?;
+    let limits: Option<PolicyLimits> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::PolicyLimits(account_id, op_type));
+    Some(OperationPolicy::from_parts(stored, limits))
+}
+
+pub fn store_proxy(env: &Env, account_id: u64, signer: Address, proxy: &SignerProxy) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::SignerProxy(account_id, signer), proxy);
+}
+
+pub fn get_proxy(env: &Env, account_id: u64, signer: Address) -> Option<SignerProxy> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SignerProxy(account_id, signer))
+}
+
+pub fn remove_proxy(env: &Env, account_id: u64, signer: Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::SignerProxy(account_id, signer));
+}
+
+/// Record `timestamp` as the last time `signer` signed for `account_id`.
+pub fn set_last_signed(env: &Env, account_id: u64, signer: Address, timestamp: u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::SignerLastSigned(account_id, signer), &timestamp);
+}
+
+pub fn get_last_signed(env: &Env, account_id: u64, signer: Address) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SignerLastSigned(account_id, signer))
+}
+
+pub fn remove_last_signed(env: &Env, account_id: u64, signer: Address) {
     env.storage()
         .persistent()
-        .get(&DataKey::OperationPolicy(account_id, op_type))
+        .remove(&DataKey::SignerLastSigned(account_id, signer));
 }
@@ -1,10 +1,17 @@
 ﻿#[cfg(test)]
 mod tests {
     use crate::governance::{ProposalType, VoteDecision};
-    use crate::multisig::types::{OperationStatus, OperationType, TIMEOUT_24H, TIMEOUT_48H};
+    use crate::guild::types::Role;
+    use crate::multisig::storage::{self, DataKey};
+    use crate::multisig::types::{
+        AccountStatus, MultiSigError, OperationPayload, OperationPolicy, OperationStatus,
+        OperationType, StoredMultiSigAccount, StoredMultiSigOperation, StoredOperationPolicy,
+        TreasuryWithdrawalPayload, TIMEOUT_24H, TIMEOUT_48H,
+    };
+    use crate::treasury::types::WithdrawalCategory;
     use crate::{StellarGuildsContract, StellarGuildsContractClient};
     use soroban_sdk::testutils::{Address as _, Ledger as _, LedgerInfo};
-    use soroban_sdk::{Address, Env, String, Vec};
+    use soroban_sdk::{Address, Bytes, BytesN, Env, Map, String, Vec};
 
     fn setup_env() -> (Env, Address, Address, Address) {
         let env = Env::default();
@@ -48,6 +55,65 @@ mod tests {
         client.ms_register_account(owner, &signers, &2u32, &None, &TIMEOUT_24H)
     }
 
+    #[test]
+    fn test_records_in_original_layout_still_load() {
+        let (env, owner, signer1, _signer2) = setup_env();
+        let client = init_client(&env);
+
+        env.as_contract(&client.address, || {
+            let persistent = env.storage().persistent();
+            let mut signers = Vec::new(&env);
+            signers.push_back(signer1.clone());
+            persistent.set(
+                &DataKey::MultiSigAccount(1),
+                &StoredMultiSigAccount {
+                    id: 1,
+                    owner: owner.clone(),
+                    signers,
+                    threshold: 1,
+                    status: AccountStatus::Active,
+                    nonce: 0,
+                },
+            );
+            persistent.set(
+                &DataKey::MultiSigOperation(1),
+                &StoredMultiSigOperation {
+                    id: 1,
+                    account_id: 1,
+                    op_type: OperationType::GovernanceUpdate,
+                    description: String::from_str(&env, "legacy"),
+                    proposer: owner.clone(),
+                    signatures: Vec::new(&env),
+                    nonce: 0,
+                    created_at: 0,
+                    expires_at: TIMEOUT_24H,
+                    status: OperationStatus::Pending,
+                },
+            );
+            persistent.set(
+                &DataKey::OperationPolicy(1, OperationType::GovernanceUpdate),
+                &StoredOperationPolicy {
+                    min_signatures: 1,
+                    require_all_signers: false,
+                    timeout_seconds: TIMEOUT_24H,
+                    require_owner_signature: false,
+                },
+            );
+
+            let account = storage::get_account(&env, 1).unwrap();
+            assert_eq!(account.signer_weights.len(), 0);
+            assert_eq!(account.effective_threshold(), 1);
+
+            let operation = storage::get_operation(&env, 1).unwrap();
+            assert!(operation.payload == OperationPayload::None);
+            assert!(operation.content_hash.is_none());
+
+            let policy = storage::get_policy(&env, 1, OperationType::GovernanceUpdate).unwrap();
+            assert_eq!(policy.max_amount, 0);
+            assert_eq!(policy.timeout_bounds(), (TIMEOUT_24H, TIMEOUT_48H));
+        });
+    }
+
     #[test]
     fn test_register_and_propose() {
         let (env, owner, signer1, signer2) = setup_env();
@@ -62,11 +128,40 @@ mod tests {
             &OperationType::TreasuryWithdrawal,
             &desc,
             &owner,
+            &None,
         );
 
         let op = client.ms_get_operation(&op_id);
         assert_eq!(op.status, OperationStatus::Pending);
         assert_eq!(op.signatures.len(), 1); // Proposer auto-signs
+        assert_eq!(op.content_hash, None);
+    }
+
+    #[test]
+    fn test_operation_content_hash_recorded() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        let hash = BytesN::from_array(&env, &[7u8; 32]);
+
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::GuildConfigChange,
+            &String::from_str(&env, "Adopt charter v2"),
+            &owner,
+            &Some(hash.clone()),
+        );
+        let expected = Some(Bytes::from(hash.clone()));
+        assert_eq!(client.ms_get_operation(&op_id).content_hash, expected);
+
+        // signing and executing leave the pinned hash untouched
+        client.ms_sign_operation(&op_id, &signer1);
+        client.ms_execute_operation(&op_id, &signer2);
+        let op = client.ms_get_operation(&op_id);
+        assert_eq!(op.status, OperationStatus::Executed);
+        assert_eq!(op.content_hash, expected);
     }
 
     #[test]
@@ -83,6 +178,7 @@ mod tests {
             &OperationType::TreasuryWithdrawal,
             &desc,
             &owner,
+            &None,
         );
 
         // Signer 1 signs (threshold is 2, so this meets it)
@@ -100,6 +196,63 @@ mod tests {
         assert_eq!(account.nonce, 1);
     }
 
+    #[test]
+    fn test_inactive_signers_track_last_signature() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        set_timestamp(&env, 1_000);
+
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        assert!(client.ms_get_inactive_signers(&account_id, &1_000).is_empty());
+
+        set_timestamp(&env, 5_000);
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::TreasuryWithdrawal,
+            &String::from_str(&env, "Test Tx"),
+            &owner,
+            &None,
+        );
+        client.ms_sign_operation(&op_id, &signer1);
+        let late_signer = Address::generate(&env);
+        client.ms_add_signer(&account_id, &late_signer, &owner);
+
+        // only signer2 has been quiet since registration; the new signer
+        // counts as active from the time they were added
+        let inactive = client.ms_get_inactive_signers(&account_id, &2_000);
+        assert_eq!(inactive.len(), 1);
+        assert_eq!(inactive.get(0).unwrap(), signer2);
+
+        assert_eq!(client.ms_get_inactive_signers(&account_id, &6_000).len(), 4);
+    }
+
+    #[test]
+    fn test_operation_and_account_counts_bound_id_ranges() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        assert_eq!(client.ms_get_account_count(), 0);
+        assert_eq!(client.ms_get_operation_count(), 0);
+
+        let first = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        let second = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        let mut op_ids = Vec::new(&env);
+        for account_id in [first, second, first] {
+            op_ids.push_back(client.ms_propose_operation(
+                &account_id,
+                &OperationType::TreasuryWithdrawal,
+                &String::from_str(&env, "Test Tx"),
+                &owner,
+                &None,
+            ));
+        }
+
+        assert_eq!(client.ms_get_account_count(), second);
+        assert_eq!(client.ms_get_operation_count(), 3);
+        assert_eq!(op_ids.get(2).unwrap(), client.ms_get_operation_count());
+    }
+
     #[test]
     fn test_pending_ops_and_sweep_expired() {
         let (env, owner, signer1, signer2) = setup_env();
@@ -114,6 +267,7 @@ mod tests {
             &OperationType::EmergencyAction,
             &desc,
             &owner,
+            &None,
         );
 
         // Default policy timeout is 48h; move past it before sweeping.
@@ -127,6 +281,54 @@ mod tests {
         assert_eq!(pending.len(), 0);
     }
 
+    #[test]
+    fn test_operations_awaiting_signer() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_a = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        let other = Address::generate(&env);
+        let account_b = register_ms_account(&env, &client, &other, &signer1, &owner);
+        let now = env.ledger().timestamp();
+
+        let desc = String::from_str(&env, "Awaiting");
+        let signed = client.ms_propose_operation(
+            &account_a,
+            &OperationType::GovernanceUpdate,
+            &desc,
+            &signer2,
+            &None,
+        );
+        let op_a = client.ms_propose_operation(
+            &account_a,
+            &OperationType::GovernanceUpdate,
+            &desc,
+            &owner,
+            &None,
+        );
+        let op_b = client.ms_propose_operation(
+            &account_b,
+            &OperationType::GovernanceUpdate,
+            &desc,
+            &other,
+            &None,
+        );
+        client.ms_sign_operation(&signed, &signer1);
+
+        let awaiting = client.ms_get_ops_awaiting_signer(&signer1);
+        assert_eq!(awaiting.len(), 2);
+        assert_eq!(awaiting.get(0), Some(op_b));
+        assert_eq!(awaiting.get(1), Some(op_a));
+
+        // signer2 is not on account_b
+        let awaiting = client.ms_get_ops_awaiting_signer(&signer2);
+        assert_eq!(awaiting.len(), 1);
+        assert_eq!(awaiting.get(0), Some(op_a));
+
+        set_timestamp(&env, now + TIMEOUT_48H + 1);
+        assert_eq!(client.ms_get_ops_awaiting_signer(&signer1).len(), 0);
+    }
+
     #[test]
     fn test_policy_require_owner_signature() {
         let (env, owner, signer1, signer2) = setup_env();
@@ -142,6 +344,7 @@ mod tests {
             &false,
             &TIMEOUT_24H,
             &true,
+            &0i128,
             &owner,
         );
 
@@ -151,6 +354,7 @@ mod tests {
             &OperationType::GovernanceUpdate,
             &desc,
             &signer1,
+            &None,
         );
         client.ms_sign_operation(&op_id, &signer2);
 
@@ -169,6 +373,61 @@ mod tests {
         assert_eq!(accounts.len(), 2);
     }
 
+    #[test]
+    fn test_list_accounts_by_guild() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let guild_id = client.create_guild(
+            &String::from_str(&env, "Safe Guild"),
+            &String::from_str(&env, "Owns multisig accounts"),
+            &owner,
+        );
+        let mut signers = Vec::new(&env);
+        signers.push_back(signer1.clone());
+        signers.push_back(signer2.clone());
+
+        let linked =
+            client.ms_register_account(&owner, &signers, &2u32, &Some(guild_id), &TIMEOUT_24H);
+        let _unlinked = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        let ids = client.ms_list_accounts_by_guild(&guild_id);
+        assert_eq!(ids.len(), 1);
+        assert_eq!(ids.get(0), Some(linked));
+
+        // only guild admins and owners may link an account to the guild
+        client.add_member(&guild_id, &signer1, &Role::Member, &owner);
+        assert_eq!(
+            client
+                .try_ms_register_account(&signer1, &signers, &2u32, &Some(guild_id), &TIMEOUT_24H)
+                .err(),
+            Some(Ok(MultiSigError::NotAuthorized))
+        );
+        assert_eq!(
+            client
+                .try_ms_register_account(&owner, &signers, &2u32, &Some(guild_id + 1), &TIMEOUT_24H)
+                .err(),
+            Some(Ok(MultiSigError::NotAuthorized))
+        );
+    }
+
+    #[test]
+    fn test_is_signer_and_get_signers() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        let outsider = Address::generate(&env);
+
+        assert!(client.ms_is_signer(&account_id, &signer1));
+        assert!(!client.ms_is_signer(&account_id, &outsider));
+        assert_eq!(client.ms_get_signers(&account_id), client.ms_get_account(&account_id).signers);
+
+        // unknown accounts answer rather than fail
+        assert!(!client.ms_is_signer(&(account_id + 1), &signer1));
+        assert_eq!(client.ms_get_signers(&(account_id + 1)).len(), 0);
+    }
+
     #[test]
     fn test_rotate_signer_key() {
         let (env, owner, signer1, signer2) = setup_env();
@@ -198,8 +457,9 @@ mod tests {
         treasury_signers.push_back(owner.clone());
         treasury_signers.push_back(signer1.clone());
         treasury_signers.push_back(signer2.clone());
-        let treasury_id = client.initialize_treasury(&guild_id, &treasury_signers, &2u32);
-        client.deposit_treasury(&treasury_id, &owner, &1_000i128, &None);
+        let treasury_id =
+            client.initialize_treasury(&guild_id, &treasury_signers, &2u32, &false, &false, &Vec::new(&env));
+        client.deposit_treasury(&treasury_id, &owner, &1_000i128, &None, &None, &None);
 
         // Multisig gate setup
         let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
@@ -209,6 +469,7 @@ mod tests {
             &OperationType::TreasuryWithdrawal,
             &op_desc,
             &owner,
+            &None,
         );
         client.ms_sign_operation(&op_id, &signer1);
         assert!(client.ms_execute_operation(&op_id, &signer2));
@@ -221,6 +482,7 @@ mod tests {
             &signer1,
             &100i128,
             &None,
+            &WithdrawalCategory::Operations,
             &reason,
         );
         // Tx id 1 is the deposit; withdrawal proposal is the next tx.
@@ -245,6 +507,7 @@ mod tests {
             &ProposalType::GeneralDecision,
             &title,
             &description,
+            &None,
         );
         client.vote(&proposal_id, &owner, &VoteDecision::For);
         // End voting period.
@@ -257,6 +520,7 @@ mod tests {
             &OperationType::GovernanceUpdate,
             &op_desc,
             &owner,
+            &None,
         );
         client.ms_sign_operation(&op_id, &signer1);
         assert!(client.ms_execute_operation(&op_id, &signer2));
@@ -307,6 +571,7 @@ mod tests {
             &OperationType::EmergencyAction,
             &String::from_str(&env, "cancel me"),
             &owner,
+            &None,
         );
         assert_eq!(client.ms_get_pending_ops(&account_id).len(), 1);
         assert!(client.ms_cancel_operation(&op_a, &owner));
@@ -318,6 +583,7 @@ mod tests {
             &OperationType::GovernanceUpdate,
             &String::from_str(&env, "expire me"),
             &owner,
+            &None,
         );
         set_timestamp(&env, env.ledger().timestamp() + TIMEOUT_48H + 5);
         assert!(client.ms_check_and_expire(&op_b));
@@ -328,12 +594,14 @@ mod tests {
             &OperationType::TreasuryWithdrawal,
             &String::from_str(&env, "sweep me"),
             &owner,
+            &None,
         );
         let op_d = client.ms_propose_operation(
             &account_id,
             &OperationType::TreasuryWithdrawal,
             &String::from_str(&env, "expire now"),
             &owner,
+            &None,
         );
         assert!(client.ms_emergency_extend_timeout(&op_c, &TIMEOUT_24H, &owner));
         assert!(client.ms_emergency_expire(&op_d, &owner));
@@ -358,6 +626,7 @@ mod tests {
             &true,
             &TIMEOUT_24H,
             &true,
+            &0i128,
             &owner,
         ));
         let policy = client.ms_get_policy(&account_id, &OperationType::EmergencyAction);
@@ -373,7 +642,60 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "ms_register_account error")]
+    fn test_register_with_policies_is_atomic() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let mut signers = Vec::new(&env);
+        signers.push_back(signer1.clone());
+        signers.push_back(signer2.clone());
+
+        let policy = |min_signatures: u32| OperationPolicy {
+            min_signatures,
+            require_all_signers: false,
+            timeout_seconds: 0,
+            require_owner_signature: true,
+            max_amount: 0,
+            min_timeout: 0,
+            max_timeout: 0,
+        };
+
+        // three signers including the owner cannot satisfy a five-signature policy
+        let mut invalid = Vec::new(&env);
+        invalid.push_back((OperationType::TreasuryWithdrawal, policy(3)));
+        invalid.push_back((OperationType::EmergencyAction, policy(5)));
+        assert_eq!(
+            client.try_ms_register_with_policies(&owner, &signers, &2u32, &None, &invalid),
+            Err(Ok(MultiSigError::InvalidPolicy))
+        );
+        assert_eq!(client.ms_get_account_count(), 0);
+
+        let mut policies = Vec::new(&env);
+        policies.push_back((OperationType::TreasuryWithdrawal, policy(3)));
+        policies.push_back((OperationType::Custom(1), policy(2)));
+        let account_id =
+            client.ms_register_with_policies(&owner, &signers, &2u32, &None, &policies);
+
+        let stored = client.ms_get_policy(&account_id, &OperationType::TreasuryWithdrawal);
+        assert_eq!(stored.min_signatures, 3);
+        assert!(stored.require_owner_signature);
+        assert_eq!(stored.timeout_seconds, TIMEOUT_48H);
+        assert_eq!(
+            client
+                .ms_get_policy(&account_id, &OperationType::Custom(1))
+                .min_signatures,
+            2
+        );
+        assert_eq!(
+            client
+                .ms_get_policy(&account_id, &OperationType::GovernanceUpdate)
+                .min_signatures,
+            1
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #3)")]
     fn test_register_invalid_threshold_panics() {
         let (env, owner, signer1, signer2) = setup_env();
         env.mock_all_auths();
@@ -386,7 +708,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "ms_propose_operation error")]
+    #[should_panic(expected = "Error(Contract, #7)")]
     fn test_frozen_account_cannot_propose_operation() {
         let (env, owner, signer1, signer2) = setup_env();
         env.mock_all_auths();
@@ -399,6 +721,932 @@ mod tests {
             &OperationType::EmergencyAction,
             &String::from_str(&env, "blocked"),
             &owner,
+            &None,
+        );
+    }
+
+    #[test]
+    fn test_weighted_signers_meet_threshold() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let mut signers = Vec::new(&env);
+        signers.push_back(signer1.clone());
+        signers.push_back(signer2.clone());
+        let account_id = client.ms_register_account(&owner, &signers, &3u32, &None, &TIMEOUT_24H);
+
+        let mut weights = Map::new(&env);
+        weights.set(owner.clone(), 2u32);
+        assert!(client.ms_set_signer_weights(&account_id, &weights, &owner));
+
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::TreasuryWithdrawal,
+            &String::from_str(&env, "weighted"),
+            &owner,
+            &None,
+        );
+        // owner (2) + signer1 (1) reaches the threshold of 3 without signer2
+        client.ms_sign_operation(&op_id, &signer1);
+        assert!(client.ms_execute_operation(&op_id, &signer1));
+        assert_eq!(
+            client.ms_get_operation(&op_id).status,
+            OperationStatus::Executed
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #13)")]
+    fn test_unweighted_signatures_below_threshold_panics() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let mut signers = Vec::new(&env);
+        signers.push_back(signer1.clone());
+        signers.push_back(signer2.clone());
+        let account_id = client.ms_register_account(&owner, &signers, &3u32, &None, &TIMEOUT_24H);
+
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::TreasuryWithdrawal,
+            &String::from_str(&env, "short"),
+            &owner,
+            &None,
+        );
+        client.ms_sign_operation(&op_id, &signer1);
+        client.ms_execute_operation(&op_id, &signer1);
+    }
+
+    #[test]
+    fn test_signer_weights_follow_remove_and_rotate() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        let mut weights = Map::new(&env);
+        weights.set(signer1.clone(), 3u32);
+        weights.set(signer2.clone(), 2u32);
+        client.ms_set_signer_weights(&account_id, &weights, &owner);
+
+        let replacement = Address::generate(&env);
+        client.ms_rotate_signer(&account_id, &signer1, &replacement, &owner);
+        client.ms_remove_signer(&account_id, &signer2, &owner, &2u32);
+
+        let account = client.ms_get_account(&account_id);
+        assert_eq!(account.signer_weights.get(signer1), None);
+        assert_eq!(account.signer_weights.get(signer2), None);
+        assert_eq!(account.signer_weights.get(replacement), Some(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")]
+    fn test_non_owner_cannot_set_signer_weights() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        let mut weights = Map::new(&env);
+        weights.set(signer1.clone(), 2u32);
+        client.ms_set_signer_weights(&account_id, &weights, &signer1);
+    }
+
+    fn setup_treasury(
+        env: &Env,
+        client: &StellarGuildsContractClient<'_>,
+        owner: &Address,
+        signer1: &Address,
+        signer2: &Address,
+    ) -> u64 {
+        let guild_id = client.create_guild(
+            &String::from_str(env, "Payload Guild"),
+            &String::from_str(env, "Guild for payload execution"),
+            owner,
+        );
+        let mut treasury_signers = Vec::new(env);
+        treasury_signers.push_back(owner.clone());
+        treasury_signers.push_back(signer1.clone());
+        treasury_signers.push_back(signer2.clone());
+        client.add_member(&guild_id, signer1, &Role::Admin, owner);
+        client.add_member(&guild_id, signer2, &Role::Admin, owner);
+        let treasury_id =
+            client.initialize_treasury(&guild_id, &treasury_signers, &2u32, &false, &false, &Vec::new(env));
+        client.deposit_treasury(&treasury_id, owner, &1_000i128, &None, &None, &None);
+        treasury_id
+    }
+
+    #[test]
+    fn test_execute_dispatches_treasury_withdrawal_payload() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let treasury_id = setup_treasury(&env, &client, &owner, &signer1, &signer2);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        let recipient = Address::generate(&env);
+        let payload = OperationPayload::TreasuryWithdrawal(TreasuryWithdrawalPayload {
+            treasury_id,
+            recipient: recipient.clone(),
+            amount: 250,
+            token: None,
+            category: WithdrawalCategory::Payroll,
+        });
+        let op_id = client.ms_propose_with_payload(
+            &account_id,
+            &OperationType::TreasuryWithdrawal,
+            &String::from_str(&env, "Pay contributor"),
+            &payload,
+            &owner,
+            &None,
+        );
+        assert_eq!(client.ms_get_operation(&op_id).payload, payload);
+
+        client.ms_sign_operation(&op_id, &signer1);
+        assert!(client.ms_execute_operation(&op_id, &signer2));
+
+        let history = client.get_transaction_history(&treasury_id, &10u32);
+        let tx = history.get(history.len() - 1).unwrap();
+        assert_eq!(tx.amount, 250);
+        assert_eq!(tx.recipient, Some(recipient));
+        assert_eq!(tx.proposer, signer2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #15)")]
+    fn test_payload_must_match_operation_type() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        let payload = OperationPayload::TreasuryWithdrawal(TreasuryWithdrawalPayload {
+            treasury_id: 1,
+            recipient: signer1.clone(),
+            amount: 10,
+            token: None,
+            category: WithdrawalCategory::Payroll,
+        });
+        client.ms_propose_with_payload(
+            &account_id,
+            &OperationType::GovernanceUpdate,
+            &String::from_str(&env, "mismatched"),
+            &payload,
+            &owner,
+            &None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #16)")]
+    fn test_payload_operation_cannot_reopen_withdrawal_gate() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let treasury_id = setup_treasury(&env, &client, &owner, &signer1, &signer2);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        let payload = OperationPayload::TreasuryWithdrawal(TreasuryWithdrawalPayload {
+            treasury_id,
+            recipient: signer1.clone(),
+            amount: 100,
+            token: None,
+            category: WithdrawalCategory::Payroll,
+        });
+        let op_id = client.ms_propose_with_payload(
+            &account_id,
+            &OperationType::TreasuryWithdrawal,
+            &String::from_str(&env, "bound withdrawal"),
+            &payload,
+            &owner,
+            &None,
+        );
+        client.ms_sign_operation(&op_id, &signer1);
+        client.ms_execute_operation(&op_id, &signer2);
+
+        // The approval was already spent on the bound withdrawal.
+        client.ms_propose_treasury_withdrawal(
+            &op_id,
+            &treasury_id,
+            &owner,
+            &signer1,
+            &900i128,
+            &None,
+            &WithdrawalCategory::Operations,
+            &String::from_str(&env, "different action"),
+        );
+    }
+
+    fn propose_capped_withdrawal(
+        env: &Env,
+        client: &StellarGuildsContractClient<'_>,
+        account_id: u64,
+        treasury_id: u64,
+        amount: i128,
+        proposer: &Address,
+    ) -> u64 {
+        let payload = OperationPayload::TreasuryWithdrawal(TreasuryWithdrawalPayload {
+            treasury_id,
+            recipient: Address::generate(env),
+            amount,
+            token: None,
+            category: WithdrawalCategory::Payroll,
+        });
+        client.ms_propose_with_payload(
+            &account_id,
+            &OperationType::TreasuryWithdrawal,
+            &String::from_str(env, "capped spend"),
+            &payload,
+            proposer,
+            &None,
+        )
+    }
+
+    #[test]
+    fn test_spend_within_cap_uses_min_signatures() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let treasury_id = setup_treasury(&env, &client, &owner, &signer1, &signer2);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        client.ms_set_policy(
+            &account_id,
+            &OperationType::TreasuryWithdrawal,
+            &2u32,
+            &false,
+            &TIMEOUT_24H,
+            &false,
+            &500i128,
+            &owner,
+        );
+
+        let op_id = propose_capped_withdrawal(&env, &client, account_id, treasury_id, 500, &owner);
+        client.ms_sign_operation(&op_id, &signer1);
+        assert!(client.ms_execute_operation(&op_id, &signer1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #13)")]
+    fn test_spend_over_cap_requires_all_signers() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let treasury_id = setup_treasury(&env, &client, &owner, &signer1, &signer2);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        client.ms_set_policy(
+            &account_id,
+            &OperationType::TreasuryWithdrawal,
+            &2u32,
+            &false,
+            &TIMEOUT_24H,
+            &false,
+            &500i128,
+            &owner,
+        );
+
+        let op_id = propose_capped_withdrawal(&env, &client, account_id, treasury_id, 501, &owner);
+        client.ms_sign_operation(&op_id, &signer1);
+        client.ms_execute_operation(&op_id, &signer1);
+    }
+
+    #[test]
+    fn test_spend_over_cap_executes_with_unanimity() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let treasury_id = setup_treasury(&env, &client, &owner, &signer1, &signer2);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        client.ms_set_policy(
+            &account_id,
+            &OperationType::TreasuryWithdrawal,
+            &2u32,
+            &false,
+            &TIMEOUT_24H,
+            &false,
+            &500i128,
+            &owner,
+        );
+
+        let op_id = propose_capped_withdrawal(&env, &client, account_id, treasury_id, 900, &owner);
+        client.ms_sign_operation(&op_id, &signer1);
+        client.ms_sign_operation(&op_id, &signer2);
+        assert!(client.ms_execute_operation(&op_id, &signer2));
+    }
+
+    #[test]
+    fn test_batch_signing_skips_ineligible_operations() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        let now = env.ledger().timestamp();
+
+        let propose = |op_type: &OperationType| {
+            client.ms_propose_operation(
+                &account_id,
+                op_type,
+                &String::from_str(&env, "batch"),
+                &owner,
+                &None,
+            )
+        };
+        // Default timeout is 48h; the 24h policy makes the emergency op expire first.
+        client.ms_set_policy(
+            &account_id,
+            &OperationType::EmergencyAction,
+            &2u32,
+            &false,
+            &TIMEOUT_24H,
+            &false,
+            &0i128,
+            &owner,
+        );
+        let op_a = propose(&OperationType::TreasuryWithdrawal);
+        let op_b = propose(&OperationType::GovernanceUpdate);
+        let op_expiring = propose(&OperationType::EmergencyAction);
+        let op_signed = propose(&OperationType::GuildConfigChange);
+        let op_cancelled = propose(&OperationType::GuildConfigChange);
+        client.ms_sign_operation(&op_signed, &signer1);
+        client.ms_cancel_operation(&op_cancelled, &owner);
+
+        set_timestamp(&env, now + TIMEOUT_24H + 1);
+
+        let mut ids = Vec::new(&env);
+        for id in [op_a, op_b, op_expiring, op_signed, op_cancelled, 999u64] {
+            ids.push_back(id);
+        }
+        assert_eq!(client.ms_sign_operations_batch(&ids, &signer1), 2);
+
+        assert_eq!(client.ms_get_operation(&op_a).signatures.len(), 2);
+        assert_eq!(client.ms_get_operation(&op_b).signatures.len(), 2);
+        assert_eq!(
+            client.ms_get_operation(&op_expiring).status,
+            OperationStatus::Expired
+        );
+        assert_eq!(client.ms_get_operation(&op_signed).signatures.len(), 2);
+    }
+
+    #[test]
+    fn test_recover_account_replaces_signers_and_cancels_pending() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::TreasuryWithdrawal,
+            &String::from_str(&env, "in flight"),
+            &owner,
+            &None,
+        );
+        let nonce_before = client.ms_get_account(&account_id).nonce;
+
+        let fresh1 = Address::generate(&env);
+        let fresh2 = Address::generate(&env);
+        let mut new_signers = Vec::new(&env);
+        new_signers.push_back(fresh1.clone());
+        new_signers.push_back(fresh2.clone());
+        assert!(client.ms_recover_account(&account_id, &new_signers, &2u32, &owner));
+
+        let account = client.ms_get_account(&account_id);
+        assert_eq!(account.signers.len(), 3);
+        assert!(account.signers.contains(&owner));
+        assert!(account.signers.contains(&fresh1));
+        assert!(!account.signers.contains(&signer1));
+        assert_eq!(account.threshold, 2);
+        assert!(account.nonce > nonce_before);
+        assert_eq!(
+            client.ms_get_operation(&op_id).status,
+            OperationStatus::Cancelled
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #2)")]
+    fn test_recover_account_requires_owner() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        let mut new_signers = Vec::new(&env);
+        new_signers.push_back(signer1.clone());
+        client.ms_recover_account(&account_id, &new_signers, &1u32, &signer1);
+    }
+
+    #[test]
+    fn test_emergency_policy_with_short_timeout_bounds() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        let two_hours = 7_200u64;
+
+        assert!(client.ms_set_timeout_bounds(
+            &account_id,
+            &OperationType::EmergencyAction,
+            &3_600u64,
+            &two_hours,
+            &owner,
+        ));
+        client.ms_set_policy(
+            &account_id,
+            &OperationType::EmergencyAction,
+            &2u32,
+            &false,
+            &two_hours,
+            &false,
+            &0i128,
+            &owner,
+        );
+        let policy = client.ms_get_policy(&account_id, &OperationType::EmergencyAction);
+        assert_eq!(policy.timeout_seconds, two_hours);
+
+        let now = env.ledger().timestamp();
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::EmergencyAction,
+            &String::from_str(&env, "urgent"),
+            &owner,
+            &None,
+        );
+        assert_eq!(client.ms_get_operation(&op_id).expires_at, now + two_hours);
+
+        // Other operation types keep the default 24-48h window.
+        let op_default = client.ms_propose_operation(
+            &account_id,
+            &OperationType::GovernanceUpdate,
+            &String::from_str(&env, "normal"),
+            &owner,
+            &None,
+        );
+        assert_eq!(
+            client.ms_get_operation(&op_default).expires_at,
+            now + TIMEOUT_48H
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #17)")]
+    fn test_timeout_bounds_outside_absolute_limits_panics() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        client.ms_set_timeout_bounds(
+            &account_id,
+            &OperationType::GovernanceUpdate,
+            &3_600u64,
+            &(31 * 86_400u64),
+            &owner,
+        );
+    }
+
+    #[test]
+    fn test_get_operations_filters_and_orders_newest_first() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        let other_account = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        let propose = |id: u64| {
+            client.ms_propose_operation(
+                &id,
+                &OperationType::GovernanceUpdate,
+                &String::from_str(&env, "audit"),
+                &owner,
+                &None,
+            )
+        };
+        let op_executed = propose(account_id);
+        client.ms_sign_operation(&op_executed, &signer1);
+        client.ms_execute_operation(&op_executed, &signer1);
+        let op_cancelled = propose(account_id);
+        client.ms_cancel_operation(&op_cancelled, &owner);
+        propose(other_account);
+        let op_pending = propose(account_id);
+
+        let all = client.ms_get_operations(&account_id, &None, &10u32);
+        assert_eq!(all.len(), 3);
+        assert_eq!(all.get(0).unwrap().id, op_pending);
+        assert_eq!(all.get(2).unwrap().id, op_executed);
+
+        let cancelled =
+            client.ms_get_operations(&account_id, &Some(OperationStatus::Cancelled), &10u32);
+        assert_eq!(cancelled.len(), 1);
+        assert_eq!(cancelled.get(0).unwrap().id, op_cancelled);
+
+        let limited = client.ms_get_operations(&account_id, &None, &2u32);
+        assert_eq!(limited.len(), 2);
+        assert_eq!(limited.get(1).unwrap().id, op_cancelled);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #21)")]
+    fn test_remove_signer_rejected_when_policy_becomes_unsatisfiable() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        client.ms_set_policy(
+            &account_id,
+            &OperationType::GovernanceUpdate,
+            &3u32,
+            &false,
+            &TIMEOUT_24H,
+            &false,
+            &0i128,
+            &owner,
+        );
+
+        client.ms_remove_signer(&account_id, &signer2, &owner, &2u32);
+    }
+
+    #[test]
+    fn test_remove_signer_allowed_when_policies_remain_satisfiable() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        client.ms_set_policy(
+            &account_id,
+            &OperationType::GovernanceUpdate,
+            &2u32,
+            &false,
+            &TIMEOUT_24H,
+            &false,
+            &0i128,
+            &owner,
+        );
+
+        assert!(client.ms_remove_signer(&account_id, &signer2, &owner, &2u32));
+        assert_eq!(client.ms_get_account(&account_id).signers.len(), 2);
+    }
+
+    #[test]
+    fn test_custom_operation_types_have_own_policies() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        client.ms_set_policy(
+            &account_id,
+            &OperationType::Custom(7),
+            &3u32,
+            &false,
+            &TIMEOUT_24H,
+            &false,
+            &0i128,
+            &owner,
+        );
+        assert_eq!(
+            client
+                .ms_get_policy(&account_id, &OperationType::Custom(7))
+                .min_signatures,
+            3
+        );
+        // other buckets, custom or built-in, keep their defaults
+        assert_eq!(
+            client
+                .ms_get_policy(&account_id, &OperationType::Custom(8))
+                .min_signatures,
+            1
+        );
+        assert_eq!(
+            client
+                .ms_get_policy(&account_id, &OperationType::GuildConfigChange)
+                .min_signatures,
+            1
+        );
+
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::Custom(7),
+            &String::from_str(&env, "mint season badges"),
+            &signer1,
+            &None,
+        );
+        let op = client.ms_get_operation(&op_id);
+        assert_eq!(op.op_type, OperationType::Custom(7));
+        client.ms_sign_operation(&op_id, &signer2);
+        assert_eq!(
+            client.try_ms_execute_operation(&op_id, &owner),
+            Err(Ok(MultiSigError::ThresholdNotMet))
+        );
+
+        assert_eq!(
+            client.try_ms_remove_signer(&account_id, &signer2, &owner, &2u32),
+            Err(Ok(MultiSigError::UnsatisfiableCustomPolicy))
+        );
+    }
+
+    #[test]
+    fn test_proxy_signature_counts_once_for_signer() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        let proxy = Address::generate(&env);
+        let now = env.ledger().timestamp();
+        assert!(client.ms_set_proxy(&account_id, &signer1, &proxy, &(now + 3_600)));
+
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::GovernanceUpdate,
+            &String::from_str(&env, "travel"),
+            &owner,
+            &None,
+        );
+        assert_eq!(client.ms_sign_as_proxy(&op_id, &signer1, &proxy), 2);
+        let op = client.ms_get_operation(&op_id);
+        assert!(op.signatures.contains(&signer1));
+        assert!(!op.signatures.contains(&proxy));
+
+        // The signer's own signature would be a duplicate.
+        assert!(client.try_ms_sign_operation(&op_id, &signer1).is_err());
+        assert!(client.ms_execute_operation(&op_id, &owner));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #18)")]
+    fn test_cleared_proxy_cannot_sign() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        let proxy = Address::generate(&env);
+        let now = env.ledger().timestamp();
+        client.ms_set_proxy(&account_id, &signer1, &proxy, &(now + 3_600));
+        assert!(client.ms_clear_proxy(&account_id, &signer1));
+        assert_eq!(client.ms_get_proxy(&account_id, &signer1), None);
+
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::GovernanceUpdate,
+            &String::from_str(&env, "revoked"),
+            &owner,
+            &None,
+        );
+        client.ms_sign_as_proxy(&op_id, &signer1, &proxy);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #19)")]
+    fn test_expired_proxy_rejected_at_sign_time() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        let proxy = Address::generate(&env);
+        let now = env.ledger().timestamp();
+        client.ms_set_proxy(&account_id, &signer1, &proxy, &(now + 3_600));
+
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::GovernanceUpdate,
+            &String::from_str(&env, "late"),
+            &owner,
+            &None,
+        );
+        set_timestamp(&env, now + 3_601);
+        client.ms_sign_as_proxy(&op_id, &signer1, &proxy);
+    }
+
+    #[test]
+    fn test_errors_surface_as_typed_contract_errors() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, StellarGuildsContract);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        assert_eq!(
+            client.try_ms_get_account(&99).err(),
+            Some(Ok(MultiSigError::AccountNotFound))
+        );
+        assert_eq!(
+            client.try_ms_get_operation(&99).err(),
+            Some(Ok(MultiSigError::OperationNotFound))
+        );
+
+        let mut signers = Vec::new(&env);
+        signers.push_back(signer1.clone());
+        signers.push_back(signer2.clone());
+        let account_id = client.ms_register_account(&owner, &signers, &2, &None, &TIMEOUT_48H);
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::GuildConfigChange,
+            &String::from_str(&env, "rename guild"),
+            &signer1,
+            &None,
+        );
+        assert_eq!(
+            client.try_ms_sign_operation(&op_id, &signer1),
+            Err(Ok(MultiSigError::AlreadySigned))
+        );
+        assert_eq!(
+            client.try_ms_execute_operation(&op_id, &owner),
+            Err(Ok(MultiSigError::ThresholdNotMet))
+        );
+    }
+
+    #[test]
+    fn test_operation_progress_tracks_policy() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        client.ms_set_policy(
+            &account_id,
+            &OperationType::GovernanceUpdate,
+            &2u32,
+            &false,
+            &TIMEOUT_24H,
+            &true,
+            &0i128,
+            &owner,
+        );
+
+        let desc = String::from_str(&env, "progress");
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::GovernanceUpdate,
+            &desc,
+            &signer1,
+            &None,
+        );
+        assert_eq!(client.ms_get_operation_progress(&op_id), (1, 2, false));
+
+        client.ms_sign_operation(&op_id, &signer2);
+        assert_eq!(client.ms_get_operation_progress(&op_id), (2, 2, false));
+
+        client.ms_sign_operation(&op_id, &owner);
+        assert_eq!(client.ms_get_operation_progress(&op_id), (3, 2, true));
+
+        // unanimity raises the requirement to every signer
+        client.ms_set_policy(
+            &account_id,
+            &OperationType::GovernanceUpdate,
+            &2u32,
+            &true,
+            &TIMEOUT_24H,
+            &false,
+            &0i128,
+            &owner,
+        );
+        let signers = client.ms_get_account(&account_id).signers.len();
+        assert_eq!(
+            client.ms_get_operation_progress(&op_id),
+            (3, signers, true)
+        );
+    }
+
+    #[test]
+    fn test_signer_can_resign() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        assert!(client.ms_resign_signer(&account_id, &signer2, &2u32));
+        let account = client.ms_get_account(&account_id);
+        assert_eq!(account.signers.len(), 2);
+        assert!(!account.signers.contains(&signer2));
+        assert_eq!(account.threshold, 2);
+
+        // the remaining pair cannot drop below a safe majority
+        assert_eq!(
+            client
+                .try_ms_resign_signer(&account_id, &signer1, &0u32)
+                .err(),
+            Some(Ok(MultiSigError::InvalidThreshold))
+        );
+    }
+
+    #[test]
+    fn test_owner_cannot_resign() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        assert_eq!(
+            client
+                .try_ms_resign_signer(&account_id, &owner, &2u32)
+                .err(),
+            Some(Ok(MultiSigError::NotAuthorized))
+        );
+        let outsider = Address::generate(&env);
+        assert_eq!(
+            client
+                .try_ms_resign_signer(&account_id, &outsider, &2u32)
+                .err(),
+            Some(Ok(MultiSigError::SignerNotFound))
+        );
+    }
+
+    #[test]
+    fn test_transfer_account_ownership_to_signer() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        let nonce_before = client.ms_get_account(&account_id).nonce;
+
+        assert!(client.ms_transfer_account_ownership(&account_id, &signer1, &owner));
+        let account = client.ms_get_account(&account_id);
+        assert_eq!(account.owner, signer1);
+        assert!(account.signers.contains(&owner));
+        assert_eq!(account.nonce, nonce_before + 1);
+
+        // the previous owner has lost owner-only rights
+        assert_eq!(
+            client
+                .try_ms_transfer_account_ownership(&account_id, &owner, &owner)
+                .err(),
+            Some(Ok(MultiSigError::NotAuthorized))
+        );
+    }
+
+    #[test]
+    fn test_transfer_account_ownership_rejects_invalid_target() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        assert_eq!(
+            client
+                .try_ms_transfer_account_ownership(&account_id, &owner, &owner)
+                .err(),
+            Some(Ok(MultiSigError::AlreadyOwner))
+        );
+        let outsider = Address::generate(&env);
+        assert_eq!(
+            client
+                .try_ms_transfer_account_ownership(&account_id, &outsider, &owner)
+                .err(),
+            Some(Ok(MultiSigError::SignerNotFound))
+        );
+    }
+
+    #[test]
+    fn test_threshold_percentage_tracks_signer_set() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        assert!(client.ms_set_threshold_percentage(&account_id, &Some(100u32), &owner));
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::TreasuryWithdrawal,
+            &String::from_str(&env, "Percentage"),
+            &owner,
+            &None,
+        );
+        client.ms_sign_operation(&op_id, &signer1);
+        assert_eq!(client.ms_get_operation_progress(&op_id), (2, 3, true));
+
+        // a new signer raises the requirement without touching the threshold
+        client.ms_add_signer(&account_id, &Address::generate(&env), &owner);
+        client.ms_sign_operation(&op_id, &signer2);
+        assert_eq!(client.ms_get_operation_progress(&op_id), (3, 4, true));
+        assert_eq!(client.ms_get_account(&account_id).threshold, 2);
+    }
+
+    #[test]
+    fn test_threshold_percentage_must_be_majority() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        assert_eq!(
+            client
+                .try_ms_set_threshold_percentage(&account_id, &Some(50u32), &owner)
+                .err(),
+            Some(Ok(MultiSigError::InvalidThreshold))
+        );
+        assert_eq!(
+            client
+                .try_ms_set_threshold_percentage(&account_id, &Some(60u32), &signer1)
+                .err(),
+            Some(Ok(MultiSigError::NotAuthorized))
+        );
+
+        // the absolute threshold may drop below a majority once the
+        // percentage guarantees one, but then the percentage cannot be cleared
+        assert!(client.ms_set_threshold_percentage(&account_id, &Some(60u32), &owner));
+        assert!(client.ms_update_threshold(&account_id, &1, &owner));
+        assert_eq!(
+            client
+                .try_ms_set_threshold_percentage(&account_id, &None, &owner)
+                .err(),
+            Some(Ok(MultiSigError::InvalidThreshold))
         );
     }
 }
@@ -1,8 +1,50 @@
-﻿use soroban_sdk::{contracttype, Address, String, Vec};
+﻿use crate::treasury::types::WithdrawalCategory;
+use soroban_sdk::{contracterror, contracttype, Address, Bytes, Env, Map, String, Vec};
 
 pub const TIMEOUT_24H: u64 = 86_400;
 pub const TIMEOUT_48H: u64 = 172_800;
 pub const DEFAULT_TIMEOUT: u64 = TIMEOUT_48H;
+/// Absolute limits for per-policy timeout bounds.
+pub const MIN_TIMEOUT_BOUND: u64 = 3_600;
+pub const MAX_TIMEOUT_BOUND: u64 = 2_592_000;
+/// Most recent operations scanned when listing those awaiting a signer.
+pub const MAX_AWAITING_SCAN: u64 = 500;
+
+/// Error types for multisig accounts, policies and operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum MultiSigError {
+    AccountNotFound = 1,
+    NotAuthorized = 2,
+    InvalidThreshold = 3,
+    InvalidWeight = 4,
+    SignerNotFound = 5,
+    SignerAlreadyExists = 6,
+    AccountFrozen = 7,
+    OperationNotFound = 8,
+    OperationNotPending = 9,
+    OperationNotExecuted = 10,
+    OperationExpired = 11,
+    AlreadySigned = 12,
+    ThresholdNotMet = 13,
+    OwnerSignatureRequired = 14,
+    OperationTypeMismatch = 15,
+    PayloadAlreadyApplied = 16,
+    InvalidPolicy = 17,
+    ProxyNotFound = 18,
+    ProxyExpired = 19,
+    /// Removing the signer would leave a stored policy needing more signatures
+    /// than remain, one variant per `OperationType`.
+    UnsatisfiableTreasuryPolicy = 20,
+    UnsatisfiableGovernancePolicy = 21,
+    UnsatisfiableConfigPolicy = 22,
+    UnsatisfiableEmergencyPolicy = 23,
+    /// The proposed new owner already owns the account
+    AlreadyOwner = 24,
+    /// Removing the signer would leave a custom operation type's policy unsatisfiable
+    UnsatisfiableCustomPolicy = 25,
+}
 
 #[contracttype]
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -27,6 +69,49 @@ pub enum OperationType {
     GovernanceUpdate,
     GuildConfigChange,
     EmergencyAction,
+    /// Account-defined policy bucket identified by a numeric id. Appended
+    /// last so the keys of the built-in variants are unchanged.
+    Custom(u32),
+}
+
+/// Parameters of a treasury withdrawal approved through a multisig operation.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TreasuryWithdrawalPayload {
+    pub treasury_id: u64,
+    pub recipient: Address,
+    pub amount: i128,
+    pub token: Option<Address>,
+    pub category: WithdrawalCategory,
+}
+
+/// Concrete effect applied when an operation is executed. `None` keeps the
+/// operation description-only, as before payloads existed.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OperationPayload {
+    None,
+    TreasuryWithdrawal(TreasuryWithdrawalPayload),
+}
+
+impl OperationPayload {
+    /// Value moved by the payload, if any.
+    pub fn amount(&self) -> i128 {
+        match self {
+            OperationPayload::None => 0,
+            OperationPayload::TreasuryWithdrawal(p) => p.amount,
+        }
+    }
+
+    /// Whether this payload may be attached to an operation of `op_type`.
+    pub fn matches(&self, op_type: &OperationType) -> bool {
+        match self {
+            OperationPayload::None => true,
+            OperationPayload::TreasuryWithdrawal(_) => {
+                *op_type == OperationType::TreasuryWithdrawal
+            }
+        }
+    }
 }
 
 #[contracttype]
@@ -38,6 +123,22 @@ pub struct MultiSigAccount {
     pub threshold: u32,
     pub status: AccountStatus,
     pub nonce: u64, // Replay protection
+    // Signers without an entry weigh 1
+    pub signer_weights: Map<Address, u32>,
+    // Threshold as a share of the current signer set; `threshold` then acts
+    // as a floor
+    pub threshold_percentage: Option<u32>,
+}
+
+impl MultiSigAccount {
+    /// Signatures required by default: the stricter of the absolute
+    /// threshold and `ceil(signers * threshold_percentage / 100)`.
+    pub fn effective_threshold(&self) -> u32 {
+        match self.threshold_percentage {
+            Some(pct) => self.threshold.max((self.signers.len() * pct).div_ceil(100)),
+            None => self.threshold,
+        }
+    }
 }
 
 #[contracttype]
@@ -47,12 +148,16 @@ pub struct MultiSigOperation {
     pub account_id: u64,
     pub op_type: OperationType,
     pub description: String,
+    pub payload: OperationPayload,
     pub proposer: Address,
     pub signatures: Vec<Address>,
     pub nonce: u64,
     pub created_at: u64,
     pub expires_at: u64,
     pub status: OperationStatus,
+    // 32-byte hash of the off-chain document the operation refers to; fixed
+    // at proposal time so signers can check which version they approve
+    pub content_hash: Option<Bytes>,
 }
 
 #[contracttype]
@@ -62,4 +167,234 @@ pub struct OperationPolicy {
     pub require_all_signers: bool,
     pub timeout_seconds: u64,
     pub require_owner_signature: bool,
+    /// Largest payload amount approvable under `min_signatures`; above it all
+    /// signers must sign. 0 means no cap.
+    pub max_amount: i128,
+    /// Shortest timeout allowed for this operation type; 0 means `TIMEOUT_24H`.
+    pub min_timeout: u64,
+    /// Longest timeout allowed for this operation type; 0 means `TIMEOUT_48H`.
+    pub max_timeout: u64,
+}
+
+impl OperationPolicy {
+    pub fn timeout_bounds(&self) -> (u64, u64) {
+        let min = if self.min_timeout == 0 {
+            TIMEOUT_24H
+        } else {
+            self.min_timeout
+        };
+        let max = if self.max_timeout == 0 {
+            TIMEOUT_48H
+        } else {
+            self.max_timeout
+        };
+        (min, max)
+    }
+
+    pub fn clamp_timeout(&self, timeout_seconds: u64) -> u64 {
+        let (min, max) = self.timeout_bounds();
+        timeout_seconds.clamp(min, max)
+    }
+}
+
+// The `Stored*` structs below keep the original layouts of the structs above
+// so records written before the later fields existed still decode. Their
+// fields must not change; the later fields are stored under their own keys.
+
+/// Original `MultiSigAccount` layout, stored under `DataKey::MultiSigAccount`
+#[contracttype]
+#[derive(Clone)]
+pub struct StoredMultiSigAccount {
+    pub id: u64,
+    pub owner: Address,
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+    pub status: AccountStatus,
+    pub nonce: u64,
+}
+
+/// Weighting of an account's signers, stored under `DataKey::AccountWeighting`
+#[contracttype]
+#[derive(Clone)]
+pub struct AccountWeighting {
+    pub signer_weights: Map<Address, u32>,
+    pub threshold_percentage: Option<u32>,
+}
+
+/// Original `MultiSigOperation` layout, stored under `DataKey::MultiSigOperation`
+#[contracttype]
+#[derive(Clone)]
+pub struct StoredMultiSigOperation {
+    pub id: u64,
+    pub account_id: u64,
+    pub op_type: OperationType,
+    pub description: String,
+    pub proposer: Address,
+    pub signatures: Vec<Address>,
+    pub nonce: u64,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub status: OperationStatus,
+}
+
+/// Effect and document hash of an operation, stored under
+/// `DataKey::OperationContent`
+#[contracttype]
+#[derive(Clone)]
+pub struct OperationContent {
+    pub payload: OperationPayload,
+    pub content_hash: Option<Bytes>,
+}
+
+/// Original `OperationPolicy` layout, stored under `DataKey::OperationPolicy`
+#[contracttype]
+#[derive(Clone)]
+pub struct StoredOperationPolicy {
+    pub min_signatures: u32,
+    pub require_all_signers: bool,
+    pub timeout_seconds: u64,
+    pub require_owner_signature: bool,
+}
+
+/// Amount cap and timeout bounds of a policy, stored under
+/// `DataKey::PolicyLimits`
+#[contracttype]
+#[derive(Clone)]
+pub struct PolicyLimits {
+    pub max_amount: i128,
+    pub min_timeout: u64,
+    pub max_timeout: u64,
+}
+
+impl MultiSigAccount {
+    pub fn from_parts(
+        env: &Env,
+        stored: StoredMultiSigAccount,
+        weighting: Option<AccountWeighting>,
+    ) -> Self {
+        let weighting = weighting.unwrap_or(AccountWeighting {
+            signer_weights: Map::new(env),
+            threshold_percentage: None,
+        });
+        Self {
+            id: stored.id,
+            owner: stored.owner,
+            signers: stored.signers,
+            threshold: stored.threshold,
+            status: stored.status,
+            nonce: stored.nonce,
+            signer_weights: weighting.signer_weights,
+            threshold_percentage: weighting.threshold_percentage,
+        }
+    }
+
+    pub fn into_parts(self) -> (StoredMultiSigAccount, AccountWeighting) {
+        let stored = StoredMultiSigAccount {
+            id: self.id,
+            owner: self.owner,
+            signers: self.signers,
+            threshold: self.threshold,
+            status: self.status,
+            nonce: self.nonce,
+        };
+        let weighting = AccountWeighting {
+            signer_weights: self.signer_weights,
+            threshold_percentage: self.threshold_percentage,
+        };
+        (stored, weighting)
+    }
+}
+
+impl MultiSigOperation {
+    pub fn from_parts(stored: StoredMultiSigOperation, content: Option<OperationContent>) -> Self {
+        let content = content.unwrap_or(OperationContent {
+            payload: OperationPayload::None,
+            content_hash: None,
+        });
+        Self {
+            id: stored.id,
+            account_id: stored.account_id,
+            op_type: stored.op_type,
+            description: stored.description,
+            payload: content.payload,
+            proposer: stored.proposer,
+            signatures: stored.signatures,
+            nonce: stored.nonce,
+            created_at: stored.created_at,
+            expires_at: stored.expires_at,
+            status: stored.status,
+            content_hash: content.content_hash,
+        }
+    }
+
+    pub fn into_parts(self) -> (StoredMultiSigOperation, OperationContent) {
+        let stored = StoredMultiSigOperation {
+            id: self.id,
+            account_id: self.account_id,
+            op_type: self.op_type,
+            description: self.description,
+            proposer: self.proposer,
+            signatures: self.signatures,
+            nonce: self.nonce,
+            created_at: self.created_at,
+            expires_at: self.expires_at,
+            status: self.status,
+        };
+        let content = OperationContent {
+            payload: self.payload,
+            content_hash: self.content_hash,
+        };
+        (stored, content)
+    }
+}
+
+impl OperationPolicy {
+    pub fn from_parts(stored: StoredOperationPolicy, limits: Option<PolicyLimits>) -> Self {
+        // 0 means no cap and the default timeout bounds
+        let limits = limits.unwrap_or(PolicyLimits {
+            max_amount: 0,
+            min_timeout: 0,
+            max_timeout: 0,
+        });
+        Self {
+            min_signatures: stored.min_signatures,
+            require_all_signers: stored.require_all_signers,
+            timeout_seconds: stored.timeout_seconds,
+            require_owner_signature: stored.require_owner_signature,
+            max_amount: limits.max_amount,
+            min_timeout: limits.min_timeout,
+            max_timeout: limits.max_timeout,
+        }
+    }
+
+    pub fn into_parts(self) -> (StoredOperationPolicy, PolicyLimits) {
+        let stored = StoredOperationPolicy {
+            min_signatures: self.min_signatures,
+            require_all_signers: self.require_all_signers,
+            timeout_seconds: self.timeout_seconds,
+            require_owner_signature: self.require_owner_signature,
+        };
+        let limits = PolicyLimits {
+            max_amount: self.max_amount,
+            min_timeout: self.min_timeout,
+            max_timeout: self.max_timeout,
+        };
+        (stored, limits)
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountOwnershipTransferredEvent {
+    pub account_id: u64,
+    pub previous_owner: Address,
+    pub new_owner: Address,
+}
+
+/// Temporary delegation of a signer's signing authority.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignerProxy {
+    pub proxy: Address,
+    pub expires_at: u64,
 }
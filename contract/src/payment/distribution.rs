@@ -10,6 +10,7 @@ use crate::payment::types::{
     DistributionExecutedEvent, DistributionFailedEvent, DistributionRule, DistributionStatus,
     PaymentPool, PaymentPoolCreatedEvent, PoolCancelledEvent, Recipient, RecipientAddedEvent,
 };
+use crate::utils::reentrancy::guarded_transfer;
 use soroban_sdk::{contracterror, Address, Env, String, Vec};
 
 /// Error types for payment distribution operations
@@ -291,8 +292,13 @@ pub fn execute_distribution(
         }
 
         if let Some(token_addr) = &pool.token {
-            let token_client = soroban_sdk::token::Client::new(env, token_addr);
-            token_client.transfer(&env.current_contract_address(), &recipient.address, &amount);
+            guarded_transfer(
+                env,
+                token_addr,
+                &env.current_contract_address(),
+                &recipient.address,
+                amount,
+            );
         }
         // TODO: native XLM transfer
 
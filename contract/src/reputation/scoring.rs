@@ -121,14 +121,16 @@ pub fn get_decayed_profile(
 /// Compute governance weight: role_weight + integer_sqrt(decayed_score).
 /// Falls back to role_weight only if no reputation profile exists.
 pub fn compute_governance_weight(env: &Env, address: &Address, guild_id: u64, role: &Role) -> i128 {
-    let base = role_weight(role);
+    role_weight(role) + reputation_bonus(env, address, guild_id)
+}
 
-    let reputation_bonus = match get_decayed_profile(env, address, guild_id) {
+/// Reputation share of governance weight: integer_sqrt(decayed_score), or 0
+/// without a profile.
+pub fn reputation_bonus(env: &Env, address: &Address, guild_id: u64) -> i128 {
+    match get_decayed_profile(env, address, guild_id) {
         Some(profile) => integer_sqrt(profile.decayed_score) as i128,
         None => 0,
-    };
-
-    base + reputation_bonus
+    }
 }
 
 /// Get the global (cross-guild) reputation for a user.
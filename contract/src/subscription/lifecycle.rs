@@ -16,7 +16,8 @@ use crate::subscription::types::{
     SubscriptionCancelledEvent, SubscriptionChange, SubscriptionCreatedEvent, SubscriptionError,
     SubscriptionPlan, SubscriptionStatus, TierChangedEvent,
 };
-use soroban_sdk::{token, Address, Env, String, Vec};
+use crate::utils::reentrancy::guarded_transfer;
+use soroban_sdk::{Address, Env, String, Vec};
 
 /// Create a new subscription plan
 ///
@@ -289,8 +290,13 @@ fn execute_payment(
 
     if let Some(token_addr) = token {
         // Transfer custom token
-        let token_client = token::Client::new(env, token_addr);
-        token_client.transfer(from, &env.current_contract_address(), &amount);
+        guarded_transfer(
+            env,
+            token_addr,
+            from,
+            &env.current_contract_address(),
+            amount,
+        );
     } else {
         // For native XLM, we would need additional handling
         // In a real implementation, this would check and transfer XLM
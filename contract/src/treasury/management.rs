@@ -1,47 +1,115 @@
 use crate::events::emit::emit_event;
 use crate::events::topics::{
     ACT_APPROVED, ACT_CREATED, ACT_EXECUTED, ACT_FUNDED, ACT_GRANTED, ACT_PAUSED, ACT_PROPOSED,
-    ACT_RESUMED, ACT_UPDATED, MOD_TREASURY,
+    ACT_RESUMED, ACT_REVOKED, ACT_SPENT, ACT_TRANSFERRED, ACT_UPDATED, MOD_TREASURY,
 };
-use soroban_sdk::{token::Client as TokenClient, Address, Env, String, Vec};
+use crate::utils::reentrancy::{ensure_not_entered, guarded_transfer};
+use soroban_sdk::{Address, Env, String, Vec};
 
 use crate::analytics::storage::store_snapshot;
 use crate::analytics::types::TreasurySnapshot;
+use crate::guild::membership::has_permission;
+use crate::guild::types::Role;
 
 use crate::treasury::multisig::{
-    add_approval, assert_signer, ensure_is_signer, expire_if_needed, required_approvals_for_tx,
-    validate_threshold, TX_EXPIRY_SECONDS,
+    add_approval, assert_signer, base_required_approvals, co_guild_for_approver, co_guilds_covered,
+    ensure_guild_role, ensure_is_signer, expire_if_needed, is_treasury_signer, majority_of,
+    required_approvals_for_tx, role_signer_count, threshold_error, valid_approvals,
+    validate_threshold, MAX_TX_EXPIRY_SECONDS, MIN_TX_EXPIRY_SECONDS, TX_EXPIRY_SECONDS,
 };
 use crate::treasury::storage::{
-    get_allowance, get_budget, get_next_treasury_id, get_next_tx_id, get_treasury,
-    get_treasury_transactions, store_allowance, store_budget, store_transaction, store_treasury,
+    add_depositor_total, get_allowance, get_approval_guilds,
+    get_approval_tiers as load_approval_tiers, get_budget, get_co_guilds, get_depositor_totals,
+    get_guild_treasuries, get_next_treasury_id, get_next_tx_id,
+    get_payee_allowlist as load_payee_allowlist, get_reserve_floor as load_reserve_floor,
+    get_treasury, get_treasury_transactions, get_treasury_tx_ids, get_tx_timeout,
+    get_withdrawal_fee, index_guild_treasury, is_operation_paused, set_approval_guilds,
+    set_approval_tiers as store_approval_tiers, set_co_guilds as store_co_guilds,
+    set_operation_paused, set_payee_allowlist as store_payee_allowlist,
+    set_reserve_floor as store_reserve_floor, set_tx_timeout, set_withdrawal_fee, store_allowance,
+    store_budget, store_transaction, store_treasury,
 };
 use crate::treasury::types::{
-    Allowance, Budget, DepositEvent, EmergencyPauseEvent, Transaction, TransactionApprovedEvent,
-    TransactionExecutedEvent, TransactionStatus, TransactionType, Treasury, TreasuryError,
-    TreasuryInitializedEvent, WithdrawalProposedEvent,
+    Allowance, AllowanceSpentEvent, ApprovalRevokedEvent, ApprovalTiersUpdatedEvent, Budget,
+    DepositEvent, EmergencyPauseEvent, OperationPauseEvent, PayeeAllowlistUpdatedEvent,
+    ReserveFloorUpdatedEvent, Transaction, TransactionApprovedEvent, TransactionExecutedEvent,
+    TransactionStatus, TransactionTimeoutUpdatedEvent, TransactionType, Treasury,
+    TreasuryConfigUpdatedEvent, TreasuryError, TreasuryInitializedEvent, TreasuryOperation,
+    TreasuryTransferEvent, WithdrawalCategory, WithdrawalFee, WithdrawalFeeUpdatedEvent,
+    WithdrawalProposedEvent,
 };
+use crate::DataKey;
 
+/// Highest withdrawal fee the contract admin may configure (10%)
+pub const MAX_WITHDRAWAL_FEE_BPS: u32 = 1_000;
+
+/// Longest memo a depositor may attach to a deposit
+pub const MAX_DEPOSIT_MEMO_LEN: u32 = 256;
+
+/// `a + b` for balance accounting, reverting rather than wrapping
+fn add_amount(a: i128, b: i128) -> i128 {
+    a.checked_add(b)
+        .unwrap_or_else(|| panic!("arithmetic overflow"))
+}
+
+/// `a - b` for balance accounting, reverting rather than wrapping
+fn sub_amount(a: i128, b: i128) -> i128 {
+    a.checked_sub(b)
+        .unwrap_or_else(|| panic!("arithmetic overflow"))
+}
+
+/// Create a treasury for a guild.
+///
+/// With `role_based_signers` the signer set is every guild member at Admin
+/// level or above, resolved on each approval; only the first entry of
+/// `signers` is used, as the owner. Otherwise `signers` is the fixed set.
+///
+/// With `auto_threshold` the given `approval_threshold` is ignored and a
+/// majority of signers is required instead; in role-based mode that majority
+/// follows the admin count as admins join and leave.
 pub fn initialize_treasury(
     env: &Env,
     guild_id: u64,
     signers: Vec<Address>,
     approval_threshold: u32,
+    role_based_signers: bool,
+    auto_threshold: bool,
+    co_guild_ids: Vec<u64>,
 ) -> u64 {
     // First signer is the owner
     let owner = signers.get(0).expect("at least one signer required");
     owner.require_auth();
 
     let mut unique_signers = Vec::new(env);
-    for addr in signers.iter() {
-        if !unique_signers.iter().any(|a| a == addr.clone()) {
-            unique_signers.push_back(addr);
+    let signers_len = if role_based_signers {
+        if !has_permission(env, guild_id, owner.clone(), Role::Admin) {
+            panic!("owner must be a guild admin");
         }
-    }
-
-    let signers_len = unique_signers.len() as u32;
+        role_signer_count(env, guild_id)
+    } else {
+        for addr in signers.iter() {
+            if !unique_signers.iter().any(|a| a == addr.clone()) {
+                unique_signers.push_back(addr);
+            }
+        }
+        unique_signers.len() as u32
+    };
+    let approval_threshold = if auto_threshold {
+        majority_of(signers_len)
+    } else {
+        approval_threshold
+    };
     validate_threshold(signers_len, approval_threshold);
 
+    for (idx, co_guild_id) in co_guild_ids.iter().enumerate() {
+        if co_guild_id == guild_id
+            || crate::guild::storage::get_guild(env, co_guild_id).is_none()
+            || co_guild_ids.first_index_of(co_guild_id) != Some(idx as u32)
+        {
+            panic!("invalid co-guild");
+        }
+    }
+
     let id = get_next_treasury_id(env);
 
     // Default high value threshold to a reasonable amount (1000 XLM)
@@ -52,7 +120,9 @@ pub fn initialize_treasury(
         guild_id,
         owner: owner.clone(),
         signers: unique_signers,
+        role_based_signers,
         approval_threshold,
+        auto_threshold,
         high_value_threshold,
         balance_xlm: 0,
         token_balances: soroban_sdk::Map::new(env),
@@ -62,6 +132,10 @@ pub fn initialize_treasury(
     };
 
     store_treasury(env, &treasury);
+    index_guild_treasury(env, guild_id, id);
+    if !co_guild_ids.is_empty() {
+        store_co_guilds(env, id, &co_guild_ids);
+    }
 
     let event = TreasuryInitializedEvent {
         treasury_id: id,
@@ -79,35 +153,52 @@ pub fn deposit(
     depositor: Address,
     amount: i128,
     token: Option<Address>,
+    memo: Option<String>,
+    on_behalf_of: Option<Address>,
 ) -> bool {
     depositor.require_auth();
+    ensure_not_entered(env);
     if amount <= 0 {
         panic!("amount must be positive");
     }
+    if let Some(ref m) = memo {
+        if m.len() > MAX_DEPOSIT_MEMO_LEN {
+            panic!("memo too long");
+        }
+    }
 
     let mut treasury = get_treasury(env, treasury_id).expect("treasury not found");
     if treasury.paused {
         panic!("treasury is paused");
     }
+    ensure_operation_open(env, treasury_id, TreasuryOperation::Deposit);
 
     match token {
         Some(ref token_addr) => {
-            let client = TokenClient::new(env, token_addr);
-            client.transfer(&depositor, &env.current_contract_address(), &amount);
+            guarded_transfer(
+                env,
+                token_addr,
+                &depositor,
+                &env.current_contract_address(),
+                amount,
+            );
 
             let mut balances = treasury.token_balances.clone();
             let current = balances.get(token_addr.clone()).unwrap_or(0i128);
-            balances.set(token_addr.clone(), current + amount);
+            balances.set(token_addr.clone(), add_amount(current, amount));
             treasury.token_balances = balances;
         }
         None => {
             // For native XLM we assume a wrapped token or external transfer; we only track accounting here.
-            treasury.balance_xlm += amount;
+            treasury.balance_xlm = add_amount(treasury.balance_xlm, amount);
         }
     }
 
-    treasury.total_deposits += amount;
+    treasury.total_deposits = add_amount(treasury.total_deposits, amount);
     store_treasury(env, &treasury);
+    // a sponsor pays, but the named contributor is credited
+    let credited = on_behalf_of.clone().unwrap_or_else(|| depositor.clone());
+    add_depositor_total(env, treasury_id, &credited, &token, amount);
 
     let tx_id = get_next_tx_id(env);
     let now = env.ledger().timestamp();
@@ -123,7 +214,12 @@ pub fn deposit(
         status: TransactionStatus::Executed,
         created_at: now,
         expires_at: now,
+        category: WithdrawalCategory::Other,
         reason: String::from_str(env, "deposit"),
+        fee: 0,
+        net_amount: amount,
+        memo,
+        on_behalf_of: on_behalf_of.clone(),
     };
     store_transaction(env, &tx);
 
@@ -135,12 +231,74 @@ pub fn deposit(
         from: depositor,
         amount,
         token,
+        on_behalf_of,
+    };
+    emit_event(env, MOD_TREASURY, ACT_FUNDED, event);
+
+    true
+}
+
+/// Credit tokens already held by the contract to a treasury (e.g. a forfeited
+/// governance bond). No transfer takes place; only treasury accounting is updated.
+pub fn credit_held_funds(
+    env: &Env,
+    treasury_id: u64,
+    from: Address,
+    amount: i128,
+    token: Address,
+    reason: String,
+) -> bool {
+    if amount <= 0 {
+        panic!("amount must be positive");
+    }
+
+    let mut treasury = get_treasury(env, treasury_id).expect("treasury not found");
+
+    let mut balances = treasury.token_balances.clone();
+    let current = balances.get(token.clone()).unwrap_or(0i128);
+    balances.set(token.clone(), add_amount(current, amount));
+    treasury.token_balances = balances;
+    treasury.total_deposits = add_amount(treasury.total_deposits, amount);
+    store_treasury(env, &treasury);
+
+    let tx_id = get_next_tx_id(env);
+    let now = env.ledger().timestamp();
+    let tx = Transaction {
+        id: tx_id,
+        treasury_id,
+        tx_type: TransactionType::Deposit,
+        amount,
+        token: Some(token.clone()),
+        recipient: Some(env.current_contract_address()),
+        proposer: from.clone(),
+        approvals: Vec::new(env),
+        status: TransactionStatus::Executed,
+        created_at: now,
+        expires_at: now,
+        category: WithdrawalCategory::Other,
+        reason,
+        fee: 0,
+        net_amount: amount,
+        memo: None,
+        on_behalf_of: None,
+    };
+    store_transaction(env, &tx);
+
+    record_snapshot(env, &treasury);
+
+    let event = DepositEvent {
+        treasury_id,
+        from,
+        amount,
+        token: Some(token),
+        on_behalf_of: None,
     };
     emit_event(env, MOD_TREASURY, ACT_FUNDED, event);
 
     true
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn propose_withdrawal(
     env: &Env,
     treasury_id: u64,
@@ -148,8 +306,118 @@ pub fn propose_withdrawal(
     recipient: Address,
     amount: i128,
     token: Option<Address>,
+    category: WithdrawalCategory,
+    reason: String,
+) -> u64 {
+    let treasury = load_for_withdrawal(env, treasury_id, amount);
+    assert_signer(env, &treasury, &proposer);
+    ensure_guild_role(env, &treasury, &proposer, Role::Member);
+    record_withdrawal(
+        env,
+        treasury_id,
+        proposer,
+        recipient,
+        amount,
+        token,
+        category,
+        reason,
+    )
+}
+
+/// Same as [`propose_withdrawal`] for callers that have already authorized
+/// `proposer` earlier in the invocation (e.g. a multisig operation executor).
+#[allow(clippy::too_many_arguments)]
+pub fn propose_withdrawal_authorized(
+    env: &Env,
+    treasury_id: u64,
+    proposer: Address,
+    recipient: Address,
+    amount: i128,
+    token: Option<Address>,
+    category: WithdrawalCategory,
+    reason: String,
+) -> u64 {
+    let treasury = load_for_withdrawal(env, treasury_id, amount);
+    ensure_is_signer(env, &treasury, &proposer);
+    ensure_guild_role(env, &treasury, &proposer, Role::Member);
+    record_withdrawal(
+        env,
+        treasury_id,
+        proposer,
+        recipient,
+        amount,
+        token,
+        category,
+        reason,
+    )
+}
+
+/// Propose moving funds from one treasury to another held by this contract.
+///
+/// Once approved and executed the source balance is debited and the
+/// destination credited in storage; no tokens leave the contract.
+#[allow(clippy::too_many_arguments)]
+pub fn propose_treasury_transfer(
+    env: &Env,
+    from_treasury: u64,
+    to_treasury: u64,
+    amount: i128,
+    token: Option<Address>,
+    proposer: Address,
+    category: WithdrawalCategory,
     reason: String,
 ) -> u64 {
+    let treasury = load_for_withdrawal(env, from_treasury, amount);
+    assert_signer(env, &treasury, &proposer);
+    ensure_guild_role(env, &treasury, &proposer, Role::Member);
+
+    if from_treasury == to_treasury {
+        panic!("cannot transfer to the same treasury");
+    }
+    let destination = get_treasury(env, to_treasury).expect("destination treasury not found");
+    if destination.paused {
+        panic!("destination treasury is paused");
+    }
+
+    let tx_id = get_next_tx_id(env);
+    let now = env.ledger().timestamp();
+    let mut approvals = Vec::new(env);
+    approvals.push_back(proposer.clone());
+
+    let tx = Transaction {
+        id: tx_id,
+        treasury_id: from_treasury,
+        tx_type: TransactionType::TreasuryTransfer(to_treasury),
+        amount,
+        token: token.clone(),
+        recipient: None,
+        proposer,
+        approvals,
+        status: TransactionStatus::Pending,
+        created_at: now,
+        expires_at: now + get_transaction_timeout(env, from_treasury),
+        category,
+        reason,
+        fee: 0,
+        net_amount: amount,
+        memo: None,
+        on_behalf_of: None,
+    };
+    store_transaction(env, &tx);
+
+    let event = TreasuryTransferEvent {
+        tx_id,
+        from_treasury_id: from_treasury,
+        to_treasury_id: to_treasury,
+        amount,
+        token,
+    };
+    emit_event(env, MOD_TREASURY, ACT_PROPOSED, event);
+
+    tx_id
+}
+
+fn load_for_withdrawal(env: &Env, treasury_id: u64, amount: i128) -> Treasury {
     if amount <= 0 {
         panic!("amount must be positive");
     }
@@ -158,8 +426,24 @@ pub fn propose_withdrawal(
     if treasury.paused {
         panic!("treasury is paused");
     }
+    ensure_operation_open(env, treasury_id, TreasuryOperation::Withdrawal);
+    treasury
+}
 
-    assert_signer(env, &treasury, &proposer);
+#[allow(clippy::too_many_arguments)]
+fn record_withdrawal(
+    env: &Env,
+    treasury_id: u64,
+    proposer: Address,
+    recipient: Address,
+    amount: i128,
+    token: Option<Address>,
+    category: WithdrawalCategory,
+    reason: String,
+) -> u64 {
+    if !is_payee_allowed(env, treasury_id, recipient.clone()) {
+        panic!("recipient not on payee allowlist");
+    }
 
     let tx_id = get_next_tx_id(env);
     let now = env.ledger().timestamp();
@@ -177,8 +461,13 @@ pub fn propose_withdrawal(
         approvals,
         status: TransactionStatus::Pending,
         created_at: now,
-        expires_at: now + TX_EXPIRY_SECONDS,
+        expires_at: now + get_transaction_timeout(env, treasury_id),
+        category,
         reason,
+        fee: 0,
+        net_amount: amount,
+        memo: None,
+        on_behalf_of: None,
     };
     store_transaction(env, &tx);
 
@@ -210,11 +499,21 @@ pub fn approve_transaction(env: &Env, tx_id: u64, approver: Address) -> bool {
         panic!("transaction not approvable");
     }
 
-    ensure_is_signer(&treasury, &approver);
+    // co-guild admins may approve without being signers
+    let co_guild = co_guild_for_approver(env, treasury.id, &tx, &approver);
+    if co_guild.is_none() {
+        ensure_is_signer(env, &treasury, &approver);
+    }
     add_approval(&mut tx, &approver);
+    if let Some(co_guild_id) = co_guild {
+        let mut represented = get_approval_guilds(env, tx_id);
+        represented.set(approver.clone(), co_guild_id);
+        set_approval_guilds(env, tx_id, &represented);
+    }
 
-    let required = required_approvals_for_tx(&treasury, &tx);
-    if (tx.approvals.len() as u32) >= required {
+    let required = required_approvals_for_tx(env, &treasury, &tx);
+    if valid_approvals(env, &treasury, &tx) >= required && co_guilds_covered(env, treasury.id, &tx)
+    {
         tx.status = TransactionStatus::Approved;
     }
 
@@ -230,10 +529,56 @@ pub fn approve_transaction(env: &Env, tx_id: u64, approver: Address) -> bool {
     true
 }
 
+/// Withdraw `signer`'s approval from a transaction that has not executed.
+/// A transaction that falls below its threshold goes back to `Pending`.
+pub fn revoke_approval(env: &Env, tx_id: u64, signer: Address) -> bool {
+    signer.require_auth();
+
+    let mut tx = crate::treasury::storage::get_transaction(env, tx_id).expect("tx not found");
+    let treasury = get_treasury(env, tx.treasury_id).expect("treasury not found");
+
+    let now = env.ledger().timestamp();
+    expire_if_needed(&mut tx, now);
+    if !matches!(
+        tx.status,
+        TransactionStatus::Pending | TransactionStatus::Approved
+    ) {
+        panic!("transaction not revocable");
+    }
+
+    let idx = tx
+        .approvals
+        .first_index_of(&signer)
+        .unwrap_or_else(|| panic!("no approval to revoke"));
+    tx.approvals.remove(idx);
+    let mut represented = get_approval_guilds(env, tx_id);
+    if represented.contains_key(signer.clone()) {
+        represented.remove(signer.clone());
+        set_approval_guilds(env, tx_id, &represented);
+    }
+
+    if valid_approvals(env, &treasury, &tx) < required_approvals_for_tx(env, &treasury, &tx)
+        || !co_guilds_covered(env, treasury.id, &tx)
+    {
+        tx.status = TransactionStatus::Pending;
+    }
+
+    store_transaction(env, &tx);
+
+    let event = ApprovalRevokedEvent {
+        treasury_id: tx.treasury_id,
+        tx_id,
+        signer,
+    };
+    emit_event(env, MOD_TREASURY, ACT_REVOKED, event);
+
+    true
+}
+
 fn enforce_budget(
     env: &Env,
     treasury_id: u64,
-    category: &String,
+    category: &WithdrawalCategory,
     amount: i128,
 ) -> Result<(), TreasuryError> {
     if amount <= 0 {
@@ -255,11 +600,13 @@ fn enforce_budget(
         budget.spent_amount = 0;
     }
 
-    if budget.allocated_amount > 0 && budget.spent_amount + amount > budget.allocated_amount {
+    if budget.allocated_amount > 0
+        && add_amount(budget.spent_amount, amount) > budget.allocated_amount
+    {
         return Err(TreasuryError::BudgetExceeded);
     }
 
-    budget.spent_amount += amount;
+    budget.spent_amount = add_amount(budget.spent_amount, amount);
     store_budget(env, &budget);
     Ok(())
 }
@@ -281,7 +628,7 @@ fn enforce_allowance(
         if allowance.remaining_amount < amount {
             return Err(TreasuryError::AllowanceExceeded);
         }
-        allowance.remaining_amount -= amount;
+        allowance.remaining_amount = sub_amount(allowance.remaining_amount, amount);
         store_allowance(env, &allowance);
         return Ok(());
     }
@@ -300,6 +647,7 @@ fn enforce_allowance(
 
 pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
     executor.require_auth();
+    ensure_not_entered(env);
 
     let mut tx = crate::treasury::storage::get_transaction(env, tx_id).expect("tx not found");
     let mut treasury = get_treasury(env, tx.treasury_id).expect("treasury not found");
@@ -317,34 +665,45 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
     if treasury.paused && !matches!(tx.status, TransactionStatus::Approved) {
         panic!("treasury is paused");
     }
+    // a scoped withdrawal pause also holds back approved outflows
+    if !matches!(tx.tx_type, TransactionType::AllowanceGrant) {
+        ensure_operation_open(env, tx.treasury_id, TreasuryOperation::Withdrawal);
+    }
 
-    ensure_is_signer(&treasury, &executor);
+    ensure_is_signer(env, &treasury, &executor);
 
-    if !matches!(tx.status, TransactionStatus::Approved) {
+    // approvals from signers who have since lost their role no longer count
+    if !matches!(tx.status, TransactionStatus::Approved)
+        || valid_approvals(env, &treasury, &tx) < required_approvals_for_tx(env, &treasury, &tx)
+    {
         panic!("transaction must be approved");
     }
+    // a co-guild whose only approver has since lost their admin role is uncovered
+    if !co_guilds_covered(env, tx.treasury_id, &tx) {
+        panic!("co-guild approval missing");
+    }
 
     match tx.tx_type {
         TransactionType::Withdrawal
         | TransactionType::BountyFunding
         | TransactionType::MilestonePayment => {
             let recipient = tx.recipient.clone().expect("recipient required");
-
-            // budget category name from tx_type
-            let category = match tx.tx_type {
-                TransactionType::Withdrawal => String::from_str(env, "withdrawal"),
-                TransactionType::BountyFunding => String::from_str(env, "bounty"),
-                TransactionType::MilestonePayment => String::from_str(env, "milestone"),
-                _ => String::from_str(env, "other"),
-            };
+            // the allowlist may have been tightened since the proposal
+            if matches!(tx.tx_type, TransactionType::Withdrawal)
+                && !is_payee_allowed(env, tx.treasury_id, recipient.clone())
+            {
+                panic!("recipient not on payee allowlist");
+            }
 
             // Convert Result to panic with expected error message
             // This creates a proper contract error (all panics in Soroban become contract errors)
             // while maintaining the expected error message for test compatibility
-            enforce_budget(env, tx.treasury_id, &category, tx.amount).unwrap_or_else(|e| match e {
-                TreasuryError::BudgetExceeded => panic!("budget exceeded"),
-                TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
-            });
+            enforce_budget(env, tx.treasury_id, &tx.category, tx.amount).unwrap_or_else(
+                |e| match e {
+                    TreasuryError::BudgetExceeded => panic!("budget exceeded"),
+                    TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
+                },
+            );
 
             let op_type = match tx.tx_type {
                 TransactionType::Withdrawal => crate::allowance::AllowanceOperation::Withdrawal,
@@ -370,30 +729,128 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
                 TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
             });
 
+            // budget, allowance and balance checks all apply to the gross amount
+            let fee = match tx.tx_type {
+                TransactionType::Withdrawal => withdrawal_fee(env, tx.amount),
+                _ => None,
+            };
+            tx.fee = fee.as_ref().map(|(cut, _)| *cut).unwrap_or(0);
+            tx.net_amount = tx.amount - tx.fee;
+
             match tx.token {
                 Some(ref token_addr) => {
-                    let client = TokenClient::new(env, token_addr);
+                    let mut balances = treasury.token_balances.clone();
+                    let current = balances.get(token_addr.clone()).unwrap_or(0i128);
+                    if current < tx.amount {
+                        panic!("insufficient treasury balance");
+                    }
+                    balances.set(token_addr.clone(), sub_amount(current, tx.amount));
+                    treasury.token_balances = balances;
+
+                    guarded_transfer(
+                        env,
+                        token_addr,
+                        &env.current_contract_address(),
+                        &recipient,
+                        tx.net_amount,
+                    );
+                    if let Some((cut, fee_recipient)) = fee {
+                        guarded_transfer(
+                            env,
+                            token_addr,
+                            &env.current_contract_address(),
+                            &fee_recipient,
+                            cut,
+                        );
+                    }
+                }
+                None => {
+                    if treasury.balance_xlm < tx.amount {
+                        panic!("insufficient XLM balance");
+                    }
+                    treasury.balance_xlm = sub_amount(treasury.balance_xlm, tx.amount);
+                }
+            }
+            ensure_reserve_kept(env, &treasury, &tx.token);
+
+            treasury.total_withdrawals = add_amount(treasury.total_withdrawals, tx.amount);
+            store_treasury(env, &treasury);
+        }
+        TransactionType::TreasuryTransfer(to_treasury) => {
+            enforce_budget(env, tx.treasury_id, &tx.category, tx.amount).unwrap_or_else(
+                |e| match e {
+                    TreasuryError::BudgetExceeded => panic!("budget exceeded"),
+                    TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
+                },
+            );
+
+            let mut destination =
+                get_treasury(env, to_treasury).expect("destination treasury not found");
+            if destination.paused {
+                panic!("destination treasury is paused");
+            }
 
+            match tx.token {
+                Some(ref token_addr) => {
                     let mut balances = treasury.token_balances.clone();
                     let current = balances.get(token_addr.clone()).unwrap_or(0i128);
                     if current < tx.amount {
                         panic!("insufficient treasury balance");
                     }
-                    balances.set(token_addr.clone(), current - tx.amount);
+                    balances.set(token_addr.clone(), sub_amount(current, tx.amount));
                     treasury.token_balances = balances;
 
-                    client.transfer(&env.current_contract_address(), &recipient, &tx.amount);
+                    let mut dest_balances = destination.token_balances.clone();
+                    let dest_current = dest_balances.get(token_addr.clone()).unwrap_or(0i128);
+                    dest_balances.set(token_addr.clone(), add_amount(dest_current, tx.amount));
+                    destination.token_balances = dest_balances;
                 }
                 None => {
                     if treasury.balance_xlm < tx.amount {
                         panic!("insufficient XLM balance");
                     }
-                    treasury.balance_xlm -= tx.amount;
+                    treasury.balance_xlm = sub_amount(treasury.balance_xlm, tx.amount);
+                    destination.balance_xlm = add_amount(destination.balance_xlm, tx.amount);
                 }
             }
+            ensure_reserve_kept(env, &treasury, &tx.token);
 
-            treasury.total_withdrawals += tx.amount;
+            treasury.total_withdrawals = add_amount(treasury.total_withdrawals, tx.amount);
+            destination.total_deposits = add_amount(destination.total_deposits, tx.amount);
             store_treasury(env, &treasury);
+            store_treasury(env, &destination);
+
+            // mirror the incoming leg in the destination's history
+            let incoming = Transaction {
+                id: get_next_tx_id(env),
+                treasury_id: to_treasury,
+                tx_type: TransactionType::Deposit,
+                amount: tx.amount,
+                token: tx.token.clone(),
+                recipient: Some(env.current_contract_address()),
+                proposer: tx.proposer.clone(),
+                approvals: Vec::new(env),
+                status: TransactionStatus::Executed,
+                created_at: now,
+                expires_at: now,
+                category: WithdrawalCategory::Other,
+                reason: tx.reason.clone(),
+                fee: 0,
+                net_amount: tx.amount,
+                memo: None,
+                on_behalf_of: None,
+            };
+            store_transaction(env, &incoming);
+            record_snapshot(env, &destination);
+
+            let event = TreasuryTransferEvent {
+                tx_id,
+                from_treasury_id: tx.treasury_id,
+                to_treasury_id: to_treasury,
+                amount: tx.amount,
+                token: tx.token.clone(),
+            };
+            emit_event(env, MOD_TREASURY, ACT_TRANSFERRED, event);
         }
         TransactionType::Deposit => {
             panic!("cannot execute deposit transaction");
@@ -419,6 +876,50 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
     true
 }
 
+/// Fee owed on a gross withdrawal of `amount` and the address it goes to, or
+/// `None` when no fee is configured or it rounds down to zero.
+fn withdrawal_fee(env: &Env, amount: i128) -> Option<(i128, Address)> {
+    let fee = get_withdrawal_fee(env)?;
+    let cut = amount
+        .checked_mul(fee.bps as i128)
+        .unwrap_or_else(|| panic!("arithmetic overflow"))
+        / 10_000;
+    if cut <= 0 {
+        return None;
+    }
+    Some((cut, fee.recipient))
+}
+
+/// Set the contract-wide fee, in basis points, deducted from executed
+/// withdrawals and paid to `fee_recipient` (contract admin only). Zero
+/// disables the fee.
+pub fn set_withdrawal_fee_bps(env: &Env, bps: u32, fee_recipient: Address, admin: Address) -> bool {
+    admin.require_auth();
+
+    let stored_admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .expect("contract admin not initialized");
+    if stored_admin != admin {
+        panic!("only the contract admin can set the withdrawal fee");
+    }
+    if bps > MAX_WITHDRAWAL_FEE_BPS {
+        panic!("withdrawal fee too high");
+    }
+
+    let fee = WithdrawalFee {
+        bps,
+        recipient: fee_recipient.clone(),
+    };
+    set_withdrawal_fee(env, &fee);
+
+    let event = WithdrawalFeeUpdatedEvent { bps, fee_recipient };
+    emit_event(env, MOD_TREASURY, ACT_UPDATED, event);
+
+    true
+}
+
 pub fn execute_milestone_payment(
     env: &Env,
     treasury_id: u64,
@@ -434,12 +935,16 @@ pub fn execute_milestone_payment(
     if treasury.paused {
         panic!("treasury is paused");
     }
+    ensure_operation_open(env, treasury_id, TreasuryOperation::Withdrawal);
+    if !is_payee_allowed(env, treasury_id, recipient.clone()) {
+        panic!("recipient not on payee allowlist");
+    }
 
-    // Budget enforcement under the "milestone" category
-    let category = String::from_str(env, "milestone");
-    enforce_budget(env, treasury_id, &category, amount).unwrap_or_else(|e| match e {
-        TreasuryError::BudgetExceeded => panic!("budget exceeded"),
-        TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
+    enforce_budget(env, treasury_id, &WithdrawalCategory::Payroll, amount).unwrap_or_else(|e| {
+        match e {
+            TreasuryError::BudgetExceeded => panic!("budget exceeded"),
+            TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
+        }
     });
 
     // Allowance enforcement (if any) keyed by current contract address;
@@ -456,27 +961,32 @@ pub fn execute_milestone_payment(
     // Move funds from treasury to recipient
     match token {
         Some(ref token_addr) => {
-            let client = TokenClient::new(env, token_addr);
-
             let mut balances = treasury.token_balances.clone();
             let current = balances.get(token_addr.clone()).unwrap_or(0i128);
             if current < amount {
                 panic!("insufficient treasury balance");
             }
-            balances.set(token_addr.clone(), current - amount);
+            balances.set(token_addr.clone(), sub_amount(current, amount));
             treasury.token_balances = balances;
 
-            client.transfer(&env.current_contract_address(), &recipient, &amount);
+            guarded_transfer(
+                env,
+                token_addr,
+                &env.current_contract_address(),
+                &recipient,
+                amount,
+            );
         }
         None => {
             if treasury.balance_xlm < amount {
                 panic!("insufficient XLM balance");
             }
-            treasury.balance_xlm -= amount;
+            treasury.balance_xlm = sub_amount(treasury.balance_xlm, amount);
         }
     }
+    ensure_reserve_kept(env, &treasury, &token);
 
-    treasury.total_withdrawals += amount;
+    treasury.total_withdrawals = add_amount(treasury.total_withdrawals, amount);
     store_treasury(env, &treasury);
 
     // Record a MilestonePayment transaction as already executed
@@ -494,7 +1004,12 @@ pub fn execute_milestone_payment(
         status: TransactionStatus::Executed,
         created_at: now,
         expires_at: now,
+        category: WithdrawalCategory::Payroll,
         reason: String::from_str(env, "milestone_payment"),
+        fee: 0,
+        net_amount: amount,
+        memo: None,
+        on_behalf_of: None,
     };
     store_transaction(env, &tx);
 
@@ -504,40 +1019,229 @@ pub fn execute_milestone_payment(
     true
 }
 
-pub fn set_budget(
+/// Pay out a spend approved by a guild governance vote. The vote stands in
+/// for signer approvals, so funds move immediately. Returns `None`, leaving
+/// the treasury untouched, when it or its withdrawals are paused, belongs to another guild,
+/// is jointly controlled with co-guilds, keeps a payee allowlist without the recipient,
+/// cannot cover the amount above its reserve floor or would overrun the category budget.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_governance_spend(
     env: &Env,
+    guild_id: u64,
     treasury_id: u64,
-    caller: Address,
-    category: String,
+    recipient: Address,
     amount: i128,
-    period_seconds: u64,
-) -> bool {
-    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
-    assert_signer(env, &treasury, &caller);
-
-    let now = env.ledger().timestamp();
-    let mut budget = get_budget(env, treasury_id, &category).unwrap_or(Budget {
-        treasury_id,
-        category: category.clone(),
-        allocated_amount: 0,
-        spent_amount: 0,
-        period_seconds,
-        period_start: now,
-    });
+    token: Option<Address>,
+    category: WithdrawalCategory,
+    reason: String,
+) -> Option<u64> {
+    ensure_not_entered(env);
+    if amount <= 0 {
+        return None;
+    }
 
-    if budget.period_seconds != period_seconds {
-        budget.period_seconds = period_seconds;
+    let mut treasury = get_treasury(env, treasury_id)?;
+    // one guild's vote cannot spend a jointly controlled treasury
+    if treasury.guild_id != guild_id
+        || treasury.paused
+        || is_operation_paused(env, treasury_id, &TreasuryOperation::Withdrawal)
+        || !get_co_guilds(env, treasury_id).is_empty()
+        || !is_payee_allowed(env, treasury_id, recipient.clone())
+    {
+        return None;
     }
 
-    if now >= budget.period_start.saturating_add(budget.period_seconds) {
-        budget.period_start = now;
-        budget.spent_amount = 0;
+    let available = match token {
+        Some(ref token_addr) => treasury.token_balances.get(token_addr.clone()).unwrap_or(0),
+        None => treasury.balance_xlm,
+    };
+    // balance is checked before the budget so a failed spend records nothing
+    if available - amount < load_reserve_floor(env, treasury_id, &token).max(0)
+        || enforce_budget(env, treasury_id, &category, amount).is_err()
+    {
+        return None;
     }
 
-    budget.allocated_amount = amount;
-    store_budget(env, &budget);
+    let fee = withdrawal_fee(env, amount);
+    let fee_amount = fee.as_ref().map(|(cut, _)| *cut).unwrap_or(0);
+    match token {
+        Some(ref token_addr) => {
+            let mut balances = treasury.token_balances.clone();
+            balances.set(token_addr.clone(), sub_amount(available, amount));
+            treasury.token_balances = balances;
 
-    let event = crate::treasury::types::BudgetUpdatedEvent {
+            guarded_transfer(
+                env,
+                token_addr,
+                &env.current_contract_address(),
+                &recipient,
+                amount - fee_amount,
+            );
+            if let Some((cut, fee_recipient)) = fee {
+                guarded_transfer(
+                    env,
+                    token_addr,
+                    &env.current_contract_address(),
+                    &fee_recipient,
+                    cut,
+                );
+            }
+        }
+        None => {
+            treasury.balance_xlm = sub_amount(treasury.balance_xlm, amount);
+        }
+    }
+
+    treasury.total_withdrawals = add_amount(treasury.total_withdrawals, amount);
+    store_treasury(env, &treasury);
+
+    let tx_id = get_next_tx_id(env);
+    let now = env.ledger().timestamp();
+    let tx = Transaction {
+        id: tx_id,
+        treasury_id,
+        tx_type: TransactionType::Withdrawal,
+        amount,
+        token,
+        recipient: Some(recipient),
+        proposer: env.current_contract_address(),
+        approvals: Vec::new(env),
+        status: TransactionStatus::Executed,
+        created_at: now,
+        expires_at: now,
+        category,
+        reason,
+        fee: fee_amount,
+        net_amount: amount - fee_amount,
+        memo: None,
+        on_behalf_of: None,
+    };
+    store_transaction(env, &tx);
+
+    let event = TransactionExecutedEvent { treasury_id, tx_id };
+    emit_event(env, MOD_TREASURY, ACT_EXECUTED, event);
+
+    Some(tx_id)
+}
+
+/// The treasury as a governance config change would leave it, without
+/// storing anything. Fails when the treasury is missing or belongs to
+/// another guild, when a role-based treasury is given a signer list, or when
+/// the threshold does not fit the resulting signers. An explicit threshold
+/// replaces automatic majority mode.
+pub fn preview_governance_config(
+    env: &Env,
+    guild_id: u64,
+    treasury_id: u64,
+    approval_threshold: u32,
+    signers: Option<Vec<Address>>,
+) -> Result<Treasury, &'static str> {
+    let mut treasury = get_treasury(env, treasury_id).ok_or("treasury not found")?;
+    if treasury.guild_id != guild_id {
+        return Err("treasury belongs to another guild");
+    }
+
+    if let Some(signers) = signers {
+        if treasury.role_based_signers {
+            return Err("role-based treasuries have no signer list");
+        }
+        let mut unique_signers = Vec::new(env);
+        for addr in signers.iter() {
+            if !unique_signers.iter().any(|a| a == addr.clone()) {
+                unique_signers.push_back(addr);
+            }
+        }
+        treasury.signers = unique_signers;
+    }
+
+    let signers_len = if treasury.role_based_signers {
+        role_signer_count(env, guild_id)
+    } else {
+        treasury.signers.len()
+    };
+    if let Some(reason) = threshold_error(signers_len, approval_threshold) {
+        return Err(reason);
+    }
+    // a tier needing more signatures than remain would lock large outflows
+    if load_approval_tiers(env, treasury_id)
+        .iter()
+        .any(|(_, required_sigs)| required_sigs > signers_len)
+    {
+        return Err("approval tiers exceed signer count");
+    }
+
+    treasury.approval_threshold = approval_threshold;
+    treasury.auto_threshold = false;
+    Ok(treasury)
+}
+
+/// Apply a treasury config change carried by a passed governance proposal.
+/// The change is re-checked against the treasury as it is now; returns
+/// `false`, leaving it untouched, if it no longer fits.
+pub fn apply_governance_config(
+    env: &Env,
+    guild_id: u64,
+    treasury_id: u64,
+    approval_threshold: u32,
+    signers: Option<Vec<Address>>,
+) -> bool {
+    let Ok(treasury) =
+        preview_governance_config(env, guild_id, treasury_id, approval_threshold, signers)
+    else {
+        return false;
+    };
+    store_treasury(env, &treasury);
+
+    let signer_count = if treasury.role_based_signers {
+        role_signer_count(env, guild_id)
+    } else {
+        treasury.signers.len()
+    };
+    let event = TreasuryConfigUpdatedEvent {
+        treasury_id,
+        approval_threshold,
+        signer_count,
+    };
+    emit_event(env, MOD_TREASURY, ACT_UPDATED, event);
+
+    true
+}
+
+pub fn set_budget(
+    env: &Env,
+    treasury_id: u64,
+    caller: Address,
+    category: WithdrawalCategory,
+    amount: i128,
+    period_seconds: u64,
+) -> bool {
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    assert_signer(env, &treasury, &caller);
+    ensure_guild_role(env, &treasury, &caller, Role::Admin);
+
+    let now = env.ledger().timestamp();
+    let mut budget = get_budget(env, treasury_id, &category).unwrap_or(Budget {
+        treasury_id,
+        category: category.clone(),
+        allocated_amount: 0,
+        spent_amount: 0,
+        period_seconds,
+        period_start: now,
+    });
+
+    if budget.period_seconds != period_seconds {
+        budget.period_seconds = period_seconds;
+    }
+
+    if now >= budget.period_start.saturating_add(budget.period_seconds) {
+        budget.period_start = now;
+        budget.spent_amount = 0;
+    }
+
+    budget.allocated_amount = amount;
+    store_budget(env, &budget);
+
+    let event = crate::treasury::types::BudgetUpdatedEvent {
         treasury_id,
         category,
         allocated_amount: amount,
@@ -548,6 +1252,166 @@ pub fn set_budget(
     true
 }
 
+/// Set the minimum balance of `token` the treasury must always keep.
+///
+/// Every withdrawal path (executed transactions, treasury transfers,
+/// milestone payments, allowance spends and governance spends) rejects an
+/// outflow that would leave the balance below the floor, regardless of
+/// budgets or pause state. A floor of 0 removes it.
+pub fn set_reserve_floor(
+    env: &Env,
+    treasury_id: u64,
+    token: Option<Address>,
+    floor: i128,
+    caller: Address,
+) -> bool {
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    assert_signer(env, &treasury, &caller);
+    ensure_guild_role(env, &treasury, &caller, Role::Admin);
+
+    if floor < 0 {
+        panic!("reserve floor must not be negative");
+    }
+    store_reserve_floor(env, treasury_id, &token, floor);
+
+    let event = ReserveFloorUpdatedEvent {
+        treasury_id,
+        token,
+        floor,
+    };
+    emit_event(env, MOD_TREASURY, ACT_UPDATED, event);
+
+    true
+}
+
+pub fn get_reserve_floor(env: &Env, treasury_id: u64, token: Option<Address>) -> i128 {
+    load_reserve_floor(env, treasury_id, &token)
+}
+
+/// Require more approvals for larger outflows.
+///
+/// Each tier is `(amount_threshold, required_sigs)`; an outflow needs the
+/// signatures of the highest threshold its amount meets or exceeds, and falls
+/// back to the base treasury threshold below every tier. Tiers must be listed
+/// in strictly ascending threshold order; an empty list removes them.
+pub fn set_approval_tiers(
+    env: &Env,
+    treasury_id: u64,
+    tiers: Vec<(i128, u32)>,
+    caller: Address,
+) -> bool {
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    assert_signer(env, &treasury, &caller);
+    ensure_guild_role(env, &treasury, &caller, Role::Admin);
+
+    let signer_count = if treasury.role_based_signers {
+        role_signer_count(env, treasury.guild_id)
+    } else {
+        treasury.signers.len()
+    };
+    let mut previous = 0i128;
+    for (amount_threshold, required_sigs) in tiers.iter() {
+        if amount_threshold <= previous {
+            panic!("tier thresholds must be positive and ascending");
+        }
+        if required_sigs == 0 || required_sigs > signer_count {
+            panic!("invalid tier signature count");
+        }
+        if required_sigs < base_required_approvals(env, &treasury, amount_threshold) {
+            panic!("tier cannot lower the approval threshold");
+        }
+        previous = amount_threshold;
+    }
+    store_approval_tiers(env, treasury_id, &tiers);
+
+    let event = ApprovalTiersUpdatedEvent { treasury_id, tiers };
+    emit_event(env, MOD_TREASURY, ACT_UPDATED, event);
+
+    true
+}
+
+pub fn get_approval_tiers(env: &Env, treasury_id: u64) -> Vec<(i128, u32)> {
+    load_approval_tiers(env, treasury_id)
+}
+
+/// Set how long new withdrawals and transfers of a treasury stay open for
+/// approval, clamped to `MIN_TX_EXPIRY_SECONDS..=MAX_TX_EXPIRY_SECONDS`.
+/// Pending transactions keep the expiry they were proposed with.
+pub fn set_transaction_timeout(env: &Env, treasury_id: u64, seconds: u64, caller: Address) -> u64 {
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    assert_signer(env, &treasury, &caller);
+    ensure_guild_role(env, &treasury, &caller, Role::Admin);
+
+    let seconds = seconds.clamp(MIN_TX_EXPIRY_SECONDS, MAX_TX_EXPIRY_SECONDS);
+    set_tx_timeout(env, treasury_id, seconds);
+
+    let event = TransactionTimeoutUpdatedEvent {
+        treasury_id,
+        seconds,
+    };
+    emit_event(env, MOD_TREASURY, ACT_UPDATED, event);
+
+    seconds
+}
+
+/// Approval window of a treasury's new transactions (`TX_EXPIRY_SECONDS` unless configured).
+pub fn get_transaction_timeout(env: &Env, treasury_id: u64) -> u64 {
+    get_tx_timeout(env, treasury_id).unwrap_or(TX_EXPIRY_SECONDS)
+}
+
+/// Restrict withdrawal proposals to pre-approved recipients.
+///
+/// Only the owner of the treasury's guild may change the list. The list
+/// replaces any previous one; an empty list lifts the restriction.
+pub fn set_payee_allowlist(
+    env: &Env,
+    treasury_id: u64,
+    payees: Vec<Address>,
+    caller: Address,
+) -> bool {
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    caller.require_auth();
+    ensure_guild_role(env, &treasury, &caller, Role::Owner);
+
+    let mut unique: Vec<Address> = Vec::new(env);
+    for payee in payees.iter() {
+        if unique.contains(&payee) {
+            panic!("duplicate payee");
+        }
+        unique.push_back(payee);
+    }
+    store_payee_allowlist(env, treasury_id, &unique);
+
+    let event = PayeeAllowlistUpdatedEvent {
+        treasury_id,
+        payee_count: unique.len(),
+    };
+    emit_event(env, MOD_TREASURY, ACT_UPDATED, event);
+
+    true
+}
+
+pub fn get_payee_allowlist(env: &Env, treasury_id: u64) -> Vec<Address> {
+    load_payee_allowlist(env, treasury_id)
+}
+
+/// Whether the treasury may pay `address`; always true without an allowlist.
+pub fn is_payee_allowed(env: &Env, treasury_id: u64, address: Address) -> bool {
+    let payees = load_payee_allowlist(env, treasury_id);
+    payees.is_empty() || payees.contains(&address)
+}
+
+/// Panics when `treasury`, already debited, holds less of `token` than its reserve floor.
+fn ensure_reserve_kept(env: &Env, treasury: &Treasury, token: &Option<Address>) {
+    let remaining = match token {
+        Some(token_addr) => treasury.token_balances.get(token_addr.clone()).unwrap_or(0),
+        None => treasury.balance_xlm,
+    };
+    if remaining < load_reserve_floor(env, treasury.id, token) {
+        panic!("withdrawal would breach reserve floor");
+    }
+}
+
 pub fn get_balance(env: &Env, treasury_id: u64, token: Option<Address>) -> i128 {
     let treasury = get_treasury(env, treasury_id).expect("treasury not found");
     match token {
@@ -559,6 +1423,64 @@ pub fn get_balance(env: &Env, treasury_id: u64, token: Option<Address>) -> i128
     }
 }
 
+/// IDs of every treasury created for a guild, oldest first.
+pub fn get_treasuries_for_guild(env: &Env, guild_id: u64) -> Vec<u64> {
+    get_guild_treasuries(env, guild_id)
+}
+
+/// The guild a treasury is linked to.
+pub fn get_treasury_guild(env: &Env, treasury_id: u64) -> u64 {
+    get_treasury(env, treasury_id)
+        .expect("treasury not found")
+        .guild_id
+}
+
+/// Guilds besides the owning one that must each approve the treasury's
+/// transactions; empty for a single-guild treasury.
+pub fn get_treasury_co_guilds(env: &Env, treasury_id: u64) -> Vec<u64> {
+    get_co_guilds(env, treasury_id)
+}
+
+/// Everything `depositor` has ever deposited into the treasury in `token`.
+/// Withdrawals do not reduce it.
+pub fn get_depositor_total(
+    env: &Env,
+    treasury_id: u64,
+    depositor: Address,
+    token: Option<Address>,
+) -> i128 {
+    get_depositor_totals(env, treasury_id, &token)
+        .get(depositor)
+        .unwrap_or(0)
+}
+
+/// Up to `limit` depositors with the largest cumulative deposits in `token`,
+/// largest first.
+pub fn get_top_depositors(
+    env: &Env,
+    treasury_id: u64,
+    token: Option<Address>,
+    limit: u32,
+) -> Vec<(Address, i128)> {
+    let mut top: Vec<(Address, i128)> = Vec::new(env);
+    if limit == 0 {
+        return top;
+    }
+    for (depositor, total) in get_depositor_totals(env, treasury_id, &token).iter() {
+        // insertion keeps `top` sorted; ties stay in address order
+        let pos = top.iter().position(|(_, t)| total > t).map(|p| p as u32);
+        match pos {
+            Some(idx) => top.insert(idx, (depositor, total)),
+            None if top.len() < limit => top.push_back((depositor, total)),
+            None => {}
+        }
+        if top.len() > limit {
+            top.pop_back();
+        }
+    }
+    top
+}
+
 pub fn get_transaction_history(env: &Env, treasury_id: u64, limit: u32) -> Vec<Transaction> {
     let all = get_treasury_transactions(env, treasury_id);
     let len = all.len();
@@ -577,6 +1499,32 @@ pub fn get_transaction_history(env: &Env, treasury_id: u64, limit: u32) -> Vec<T
     result
 }
 
+/// Page backwards through a treasury's history, newest first.
+///
+/// Returns up to `limit` transactions with an id below `before_id`, or the
+/// newest ones when it is `None`. Pass the smallest id of a page as the next
+/// `before_id` to continue; an empty page means the start was reached.
+pub fn get_transaction_history_paged(
+    env: &Env,
+    treasury_id: u64,
+    before_id: Option<u64>,
+    limit: u32,
+) -> Vec<Transaction> {
+    let mut page = Vec::new(env);
+    for id in get_treasury_tx_ids(env, treasury_id).iter().rev() {
+        if page.len() >= limit {
+            break;
+        }
+        if before_id.is_some_and(|before| id >= before) {
+            continue;
+        }
+        if let Some(tx) = crate::treasury::storage::get_transaction(env, id) {
+            page.push_back(tx);
+        }
+    }
+    page
+}
+
 pub fn grant_allowance(
     env: &Env,
     treasury_id: u64,
@@ -593,7 +1541,12 @@ pub fn grant_allowance(
     }
     owner.require_auth();
 
-    if !treasury.is_signer(&admin) {
+    // an allowance would let one guild spend without the co-guilds' sign-off
+    if !get_co_guilds(env, treasury_id).is_empty() {
+        panic!("allowances are not available on co-guild treasuries");
+    }
+
+    if !is_treasury_signer(env, &treasury, &admin) {
         panic!("admin must be signer");
     }
 
@@ -627,9 +1580,135 @@ pub fn grant_allowance(
     true
 }
 
+/// Spend directly from `grantee`'s allowance without a multisig proposal.
+///
+/// The allowance, the treasury's withdrawal budget and its balance are all
+/// charged in this one call, and the withdrawal fee applies as it does to
+/// executed withdrawals. Spending more than the remaining allowance reverts.
+pub fn spend_allowance(
+    env: &Env,
+    treasury_id: u64,
+    grantee: Address,
+    recipient: Address,
+    amount: i128,
+    token: Option<Address>,
+) -> u64 {
+    grantee.require_auth();
+    ensure_not_entered(env);
+
+    if amount <= 0 {
+        panic!("amount must be positive");
+    }
+
+    let mut treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    if treasury.paused {
+        panic!("treasury is paused");
+    }
+    ensure_operation_open(env, treasury_id, TreasuryOperation::Allowance);
+    if !get_co_guilds(env, treasury_id).is_empty() {
+        panic!("allowances are not available on co-guild treasuries");
+    }
+
+    // the grant outlives the grantee's seat, so re-check it on every spend
+    if !is_treasury_signer(env, &treasury, &grantee) {
+        panic!("grantee is no longer a signer");
+    }
+    ensure_guild_role(env, &treasury, &grantee, Role::Member);
+    if !is_payee_allowed(env, treasury_id, recipient.clone()) {
+        panic!("recipient not on payee allowlist");
+    }
+
+    let mut allowance =
+        get_allowance(env, treasury_id, &grantee, &token).expect("no allowance for grantee");
+    allowance.ensure_period_current(env);
+    if allowance.remaining_amount < amount {
+        panic!("allowance exceeded");
+    }
+    allowance.remaining_amount = sub_amount(allowance.remaining_amount, amount);
+    store_allowance(env, &allowance);
+
+    enforce_budget(env, treasury_id, &WithdrawalCategory::Operations, amount).unwrap_or_else(|e| {
+        match e {
+            TreasuryError::BudgetExceeded => panic!("budget exceeded"),
+            TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
+        }
+    });
+
+    let fee = withdrawal_fee(env, amount);
+    let fee_amount = fee.as_ref().map(|(cut, _)| *cut).unwrap_or(0);
+
+    match token {
+        Some(ref token_addr) => {
+            let mut balances = treasury.token_balances.clone();
+            let current = balances.get(token_addr.clone()).unwrap_or(0i128);
+            if current < amount {
+                panic!("insufficient treasury balance");
+            }
+            balances.set(token_addr.clone(), sub_amount(current, amount));
+            treasury.token_balances = balances;
+        }
+        None => {
+            if treasury.balance_xlm < amount {
+                panic!("insufficient XLM balance");
+            }
+            treasury.balance_xlm = sub_amount(treasury.balance_xlm, amount);
+        }
+    }
+    ensure_reserve_kept(env, &treasury, &token);
+    treasury.total_withdrawals = add_amount(treasury.total_withdrawals, amount);
+    store_treasury(env, &treasury);
+
+    let tx_id = get_next_tx_id(env);
+    let now = env.ledger().timestamp();
+    let tx = Transaction {
+        id: tx_id,
+        treasury_id,
+        tx_type: TransactionType::Withdrawal,
+        amount,
+        token: token.clone(),
+        recipient: Some(recipient.clone()),
+        proposer: grantee.clone(),
+        approvals: Vec::new(env),
+        status: TransactionStatus::Executed,
+        created_at: now,
+        expires_at: now,
+        category: WithdrawalCategory::Operations,
+        reason: String::from_str(env, "allowance spend"),
+        fee: fee_amount,
+        net_amount: amount - fee_amount,
+        memo: None,
+        on_behalf_of: None,
+    };
+    store_transaction(env, &tx);
+
+    // state is settled before the token contract runs
+    if let Some(ref token_addr) = token {
+        let contract = env.current_contract_address();
+        guarded_transfer(env, token_addr, &contract, &recipient, tx.net_amount);
+        if let Some((cut, fee_recipient)) = fee {
+            guarded_transfer(env, token_addr, &contract, &fee_recipient, cut);
+        }
+    }
+
+    record_snapshot(env, &treasury);
+
+    let event = AllowanceSpentEvent {
+        treasury_id,
+        tx_id,
+        grantee,
+        recipient,
+        amount,
+        token,
+    };
+    emit_event(env, MOD_TREASURY, ACT_SPENT, event);
+
+    tx_id
+}
+
 pub fn emergency_pause(env: &Env, treasury_id: u64, signer: Address, paused: bool) -> bool {
     let mut treasury = get_treasury(env, treasury_id).expect("treasury not found");
     assert_signer(env, &treasury, &signer);
+    ensure_guild_role(env, &treasury, &signer, Role::Admin);
 
     treasury.paused = paused;
     store_treasury(env, &treasury);
@@ -644,6 +1723,44 @@ pub fn emergency_pause(env: &Env, treasury_id: u64, signer: Address, paused: boo
     true
 }
 
+/// Pause or resume one kind of operation, e.g. freeze outflows while
+/// deposits keep arriving. `emergency_pause` still pauses everything.
+pub fn set_operation_pause(
+    env: &Env,
+    treasury_id: u64,
+    op_kind: TreasuryOperation,
+    paused: bool,
+    caller: Address,
+) -> bool {
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    assert_signer(env, &treasury, &caller);
+    ensure_guild_role(env, &treasury, &caller, Role::Admin);
+
+    set_operation_paused(env, treasury_id, &op_kind, paused);
+
+    let event = OperationPauseEvent {
+        treasury_id,
+        op_kind,
+        paused,
+    };
+    let action = if paused { ACT_PAUSED } else { ACT_RESUMED };
+    emit_event(env, MOD_TREASURY, action, event);
+
+    true
+}
+
+/// Whether `op_kind` is currently blocked, by its own flag or the blanket pause.
+pub fn is_operation_blocked(env: &Env, treasury_id: u64, op_kind: TreasuryOperation) -> bool {
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    treasury.paused || is_operation_paused(env, treasury_id, &op_kind)
+}
+
+fn ensure_operation_open(env: &Env, treasury_id: u64, op_kind: TreasuryOperation) {
+    if is_operation_paused(env, treasury_id, &op_kind) {
+        panic!("operation is paused");
+    }
+}
+
 /// Record a point-in-time treasury snapshot for analytics tracking.
 fn record_snapshot(env: &Env, treasury: &Treasury) {
     use crate::analytics::storage::get_snapshot_count;
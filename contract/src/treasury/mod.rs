@@ -5,15 +5,20 @@ pub mod types;
 
 pub use management::{
     approve_transaction, deposit, emergency_pause, execute_milestone_payment, execute_transaction,
-    get_balance, get_transaction_history, grant_allowance, initialize_treasury, propose_withdrawal,
-    set_budget,
+    get_approval_tiers, get_balance, get_depositor_total, get_payee_allowlist, get_reserve_floor, get_top_depositors, get_transaction_history, get_transaction_timeout,
+    get_transaction_history_paged,
+    get_treasuries_for_guild, get_treasury_co_guilds, get_treasury_guild, grant_allowance, initialize_treasury, is_operation_blocked, is_payee_allowed, propose_treasury_transfer, propose_withdrawal,
+    revoke_approval, set_approval_tiers, set_budget, set_operation_pause, set_payee_allowlist, set_reserve_floor, set_transaction_timeout, set_withdrawal_fee_bps, spend_allowance,
 };
 
 #[allow(unused_imports)]
 pub use storage::initialize_treasury_storage;
 
 #[allow(unused_imports)]
-pub use types::{Allowance, Budget, Transaction, TransactionStatus, TransactionType, Treasury};
+pub use types::{
+    Allowance, Budget, Transaction, TransactionStatus, TransactionType, Treasury,
+    TreasuryOperation, WithdrawalCategory,
+};
 // Tests disabled pending fixes
 #[cfg(test)]
 mod tests;
@@ -1,37 +1,153 @@
 ﻿use soroban_sdk::{Address, Env};
 
+use crate::guild::membership::has_permission;
+use crate::guild::storage as guild_storage;
+use crate::guild::types::Role;
+use crate::treasury::storage::{
+    get_approval_guilds, get_approval_tiers, get_co_guilds, get_guild_treasuries, get_treasury,
+};
 use crate::treasury::types::{Transaction, TransactionStatus, TransactionType, Treasury};
 
 pub const TX_EXPIRY_SECONDS: u64 = 60 * 60 * 24 * 7; // 7 days
+/// Bounds a treasury's configured transaction timeout is clamped to
+pub const MIN_TX_EXPIRY_SECONDS: u64 = 60 * 60; // 1 hour
+pub const MAX_TX_EXPIRY_SECONDS: u64 = 60 * 60 * 24 * 30; // 30 days
 
 pub fn validate_threshold(signers_len: u32, threshold: u32) {
+    if let Some(reason) = threshold_error(signers_len, threshold) {
+        panic!("{}", reason);
+    }
+}
+
+/// Why `threshold` is unusable for `signers_len` signers, if it is.
+pub fn threshold_error(signers_len: u32, threshold: u32) -> Option<&'static str> {
     if signers_len == 0 {
-        panic!("at least one signer required");
+        return Some("at least one signer required");
     }
     if threshold == 0 || threshold > signers_len {
-        panic!("invalid threshold");
+        return Some("invalid threshold");
     }
     let half = (signers_len + 1) / 2; // ceil(0.5 * n)
     if threshold < half {
-        panic!("threshold must be at least 50% of signers");
+        return Some("threshold must be at least 50% of signers");
+    }
+    None
+}
+
+/// Smallest strict majority of `count` signers: `(count / 2) + 1`.
+pub fn majority_of(count: u32) -> u32 {
+    (count / 2) + 1
+}
+
+/// Approvals a high-value outflow needs. Auto-threshold treasuries in
+/// role-based mode require a majority of the admins eligible right now.
+pub fn effective_threshold(env: &Env, treasury: &Treasury) -> u32 {
+    if treasury.auto_threshold && treasury.role_based_signers {
+        majority_of(role_signer_count(env, treasury.guild_id))
+    } else {
+        treasury.approval_threshold
+    }
+}
+
+/// Whether the address may currently sign for the treasury: a listed signer,
+/// or in role-based mode any guild member at Admin level or above.
+pub fn is_treasury_signer(env: &Env, treasury: &Treasury, addr: &Address) -> bool {
+    if treasury.role_based_signers {
+        has_permission(env, treasury.guild_id, addr.clone(), Role::Admin)
+    } else {
+        treasury.is_signer(addr)
+    }
+}
+
+/// Whether `addr` holds a seat on the explicit signer list of any of the
+/// guild's treasuries. Role-based treasuries have no seats to vacate.
+pub fn is_listed_signer(env: &Env, guild_id: u64, addr: &Address) -> bool {
+    get_guild_treasuries(env, guild_id).iter().any(|id| {
+        get_treasury(env, id)
+            .map(|t| !t.role_based_signers && t.is_signer(addr))
+            .unwrap_or(false)
+    })
+}
+
+/// Require the address to hold at least `role` in the treasury's guild, so a
+/// listed signer who has left the guild can no longer drive treasury actions.
+pub fn ensure_guild_role(env: &Env, treasury: &Treasury, addr: &Address, role: Role) {
+    if !has_permission(env, treasury.guild_id, addr.clone(), role) {
+        panic!("caller lacks required guild role");
     }
 }
 
+/// Number of guild members currently eligible to sign in role-based mode.
+pub fn role_signer_count(env: &Env, guild_id: u64) -> u32 {
+    let mut count = 0u32;
+    for member in guild_storage::get_all_members(env, guild_id).iter() {
+        if has_permission(env, guild_id, member.address, Role::Admin) {
+            count += 1;
+        }
+    }
+    count
+}
+
 /// Authorize and verify the address is a treasury signer.
 /// Call this ONLY when the address has not been authorized yet in this invocation.
-pub fn assert_signer(_env: &Env, treasury: &Treasury, addr: &Address) {
+pub fn assert_signer(env: &Env, treasury: &Treasury, addr: &Address) {
     addr.require_auth();
-    ensure_is_signer(treasury, addr);
+    ensure_is_signer(env, treasury, addr);
 }
 
 /// Verify the address is a treasury signer (no auth).
 /// Use when the address was already authorized at the entrypoint (e.g. approve_transaction, execute_transaction).
-pub fn ensure_is_signer(treasury: &Treasury, addr: &Address) {
-    if !treasury.is_signer(addr) {
+pub fn ensure_is_signer(env: &Env, treasury: &Treasury, addr: &Address) {
+    if !is_treasury_signer(env, treasury, addr) {
         panic!("caller is not a signer");
     }
 }
 
+/// Approvals on `tx` from addresses that are still signers, so a signer who
+/// lost their role no longer counts towards the threshold.
+pub fn valid_approvals(env: &Env, treasury: &Treasury, tx: &Transaction) -> u32 {
+    tx.approvals
+        .iter()
+        .filter(|a| is_treasury_signer(env, treasury, a))
+        .count() as u32
+}
+
+/// Co-guild an approval from `addr` counts for: the first listed co-guild
+/// they administer that has no approval yet, else the first they administer.
+/// `None` if they are not an admin of any co-guild.
+pub fn co_guild_for_approver(
+    env: &Env,
+    treasury_id: u64,
+    tx: &Transaction,
+    addr: &Address,
+) -> Option<u64> {
+    let covered = get_approval_guilds(env, tx.id).values();
+    let mut fallback = None;
+    for guild_id in get_co_guilds(env, treasury_id).iter() {
+        if !has_permission(env, guild_id, addr.clone(), Role::Admin) {
+            continue;
+        }
+        if !covered.contains(guild_id) {
+            return Some(guild_id);
+        }
+        fallback = fallback.or(Some(guild_id));
+    }
+    fallback
+}
+
+/// Whether every co-guild of the treasury has approved `tx` through someone
+/// who is still one of its admins. Always true for single-guild treasuries.
+pub fn co_guilds_covered(env: &Env, treasury_id: u64, tx: &Transaction) -> bool {
+    let recorded = get_approval_guilds(env, tx.id);
+    get_co_guilds(env, treasury_id).iter().all(|guild_id| {
+        recorded.iter().any(|(addr, represented)| {
+            represented == guild_id
+                && has_approved(tx, &addr)
+                && has_permission(env, guild_id, addr, Role::Admin)
+        })
+    })
+}
+
 pub fn has_approved(tx: &Transaction, addr: &Address) -> bool {
     tx.approvals.iter().any(|a| a == addr.clone())
 }
@@ -43,16 +159,33 @@ pub fn add_approval(tx: &mut Transaction, addr: &Address) {
     tx.approvals.push_back(addr.clone());
 }
 
-pub fn required_approvals_for_tx(treasury: &Treasury, tx: &Transaction) -> u32 {
+/// Approvals the base threshold rules ask of an outflow of `amount`,
+/// before any approval tier is applied.
+pub fn base_required_approvals(env: &Env, treasury: &Treasury, amount: i128) -> u32 {
+    if amount >= treasury.high_value_threshold {
+        effective_threshold(env, treasury)
+    } else {
+        // low-value operations: single signer is enough, but cannot exceed threshold
+        1u32.min(effective_threshold(env, treasury))
+    }
+}
+
+/// Approvals an outflow needs: the highest approval tier its amount reaches,
+/// which can only raise the base threshold rules, never lower them.
+pub fn required_approvals_for_tx(env: &Env, treasury: &Treasury, tx: &Transaction) -> u32 {
     match tx.tx_type {
         TransactionType::Withdrawal
         | TransactionType::BountyFunding
-        | TransactionType::MilestonePayment => {
-            if tx.amount >= treasury.high_value_threshold {
-                treasury.approval_threshold
-            } else {
-                // low-value operations: single signer is enough, but cannot exceed threshold
-                1u32.min(treasury.approval_threshold)
+        | TransactionType::MilestonePayment
+        | TransactionType::TreasuryTransfer(_) => {
+            let base = base_required_approvals(env, treasury, tx.amount);
+            let tier = get_approval_tiers(env, treasury.id)
+                .iter()
+                .rev()
+                .find(|(amount_threshold, _)| tx.amount >= *amount_threshold);
+            match tier {
+                Some((_, required_sigs)) => required_sigs.max(base),
+                None => base,
             }
         }
         _ => 1,
@@ -1,17 +1,34 @@
 ﻿use soroban_sdk::{symbol_short, Address, Env, Map, String, Symbol, Vec};
 
-use crate::treasury::types::{Allowance, Budget, Transaction, Treasury};
+use crate::treasury::types::{
+    Allowance, Budget, StoredBudget, StoredTransaction, StoredTreasury, Transaction,
+    TransactionExtras, Treasury, TreasuryOperation, TreasurySettings, WithdrawalCategory,
+    WithdrawalFee,
+};
 
 const TREASURY_CNT_KEY: Symbol = symbol_short!("t_cnt");
 const TREASURIES_KEY: Symbol = symbol_short!("trsries");
+const TREASURY_SETTINGS_KEY: Symbol = symbol_short!("t_setting");
+const GUILD_TREASURIES_KEY: Symbol = symbol_short!("g_trsry");
 
 const TX_CNT_KEY: Symbol = symbol_short!("tx_cnt");
 const TRANSACTIONS_KEY: Symbol = symbol_short!("txs");
+const TX_EXTRAS_KEY: Symbol = symbol_short!("t_txextra");
 const TREASURY_TX_INDEX_KEY: Symbol = symbol_short!("t_tx_idx");
 
 const BUDGETS_KEY: Symbol = symbol_short!("budgets");
 const ALLOWANCES_KEY: Symbol = symbol_short!("allows");
 
+const WITHDRAWAL_FEE_KEY: Symbol = symbol_short!("t_fee");
+const RESERVE_FLOORS_KEY: Symbol = symbol_short!("t_rsrv");
+const APPROVAL_TIERS_KEY: Symbol = symbol_short!("t_tiers");
+const OPERATION_PAUSES_KEY: Symbol = symbol_short!("t_opause");
+const DEPOSITOR_TOTALS_KEY: Symbol = symbol_short!("t_deps");
+const CO_GUILDS_KEY: Symbol = symbol_short!("t_coguild");
+const APPROVAL_GUILDS_KEY: Symbol = symbol_short!("t_apguild");
+const PAYEE_ALLOWLISTS_KEY: Symbol = symbol_short!("t_payees");
+const TX_TIMEOUTS_KEY: Symbol = symbol_short!("t_txtmo");
+
 #[allow(dead_code)]
 pub fn initialize_treasury_storage(env: &Env) {
     let storage = env.storage().persistent();
@@ -39,37 +56,121 @@ pub fn get_next_tx_id(env: &Env) -> u64 {
     next
 }
 
+/// Store a treasury. Its signer-mode settings go under a separate key so
+/// treasuries stored before those settings existed still decode.
 pub fn store_treasury(env: &Env, treasury: &Treasury) {
-    let mut treasuries: Map<u64, Treasury> = env
+    let (stored, settings) = treasury.clone().into_parts();
+
+    let mut treasuries: Map<u64, StoredTreasury> = env
         .storage()
         .persistent()
         .get(&TREASURIES_KEY)
         .unwrap_or_else(|| Map::new(env));
-
-    treasuries.set(treasury.id, treasury.clone());
+    treasuries.set(treasury.id, stored);
     env.storage().persistent().set(&TREASURIES_KEY, &treasuries);
+
+    let mut all_settings: Map<u64, TreasurySettings> = env
+        .storage()
+        .persistent()
+        .get(&TREASURY_SETTINGS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+    all_settings.set(treasury.id, settings);
+    env.storage()
+        .persistent()
+        .set(&TREASURY_SETTINGS_KEY, &all_settings);
 }
 
 pub fn get_treasury(env: &Env, id: u64) -> Option<Treasury> {
-    let treasuries: Map<u64, Treasury> = env
+    let treasuries: Map<u64, StoredTreasury> = env
         .storage()
         .persistent()
         .get(&TREASURIES_KEY)
         .unwrap_or_else(|| Map::new(env));
+    let stored = treasuries.get(id)?;
+
+    let all_settings: Map<u64, TreasurySettings> = env
+        .storage()
+        .persistent()
+        .get(&TREASURY_SETTINGS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+    let settings = all_settings
+        .get(id)
+        .unwrap_or_else(TreasurySettings::default);
 
-    treasuries.get(id)
+    Some(Treasury::from_parts(stored, settings))
 }
 
-pub fn store_transaction(env: &Env, tx: &Transaction) {
-    // Store main tx map
-    let mut txs: Map<u64, Transaction> = env
+/// Record `treasury_id` under its guild; re-adding an indexed treasury is a no-op.
+pub fn index_guild_treasury(env: &Env, guild_id: u64, treasury_id: u64) {
+    let mut index: Map<u64, Vec<u64>> = env
+        .storage()
+        .persistent()
+        .get(&GUILD_TREASURIES_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let mut list = index.get(guild_id).unwrap_or_else(|| Vec::new(env));
+    if !list.iter().any(|id| id == treasury_id) {
+        list.push_back(treasury_id);
+        index.set(guild_id, list);
+        env.storage()
+            .persistent()
+            .set(&GUILD_TREASURIES_KEY, &index);
+    }
+}
+
+pub fn get_guild_treasuries(env: &Env, guild_id: u64) -> Vec<u64> {
+    let index: Map<u64, Vec<u64>> = env
         .storage()
         .persistent()
-        .get(&TRANSACTIONS_KEY)
+        .get(&GUILD_TREASURIES_KEY)
         .unwrap_or_else(|| Map::new(env));
 
-    txs.set(tx.id, tx.clone());
-    env.storage().persistent().set(&TRANSACTIONS_KEY, &txs);
+    index.get(guild_id).unwrap_or_else(|| Vec::new(env))
+}
+
+/// Stored transactions and their side map of later data. Transactions are
+/// split so records written before that data existed still decode.
+struct TransactionMaps {
+    stored: Map<u64, StoredTransaction>,
+    extras: Map<u64, TransactionExtras>,
+}
+
+impl TransactionMaps {
+    fn load(env: &Env) -> Self {
+        Self {
+            stored: env
+                .storage()
+                .persistent()
+                .get(&TRANSACTIONS_KEY)
+                .unwrap_or_else(|| Map::new(env)),
+            extras: env
+                .storage()
+                .persistent()
+                .get(&TX_EXTRAS_KEY)
+                .unwrap_or_else(|| Map::new(env)),
+        }
+    }
+
+    fn get(&self, tx_id: u64) -> Option<Transaction> {
+        let stored = self.stored.get(tx_id)?;
+        let extras = self
+            .extras
+            .get(tx_id)
+            .unwrap_or_else(|| TransactionExtras::legacy(stored.amount));
+        Some(Transaction::from_parts(stored, extras))
+    }
+}
+
+pub fn store_transaction(env: &Env, tx: &Transaction) {
+    // Store main tx map and its extras
+    let mut maps = TransactionMaps::load(env);
+    let (stored, extras) = tx.clone().into_parts();
+    maps.stored.set(tx.id, stored);
+    maps.extras.set(tx.id, extras);
+    env.storage()
+        .persistent()
+        .set(&TRANSACTIONS_KEY, &maps.stored);
+    env.storage().persistent().set(&TX_EXTRAS_KEY, &maps.extras);
 
     // Update treasury index - only add if this is a new transaction (updates don't append)
     let mut index: Map<u64, Vec<u64>> = env
@@ -90,29 +191,23 @@ pub fn store_transaction(env: &Env, tx: &Transaction) {
 }
 
 pub fn get_transaction(env: &Env, tx_id: u64) -> Option<Transaction> {
-    let txs: Map<u64, Transaction> = env
-        .storage()
-        .persistent()
-        .get(&TRANSACTIONS_KEY)
-        .unwrap_or_else(|| Map::new(env));
-
-    txs.get(tx_id)
+    TransactionMaps::load(env).get(tx_id)
 }
 
-pub fn get_treasury_transactions(env: &Env, treasury_id: u64) -> Vec<Transaction> {
+/// IDs of a treasury's transactions in creation order (ascending).
+pub fn get_treasury_tx_ids(env: &Env, treasury_id: u64) -> Vec<u64> {
     let index: Map<u64, Vec<u64>> = env
         .storage()
         .persistent()
         .get(&TREASURY_TX_INDEX_KEY)
         .unwrap_or_else(|| Map::new(env));
 
-    let ids = index.get(treasury_id).unwrap_or_else(|| Vec::new(env));
+    index.get(treasury_id).unwrap_or_else(|| Vec::new(env))
+}
 
-    let txs: Map<u64, Transaction> = env
-        .storage()
-        .persistent()
-        .get(&TRANSACTIONS_KEY)
-        .unwrap_or_else(|| Map::new(env));
+pub fn get_treasury_transactions(env: &Env, treasury_id: u64) -> Vec<Transaction> {
+    let ids = get_treasury_tx_ids(env, treasury_id);
+    let txs = TransactionMaps::load(env);
 
     let mut result = Vec::new(env);
     for id in ids.iter() {
@@ -123,27 +218,44 @@ pub fn get_treasury_transactions(env: &Env, treasury_id: u64) -> Vec<Transaction
     result
 }
 
-pub fn get_budget(env: &Env, treasury_id: u64, category: &String) -> Option<Budget> {
-    let budgets: Map<(u64, String), Budget> = env
+/// Budgets are stored under their category's name so budgets written before
+/// categories were typed still decode.
+pub fn get_budget(env: &Env, treasury_id: u64, category: &WithdrawalCategory) -> Option<Budget> {
+    let budgets: Map<(u64, String), StoredBudget> = env
         .storage()
         .persistent()
         .get(&BUDGETS_KEY)
         .unwrap_or_else(|| Map::new(env));
 
-    budgets.get((treasury_id, category.clone()))
+    budgets
+        .get((treasury_id, category.name(env)))
+        .map(|stored| Budget {
+            treasury_id: stored.treasury_id,
+            category: category.clone(),
+            allocated_amount: stored.allocated_amount,
+            spent_amount: stored.spent_amount,
+            period_seconds: stored.period_seconds,
+            period_start: stored.period_start,
+        })
 }
 
 pub fn store_budget(env: &Env, budget: &Budget) {
-    let mut budgets: Map<(u64, String), Budget> = env
+    let mut budgets: Map<(u64, String), StoredBudget> = env
         .storage()
         .persistent()
         .get(&BUDGETS_KEY)
         .unwrap_or_else(|| Map::new(env));
 
-    budgets.set(
-        (budget.treasury_id, budget.category.clone()),
-        budget.clone(),
-    );
+    let category = budget.category.name(env);
+    let stored = StoredBudget {
+        treasury_id: budget.treasury_id,
+        category: category.clone(),
+        allocated_amount: budget.allocated_amount,
+        spent_amount: budget.spent_amount,
+        period_seconds: budget.period_seconds,
+        period_start: budget.period_start,
+    };
+    budgets.set((budget.treasury_id, category), stored);
     env.storage().persistent().set(&BUDGETS_KEY, &budgets);
 }
 
@@ -181,19 +293,244 @@ pub fn store_allowance(env: &Env, allowance: &Allowance) {
 }
 
 #[allow(dead_code)]
+/// A treasury's budgets. Free-form budgets from before categories were typed
+/// can no longer be charged and are left out.
 pub fn list_budgets_for_treasury(env: &Env, treasury_id: u64) -> Vec<Budget> {
-    let budgets: Map<(u64, String), Budget> = env
+    let budgets: Map<(u64, String), StoredBudget> = env
         .storage()
         .persistent()
         .get(&BUDGETS_KEY)
         .unwrap_or_else(|| Map::new(env));
 
     let mut result = Vec::new(env);
-    for entry in budgets.iter() {
-        let ((t_id, _category), budget) = entry;
-        if t_id == treasury_id {
-            result.push_back(budget);
+    for ((t_id, name), stored) in budgets.iter() {
+        if t_id != treasury_id {
+            continue;
+        }
+        if let Some(category) = WithdrawalCategory::from_name(env, &name) {
+            result.push_back(Budget {
+                treasury_id: stored.treasury_id,
+                category,
+                allocated_amount: stored.allocated_amount,
+                spent_amount: stored.spent_amount,
+                period_seconds: stored.period_seconds,
+                period_start: stored.period_start,
+            });
         }
     }
     result
 }
+
+/// Minimum balance a treasury must keep of `token` (0 when unset).
+pub fn get_reserve_floor(env: &Env, treasury_id: u64, token: &Option<Address>) -> i128 {
+    let floors: Map<(u64, Option<Address>), i128> = env
+        .storage()
+        .persistent()
+        .get(&RESERVE_FLOORS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    floors.get((treasury_id, token.clone())).unwrap_or(0)
+}
+
+pub fn set_reserve_floor(env: &Env, treasury_id: u64, token: &Option<Address>, floor: i128) {
+    let mut floors: Map<(u64, Option<Address>), i128> = env
+        .storage()
+        .persistent()
+        .get(&RESERVE_FLOORS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    floors.set((treasury_id, token.clone()), floor);
+    env.storage().persistent().set(&RESERVE_FLOORS_KEY, &floors);
+}
+
+/// Amount-based approval tiers of a treasury as `(amount_threshold,
+/// required_sigs)`, ascending by threshold (empty when unset).
+pub fn get_approval_tiers(env: &Env, treasury_id: u64) -> Vec<(i128, u32)> {
+    let tiers: Map<u64, Vec<(i128, u32)>> = env
+        .storage()
+        .persistent()
+        .get(&APPROVAL_TIERS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    tiers.get(treasury_id).unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn set_approval_tiers(env: &Env, treasury_id: u64, tiers: &Vec<(i128, u32)>) {
+    let mut all: Map<u64, Vec<(i128, u32)>> = env
+        .storage()
+        .persistent()
+        .get(&APPROVAL_TIERS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    all.set(treasury_id, tiers.clone());
+    env.storage().persistent().set(&APPROVAL_TIERS_KEY, &all);
+}
+
+/// Whether one kind of operation is paused on a treasury, ignoring the
+/// blanket `Treasury::paused` flag.
+pub fn is_operation_paused(env: &Env, treasury_id: u64, op_kind: &TreasuryOperation) -> bool {
+    let pauses: Map<(u64, TreasuryOperation), bool> = env
+        .storage()
+        .persistent()
+        .get(&OPERATION_PAUSES_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    pauses.get((treasury_id, op_kind.clone())).unwrap_or(false)
+}
+
+pub fn set_operation_paused(
+    env: &Env,
+    treasury_id: u64,
+    op_kind: &TreasuryOperation,
+    paused: bool,
+) {
+    let mut pauses: Map<(u64, TreasuryOperation), bool> = env
+        .storage()
+        .persistent()
+        .get(&OPERATION_PAUSES_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    pauses.set((treasury_id, op_kind.clone()), paused);
+    env.storage()
+        .persistent()
+        .set(&OPERATION_PAUSES_KEY, &pauses);
+}
+
+/// Other guilds that must each approve a treasury's transactions.
+pub fn get_co_guilds(env: &Env, treasury_id: u64) -> Vec<u64> {
+    let all: Map<u64, Vec<u64>> = env
+        .storage()
+        .persistent()
+        .get(&CO_GUILDS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    all.get(treasury_id).unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn set_co_guilds(env: &Env, treasury_id: u64, guild_ids: &Vec<u64>) {
+    let mut all: Map<u64, Vec<u64>> = env
+        .storage()
+        .persistent()
+        .get(&CO_GUILDS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    all.set(treasury_id, guild_ids.clone());
+    env.storage().persistent().set(&CO_GUILDS_KEY, &all);
+}
+
+/// Addresses a treasury may pay out to (empty when unrestricted).
+pub fn get_payee_allowlist(env: &Env, treasury_id: u64) -> Vec<Address> {
+    let all: Map<u64, Vec<Address>> = env
+        .storage()
+        .persistent()
+        .get(&PAYEE_ALLOWLISTS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    all.get(treasury_id).unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn set_payee_allowlist(env: &Env, treasury_id: u64, payees: &Vec<Address>) {
+    let mut all: Map<u64, Vec<Address>> = env
+        .storage()
+        .persistent()
+        .get(&PAYEE_ALLOWLISTS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    all.set(treasury_id, payees.clone());
+    env.storage().persistent().set(&PAYEE_ALLOWLISTS_KEY, &all);
+}
+
+/// Approval window of a treasury's new transactions, if one was configured.
+pub fn get_tx_timeout(env: &Env, treasury_id: u64) -> Option<u64> {
+    let timeouts: Map<u64, u64> = env
+        .storage()
+        .persistent()
+        .get(&TX_TIMEOUTS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    timeouts.get(treasury_id)
+}
+
+pub fn set_tx_timeout(env: &Env, treasury_id: u64, seconds: u64) {
+    let mut timeouts: Map<u64, u64> = env
+        .storage()
+        .persistent()
+        .get(&TX_TIMEOUTS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    timeouts.set(treasury_id, seconds);
+    env.storage().persistent().set(&TX_TIMEOUTS_KEY, &timeouts);
+}
+
+/// The co-guild each approver of a transaction approved on behalf of.
+pub fn get_approval_guilds(env: &Env, tx_id: u64) -> Map<Address, u64> {
+    let all: Map<u64, Map<Address, u64>> = env
+        .storage()
+        .persistent()
+        .get(&APPROVAL_GUILDS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    all.get(tx_id).unwrap_or_else(|| Map::new(env))
+}
+
+pub fn set_approval_guilds(env: &Env, tx_id: u64, guilds: &Map<Address, u64>) {
+    let mut all: Map<u64, Map<Address, u64>> = env
+        .storage()
+        .persistent()
+        .get(&APPROVAL_GUILDS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    all.set(tx_id, guilds.clone());
+    env.storage().persistent().set(&APPROVAL_GUILDS_KEY, &all);
+}
+
+pub fn set_withdrawal_fee(env: &Env, fee: &WithdrawalFee) {
+    env.storage().persistent().set(&WITHDRAWAL_FEE_KEY, fee);
+}
+
+pub fn get_withdrawal_fee(env: &Env) -> Option<WithdrawalFee> {
+    env.storage().persistent().get(&WITHDRAWAL_FEE_KEY)
+}
+
+/// Cumulative deposits per depositor for one (treasury, token) pair.
+pub fn get_depositor_totals(
+    env: &Env,
+    treasury_id: u64,
+    token: &Option<Address>,
+) -> Map<Address, i128> {
+    let totals: Map<(u64, Option<Address>), Map<Address, i128>> = env
+        .storage()
+        .persistent()
+        .get(&DEPOSITOR_TOTALS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    totals
+        .get((treasury_id, token.clone()))
+        .unwrap_or_else(|| Map::new(env))
+}
+
+pub fn add_depositor_total(
+    env: &Env,
+    treasury_id: u64,
+    depositor: &Address,
+    token: &Option<Address>,
+    amount: i128,
+) {
+    let mut totals: Map<(u64, Option<Address>), Map<Address, i128>> = env
+        .storage()
+        .persistent()
+        .get(&DEPOSITOR_TOTALS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let key = (treasury_id, token.clone());
+    let mut per_depositor = totals.get(key.clone()).unwrap_or_else(|| Map::new(env));
+    let current = per_depositor.get(depositor.clone()).unwrap_or(0);
+    let total = current
+        .checked_add(amount)
+        .unwrap_or_else(|| panic!("arithmetic overflow"));
+    per_depositor.set(depositor.clone(), total);
+    totals.set(key, per_depositor);
+    env.storage()
+        .persistent()
+        .set(&DEPOSITOR_TOTALS_KEY, &totals);
+}
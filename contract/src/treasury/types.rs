@@ -17,6 +17,63 @@ pub enum TransactionType {
     BountyFunding,
     MilestonePayment,
     AllowanceGrant,
+    /// Movement to another treasury held by this contract, by destination id
+    TreasuryTransfer(u64),
+}
+
+/// Spending category a transaction is budgeted under. Milestone payments
+/// count as `Payroll`, allowance spends as `Operations`, and deposits as
+/// `Other`; treasury transfers carry the category chosen by the proposer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WithdrawalCategory {
+    Payroll,
+    Grant,
+    Operations,
+    Reimbursement,
+    Other,
+}
+
+impl WithdrawalCategory {
+    const ALL: [WithdrawalCategory; 5] = [
+        WithdrawalCategory::Payroll,
+        WithdrawalCategory::Grant,
+        WithdrawalCategory::Operations,
+        WithdrawalCategory::Reimbursement,
+        WithdrawalCategory::Other,
+    ];
+
+    /// Name the category's budget is stored under
+    pub fn name(&self, env: &Env) -> String {
+        let name = match self {
+            WithdrawalCategory::Payroll => "Payroll",
+            WithdrawalCategory::Grant => "Grant",
+            WithdrawalCategory::Operations => "Operations",
+            WithdrawalCategory::Reimbursement => "Reimbursement",
+            WithdrawalCategory::Other => "Other",
+        };
+        String::from_str(env, name)
+    }
+
+    /// Category stored under `name`, if any. Free-form budget names from
+    /// before categories were typed match none.
+    pub fn from_name(env: &Env, name: &String) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.name(env) == *name)
+    }
+}
+
+/// Kind of treasury operation that can be paused on its own
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TreasuryOperation {
+    Deposit,
+    /// Every outflow: proposed and executed withdrawals, treasury transfers,
+    /// milestone payments and governance spends
+    Withdrawal,
+    /// Direct spends from granted allowances
+    Allowance,
 }
 
 #[contracttype]
@@ -32,6 +89,35 @@ pub enum TransactionStatus {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Transaction {
+    pub id: u64,
+    pub treasury_id: u64,
+    pub tx_type: TransactionType,
+    pub amount: i128,
+    pub token: Option<Address>,
+    pub recipient: Option<Address>,
+    pub proposer: Address,
+    pub approvals: Vec<Address>,
+    pub status: TransactionStatus,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub category: WithdrawalCategory,
+    pub reason: String,
+    /// Protocol fee deducted when the transaction was executed
+    pub fee: i128,
+    /// Amount the recipient actually received (`amount - fee`)
+    pub net_amount: i128,
+    /// Free-form note attached by the depositor, e.g. the agreement it settles
+    pub memo: Option<String>,
+    /// Contributor credited for a deposit `proposer` paid on their behalf
+    pub on_behalf_of: Option<Address>,
+}
+
+/// Original `Transaction` layout, as stored under `txs`. Its fields must not
+/// change or existing transactions stop decoding; later per-transaction data
+/// lives in [`TransactionExtras`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StoredTransaction {
     pub id: u64,
     pub treasury_id: u64,
     pub tx_type: TransactionType,
@@ -46,6 +132,83 @@ pub struct Transaction {
     pub reason: String,
 }
 
+/// Transaction data added after the original layout, stored in a side map
+/// keyed by transaction id.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactionExtras {
+    pub category: WithdrawalCategory,
+    pub fee: i128,
+    pub net_amount: i128,
+    pub memo: Option<String>,
+    pub on_behalf_of: Option<Address>,
+}
+
+impl TransactionExtras {
+    /// Extras for a transaction stored before they existed: uncategorized
+    /// and fee-free
+    pub fn legacy(amount: i128) -> Self {
+        Self {
+            category: WithdrawalCategory::Other,
+            fee: 0,
+            net_amount: amount,
+            memo: None,
+            on_behalf_of: None,
+        }
+    }
+}
+
+impl Transaction {
+    /// Reassemble a transaction from its stored parts
+    pub fn from_parts(stored: StoredTransaction, extras: TransactionExtras) -> Self {
+        Self {
+            id: stored.id,
+            treasury_id: stored.treasury_id,
+            tx_type: stored.tx_type,
+            amount: stored.amount,
+            token: stored.token,
+            recipient: stored.recipient,
+            proposer: stored.proposer,
+            approvals: stored.approvals,
+            status: stored.status,
+            created_at: stored.created_at,
+            expires_at: stored.expires_at,
+            category: extras.category,
+            reason: stored.reason,
+            fee: extras.fee,
+            net_amount: extras.net_amount,
+            memo: extras.memo,
+            on_behalf_of: extras.on_behalf_of,
+        }
+    }
+
+    /// Split a transaction into its original stored layout and the later data
+    pub fn into_parts(self) -> (StoredTransaction, TransactionExtras) {
+        let stored = StoredTransaction {
+            id: self.id,
+            treasury_id: self.treasury_id,
+            tx_type: self.tx_type,
+            amount: self.amount,
+            token: self.token,
+            recipient: self.recipient,
+            proposer: self.proposer,
+            approvals: self.approvals,
+            status: self.status,
+            created_at: self.created_at,
+            expires_at: self.expires_at,
+            reason: self.reason,
+        };
+        let extras = TransactionExtras {
+            category: self.category,
+            fee: self.fee,
+            net_amount: self.net_amount,
+            memo: self.memo,
+            on_behalf_of: self.on_behalf_of,
+        };
+        (stored, extras)
+    }
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Treasury {
@@ -53,7 +216,13 @@ pub struct Treasury {
     pub guild_id: u64,
     pub owner: Address,
     pub signers: Vec<Address>,
+    /// When set, `signers` is unused and every guild member at Admin level or
+    /// above may sign, resolved at the time of each approval.
+    pub role_based_signers: bool,
     pub approval_threshold: u32,
+    /// When set, the threshold is a majority of the current signers rather
+    /// than `approval_threshold`, which only records the value at creation.
+    pub auto_threshold: bool,
     pub high_value_threshold: i128,
     pub balance_xlm: i128,
     pub token_balances: Map<Address, i128>,
@@ -62,9 +231,103 @@ pub struct Treasury {
     pub paused: bool,
 }
 
+/// Original `Treasury` layout, as stored under `trsries`. Its fields must not
+/// change or existing treasuries stop decoding; later settings live in
+/// [`TreasurySettings`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StoredTreasury {
+    pub id: u64,
+    pub guild_id: u64,
+    pub owner: Address,
+    pub signers: Vec<Address>,
+    pub approval_threshold: u32,
+    pub high_value_threshold: i128,
+    pub balance_xlm: i128,
+    pub token_balances: Map<Address, i128>,
+    pub total_deposits: i128,
+    pub total_withdrawals: i128,
+    pub paused: bool,
+}
+
+/// Signer-mode settings of a treasury, stored per treasury under their own
+/// key. Treasuries without an entry use a fixed signer list and threshold.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasurySettings {
+    pub role_based_signers: bool,
+    pub auto_threshold: bool,
+}
+
+impl TreasurySettings {
+    pub fn default() -> Self {
+        Self {
+            role_based_signers: false,
+            auto_threshold: false,
+        }
+    }
+}
+
+impl Treasury {
+    /// Reassemble a treasury from its stored parts
+    pub fn from_parts(stored: StoredTreasury, settings: TreasurySettings) -> Self {
+        Self {
+            id: stored.id,
+            guild_id: stored.guild_id,
+            owner: stored.owner,
+            signers: stored.signers,
+            role_based_signers: settings.role_based_signers,
+            approval_threshold: stored.approval_threshold,
+            auto_threshold: settings.auto_threshold,
+            high_value_threshold: stored.high_value_threshold,
+            balance_xlm: stored.balance_xlm,
+            token_balances: stored.token_balances,
+            total_deposits: stored.total_deposits,
+            total_withdrawals: stored.total_withdrawals,
+            paused: stored.paused,
+        }
+    }
+
+    /// Split a treasury into its original stored layout and its settings
+    pub fn into_parts(self) -> (StoredTreasury, TreasurySettings) {
+        let stored = StoredTreasury {
+            id: self.id,
+            guild_id: self.guild_id,
+            owner: self.owner,
+            signers: self.signers,
+            approval_threshold: self.approval_threshold,
+            high_value_threshold: self.high_value_threshold,
+            balance_xlm: self.balance_xlm,
+            token_balances: self.token_balances,
+            total_deposits: self.total_deposits,
+            total_withdrawals: self.total_withdrawals,
+            paused: self.paused,
+        };
+        let settings = TreasurySettings {
+            role_based_signers: self.role_based_signers,
+            auto_threshold: self.auto_threshold,
+        };
+        (stored, settings)
+    }
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Budget {
+    pub treasury_id: u64,
+    pub category: WithdrawalCategory,
+    pub allocated_amount: i128,
+    pub spent_amount: i128,
+    pub period_seconds: u64,
+    pub period_start: u64,
+}
+
+/// Original `Budget` layout, as stored under `budgets` keyed by
+/// `(treasury_id, category name)`. The category stays a string so budgets
+/// stored before categories were typed still decode.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StoredBudget {
     pub treasury_id: u64,
     pub category: String,
     pub allocated_amount: i128,
@@ -102,6 +365,8 @@ pub struct DepositEvent {
     pub from: Address,
     pub amount: i128,
     pub token: Option<Address>,
+    /// Contributor credited instead of `from`, if any
+    pub on_behalf_of: Option<Address>,
 }
 
 #[contracttype]
@@ -115,6 +380,16 @@ pub struct WithdrawalProposedEvent {
     pub token: Option<Address>,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryTransferEvent {
+    pub tx_id: u64,
+    pub from_treasury_id: u64,
+    pub to_treasury_id: u64,
+    pub amount: i128,
+    pub token: Option<Address>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TransactionApprovedEvent {
@@ -123,6 +398,14 @@ pub struct TransactionApprovedEvent {
     pub approver: Address,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalRevokedEvent {
+    pub treasury_id: u64,
+    pub tx_id: u64,
+    pub signer: Address,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TransactionExecutedEvent {
@@ -134,11 +417,40 @@ pub struct TransactionExecutedEvent {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BudgetUpdatedEvent {
     pub treasury_id: u64,
-    pub category: String,
+    pub category: WithdrawalCategory,
     pub allocated_amount: i128,
     pub period_seconds: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReserveFloorUpdatedEvent {
+    pub treasury_id: u64,
+    pub token: Option<Address>,
+    pub floor: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalTiersUpdatedEvent {
+    pub treasury_id: u64,
+    pub tiers: Vec<(i128, u32)>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactionTimeoutUpdatedEvent {
+    pub treasury_id: u64,
+    pub seconds: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayeeAllowlistUpdatedEvent {
+    pub treasury_id: u64,
+    pub payee_count: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AllowanceGrantedEvent {
@@ -149,6 +461,25 @@ pub struct AllowanceGrantedEvent {
     pub period_seconds: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceSpentEvent {
+    pub treasury_id: u64,
+    pub tx_id: u64,
+    pub grantee: Address,
+    pub recipient: Address,
+    pub amount: i128,
+    pub token: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryConfigUpdatedEvent {
+    pub treasury_id: u64,
+    pub approval_threshold: u32,
+    pub signer_count: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EmergencyPauseEvent {
@@ -156,6 +487,29 @@ pub struct EmergencyPauseEvent {
     pub paused: bool,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperationPauseEvent {
+    pub treasury_id: u64,
+    pub op_kind: TreasuryOperation,
+    pub paused: bool,
+}
+
+/// Contract-wide fee skimmed from executed withdrawals
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawalFee {
+    pub bps: u32,
+    pub recipient: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawalFeeUpdatedEvent {
+    pub bps: u32,
+    pub fee_recipient: Address,
+}
+
 impl Treasury {
     pub fn is_signer(&self, addr: &Address) -> bool {
         self.signers.iter().any(|a| &a == addr)
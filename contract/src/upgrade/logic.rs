@@ -1,11 +1,11 @@
 use crate::events::emit::emit_event;
 use crate::events::topics::{
     ACT_APPROVED, ACT_COMPLETED, ACT_EMERGENCY_UPGRADE, ACT_REJECTED, ACT_STARTED, ACT_UPDATED,
-    ACT_UPGRADE_EXECUTED, ACT_UPGRADE_PROPOSED, MOD_UPGRADE,
+    ACT_UPGRADE_EXECUTED, ACT_UPGRADE_PROPOSED, ACT_WASM_UPGRADED, MOD_UPGRADE,
 };
 use crate::upgrade::storage;
 use crate::upgrade::types::{MigrationPlan, UpgradeProposal, UpgradeStatus, Version};
-use soroban_sdk::{symbol_short, Address, Env, String};
+use soroban_sdk::{symbol_short, Address, BytesN, Env, String};
 
 /// Create a new upgrade proposal
 pub fn propose_upgrade(
@@ -151,6 +151,17 @@ pub fn emergency_upgrade(
     Ok(())
 }
 
+/// Replace the running contract code with an already uploaded wasm. The
+/// contract keeps its address and storage untouched: the new code must still
+/// decode every record written by the old one, and any change to a stored
+/// layout needs a migration registered through `register_migration_plan`.
+pub fn upgrade_wasm(env: &Env, new_wasm_hash: BytesN<32>) {
+    env.deployer()
+        .update_current_contract_wasm(new_wasm_hash.clone());
+
+    emit_event(env, MOD_UPGRADE, ACT_WASM_UPGRADED, new_wasm_hash);
+}
+
 /// Enable or disable emergency upgrades
 pub fn toggle_emergency_upgrades(
     env: &Env,
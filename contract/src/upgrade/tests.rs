@@ -2,9 +2,11 @@
 
 use super::{logic, storage};
 use super::types::*;
-use crate::StellarGuildsContract;
+use crate::guild::storage::get_guild;
+use crate::multisig::types::{MultiSigError, OperationType};
+use crate::{StellarGuildsContract, StellarGuildsContractClient};
 use soroban_sdk::testutils::Address as _;
-use soroban_sdk::{Address, Env, String};
+use soroban_sdk::{vec, Address, Bytes, Env, String, Symbol};
 
 fn create_test_version(major: u32, minor: u32, patch: u32) -> Version {
     Version::new(major, minor, patch)
@@ -340,3 +342,99 @@ fn test_version_compatibility_and_rollback() {
         assert_eq!(storage::get_current_version(&env), earlier);
     });
 }
+
+/// Smallest wasm the host accepts as a contract: a `contractenvmetav0` section
+/// for protocol 21 and one exported `hello() -> u32` returning 7.
+const UPGRADED_WASM: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic + version
+    0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7e, // type: () -> i64
+    0x03, 0x02, 0x01, 0x00, // function 0 has type 0
+    0x07, 0x09, 0x01, 0x05, b'h', b'e', b'l', b'l', b'o', 0x00, 0x00, // export "hello"
+    0x0a, 0x0b, 0x01, 0x09, 0x00, // code: one body, no locals
+    0x42, 0x84, 0x80, 0x80, 0x80, 0xf0, 0x00, 0x0b, // i64.const U32Val(7); end
+    0x00, 0x1e, 0x11, b'c', b'o', b'n', b't', b'r', b'a', b'c', b't', b'e', b'n', b'v', b'm', b'e',
+    b't', b'a', b'v', b'0', // custom section "contractenvmetav0"
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x15, 0x00, 0x00, 0x00, 0x00, // interface v21
+];
+
+fn setup_guild_contract() -> (Env, Address, StellarGuildsContractClient<'static>, u64) {
+    let env = Env::default();
+    env.budget().reset_unlimited();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, StellarGuildsContract);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let guild_id = client.create_guild(
+        &String::from_str(&env, "Guild"),
+        &String::from_str(&env, "Survives upgrades"),
+        &admin,
+    );
+
+    (env, contract_id, client, guild_id)
+}
+
+#[test]
+fn test_upgrade_swaps_wasm_and_keeps_guild_data() {
+    let (env, contract_id, client, guild_id) = setup_guild_contract();
+    let owner = env.as_contract(&contract_id, || get_guild(&env, guild_id).unwrap().owner);
+
+    let wasm_hash = env
+        .deployer()
+        .upload_contract_wasm(Bytes::from_slice(&env, UPGRADED_WASM));
+    assert!(client.upgrade(&wasm_hash));
+
+    let hello: u32 = env.invoke_contract(&contract_id, &Symbol::new(&env, "hello"), vec![&env]);
+    assert_eq!(hello, 7);
+    env.as_contract(&contract_id, || {
+        let guild = get_guild(&env, guild_id).unwrap();
+        assert_eq!(guild.owner, owner);
+        assert_eq!(guild.name, String::from_str(&env, "Guild"));
+        assert_eq!(guild.member_count, 1);
+    });
+}
+
+#[test]
+#[should_panic]
+fn test_upgrade_requires_admin_auth() {
+    let (env, _contract_id, client, _guild_id) = setup_guild_contract();
+    let wasm_hash = env
+        .deployer()
+        .upload_contract_wasm(Bytes::from_slice(&env, UPGRADED_WASM));
+
+    env.mock_auths(&[]);
+    client.upgrade(&wasm_hash);
+}
+
+#[test]
+fn test_ms_upgrade_waits_for_executed_config_change() {
+    let (env, contract_id, client, _guild_id) = setup_guild_contract();
+    let owner = Address::generate(&env);
+    let signer = Address::generate(&env);
+    let mut signers = soroban_sdk::Vec::new(&env);
+    signers.push_back(signer.clone());
+    let account_id = client.ms_register_account(&owner, &signers, &2, &None, &0);
+    let op_id = client.ms_propose_operation(
+        &account_id,
+        &OperationType::GuildConfigChange,
+        &String::from_str(&env, "upgrade contract"),
+        &owner,
+        &None,
+    );
+    let wasm_hash = env
+        .deployer()
+        .upload_contract_wasm(Bytes::from_slice(&env, UPGRADED_WASM));
+
+    assert_eq!(
+        client.try_ms_upgrade(&op_id, &wasm_hash),
+        Err(Ok(MultiSigError::OperationNotExecuted))
+    );
+
+    client.ms_sign_operation(&op_id, &signer);
+    client.ms_execute_operation(&op_id, &owner);
+    assert!(client.ms_upgrade(&op_id, &wasm_hash));
+
+    let hello: u32 = env.invoke_contract(&contract_id, &Symbol::new(&env, "hello"), vec![&env]);
+    assert_eq!(hello, 7);
+}
@@ -1,4 +1,5 @@
 pub mod errors;
+pub mod reentrancy;
 pub mod validation;
 
 #[cfg(test)]
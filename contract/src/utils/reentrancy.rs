@@ -0,0 +1,29 @@
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+
+// Set in instance storage for the duration of an external token call
+pub(crate) const LOCK_KEY: Symbol = symbol_short!("reentry");
+
+/// Panic if an external token call is already in progress further up the stack.
+pub fn ensure_not_entered(env: &Env) {
+    if env.storage().instance().has(&LOCK_KEY) {
+        panic!("reentrant call");
+    }
+}
+
+/// Run `f` with the reentrancy lock held.
+pub fn with_guard<T>(env: &Env, f: impl FnOnce() -> T) -> T {
+    ensure_not_entered(env);
+    env.storage().instance().set(&LOCK_KEY, &true);
+    let result = f();
+    env.storage().instance().remove(&LOCK_KEY);
+    result
+}
+
+/// Token transfer that holds the lock while the token contract runs, so a
+/// malicious token cannot re-enter a fund-moving entry point.
+pub fn guarded_transfer(env: &Env, token: &Address, from: &Address, to: &Address, amount: i128) {
+    with_guard(env, || {
+        TokenClient::new(env, token).transfer(from, to, &amount)
+    });
+}